@@ -40,8 +40,9 @@ use leptos_router::{
 use or_poisoned::OrPoisoned;
 use send_wrapper::SendWrapper;
 use server_fn::{
-    error::ServerFnErrorErr, redirect::REDIRECT_HEADER,
-    request::actix::ActixRequest,
+    error::ServerFnErrorErr, middleware::BoxedService,
+    redirect::REDIRECT_HEADER, request::actix::ActixRequest,
+    server::CancellationToken,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -301,10 +302,16 @@ pub fn redirect(path: &str) {
 /// # fn main() {}
 /// ```
 ///
+/// Batches of calls sent by a [`BatchClient`](server_fn::batch::BatchClient) to the
+/// [batch path](server_fn::batch::batch_path) are handled as well. Each call in a batch runs
+/// with its own context, just like a call that was sent on its own.
+///
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
 /// - [Request]
+/// - [CancellationToken], which is canceled if the client disconnects before the server
+///   function has returned
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
@@ -328,10 +335,16 @@ pub fn handle_server_fns() -> Route {
 /// method, while subsequent calls from the client are handled by the server function handler.
 /// The same context needs to be provided to both handlers.
 ///
+/// Batches of calls sent by a [`BatchClient`](server_fn::batch::BatchClient) to the
+/// [batch path](server_fn::batch::batch_path) are handled as well. Each call in a batch runs
+/// with its own context, just like a call that was sent on its own.
+///
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
 /// - [Request]
+/// - [CancellationToken], which is canceled if the client disconnects before the server
+///   function has returned
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
@@ -346,69 +359,27 @@ pub fn handle_server_fns_with_context(
 
             let path = req.path();
             let method = req.method();
-            if let Some(mut service) =
+            if path == server_fn::batch::batch_path() {
+                // each call in the batch is handled like any other server function request, in
+                // its own reactive owner and with its own context
+                server_fn::actix::handle_batch(
+                    req,
+                    payload,
+                    move |req, server_fn_req, service| {
+                        run_server_fn(
+                            additional_context.clone(),
+                            req,
+                            server_fn_req,
+                            service,
+                        )
+                    },
+                )
+                .await
+            } else if let Some(service) =
                 server_fn::actix::get_server_fn_service(path, method)
             {
-                let owner = Owner::new();
-                owner
-                    .with(|| {
-                        ScopedFuture::new(async move {
-                            provide_context(Request::new(&req));
-                            let res_options = ResponseOptions::default();
-                            provide_context(res_options.clone());
-                            additional_context();
-
-                            // store Accepts and Referer in case we need them for redirect (below)
-                            let accepts_html = req
-                                .headers()
-                                .get(ACCEPT)
-                                .and_then(|v| v.to_str().ok())
-                                .map(|v| v.contains("text/html"))
-                                .unwrap_or(false);
-                            let referrer = req.headers().get(REFERER).cloned();
-
-                            // actually run the server fn
-                            let mut res = ActixResponse(
-                                service
-                                    .run(ActixRequest::from((req, payload)))
-                                    .await
-                                    .take(),
-                            );
-
-                            // if it accepts text/html (i.e., is a plain form post) and doesn't already have a
-                            // Location set, then redirect to the Referer
-                            if accepts_html {
-                                if let Some(referrer) = referrer {
-                                    let has_location =
-                                        res.0.headers().get(LOCATION).is_some();
-                                    if !has_location {
-                                        *res.0.status_mut() = StatusCode::FOUND;
-                                        res.0
-                                            .headers_mut()
-                                            .insert(LOCATION, referrer);
-                                    }
-                                }
-                            }
-
-                            // the Location header may have been set to Referer, so any redirection by the
-                            // user must overwrite it
-                            {
-                                let mut res_options =
-                                    res_options.0.write().or_poisoned();
-                                let headers = res.0.headers_mut();
-
-                                for location in
-                                    res_options.headers.remove(header::LOCATION)
-                                {
-                                    headers.insert(header::LOCATION, location);
-                                }
-                            }
-
-                            // apply status code and headers if user changed them
-                            res.extend_response(&res_options);
-                            res.0
-                        })
-                    })
+                let server_fn_req = ActixRequest::from((req.clone(), payload));
+                run_server_fn(additional_context, req, server_fn_req, service)
                     .await
             } else {
                 HttpResponse::BadRequest().body(format!(
@@ -428,6 +399,74 @@ pub fn handle_server_fns_with_context(
     })
 }
 
+async fn run_server_fn(
+    additional_context: impl Fn() + 'static + Clone + Send,
+    req: HttpRequest,
+    server_fn_req: ActixRequest,
+    mut service: BoxedService<
+        ActixRequest,
+        server_fn::response::actix::ActixResponse,
+    >,
+) -> HttpResponse {
+    let owner = Owner::new();
+    owner
+        .with(|| {
+            ScopedFuture::new(async move {
+                provide_context(Request::new(&req));
+                let res_options = ResponseOptions::default();
+                provide_context(res_options.clone());
+                let (cancellation, cancel_on_drop) = CancellationToken::new();
+                provide_context(cancellation);
+                additional_context();
+
+                // store Accepts and Referer in case we need them for redirect (below)
+                let accepts_html = req
+                    .headers()
+                    .get(ACCEPT)
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| v.contains("text/html"))
+                    .unwrap_or(false);
+                let referrer = req.headers().get(REFERER).cloned();
+
+                // actually run the server fn
+                let mut res =
+                    ActixResponse(service.run(server_fn_req).await.take());
+                // if this future is dropped before getting here, the token is canceled
+                cancel_on_drop.disarm();
+
+                // if it accepts text/html (i.e., is a plain form post) and doesn't already have a
+                // Location set, then redirect to the Referer
+                if accepts_html {
+                    if let Some(referrer) = referrer {
+                        let has_location =
+                            res.0.headers().get(LOCATION).is_some();
+                        if !has_location {
+                            *res.0.status_mut() = StatusCode::FOUND;
+                            res.0.headers_mut().insert(LOCATION, referrer);
+                        }
+                    }
+                }
+
+                // the Location header may have been set to Referer, so any redirection by the
+                // user must overwrite it
+                {
+                    let mut res_options = res_options.0.write().or_poisoned();
+                    let headers = res.0.headers_mut();
+
+                    for location in res_options.headers.remove(header::LOCATION)
+                    {
+                        headers.insert(header::LOCATION, location);
+                    }
+                }
+
+                // apply status code and headers if user changed them
+                res.extend_response(&res_options);
+                res.0
+            })
+        })
+        .await
+}
+
 /// Returns an Actix [struct@Route](actix_web::Route) that listens for a `GET` request and tries
 /// to route it using [leptos_router], serving an HTML stream of your application. The stream
 /// will include fallback content for any `<Suspense/>` nodes, and be immediately interactive,
@@ -791,11 +830,40 @@ fn provide_contexts(
     provide_context(RequestUrl::new(&path));
     provide_context(meta_context.clone());
     provide_context(res_options.clone());
+    #[cfg(feature = "persist")]
+    provide_context(leptos::reactive::signal::persisted::RequestCookies(
+        req.headers()
+            .get_all(header::COOKIE)
+            .filter_map(|cookie| cookie.to_str().ok())
+            .collect::<Vec<_>>()
+            .join("; "),
+    ));
     provide_context(req);
     provide_server_redirect(redirect);
     leptos::nonce::provide_nonce();
 }
 
+/// Issues a token to protect server functions against cross-site request forgery, if
+/// protection has been set up with [`protect_server_fns`](server_fn::csrf::protect_server_fns).
+fn issue_csrf_token(req: &HttpRequest, res_options: &ResponseOptions) {
+    let Some(csrf) = server_fn::csrf::csrf_protection() else {
+        return;
+    };
+    let cookies = req
+        .headers()
+        .get_all(header::COOKIE)
+        .filter_map(|cookie| cookie.to_str().ok())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let issued = csrf.issue(Some(&cookies));
+    for cookie in issued.set_cookies {
+        if let Ok(cookie) = header::HeaderValue::from_str(&cookie) {
+            res_options.append_header(header::SET_COOKIE, cookie);
+        }
+    }
+    leptos::csrf::provide_csrf_token(issued.token);
+}
+
 fn leptos_corrected_path(req: &HttpRequest) -> String {
     let path = req.path();
     let query = req.query_string();
@@ -836,6 +904,10 @@ where
                 let res_options = res_options.clone();
                 let req = Request::new(&req);
                 move || {
+                    // tokens are only issued for pages rendered for a particular browser,
+                    // never for static routes, which would share one token between every
+                    // visitor
+                    issue_csrf_token(&req, &res_options);
                     provide_contexts(req, &meta_context, &res_options);
                     add_context();
 
@@ -1447,6 +1519,13 @@ where
                 router = router.route(path, handler);
             }
         }
+        let batch_path = server_fn::batch::batch_path();
+        if !excluded.contains(batch_path) {
+            router = router.route(
+                batch_path,
+                handle_server_fns_with_context(additional_context.clone()),
+            );
+        }
 
         // register routes defined in Leptos's Router
         for listing in paths.iter().filter(|p| !p.exclude) {
@@ -1560,6 +1639,13 @@ impl LeptosRoutes for &mut ServiceConfig {
                 router = router.route(path, handler);
             }
         }
+        let batch_path = server_fn::batch::batch_path();
+        if !excluded.contains(batch_path) {
+            router = router.route(
+                batch_path,
+                handle_server_fns_with_context(additional_context.clone()),
+            );
+        }
 
         // register routes defined in Leptos's Router
         for listing in paths.iter().filter(|p| !p.exclude) {
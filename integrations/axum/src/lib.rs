@@ -68,7 +68,10 @@ use leptos_router::{
     RouteListing, SsrMode,
 };
 use or_poisoned::OrPoisoned;
-use server_fn::{error::ServerFnErrorErr, redirect::REDIRECT_HEADER};
+use server_fn::{
+    error::ServerFnErrorErr, redirect::REDIRECT_HEADER,
+    server::CancellationToken,
+};
 #[cfg(feature = "default")]
 use std::sync::LazyLock;
 #[cfg(feature = "default")]
@@ -315,10 +318,16 @@ pub fn generate_request_and_parts(
 /// Leptos provides a generic implementation of `handle_server_fns`. If access to more specific parts of the Request is desired,
 /// you can specify your own server fn handler based on this one and give it it's own route in the server macro.
 ///
+/// Batches of calls sent by a [`BatchClient`](server_fn::batch::BatchClient) to the
+/// [batch path](server_fn::batch::batch_path) are handled as well. Each call in a batch runs
+/// with its own context, just like a call that was sent on its own.
+///
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
 /// - [`ResponseOptions`]
+/// - [`CancellationToken`], which is canceled if the client disconnects before the server
+///   function has returned
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
@@ -360,10 +369,16 @@ fn init_executor() {
 /// method, while subsequent calls from the client are handled by the server function handler.
 /// The same context needs to be provided to both handlers.
 ///
+/// Batches of calls sent by a [`BatchClient`](server_fn::batch::BatchClient) to the
+/// [batch path](server_fn::batch::batch_path) are handled as well. Each call in a batch runs
+/// with its own context, just like a call that was sent on its own.
+///
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
 /// - [`ResponseOptions`]
+/// - [`CancellationToken`], which is canceled if the client disconnects before the server
+///   function has returned
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
@@ -378,7 +393,23 @@ pub async fn handle_server_fns_with_context(
 async fn handle_server_fns_inner(
     additional_context: impl Fn() + 'static + Clone + Send,
     req: Request<Body>,
-) -> impl IntoResponse {
+) -> Response<Body> {
+    if req.uri().path() == server_fn::batch::batch_path() {
+        // each call in the batch is handled like any other server function request, in its own
+        // reactive owner and with its own context
+        server_fn::axum::handle_batch(req, move |req| {
+            handle_server_fn_call(additional_context.clone(), req)
+        })
+        .await
+    } else {
+        handle_server_fn_call(additional_context, req).await
+    }
+}
+
+async fn handle_server_fn_call(
+    additional_context: impl Fn() + 'static + Clone + Send,
+    req: Request<Body>,
+) -> Response<Body> {
     let method = req.method().clone();
     let path = req.uri().path().to_string();
     let (req, parts) = generate_request_and_parts(req);
@@ -393,6 +424,9 @@ async fn handle_server_fns_inner(
                     provide_context(parts);
                     let res_options = ResponseOptions::default();
                     provide_context(res_options.clone());
+                    let (cancellation, cancel_on_drop) =
+                        CancellationToken::new();
+                    provide_context(cancellation);
                     additional_context();
 
                     // store Accepts and Referer in case we need them for redirect (below)
@@ -406,6 +440,8 @@ async fn handle_server_fns_inner(
 
                     // actually run the server fn
                     let mut res = AxumResponse(service.run(req).await);
+                    // if this future is dropped before getting here, the token is canceled
+                    cancel_on_drop.disarm();
 
                     // if it accepts text/html (i.e., is a plain form post) and doesn't already have a
                     // Location set, then redirect to the Referer
@@ -920,6 +956,9 @@ where
 
                 let full_path = format!("http://leptos.dev{path}");
                 let (_, req_parts) = generate_request_and_parts(req);
+                // tokens are only issued for pages rendered for a particular browser, never
+                // for static routes, which would share one token between every visitor
+                issue_csrf_token(&req_parts.headers, &res_options);
                 provide_contexts(
                     &full_path,
                     &meta_context,
@@ -960,12 +999,43 @@ fn provide_contexts(
 ) {
     provide_context(RequestUrl::new(path));
     provide_context(meta_context.clone());
+    #[cfg(feature = "persist")]
+    provide_context(leptos::reactive::signal::persisted::RequestCookies(
+        parts
+            .headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|cookie| cookie.to_str().ok())
+            .collect::<Vec<_>>()
+            .join("; "),
+    ));
     provide_context(parts);
     provide_context(default_res_options);
     provide_server_redirect(redirect);
     leptos::nonce::provide_nonce();
 }
 
+/// Issues a token to protect server functions against cross-site request forgery, if
+/// protection has been set up with [`protect_server_fns`](server_fn::csrf::protect_server_fns).
+fn issue_csrf_token(headers: &HeaderMap, res_options: &ResponseOptions) {
+    let Some(csrf) = server_fn::csrf::csrf_protection() else {
+        return;
+    };
+    let cookies = headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|cookie| cookie.to_str().ok())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let issued = csrf.issue(Some(&cookies));
+    for cookie in issued.set_cookies {
+        if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+            res_options.append_header(header::SET_COOKIE, cookie);
+        }
+    }
+    leptos::csrf::provide_csrf_token(issued.token);
+}
+
 /// Returns an Axum [Handler](axum::handler::Handler) that listens for a `GET` request and tries
 /// to route it using [leptos_router], asynchronously rendering an HTML page after all
 /// `async` resources have loaded.
@@ -1822,6 +1892,18 @@ where
             .map(|p| p.path.as_str())
             .collect::<HashSet<_>>();
 
+        // register the endpoint for batches of server function calls
+        let batch_path = server_fn::batch::batch_path();
+        if !excluded.contains(batch_path) {
+            let cx_with_state = cx_with_state.clone();
+            router = router.route(
+                batch_path,
+                post(move |req: Request<Body>| async move {
+                    handle_server_fns_with_context(cx_with_state, req).await
+                }),
+            );
+        }
+
         // register server functions
         for (path, method) in server_fn::axum::server_fn_paths() {
             let cx_with_state = cx_with_state.clone();
@@ -0,0 +1,59 @@
+use crate::{
+    context::{provide_context, use_context},
+    server::SharedValue,
+    IntoView,
+};
+use leptos_macro::component;
+use server_fn::csrf::CSRF_FIELD;
+use std::sync::Arc;
+use tachys::html::element::input;
+
+/// The token that protects server functions against cross-site request forgery, for the current
+/// request.
+///
+/// This is provided by the server integrations when protection has been set up with
+/// [`protect_server_fns`](server_fn::csrf::protect_server_fns).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsrfToken(pub Arc<str>);
+
+/// Provides the token issued for the current request via context.
+///
+/// This is called by the server integrations, and does not usually need to be called directly.
+pub fn provide_csrf_token(token: impl Into<Arc<str>>) {
+    provide_context(CsrfToken(token.into()))
+}
+
+/// Accesses the token that protects server functions against cross-site request forgery.
+///
+/// On the server, this is the token issued for the current request. In the browser, it is the
+/// value of the cookie the server set along with it, or else the token that was rendered on the
+/// server, once a [`CsrfInput`] has been hydrated. Returns `None` if protection has not been set
+/// up.
+pub fn use_csrf_token() -> Option<Arc<str>> {
+    use_context::<CsrfToken>()
+        .map(|token| token.0)
+        .or_else(server_fn::csrf::csrf_token)
+}
+
+/// Renders a hidden `<input>` with the token that protects server functions against cross-site
+/// request forgery, if there is one.
+///
+/// [`ActionForm`](crate::form::ActionForm) and [`MultiActionForm`](crate::form::MultiActionForm)
+/// include this automatically. Any other form that submits to a server function without
+/// JavaScript should include it as well.
+#[component]
+pub fn CsrfInput() -> impl IntoView {
+    // the token is serialized from the server, so that the input is hydrated with the value it
+    // was rendered with, rather than with whatever cookie the browser happens to have
+    let token =
+        SharedValue::new(|| use_csrf_token().map(|token| token.to_string()))
+            .into_inner();
+    // the rendered token is only a fallback for browsers without the cookie: the client sends
+    // the cookie's value whenever there is one, because a cached page may have been rendered
+    // with a token that was issued to another browser
+    #[cfg(feature = "hydrate")]
+    if let Some(token) = &token {
+        server_fn::csrf::set_csrf_token(token.as_str());
+    }
+    token.map(|token| input().r#type("hidden").name(CSRF_FIELD).value(token))
+}
@@ -1,12 +1,15 @@
-use crate::{children::Children, component, prelude::*, IntoView};
+use crate::{
+    children::Children, component, csrf::CsrfInput, prelude::*, IntoView,
+};
 use leptos_dom::helpers::window;
 use leptos_server::{ServerAction, ServerMultiAction};
 use serde::de::DeserializeOwned;
 use server_fn::{
     client::Client,
     codec::PostUrl,
-    error::{IntoAppError, ServerFnErrorErr},
+    error::{FromServerFnError, IntoAppError, ServerFnErrorErr},
     request::ClientReq,
+    validate::FieldErrors,
     Http, ServerFn,
 };
 use tachys::{
@@ -73,6 +76,45 @@ use web_sys::{
 ///     Ok(())
 /// }
 /// ```
+///
+/// ## Validation
+/// If the server function has `#[validate(...)]` rules on its arguments, any invalid fields
+/// are returned from the server as [`ServerFnError::Validation`]. These errors are stored in
+/// [`FormErrors`], keyed by the `name` of each input, which the children of the form can access
+/// with [`use_form_errors`]. Setting `validate` also checks the rules in the browser, without
+/// submitting the form if any fields are invalid.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos::form::{use_form_errors, ActionForm};
+///
+/// #[component]
+/// fn SignUp() -> impl IntoView {
+///     let sign_up = ServerAction::<SignUpFn>::new();
+///
+///     view! {
+///       <ActionForm action=sign_up validate=true>
+///         <input type="text" name="name"/>
+///         <NameError/>
+///         <input type="submit"/>
+///       </ActionForm>
+///     }
+/// }
+///
+/// #[component]
+/// fn NameError() -> impl IntoView {
+///     let errors = use_form_errors().expect("inside <ActionForm/>");
+///     let error = errors.field("name");
+///     move || error.get().map(|error| view! { <p class="error">{error}</p> })
+/// }
+///
+/// #[server(name = SignUpFn)]
+/// async fn sign_up(
+///     #[validate(required, length(min = 3))] name: String,
+/// ) -> Result<(), ServerFnError> {
+///     Ok(())
+/// }
+/// ```
 #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
 #[component]
 pub fn ActionForm<ServFn, OutputProtocol>(
@@ -81,6 +123,14 @@ pub fn ActionForm<ServFn, OutputProtocol>(
     /// A [`NodeRef`] in which the `<form>` element should be stored.
     #[prop(optional)]
     node_ref: Option<NodeRef<Form>>,
+    /// Whether to check the server function's validation rules in the browser before
+    /// submitting the form.
+    #[prop(optional)]
+    validate: bool,
+    /// The [`FormErrors`] in which the errors for invalid fields should be stored. If this is
+    /// not set, a new one is created.
+    #[prop(optional)]
+    errors: Option<FormErrors>,
     /// Component children; should include the HTML of the form elements.
     children: Children,
 ) -> impl IntoView
@@ -109,6 +159,20 @@ where
     let version = action.version();
     let value = action.value();
 
+    let errors = errors.unwrap_or_default();
+    provide_context(errors);
+    Effect::new(move |_| {
+        value.with(|value| {
+            if let Some(field_errors) = value
+                .as_ref()
+                .and_then(|value| value.as_ref().err())
+                .and_then(FromServerFnError::field_errors)
+            {
+                errors.set(field_errors.clone());
+            }
+        })
+    });
+
     let on_submit = {
         move |ev: SubmitEvent| {
             if ev.default_prevented() {
@@ -119,6 +183,15 @@ where
 
             match ServFn::from_event(&ev) {
                 Ok(new_input) => {
+                    if validate {
+                        if let Err(field_errors) =
+                            ServerFn::validate(&new_input)
+                        {
+                            errors.set(field_errors);
+                            return;
+                        }
+                    }
+                    errors.clear();
                     action.dispatch(new_input);
                 }
                 Err(err) => {
@@ -140,7 +213,7 @@ where
         .action(ServFn::url())
         .method("post")
         .on(submit, on_submit)
-        .child(children());
+        .child((CsrfInput(), children()));
     if let Some(node_ref) = node_ref {
         Either::Left(action_form.node_ref(node_ref))
     } else {
@@ -148,6 +221,75 @@ where
     }
 }
 
+/// The errors for each invalid field of an [`ActionForm`], keyed by the `name` of the input.
+///
+/// These are set from the errors returned by a server function with `#[validate(...)]`
+/// rules on its arguments, or by checking those rules in the browser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormErrors(RwSignal<FieldErrors>);
+
+impl Default for FormErrors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormErrors {
+    /// Creates an empty set of form errors.
+    pub fn new() -> Self {
+        Self(RwSignal::new(FieldErrors::new()))
+    }
+
+    /// Returns the first error message for the input with the given name, if it is invalid.
+    ///
+    /// This only notifies subscribers when the error for this input changes.
+    pub fn field(&self, name: impl Into<String>) -> Memo<Option<String>> {
+        let errors = self.0;
+        let name = name.into();
+        Memo::new(move |_| {
+            errors.with(|errors| errors.first(&name).map(ToOwned::to_owned))
+        })
+    }
+
+    /// Returns all the error messages for the input with the given name.
+    ///
+    /// This only notifies subscribers when the errors for this input change.
+    pub fn messages(&self, name: impl Into<String>) -> Memo<Vec<String>> {
+        let errors = self.0;
+        let name = name.into();
+        Memo::new(move |_| errors.with(|errors| errors.get(&name).to_vec()))
+    }
+
+    /// Whether every field is valid. This is reactive.
+    pub fn is_empty(&self) -> bool {
+        self.0.with(FieldErrors::is_empty)
+    }
+
+    /// Returns the errors for every field. This is reactive.
+    pub fn get(&self) -> FieldErrors {
+        self.0.get()
+    }
+
+    /// Replaces the errors for every field.
+    pub fn set(&self, errors: FieldErrors) {
+        self.0.set(errors);
+    }
+
+    /// Clears the errors for every field.
+    pub fn clear(&self) {
+        self.0.maybe_update(|errors| {
+            let changed = !errors.is_empty();
+            *errors = FieldErrors::new();
+            changed
+        });
+    }
+}
+
+/// Returns the [`FormErrors`] of the nearest [`ActionForm`], if any.
+pub fn use_form_errors() -> Option<FormErrors> {
+    use_context()
+}
+
 /// Automatically turns a server [MultiAction](MultiAction) into an HTML
 /// [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form)
 /// progressively enhanced to use client-side routing.
@@ -207,7 +349,7 @@ where
         .method("post")
         .attr("method", "post")
         .on(submit, on_submit)
-        .child(children());
+        .child((CsrfInput(), children()));
     if let Some(node_ref) = node_ref {
         Either::Left(action_form.node_ref(node_ref))
     } else {
@@ -1,5 +1,9 @@
 ((root, pkg_path, output_name, wasm_output_name) => {
-	let MOST_RECENT_CHILDREN_CB = [];
+	// events that trigger an island with `data-hydrate="interaction"`
+	// these are replayed on their original target once the island has hydrated
+	const REPLAYED_EVENTS = ["click", "dblclick", "input", "change", "submit"];
+	// these only trigger hydration, and are not replayed
+	const TRIGGER_EVENTS = ["pointerover", "focusin", "touchstart", "keydown"];
 
 	function idle(c) {
 		if ("requestIdleCallback" in window) {
@@ -9,41 +13,131 @@
 		}
 	}
 	async function hydrateIslands(rootNode, mod) {
-		async function traverse(node) {
+		async function traverse(node, children_cb) {
 			if (node.nodeType === Node.ELEMENT_NODE) {
 				const tag = node.tagName.toLowerCase();
 				if(tag === 'leptos-island') {
-					const children = [];
 					const id = node.dataset.component || null;
+					const hydrateAndTraverse = async () => {
+						await hydrateIsland(node, id, mod, children_cb);
+						for(const child of node.children) {
+							await traverse(child, children_cb);
+						}
+					};
 
-					await hydrateIsland(node, id, mod);
-					
+					const strategy = node.dataset.hydrate;
+					if (strategy) {
+						// nested islands are hydrated along with this one
+						scheduleHydration(node, strategy, hydrateAndTraverse);
+					} else {
+						await hydrateAndTraverse();
+					}
+				} else if (tag === 'leptos-children') {
 					for(const child of node.children) {
-						await traverse(child, children);
+						await traverse(child, node.$$on_hydrate);
+					};
+				} else {
+					for(const child of node.children) {
+						await traverse(child, children_cb);
+					};
+				}
+			}
+		}
+
+		await traverse(rootNode, undefined);
+	}
+	function scheduleHydration(el, strategy, hydrate) {
+		let done = false;
+		const run = () => {
+			if (done) {
+				return Promise.resolve();
+			}
+			done = true;
+			return hydrate();
+		};
+
+		switch (strategy) {
+			case "idle":
+				idle(run);
+				break;
+			case "visible":
+				if ("IntersectionObserver" in window) {
+					const observer = new IntersectionObserver((entries) => {
+						if (entries.some(entry => entry.isIntersecting)) {
+							observer.disconnect();
+							run();
+						}
+					});
+					// the island element itself has no box of its own if it's
+					// `display: contents`, so observe its children instead
+					const targets = el.children.length > 0 ? el.children : [el];
+					for(const target of targets) {
+						observer.observe(target);
 					}
 				} else {
-					if (tag === 'leptos-children') {
-						MOST_RECENT_CHILDREN_CB.push(node.$$on_hydrate);
-						for(const child of node.children) {
-							await traverse(child);
-						};
-						// un-set the "most recent children"
-						MOST_RECENT_CHILDREN_CB.pop();
-					} else {
-						for(const child of node.children) {
-							await traverse(child);
-						};
+					run();
+				}
+				break;
+			case "interaction": {
+				const pending = [];
+				const onEvent = (ev) => {
+					if (REPLAYED_EVENTS.includes(ev.type)) {
+						// hold the event until the island is interactive
+						ev.preventDefault();
+						ev.stopImmediatePropagation();
+						pending.push(ev);
+					}
+					if (!done) {
+						for(const name of TRIGGER_EVENTS) {
+							el.removeEventListener(name, onEvent, { capture: true, passive: true });
+						}
+						// keep holding events until the island is interactive, so that
+						// none of them reach the inert DOM while it's hydrating
+						run().finally(() => {
+							for(const name of REPLAYED_EVENTS) {
+								el.removeEventListener(name, onEvent, true);
+							}
+							for(const ev of pending) {
+								if (ev.type === "submit" && ev.target.requestSubmit) {
+									ev.target.requestSubmit(ev.submitter);
+								} else {
+									ev.target.dispatchEvent(new ev.constructor(ev.type, ev));
+								}
+							}
+						});
 					}
+				};
+				for(const name of REPLAYED_EVENTS) {
+					el.addEventListener(name, onEvent, true);
+				}
+				for(const name of TRIGGER_EVENTS) {
+					el.addEventListener(name, onEvent, { capture: true, passive: true });
 				}
+				break;
 			}
+			case "media": {
+				const query = window.matchMedia(el.dataset.media || "all");
+				if (query.matches) {
+					run();
+				} else {
+					const onChange = (ev) => {
+						if (ev.matches) {
+							query.removeEventListener("change", onChange);
+							run();
+						}
+					};
+					query.addEventListener("change", onChange);
+				}
+				break;
+			}
+			default:
+				console.warn(`Unknown hydration strategy "${strategy}" for the island ${el.dataset.component}.`);
+				run();
 		}
-
-		await traverse(rootNode);
 	}
-	async function hydrateIsland(el, id, mod) {
+	async function hydrateIsland(el, id, mod, children_cb) {
 		const islandFn = mod[id];
 		if (islandFn) {
-			const children_cb = MOST_RECENT_CHILDREN_CB[MOST_RECENT_CHILDREN_CB.length-1];
 			if (children_cb) {
 				children_cb();
 			}
@@ -63,7 +157,14 @@
 					hydrateIslands(document.body, mod);
 				});
 
-				window.__hydrateIsland = (el, id) => hydrateIsland(el, id, mod);
+				window.__hydrateIsland = (el, id) => {
+					const strategy = el.dataset.hydrate;
+					if (strategy) {
+						scheduleHydration(el, strategy, () => hydrateIsland(el, id, mod));
+					} else {
+						hydrateIsland(el, id, mod);
+					}
+				};
 			})
 	});
 })
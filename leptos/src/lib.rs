@@ -99,6 +99,10 @@
 //!   system. (This improves the performance of creating large numbers of elements simultaneously,
 //!   in exchange for occasional edge cases in which events behave differently from native browser
 //!   events.)
+//! - **`persist`** Adds signals that persist their values to `localStorage`, `sessionStorage`,
+//!   or cookies. (See [`PersistedSignal`](reactive_graph::signal::PersistedSignal).)
+//! - **`devtools`** Records the owners, signals, memos, and effects in the reactive graph, and a
+//!   timeline of updates, for use by developer tools. (See [`reactive_graph::devtools`].)
 //! - **`rustls`** Use `rustls` for server functions.
 //!
 //! **Important Note:** You must enable one of `csr`, `hydrate`, or `ssr` to tell Leptos
@@ -164,8 +168,8 @@ pub mod prelude {
     // However, it is definitely useful relative to looking up every struct etc.
     mod export_types {
         pub use crate::{
-            callback::*, children::*, component::*, control_flow::*, error::*,
-            form::*, hydration::*, into_view::*, mount::*, nonce::*,
+            callback::*, children::*, component::*, control_flow::*, csrf::*,
+            error::*, form::*, hydration::*, into_view::*, mount::*, nonce::*,
             suspense::*, text_prop::*,
         };
         pub use leptos_config::*;
@@ -185,6 +189,7 @@ pub mod prelude {
         pub use server_fn::{
             self,
             error::{FromServerFnError, ServerFnError, ServerFnErrorErr},
+            validate::{FieldErrors, Validate},
         };
         pub use tachys::{
             reactive_graph::{bind::BindAttribute, node_ref::*, Suspend},
@@ -221,6 +226,7 @@ pub mod error {
 pub mod control_flow {
     pub use crate::{
         animated_show::*, await_::*, for_loop::*, show::*, show_let::*,
+        virtual_for::*,
     };
 }
 mod animated_show;
@@ -228,6 +234,7 @@ mod await_;
 mod for_loop;
 mod show;
 mod show_let;
+mod virtual_for;
 
 /// A component that allows rendering a component somewhere else.
 pub mod portal;
@@ -238,6 +245,9 @@ pub mod hydration;
 /// Utilities for exporting nonces to be used for a Content Security Policy.
 pub mod nonce;
 
+/// Utilities for protecting server functions against cross-site request forgery.
+pub mod csrf;
+
 /// Components to load asynchronous data.
 pub mod suspense {
     pub use crate::{suspense_component::*, transition::*};
@@ -1,12 +1,14 @@
 #[cfg(debug_assertions)]
 use crate::logging;
 use crate::IntoView;
+#[cfg(any(feature = "hydrate", target_arch = "wasm32"))]
 use any_spawner::Executor;
 use reactive_graph::owner::Owner;
 #[cfg(debug_assertions)]
 use std::cell::Cell;
 use tachys::{
     dom::body,
+    renderer::types::HtmlElement,
     view::{Mountable, Render},
 };
 #[cfg(feature = "hydrate")]
@@ -14,9 +16,6 @@ use tachys::{
     hydration::Cursor,
     view::{PositionState, RenderHtml},
 };
-#[cfg(feature = "hydrate")]
-use wasm_bindgen::JsCast;
-use web_sys::HtmlElement;
 
 #[cfg(feature = "hydrate")]
 /// Hydrates the app described by the provided function, starting at `<body>`.
@@ -88,7 +87,7 @@ where
     let mountable = owner.with(move || {
         let view = f().into_view();
         view.hydrate::<true>(
-            &Cursor::new(parent.unchecked_into()),
+            &Cursor::new(parent.into()),
             &PositionState::default(),
         )
     });
@@ -144,7 +143,7 @@ where
             ScopedFuture::new(async move {
                 let view = f().into_view();
                 view.hydrate_async(
-                    &Cursor::new(parent.unchecked_into()),
+                    &Cursor::new(parent.into()),
                     &PositionState::default(),
                 )
                 .await
@@ -172,15 +171,23 @@ where
 }
 
 /// Runs the provided closure and mounts the result to the provided element.
+///
+/// With the `mock_dom` feature enabled on a native target, `parent` is an element in the
+/// in-memory mock DOM (see `tachys::renderer::mock_dom`), so components can be tested with
+/// `cargo test`. Outside the browser no async executor is initialized, so tests that need one
+/// should initialize it themselves.
 pub fn mount_to<F, N>(parent: HtmlElement, f: F) -> UnmountHandle<N::State>
 where
     F: FnOnce() -> N + 'static,
     N: IntoView,
 {
-    // use wasm-bindgen-futures to drive the reactive system
-    // we ignore the return value because an Err here just means the wasm-bindgen executor is
-    // already initialized, which is not an issue
-    _ = Executor::init_wasm_bindgen();
+    #[cfg(target_arch = "wasm32")]
+    {
+        // use wasm-bindgen-futures to drive the reactive system
+        // we ignore the return value because an Err here just means the wasm-bindgen executor is
+        // already initialized, which is not an issue
+        _ = Executor::init_wasm_bindgen();
+    }
 
     #[cfg(debug_assertions)]
     {
@@ -221,10 +228,13 @@ where
     F: FnOnce() -> N + 'static,
     N: Render,
 {
-    // use wasm-bindgen-futures to drive the reactive system
-    // we ignore the return value because an Err here just means the wasm-bindgen executor is
-    // already initialized, which is not an issue
-    _ = Executor::init_wasm_bindgen();
+    #[cfg(target_arch = "wasm32")]
+    {
+        // use wasm-bindgen-futures to drive the reactive system
+        // we ignore the return value because an Err here just means the wasm-bindgen executor is
+        // already initialized, which is not an issue
+        _ = Executor::init_wasm_bindgen();
+    }
 
     // create a new reactive owner and use it as the root node to run the app
     let owner = Owner::new();
@@ -1,8 +1,12 @@
-use crate::{children::TypedChildrenFn, mount, IntoView};
-use leptos_dom::helpers::document;
+use crate::{children::TypedChildrenFn, IntoView};
 use leptos_macro::component;
-use reactive_graph::{effect::Effect, graph::untrack, owner::Owner};
-use std::sync::Arc;
+#[cfg(target_arch = "wasm32")]
+use {
+    crate::mount,
+    leptos_dom::helpers::document,
+    reactive_graph::{effect::Effect, graph::untrack, owner::Owner},
+    std::sync::Arc,
+};
 
 /// Renders components somewhere else in the DOM.
 ///
@@ -28,10 +32,15 @@ pub fn Portal<V>(
 where
     V: IntoView + 'static,
 {
-    if cfg!(target_arch = "wasm32")
-        && Owner::current_shared_context()
-            .map(|sc| sc.is_browser())
-            .unwrap_or(true)
+    // portals are only created in the browser
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        _ = (mount, use_shadow, is_svg, children);
+    }
+    #[cfg(target_arch = "wasm32")]
+    if Owner::current_shared_context()
+        .map(|sc| sc.is_browser())
+        .unwrap_or(true)
     {
         use send_wrapper::SendWrapper;
         use wasm_bindgen::JsCast;
@@ -0,0 +1,472 @@
+use crate::into_view::IntoView;
+use leptos_dom::helpers::window_event_listener;
+use leptos_macro::{component, view};
+use reactive_graph::{
+    computed::Memo,
+    effect::Effect,
+    owner::{on_cleanup, Owner, StoredValue},
+    signal::RwSignal,
+    traits::{
+        Get, GetUntracked, GetValue, Set, SetValue, Track, Update, With,
+        WithUntracked,
+    },
+    wrappers::read::Signal,
+};
+use std::{fmt::Debug, hash::Hash, ops::Range, sync::Arc};
+use tachys::{
+    html::{element::Div, event},
+    prelude::*,
+    reactive_graph::{node_ref::NodeRef, OwnedView},
+    view::keyed::{keyed, SerializableKey},
+};
+
+/// How the items of a [`VirtualFor`] are laid out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VirtualLayout {
+    /// One item per row, scrolling vertically.
+    #[default]
+    Vertical,
+    /// One item per column, scrolling horizontally.
+    Horizontal,
+    /// Rows of a fixed number of items, scrolling vertically.
+    Grid {
+        /// The number of items in each row.
+        columns: usize,
+    },
+}
+
+impl VirtualLayout {
+    fn columns(self) -> usize {
+        match self {
+            VirtualLayout::Grid { columns } => columns.max(1),
+            _ => 1,
+        }
+    }
+}
+
+/// The size of the items of a [`VirtualFor`] along the direction it scrolls: their height, or
+/// their width if the layout is [`VirtualLayout::Horizontal`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemSize {
+    /// Every item has the same size, in pixels.
+    Fixed(f64),
+    /// Each item is measured once it has been rendered. Items that have not been rendered yet
+    /// are assumed to have the estimated size, in pixels.
+    Measured {
+        /// The size assumed for items that have not been measured.
+        estimate: f64,
+    },
+}
+
+impl From<f64> for ItemSize {
+    fn from(size: f64) -> Self {
+        ItemSize::Fixed(size)
+    }
+}
+
+type ScrollToIndex = Arc<dyn Fn(usize) + Send + Sync>;
+
+/// A handle that can be used to scroll a [`VirtualFor`].
+#[derive(Clone, Copy)]
+pub struct VirtualForHandle {
+    scroll_to: StoredValue<Option<ScrollToIndex>>,
+    pending: StoredValue<Option<usize>>,
+}
+
+impl VirtualForHandle {
+    /// Creates a new handle, which can be passed to a [`VirtualFor`].
+    pub fn new() -> Self {
+        Self {
+            scroll_to: StoredValue::new(None),
+            pending: StoredValue::new(None),
+        }
+    }
+
+    /// Scrolls the list so that the item at `index` is at the start of the viewport.
+    ///
+    /// If the list has not been mounted yet, it is scrolled as soon as it is.
+    pub fn scroll_to_index(&self, index: usize) {
+        match self.scroll_to.get_value() {
+            Some(scroll_to) => scroll_to(index),
+            None => self.pending.set_value(Some(index)),
+        }
+    }
+}
+
+impl Default for VirtualForHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for VirtualForHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VirtualForHandle").finish_non_exhaustive()
+    }
+}
+
+/// Iterates over a list of items and displays only those that are currently scrolled into view,
+/// keyed by the `key` function given.
+///
+/// Like [`For`](crate::control_flow::For), each item is rendered with its own reactive owner and
+/// updated with keyed diffing, so items that stay in view while scrolling keep their state. Items
+/// that are scrolled out of view, beyond the `overscan`, are removed from the DOM. This makes it
+/// possible to show lists with many thousands of items.
+///
+/// `VirtualFor` renders a scrollable viewport, which needs to be given a fixed size with CSS (for
+/// example, by adding a class with `attr:class`). Each item should render a single element. If
+/// the items are all the same size, use [`ItemSize::Fixed`] and make sure that each item has
+/// that size; otherwise, use [`ItemSize::Measured`] so that each item is measured after it is
+/// rendered.
+///
+/// The size of the viewport is only known once it has been mounted in the browser, so during
+/// server rendering (and the first render in the browser, when hydrating) the first
+/// `initial_lines` rows or columns are rendered instead.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Row {
+///     id: usize,
+///     name: String,
+/// }
+///
+/// #[component]
+/// fn Rows(rows: RwSignal<Vec<Row>>) -> impl IntoView {
+///     let handle = VirtualForHandle::new();
+///
+///     view! {
+///         <button on:click=move |_| handle.scroll_to_index(0)>"Back to top"</button>
+///         <VirtualFor
+///             attr:style="height: 600px"
+///             each=rows
+///             key=|row| row.id
+///             item_size=32.0
+///             handle=handle
+///             let(row)
+///         >
+///             <div style="height: 32px">{row.name}</div>
+///         </VirtualFor>
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+#[component]
+pub fn VirtualFor<T, EF, N, KF, K>(
+    /// Items over which the component should iterate.
+    #[prop(into)]
+    each: Signal<Vec<T>>,
+    /// A key function that will be applied to each item.
+    key: KF,
+    /// A function that takes the item, and returns the view that will be displayed for each item.
+    children: EF,
+    /// The size of each item along the direction the list scrolls, in pixels.
+    #[prop(into)]
+    item_size: ItemSize,
+    /// How the items are laid out. Defaults to a vertical list.
+    #[prop(optional)]
+    layout: VirtualLayout,
+    /// The number of rows (or columns) to render beyond each edge of the viewport, so that they
+    /// are already there when they are scrolled into view. Defaults to `3`.
+    #[prop(default = 3)]
+    overscan: usize,
+    /// The number of rows (or columns) to render before the size of the viewport is known.
+    /// Defaults to `20`.
+    #[prop(default = 20)]
+    initial_lines: usize,
+    /// A handle that can be used to scroll the list to an item.
+    #[prop(optional)]
+    handle: Option<VirtualForHandle>,
+) -> impl IntoView
+where
+    EF: Fn(T) -> N + Send + Clone + 'static,
+    N: IntoView + 'static,
+    KF: Fn(&T) -> K + Send + Clone + 'static,
+    K: Eq + Hash + SerializableKey + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    let columns = layout.columns();
+    let horizontal = layout == VirtualLayout::Horizontal;
+
+    let viewport_ref = NodeRef::<Div>::new();
+    let window_ref = NodeRef::<Div>::new();
+    let scroll = RwSignal::new(0.0);
+    let viewport = RwSignal::new(None::<f64>);
+    let measured = RwSignal::new(Vec::<Option<f64>>::new());
+
+    let lines = Memo::new(move |_| {
+        let count = each.with(|items| items.len().div_ceil(columns));
+        measured.with(|measured| Lines::new(item_size, count, measured))
+    });
+    let visible = Memo::new(move |_| {
+        let lines = match viewport.get() {
+            None => 0..initial_lines,
+            Some(size) => {
+                lines.with(|lines| lines.visible(scroll.get(), size, overscan))
+            }
+        };
+        let len = each.with(Vec::len);
+        (lines.start * columns).min(len)..(lines.end * columns).min(len)
+    });
+
+    let read_viewport = move || {
+        if let Some(el) = viewport_ref.get_untracked() {
+            let (offset, size) = if horizontal {
+                (el.scroll_left(), el.client_width())
+            } else {
+                (el.scroll_top(), el.client_height())
+            };
+            scroll.set(offset as f64);
+            viewport.set(Some(size as f64));
+        }
+    };
+    let scroll_to_index = move |index: usize| {
+        if let Some(el) = viewport_ref.get_untracked() {
+            let offset =
+                lines.with_untracked(|lines| lines.offset(index / columns));
+            if horizontal {
+                el.set_scroll_left(offset as i32);
+            } else {
+                el.set_scroll_top(offset as i32);
+            }
+        }
+    };
+
+    // once mounted, measure the viewport, and keep it measured when the window is resized
+    Effect::new(move |_| {
+        if viewport_ref.get().is_some() {
+            read_viewport();
+            let resize =
+                window_event_listener(event::resize, move |_| read_viewport());
+            on_cleanup(move || resize.remove());
+
+            if let Some(handle) = handle {
+                handle.scroll_to.set_value(Some(Arc::new(scroll_to_index)));
+                if let Some(index) = handle.pending.get_value() {
+                    handle.pending.set_value(None);
+                    scroll_to_index(index);
+                }
+            }
+        }
+    });
+    on_cleanup(move || {
+        if let Some(handle) = handle {
+            handle.scroll_to.set_value(None);
+        }
+    });
+
+    // measure the items that have just been rendered
+    if let ItemSize::Measured { .. } = item_size {
+        Effect::new(move |_| {
+            let range = visible.get();
+            each.track();
+            let Some(window) = window_ref.get() else {
+                return;
+            };
+            let elements = window.children();
+            let mut sizes = Vec::<(usize, f64)>::new();
+            for (n, index) in range.enumerate() {
+                let Some(el) = elements.item(n as u32) else {
+                    break;
+                };
+                let rect = el.get_bounding_client_rect();
+                let size = if horizontal {
+                    rect.width()
+                } else {
+                    rect.height()
+                };
+                // a row of a grid is as large as its largest item
+                let line = index / columns;
+                match sizes.last_mut() {
+                    Some((prev, prev_size)) if *prev == line => {
+                        *prev_size = prev_size.max(size)
+                    }
+                    _ => sizes.push((line, size)),
+                }
+            }
+            let changed = measured.with_untracked(|measured| {
+                sizes.iter().any(|(line, size)| {
+                    measured.get(*line).copied().flatten().is_none_or(
+                        |prev: f64| (prev - size).abs() >= MEASURE_THRESHOLD,
+                    )
+                })
+            });
+            if changed {
+                measured.update(|measured| {
+                    for (line, size) in sizes {
+                        if measured.len() <= line {
+                            measured.resize(line + 1, None);
+                        }
+                        measured[line] = Some(size);
+                    }
+                });
+            }
+        });
+    }
+
+    // this takes the owner of the VirtualFor itself, as in For
+    let parent = Owner::current().expect("no reactive owner");
+    let children = move |_, child| {
+        let owner = parent.with(Owner::new);
+        let view = owner.with(|| children(child));
+        (drop, OwnedView::new_with_owner(view, owner))
+    };
+    let items = move || {
+        let range = visible.get();
+        let items = each.with(|items| items[range].to_vec());
+        keyed(items, key.clone(), children.clone())
+    };
+
+    let spacer_style = move || {
+        let total = lines.with(Lines::total);
+        if horizontal {
+            format!("position: relative; height: 100%; width: {total}px")
+        } else {
+            format!("position: relative; height: {total}px")
+        }
+    };
+    let window_style = move || {
+        let offset =
+            lines.with(|lines| lines.offset(visible.get().start / columns));
+        match layout {
+            VirtualLayout::Vertical => format!(
+                "position: absolute; top: 0; left: 0; right: 0; \
+                 transform: translateY({offset}px)"
+            ),
+            VirtualLayout::Horizontal => format!(
+                "position: absolute; top: 0; bottom: 0; left: 0; display: \
+                 flex; transform: translateX({offset}px)"
+            ),
+            VirtualLayout::Grid { .. } => format!(
+                "position: absolute; top: 0; left: 0; right: 0; display: \
+                 grid; grid-template-columns: repeat({columns}, minmax(0, \
+                 1fr)); transform: translateY({offset}px)"
+            ),
+        }
+    };
+
+    view! {
+        <div node_ref=viewport_ref style="overflow: auto" on:scroll=move |_| read_viewport()>
+            <div style=spacer_style>
+                <div node_ref=window_ref style=window_style>
+                    {items}
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/// Measured sizes that differ by less than this many pixels are treated as unchanged, so that
+/// rounding doesn't cause the list to be laid out again.
+const MEASURE_THRESHOLD: f64 = 0.5;
+
+/// The sizes and positions of the lines of a [`VirtualFor`], where each line is a row of items
+/// (or a single item, unless the layout is a grid).
+#[derive(Debug, Clone, PartialEq)]
+struct Lines {
+    count: usize,
+    size: f64,
+    /// The start of each line, followed by the end of the last line, if lines are measured.
+    offsets: Option<Vec<f64>>,
+}
+
+impl Lines {
+    fn new(size: ItemSize, count: usize, measured: &[Option<f64>]) -> Self {
+        match size {
+            ItemSize::Fixed(size) => Self {
+                count,
+                size,
+                offsets: None,
+            },
+            ItemSize::Measured { estimate } => {
+                let mut offsets = Vec::with_capacity(count + 1);
+                let mut offset = 0.0;
+                offsets.push(offset);
+                for line in 0..count {
+                    offset += measured
+                        .get(line)
+                        .copied()
+                        .flatten()
+                        .unwrap_or(estimate);
+                    offsets.push(offset);
+                }
+                Self {
+                    count,
+                    size: estimate,
+                    offsets: Some(offsets),
+                }
+            }
+        }
+    }
+
+    /// The position at which the given line starts.
+    fn offset(&self, line: usize) -> f64 {
+        let line = line.min(self.count);
+        match &self.offsets {
+            None => line as f64 * self.size,
+            Some(offsets) => offsets[line],
+        }
+    }
+
+    /// The size of all the lines together.
+    fn total(&self) -> f64 {
+        self.offset(self.count)
+    }
+
+    /// The lines that are at least partly inside a viewport of the given size, scrolled to the
+    /// given position, with `overscan` additional lines on either side.
+    fn visible(
+        &self,
+        scroll: f64,
+        viewport: f64,
+        overscan: usize,
+    ) -> Range<usize> {
+        let end_of_viewport = scroll + viewport;
+        let (first, last) = match &self.offsets {
+            None if self.size <= 0.0 => (0, self.count),
+            None => (
+                (scroll / self.size).floor() as usize,
+                (end_of_viewport / self.size).ceil() as usize,
+            ),
+            Some(offsets) => (
+                // the first line that ends after the start of the viewport...
+                offsets[1..].partition_point(|end| *end <= scroll),
+                // ...up to the first line that starts after the end of it
+                offsets[..self.count]
+                    .partition_point(|start| *start < end_of_viewport),
+            ),
+        };
+        let start = first.saturating_sub(overscan).min(self.count);
+        let end = last.saturating_add(overscan).min(self.count);
+        start..end.max(start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ItemSize, Lines};
+
+    #[test]
+    fn fixed_lines_show_the_viewport_and_overscan() {
+        let lines = Lines::new(ItemSize::Fixed(10.0), 100, &[]);
+        assert_eq!(lines.total(), 1000.0);
+        assert_eq!(lines.offset(5), 50.0);
+        assert_eq!(lines.visible(0.0, 50.0, 0), 0..5);
+        assert_eq!(lines.visible(25.0, 50.0, 2), 0..10);
+        assert_eq!(lines.visible(995.0, 50.0, 2), 97..100);
+    }
+
+    #[test]
+    fn measured_lines_use_estimate_until_measured() {
+        let measured = [Some(30.0), None, Some(5.0)];
+        let lines =
+            Lines::new(ItemSize::Measured { estimate: 10.0 }, 5, &measured);
+        assert_eq!(lines.offset(1), 30.0);
+        assert_eq!(lines.offset(2), 40.0);
+        assert_eq!(lines.offset(3), 45.0);
+        assert_eq!(lines.total(), 65.0);
+        assert_eq!(lines.visible(0.0, 30.0, 0), 0..1);
+        assert_eq!(lines.visible(35.0, 8.0, 0), 1..3);
+        assert_eq!(lines.visible(35.0, 8.0, 1), 0..4);
+    }
+}
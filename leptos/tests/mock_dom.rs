@@ -0,0 +1,31 @@
+#[cfg(all(feature = "mock_dom", not(feature = "ssr")))]
+#[tokio::test]
+async fn mounted_components_can_be_tested_with_the_mock_dom() {
+    use any_spawner::Executor;
+    use leptos::{prelude::*, tachys::renderer::mock_dom::MockDom};
+    use tokio::task::LocalSet;
+
+    #[component]
+    fn Counter() -> impl IntoView {
+        let (count, set_count) = signal(0);
+        view! {
+            <button on:click=move |_| set_count.update(|n| *n += 1)>"+1"</button>
+            <p>{count}</p>
+        }
+    }
+
+    _ = Executor::init_tokio();
+    LocalSet::new()
+        .run_until(async {
+            let parent = MockDom::create_element("div", None);
+            let _handle = leptos::mount::mount_to(parent.clone(), Counter);
+            assert_eq!(parent.inner_html(), "<button>+1</button><p>0</p>");
+
+            let button = parent.query_selector("button").unwrap();
+            button.click();
+            button.click();
+            Executor::tick().await;
+            assert_eq!(parent.query_selector("p").unwrap().text_content(), "2");
+        })
+        .await;
+}
@@ -252,3 +252,36 @@ fn ssr_textarea_escapes_static_content() {
 //          </textarea>"
 //     );
 // }
+
+#[cfg(feature = "ssr")]
+#[test]
+fn virtual_for_renders_first_window() {
+    use any_spawner::Executor;
+    use leptos::prelude::*;
+
+    // the scroll handlers are set up in effects, which need an executor if `effects` is enabled
+    _ = Executor::init_futures_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let items = (0..1000).collect::<Vec<usize>>();
+    let rendered = view! {
+        <VirtualFor
+            each=items
+            key=|i| *i
+            item_size=10.0
+            initial_lines=3
+            let(i)
+        >
+            <p>{i}</p>
+        </VirtualFor>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<div style=\"overflow: auto;\"><div style=\"position: relative; \
+         height: 10000px;\"><div style=\"position: absolute; top: 0; left: \
+         0; right: 0; transform: translateY(0px);\"><p>0</p><p>1</p><p>2</p>\
+         <!></div></div></div>"
+    );
+}
@@ -22,6 +22,7 @@ pub struct Model {
     is_transparent: bool,
     is_lazy: bool,
     island: Option<String>,
+    island_hydration: Option<TokenStream>,
     docs: Docs,
     unknown_attrs: UnknownAttrs,
     vis: Visibility,
@@ -70,6 +71,7 @@ impl Parse for Model {
             is_transparent: false,
             is_lazy: false,
             island: None,
+            island_hydration: None,
             docs,
             unknown_attrs,
             vis: item.vis.clone(),
@@ -145,6 +147,7 @@ impl ToTokens for Model {
             is_transparent,
             is_lazy,
             island,
+            island_hydration,
             docs,
             unknown_attrs,
             vis,
@@ -315,6 +318,11 @@ impl ToTokens for Model {
         } else {
             quote! {}
         };
+        let island_hydration = island_hydration.as_ref().map(|hydration| {
+            quote! {
+                .with_hydration(#hydration)
+            }
+        });
 
         let body_name = unmodified_fn_name_from_fn_name(&body_name);
         let body_expr = if is_island {
@@ -368,6 +376,7 @@ impl ToTokens for Model {
                     #component
                 )
                 #island_serialized_props
+                #island_hydration
             }
         } else {
             component
@@ -653,6 +662,16 @@ impl Model {
 
         self
     }
+
+    #[allow(clippy::wrong_self_convention)]
+    pub fn with_island_hydration(
+        mut self,
+        island_hydration: Option<TokenStream>,
+    ) -> Self {
+        self.island_hydration = island_hydration;
+
+        self
+    }
 }
 
 /// A model that is more lenient in case of a syntax error in the function body,
@@ -16,7 +16,10 @@ use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenTree};
 use quote::{quote, ToTokens};
 use std::str::FromStr;
-use syn::{parse_macro_input, spanned::Spanned, token::Pub, Visibility};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, spanned::Spanned, token::Pub,
+    Token, Visibility,
+};
 
 mod params;
 mod view;
@@ -578,7 +581,7 @@ pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
         false
     };
 
-    component_macro(s, is_transparent, false, None)
+    component_macro(s, is_transparent, false, None, None)
 }
 
 /// Defines a component as an interactive island when you are using the
@@ -612,6 +615,19 @@ pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// HTML isn't present in the DOM, even if hidden, it is never sent and not available
 /// to the client at all.
 ///
+/// ## Hydration Strategies
+/// By default, every island is hydrated as soon as the WASM module has loaded. Islands
+/// that aren't needed right away can defer their hydration with `hydrate = "..."`:
+/// - `#[island(hydrate = "idle")]` hydrates when the browser is idle.
+/// - `#[island(hydrate = "visible")]` hydrates when the island scrolls into view.
+/// - `#[island(hydrate = "interaction")]` hydrates on the first interaction with the
+///   island. Clicks, inputs, and form submissions are replayed once it has hydrated.
+/// - `#[island(hydrate = "media:(max-width: 600px)")]` hydrates once the media query matches.
+///
+/// Islands nested inside a deferred island are hydrated along with it. The strategy can be
+/// combined with `lazy`, as in `#[island(lazy, hydrate = "visible")]`, so that the island's
+/// code is not loaded until it is needed either.
+///
 /// ## Example
 /// ```rust,ignore
 /// use leptos::prelude::*;
@@ -655,24 +671,65 @@ pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 #[proc_macro_error2::proc_macro_error]
 #[proc_macro_attribute]
 pub fn island(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
-    let (is_transparent, is_lazy) = if !args.is_empty() {
-        let arg = parse_macro_input!(args as syn::Ident);
+    let args = parse_macro_input!(
+        args with Punctuated::<syn::Meta, Token![,]>::parse_terminated
+    );
 
-        if arg != "transparent" && arg != "lazy" {
-            abort!(
-                arg,
-                "only `transparent` or `lazy` are supported";
-                help = "try `#[island(transparent)]`, `#[island(lazy)]`, or `#[island]`"
-            );
+    let mut is_transparent = false;
+    let mut is_lazy = false;
+    let mut hydration = None;
+    for arg in args {
+        match arg {
+            syn::Meta::Path(path) if path.is_ident("transparent") => {
+                is_transparent = true
+            }
+            syn::Meta::Path(path) if path.is_ident("lazy") => is_lazy = true,
+            syn::Meta::NameValue(syn::MetaNameValue {
+                path,
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(strategy),
+                        ..
+                    }),
+                ..
+            }) if path.is_ident("hydrate") => {
+                hydration = Some(island_hydration(&strategy));
+            }
+            other => abort!(
+                other,
+                "only `transparent`, `lazy`, or `hydrate = \"...\"` are \
+                 supported";
+                help = "try `#[island(transparent)]`, `#[island(lazy)]`, \
+                        `#[island(hydrate = \"visible\")]`, or `#[island]`"
+            ),
         }
-
-        (arg == "transparent", arg == "lazy")
-    } else {
-        (false, false)
-    };
+    }
 
     let island_src = s.to_string();
-    component_macro(s, is_transparent, is_lazy, Some(island_src))
+    component_macro(s, is_transparent, is_lazy, Some(island_src), hydration)
+}
+
+fn island_hydration(strategy: &syn::LitStr) -> proc_macro2::TokenStream {
+    let value = strategy.value();
+    let variant = match value.as_str() {
+        "eager" => quote! { Eager },
+        "idle" => quote! { Idle },
+        "visible" => quote! { Visible },
+        "interaction" => quote! { Interaction },
+        _ => match value.strip_prefix("media:") {
+            Some(query) => {
+                let query = query.trim();
+                quote! { Media(#query) }
+            }
+            None => abort!(
+                strategy,
+                "unknown hydration strategy";
+                help = "try `eager`, `idle`, `visible`, `interaction`, or \
+                        `media:(<media query>)`"
+            ),
+        },
+    };
+    quote! { ::leptos::tachys::html::islands::IslandHydration::#variant }
 }
 
 fn component_macro(
@@ -680,6 +737,7 @@ fn component_macro(
     is_transparent: bool,
     is_lazy: bool,
     island: Option<String>,
+    island_hydration: Option<proc_macro2::TokenStream>,
 ) -> TokenStream {
     let mut dummy = syn::parse::<DummyModel>(s.clone());
     let parse_result = syn::parse::<component::Model>(s);
@@ -689,6 +747,7 @@ fn component_macro(
             .is_transparent(is_transparent)
             .is_lazy(is_lazy)
             .with_island(island)
+            .with_island_hydration(island_hydration)
             .into_token_stream();
         if !matches!(unexpanded.vis, Visibility::Public(_)) {
             unexpanded.vis = Visibility::Public(Pub {
@@ -912,6 +971,22 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// pub async fn with_default_value(#[server(default)] values: Vec<u32>) /* etc. */
 /// ```
 ///
+/// ## Validating Parameters
+///
+/// Individual function parameters can be annotated with `#[validate(...)]` rules, in the same
+/// way as the fields of a struct that derives [`Validate`](derive@Validate). The arguments
+/// are checked on the server before the function body runs, and any invalid fields are
+/// returned as [`ServerFnError::Validation`](../server_fn/error/enum.ServerFnError.html).
+/// [`ActionForm`](../leptos/form/fn.ActionForm.html) can also check them in the browser before
+/// the form is submitted.
+/// ```rust,ignore
+/// #[server]
+/// pub async fn sign_up(
+///     #[validate(required, length(min = 3, max = 32))] name: String,
+///     #[validate(nested)] address: Address,
+/// ) -> Result<(), ServerFnError> /* etc. */
+/// ```
+///
 /// ## Important Notes
 /// - **Server functions must be `async`.** Even if the work being done inside the function body
 ///   can run synchronously on the server, from the client’s perspective it involves an asynchronous
@@ -960,6 +1035,43 @@ pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
     }
 }
 
+/// Derives [`Validate`](../server_fn/validate/trait.Validate.html) for a struct, using the
+/// `#[validate(...)]` rules on each of its fields.
+///
+/// The available rules are:
+/// - `required`: the value is not empty
+/// - `length(min = 3, max = 32)`: the number of characters, or items, is within the bounds
+/// - `range(min = 1, max = 10)`: the value is within the bounds
+/// - `regex = "^[a-z]+$"`: the value matches the regular expression (requires the `regex` feature)
+/// - `custom = my_fn`: calls `my_fn(&value) -> Result<(), String>`
+/// - `nested`: validates a field that itself implements `Validate`
+///
+/// Each rule can take a `message` to replace the default error message, e.g.,
+/// `required(message = "Please enter your name.")` or
+/// `regex(pattern = "^[a-z]+$", message = "Lowercase letters only.")`.
+///
+/// ```rust,ignore
+/// #[derive(Clone, Deserialize, Serialize, Validate)]
+/// pub struct Address {
+///     #[validate(required)]
+///     street: String,
+///     #[validate(length(min = 5, max = 5, message = "Enter a 5-digit ZIP code."))]
+///     zip: String,
+///     #[validate(range(min = 1))]
+///     unit: Option<u32>,
+/// }
+/// ```
+#[proc_macro_derive(Validate, attributes(validate))]
+pub fn validate_derive(input: TokenStream) -> TokenStream {
+    match server_fn_macro::validate_derive_impl(
+        input.into(),
+        Some(syn::parse_quote!(::leptos::server_fn)),
+    ) {
+        Err(e) => e.to_compile_error().into(),
+        Ok(s) => s.into(),
+    }
+}
+
 /// Derives a trait that parses a map of string keys and values into a typed
 /// data structure, e.g., for route params.
 #[proc_macro_derive(Params)]
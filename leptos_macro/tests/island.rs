@@ -0,0 +1,39 @@
+use leptos::prelude::*;
+
+#[island]
+fn Eager() -> impl IntoView {}
+
+#[island(hydrate = "visible")]
+fn Visible() -> impl IntoView {}
+
+#[island(lazy, hydrate = "interaction")]
+fn Interaction() -> impl IntoView {}
+
+#[island(hydrate = "media: (max-width: 600px)")]
+fn Media() -> impl IntoView {}
+
+#[test]
+fn island_hydration_strategies() {
+    // the attributes that follow `data-component` in the island's opening tag
+    let hydration_attrs = |html: String| {
+        let open_tag = html.split_once('>').unwrap().0;
+        open_tag
+            .split_once("\" ")
+            .map_or("", |(_, attrs)| attrs)
+            .to_string()
+    };
+
+    assert_eq!(hydration_attrs(view! { <Eager/> }.to_html()), "");
+    assert_eq!(
+        hydration_attrs(view! { <Visible/> }.to_html()),
+        r#"data-hydrate="visible""#
+    );
+    assert_eq!(
+        hydration_attrs(view! { <Interaction/> }.to_html()),
+        r#"data-hydrate="interaction""#
+    );
+    assert_eq!(
+        hydration_attrs(view! { <Media/> }.to_html()),
+        r#"data-hydrate="media" data-media="(max-width: 600px)""#
+    );
+}
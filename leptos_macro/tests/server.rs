@@ -127,4 +127,78 @@ pub mod tests {
             TypeId::of::<Http<codec::PostUrl, codec::Json>>()
         );
     }
+
+    #[test]
+    fn server_validate() {
+        use leptos::{
+            server_fn::validate::{FieldErrors, Validate},
+            Validate,
+        };
+        use serde::{Deserialize, Serialize};
+
+        fn not_admin(name: &str) -> Result<(), String> {
+            if name == "admin" {
+                Err("This name is reserved.".to_string())
+            } else {
+                Ok(())
+            }
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize, Validate)]
+        pub struct Address {
+            #[validate(required)]
+            street: String,
+            #[validate(regex(pattern = "^[0-9]{5}$", message = "Bad ZIP."))]
+            zip: String,
+            #[serde(rename = "apt")]
+            #[validate(range(min = 1))]
+            unit: Option<u32>,
+        }
+
+        #[server]
+        pub async fn sign_up(
+            #[validate(required, length(min = 3, max = 8), custom = not_admin)]
+            name: String,
+            #[validate(nested)] address: Address,
+            age: u8,
+        ) -> Result<String, ServerFnError> {
+            Ok(format!("{name} ({age}) lives on {}", address.street))
+        }
+
+        let valid = SignUp {
+            name: "leptos".to_string(),
+            address: Address {
+                street: "Main St".to_string(),
+                zip: "12345".to_string(),
+                unit: None,
+            },
+            age: 3,
+        };
+        assert_eq!(ServerFn::validate(&valid), Ok(()));
+
+        let invalid = SignUp {
+            name: "admin".to_string(),
+            address: Address {
+                street: " ".to_string(),
+                zip: "1234".to_string(),
+                unit: Some(0),
+            },
+            age: 3,
+        };
+        let mut expected = FieldErrors::new();
+        expected.add("name", "This name is reserved.");
+        expected.add("address[street]", "This field is required.");
+        expected.add("address[zip]", "Bad ZIP.");
+        expected.add("address[apt]", "Must be at least 1.");
+        assert_eq!(ServerFn::validate(&invalid), Err(expected));
+
+        let too_short = SignUp {
+            name: "".to_string(),
+            ..valid
+        };
+        assert_eq!(
+            Validate::validate(&too_short).unwrap_err().get("name"),
+            ["This field is required.", "Must be between 3 and 8 characters."]
+        );
+    }
 }
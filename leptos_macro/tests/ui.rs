@@ -7,4 +7,5 @@ fn ui() {
     #[cfg(all(feature = "nightly", rustc_nightly))]
     t.compile_fail("tests/ui/component_absolute.rs");
     t.compile_fail("tests/ui/server.rs");
+    t.compile_fail("tests/ui/island.rs");
 }
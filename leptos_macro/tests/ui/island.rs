@@ -0,0 +1,9 @@
+use leptos::prelude::*;
+
+#[island(hydrate = "hover")]
+fn UnknownStrategy() -> impl IntoView {}
+
+#[island(hydrate = 1)]
+fn NotAString() -> impl IntoView {}
+
+fn main() {}
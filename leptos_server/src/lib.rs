@@ -12,6 +12,8 @@ mod multi_action;
 pub use multi_action::*;
 mod once_resource;
 pub use once_resource::*;
+mod query;
+pub use query::*;
 mod resource;
 pub use resource::*;
 mod shared;
@@ -0,0 +1,933 @@
+use crate::{ArcResource, FromEncodedStr, IntoEncodedString, Resource};
+use codee::{string::JsonSerdeCodec, Decoder, Encoder};
+use core::{fmt::Debug, marker::PhantomData};
+use futures::{
+    future::{BoxFuture, Shared},
+    Future, FutureExt,
+};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    owner::{ArenaItem, Owner},
+    prelude::*,
+    signal::ArcRwSignal,
+    unwrap_signal,
+};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    panic::Location,
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
+
+/// Configures how long a [`QueryCache`] considers its data fresh, and how long it keeps data
+/// that is no longer being used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryOptions {
+    /// How long loaded data is considered fresh.
+    ///
+    /// Once data is older than this, it is treated as stale: the next time it is read, the stale
+    /// value is returned immediately and the query is revalidated in the background. If this is
+    /// `None`, data only becomes stale when it is explicitly invalidated.
+    pub stale_time: Option<Duration>,
+    /// How long an entry is kept in the cache once it has no subscribers left.
+    ///
+    /// Entries that have been unused for longer than this are garbage-collected the next time the
+    /// cache is accessed.
+    pub gc_time: Duration,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            stale_time: Some(Duration::ZERO),
+            gc_time: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// Allows a key to be matched against a prefix, for use with
+/// [`ArcQueryCache::invalidate_prefix`].
+pub trait KeyPrefix<P: ?Sized> {
+    /// Returns `true` if the given `prefix` is a prefix of this key.
+    fn has_prefix(&self, prefix: &P) -> bool;
+}
+
+impl KeyPrefix<str> for String {
+    fn has_prefix(&self, prefix: &str) -> bool {
+        self.starts_with(prefix)
+    }
+}
+
+impl<T: PartialEq> KeyPrefix<[T]> for Vec<T> {
+    fn has_prefix(&self, prefix: &[T]) -> bool {
+        self.starts_with(prefix)
+    }
+}
+
+type SharedQueryFuture<V> = Shared<BoxFuture<'static, V>>;
+
+struct QueryEntry<V> {
+    value: Option<V>,
+    updated_at: Option<Duration>,
+    invalidated: bool,
+    in_flight: Option<SharedQueryFuture<V>>,
+    version: ArcRwSignal<usize>,
+    subscribers: usize,
+    unused_since: Option<Duration>,
+}
+
+impl<V> QueryEntry<V> {
+    fn new(now: Duration) -> Self {
+        Self {
+            value: None,
+            updated_at: None,
+            invalidated: false,
+            in_flight: None,
+            version: ArcRwSignal::new(0),
+            subscribers: 0,
+            unused_since: Some(now),
+        }
+    }
+
+    fn is_stale(&self, now: Duration, stale_time: Option<Duration>) -> bool {
+        self.invalidated
+            || match (self.updated_at, stale_time) {
+                (Some(updated_at), Some(stale_time)) => {
+                    now.saturating_sub(updated_at) >= stale_time
+                }
+                (None, _) => true,
+                (_, None) => false,
+            }
+    }
+}
+
+struct QueryCacheInner<K, V> {
+    entries: HashMap<K, QueryEntry<V>>,
+    options: QueryOptions,
+}
+
+impl<K, V> QueryCacheInner<K, V>
+where
+    K: Eq + Hash,
+{
+    fn collect_garbage(&mut self, now: Duration) {
+        let gc_time = self.options.gc_time;
+        // entries are collected even if a request for them is still in flight, because nothing
+        // guarantees that the request will ever be polled; anyone who is waiting on it will still
+        // receive its value
+        self.entries.retain(|_, entry| match entry.unused_since {
+            Some(since) => now.saturating_sub(since) < gc_time,
+            None => true,
+        });
+    }
+}
+
+/// A reference-counted, keyed cache of asynchronous data.
+///
+/// A query cache wraps a single `fetcher` function, and stores the data it loads for each key.
+/// Any number of [`ArcResource`]s can be created from the cache with
+/// [`resource`](Self::resource). Resources that read the same key share a single entry:
+/// - while a request for a key is in flight, every other read of that key waits on the same
+///   request, rather than starting its own
+/// - once data has been loaded, it is reused until it becomes stale (see [`QueryOptions`])
+/// - when stale data is read, the stale value is returned immediately while the query is
+///   revalidated in the background; when the new value arrives, every resource reading that key
+///   is updated
+/// - when no resource is subscribed to a key any more, its data is garbage-collected after
+///   [`QueryOptions::gc_time`]
+///
+/// ```rust,no_run
+/// # use leptos_server::*;
+/// # use reactive_graph::{owner::Owner, signal::ArcRwSignal, prelude::*};
+/// # let owner = Owner::new(); owner.set();
+/// # async fn load_user(id: u32) -> String { format!("user {id}") }
+/// let users = ArcQueryCache::new(|id: u32| load_user(id));
+///
+/// let id = ArcRwSignal::new(1);
+/// // both resources share a single request, and a single cached value
+/// let user = users.resource({
+///     let id = id.clone();
+///     move || id.get()
+/// });
+/// let same_user = users.resource(move || id.get());
+///
+/// // mark all the cached data as stale, so that it is reloaded
+/// users.invalidate_all();
+/// ```
+pub struct ArcQueryCache<K, V, Ser = JsonSerdeCodec> {
+    inner: Arc<RwLock<QueryCacheInner<K, V>>>,
+    fetcher: Arc<dyn Fn(K) -> BoxFuture<'static, V> + Send + Sync>,
+    ser: PhantomData<fn() -> Ser>,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+}
+
+impl<K, V, Ser> Clone for ArcQueryCache<K, V, Ser> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            fetcher: Arc::clone(&self.fetcher),
+            ser: PhantomData,
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+        }
+    }
+}
+
+impl<K, V, Ser> Debug for ArcQueryCache<K, V, Ser> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut d = f.debug_struct("ArcQueryCache");
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        d.field("defined_at", self.defined_at);
+        d.finish_non_exhaustive()
+    }
+}
+
+impl<K, V, Ser> DefinedAt for ArcQueryCache<K, V, Ser> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<K, V> ArcQueryCache<K, V, JsonSerdeCodec>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// Creates a new query cache, which loads data using the given `fetcher`, and serializes it
+    /// from the server to the client with the [`JsonSerdeCodec`] encoding.
+    #[track_caller]
+    pub fn new<Fut>(fetcher: impl Fn(K) -> Fut + Send + Sync + 'static) -> Self
+    where
+        Fut: Future<Output = V> + Send + 'static,
+    {
+        Self::new_with_options(fetcher, QueryOptions::default())
+    }
+}
+
+impl<K, V, Ser> ArcQueryCache<K, V, Ser>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// Creates a new query cache with the encoding `Ser`, which loads data using the given
+    /// `fetcher` and handles freshness and garbage collection according to `options`.
+    #[track_caller]
+    pub fn new_with_options<Fut>(
+        fetcher: impl Fn(K) -> Fut + Send + Sync + 'static,
+        options: QueryOptions,
+    ) -> Self
+    where
+        Fut: Future<Output = V> + Send + 'static,
+    {
+        Self {
+            inner: Arc::new(RwLock::new(QueryCacheInner {
+                entries: HashMap::new(),
+                options,
+            })),
+            fetcher: Arc::new(move |key| fetcher(key).boxed()),
+            ser: PhantomData,
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+        }
+    }
+
+    /// Loads the data for the given key.
+    ///
+    /// If fresh data is cached, it is returned immediately. If stale data is cached, it is
+    /// returned immediately and the key is revalidated in the background. If a request for this
+    /// key is already in flight, this waits for that request rather than starting a new one.
+    pub fn fetch(&self, key: K) -> impl Future<Output = V> + Send + 'static {
+        self.fetch_inner(key, true)
+    }
+
+    fn fetch_inner(
+        &self,
+        key: K,
+        check_stale: bool,
+    ) -> impl Future<Output = V> + Send + 'static {
+        let now = now();
+        // the fetcher is only called once the lock has been released, so that it can use this
+        // cache itself
+        let (cached, in_flight) = {
+            let mut inner = self.inner.write().or_poisoned();
+            inner.collect_garbage(now);
+            let stale_time = inner.options.stale_time;
+            let entry = inner
+                .entries
+                .entry(key.clone())
+                .or_insert_with(|| QueryEntry::new(now));
+            let revalidate = check_stale
+                && entry.is_stale(now, stale_time)
+                && entry.in_flight.is_none();
+            match &entry.value {
+                Some(value) => (Some((value.clone(), revalidate)), None),
+                None => (None, entry.in_flight.clone()),
+            }
+        };
+
+        let wait = match cached {
+            Some((_, true)) => {
+                let fut = self.start_fetch(key.clone(), true);
+                if self.set_in_flight(&key, fut.clone()).is_none() {
+                    reactive_graph::spawn(fut.map(|_| ()));
+                }
+                None
+            }
+            Some((_, false)) => None,
+            None => Some(in_flight.unwrap_or_else(|| {
+                let fut = self.start_fetch(key.clone(), false);
+                self.set_in_flight(&key, fut.clone()).unwrap_or(fut)
+            })),
+        };
+
+        async move {
+            match (cached, wait) {
+                (Some((value, _)), _) => value,
+                (None, Some(fut)) => fut.await,
+                (None, None) => unreachable!(),
+            }
+        }
+    }
+
+    /// Stores a request that has just been started as the one in flight for the given key.
+    ///
+    /// If another request for the same key was started while the lock was released, that request
+    /// is kept instead, and returned.
+    fn set_in_flight(
+        &self,
+        key: &K,
+        fut: SharedQueryFuture<V>,
+    ) -> Option<SharedQueryFuture<V>> {
+        let mut inner = self.inner.write().or_poisoned();
+        let entry = inner.entries.get_mut(key)?;
+        match &entry.in_flight {
+            Some(in_flight) => Some(in_flight.clone()),
+            None => {
+                entry.in_flight = Some(fut);
+                None
+            }
+        }
+    }
+
+    /// Starts loading the data for the given key in the background, if it is not already cached
+    /// or loading, so that it is available by the time it is read.
+    pub fn prefetch(&self, key: K) {
+        reactive_graph::spawn(self.fetch(key).map(|_| ()));
+    }
+
+    fn start_fetch(&self, key: K, revalidating: bool) -> SharedQueryFuture<V> {
+        let fut = (self.fetcher)(key.clone());
+        let inner = Arc::clone(&self.inner);
+        async move {
+            let value = fut.await;
+            let version = {
+                let mut inner = inner.write().or_poisoned();
+                inner.entries.get_mut(&key).and_then(|entry| {
+                    entry.value = Some(value.clone());
+                    entry.updated_at = Some(now());
+                    entry.invalidated = false;
+                    entry.in_flight = None;
+                    revalidating.then(|| entry.version.clone())
+                })
+            };
+            // readers that were waiting on this request have already received the value, so
+            // only background revalidations need to notify subscribers
+            if let Some(version) = version {
+                *version.write() += 1;
+            }
+            value
+        }
+        .boxed()
+        .shared()
+    }
+
+    /// Returns the currently-cached data for the given key, if any, without loading it or
+    /// checking whether it is stale.
+    pub fn get_data(&self, key: &K) -> Option<V> {
+        self.inner
+            .read()
+            .or_poisoned()
+            .entries
+            .get(key)
+            .and_then(|entry| entry.value.clone())
+    }
+
+    /// Replaces the cached data for the given key, notifying any resources that are reading it.
+    ///
+    /// This can be used to update the cache optimistically, or with data returned by a mutation.
+    pub fn set_data(&self, key: K, value: V) {
+        let now = now();
+        let version = {
+            let mut inner = self.inner.write().or_poisoned();
+            let entry = inner
+                .entries
+                .entry(key)
+                .or_insert_with(|| QueryEntry::new(now));
+            entry.value = Some(value);
+            entry.updated_at = Some(now);
+            entry.invalidated = false;
+            entry.version.clone()
+        };
+        *version.write() += 1;
+    }
+
+    /// Returns `true` if the data for the given key is missing, has expired, or has been
+    /// invalidated.
+    pub fn is_stale(&self, key: &K) -> bool {
+        let inner = self.inner.read().or_poisoned();
+        inner
+            .entries
+            .get(key)
+            .map(|entry| entry.is_stale(now(), inner.options.stale_time))
+            .unwrap_or(true)
+    }
+
+    /// Marks the data for the given key as stale.
+    ///
+    /// If any resources are currently reading this key, it is revalidated immediately, and they
+    /// continue to show the stale data until it has loaded. Otherwise, it will be revalidated the
+    /// next time it is read.
+    pub fn invalidate(&self, key: &K) {
+        self.invalidate_where(|k| k == key);
+    }
+
+    /// Marks the data for every key that begins with the given prefix as stale.
+    pub fn invalidate_prefix<P>(&self, prefix: &P)
+    where
+        K: KeyPrefix<P>,
+        P: ?Sized,
+    {
+        self.invalidate_where(|k| k.has_prefix(prefix));
+    }
+
+    /// Marks the data for every key in the cache as stale.
+    pub fn invalidate_all(&self) {
+        self.invalidate_where(|_| true);
+    }
+
+    /// Marks the data for every key that matches the given predicate as stale.
+    pub fn invalidate_where(&self, mut predicate: impl FnMut(&K) -> bool) {
+        let to_revalidate = {
+            let mut inner = self.inner.write().or_poisoned();
+            inner
+                .entries
+                .iter_mut()
+                .filter(|(key, _)| predicate(key))
+                .filter_map(|(key, entry)| {
+                    entry.invalidated = true;
+                    (entry.subscribers > 0 && entry.in_flight.is_none())
+                        .then(|| key.clone())
+                })
+                .collect::<Vec<_>>()
+        };
+        // as in fetch(), the fetcher is only called once the lock has been released
+        for key in to_revalidate {
+            let fut = self.start_fetch(key.clone(), true);
+            if self.set_in_flight(&key, fut.clone()).is_none() {
+                reactive_graph::spawn(fut.map(|_| ()));
+            }
+        }
+    }
+
+    /// Removes the data for the given key from the cache.
+    pub fn remove(&self, key: &K) {
+        self.inner.write().or_poisoned().entries.remove(key);
+    }
+
+    /// Removes every entry that has had no subscribers for longer than
+    /// [`QueryOptions::gc_time`].
+    ///
+    /// This happens automatically whenever the cache is read, but can also be triggered manually.
+    pub fn collect_garbage(&self) {
+        self.inner.write().or_poisoned().collect_garbage(now());
+    }
+
+    fn subscribe(&self, key: &K) -> ArcRwSignal<usize> {
+        let now = now();
+        let mut inner = self.inner.write().or_poisoned();
+        let entry = inner
+            .entries
+            .entry(key.clone())
+            .or_insert_with(|| QueryEntry::new(now));
+        entry.subscribers += 1;
+        entry.unused_since = None;
+        entry.version.clone()
+    }
+
+    fn unsubscribe(&self, key: &K) {
+        let now = now();
+        let mut inner = self.inner.write().or_poisoned();
+        if let Some(entry) = inner.entries.get_mut(key) {
+            entry.subscribers = entry.subscribers.saturating_sub(1);
+            if entry.subscribers == 0 {
+                entry.unused_since = Some(now);
+            }
+        }
+        inner.collect_garbage(now);
+    }
+
+    fn version(&self, key: &K) -> Option<ArcRwSignal<usize>> {
+        self.inner
+            .read()
+            .or_poisoned()
+            .entries
+            .get(key)
+            .map(|entry| entry.version.clone())
+    }
+}
+
+impl<K, V, Ser> ArcQueryCache<K, V, Ser>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    Ser: Encoder<V> + Decoder<V>,
+    <Ser as Encoder<V>>::Error: Debug,
+    <Ser as Decoder<V>>::Error: Debug,
+    <<Ser as Decoder<V>>::Encoded as FromEncodedStr>::DecodingError: Debug,
+    <Ser as Encoder<V>>::Encoded: IntoEncodedString,
+    <Ser as Decoder<V>>::Encoded: FromEncodedStr,
+{
+    /// Creates a resource that reads the data for the key returned by `key`.
+    ///
+    /// The key is tracked reactively, like the `source` of [`ArcResource::new`]. The resource is
+    /// subscribed to its current key until the key changes or the current reactive owner is
+    /// cleaned up, and is updated whenever the data for that key is revalidated or replaced.
+    #[track_caller]
+    pub fn resource(
+        &self,
+        key: impl Fn() -> K + Send + Sync + 'static,
+    ) -> ArcResource<V, Ser> {
+        self.resource_with_options(key, false)
+    }
+
+    /// Creates a blocking resource that reads the data for the key returned by `key`.
+    ///
+    /// See [`resource`](Self::resource) for more details. Blocking resources prevent any of the
+    /// HTTP response from being sent until they have loaded.
+    #[track_caller]
+    pub fn resource_blocking(
+        &self,
+        key: impl Fn() -> K + Send + Sync + 'static,
+    ) -> ArcResource<V, Ser> {
+        self.resource_with_options(key, true)
+    }
+
+    #[track_caller]
+    fn resource_with_options(
+        &self,
+        key: impl Fn() -> K + Send + Sync + 'static,
+        blocking: bool,
+    ) -> ArcResource<V, Ser> {
+        let subscribed = Arc::new(Mutex::new(None::<K>));
+
+        let source = {
+            let cache = self.clone();
+            let subscribed = Arc::clone(&subscribed);
+            move || {
+                let key = key();
+                let mut subscribed = subscribed.lock().or_poisoned();
+                let version = match subscribed.as_ref() {
+                    Some(prev) if prev == &key => cache.version(&key),
+                    _ => None,
+                };
+                let version = version.unwrap_or_else(|| {
+                    if let Some(prev) = subscribed.take() {
+                        cache.unsubscribe(&prev);
+                    }
+                    *subscribed = Some(key.clone());
+                    cache.subscribe(&key)
+                });
+                drop(subscribed);
+                (key, version.get())
+            }
+        };
+        let fetcher = {
+            let cache = self.clone();
+            let last_fetched = Mutex::new(None::<(K, usize)>);
+            move |(key, version): (K, usize)| {
+                let mut last_fetched = last_fetched.lock().or_poisoned();
+                // if only the version has changed, new data has just been written to the cache,
+                // so there is no need to check whether it is stale
+                let check_stale = !matches!(
+                    last_fetched.as_ref(),
+                    Some((prev_key, prev_version))
+                        if prev_key == &key && *prev_version != version
+                );
+                *last_fetched = Some((key.clone(), version));
+                cache.fetch_inner(key, check_stale)
+            }
+        };
+        let resource = ArcResource::new_with_options(source, fetcher, blocking);
+
+        // if the resource was hydrated from the server, seed the cache with its value
+        if let Some(value) =
+            (*resource).try_with_untracked(Option::clone).flatten()
+        {
+            if let Some(key) = subscribed.lock().or_poisoned().clone() {
+                let mut inner = self.inner.write().or_poisoned();
+                if let Some(entry) = inner.entries.get_mut(&key) {
+                    if entry.value.is_none() {
+                        entry.value = Some(value);
+                        entry.updated_at = Some(now());
+                    }
+                }
+            }
+        }
+
+        Owner::on_cleanup({
+            let cache = self.clone();
+            move || {
+                if let Some(key) = subscribed.lock().or_poisoned().take() {
+                    cache.unsubscribe(&key);
+                }
+            }
+        });
+
+        resource
+    }
+}
+
+/// A keyed cache of asynchronous data.
+///
+/// This is an arena-allocated, `Copy` version of [`ArcQueryCache`]. See its documentation for
+/// more details.
+pub struct QueryCache<K, V, Ser = JsonSerdeCodec> {
+    inner: ArenaItem<ArcQueryCache<K, V, Ser>>,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+}
+
+impl<K, V, Ser> Copy for QueryCache<K, V, Ser> {}
+
+impl<K, V, Ser> Clone for QueryCache<K, V, Ser> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V, Ser> Debug for QueryCache<K, V, Ser> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut d = f.debug_struct("QueryCache");
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        d.field("defined_at", self.defined_at);
+        d.finish_non_exhaustive()
+    }
+}
+
+impl<K, V, Ser> DefinedAt for QueryCache<K, V, Ser> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<K, V, Ser> Dispose for QueryCache<K, V, Ser> {
+    fn dispose(self) {
+        self.inner.dispose()
+    }
+}
+
+impl<K, V, Ser> From<ArcQueryCache<K, V, Ser>> for QueryCache<K, V, Ser>
+where
+    K: Send + Sync + 'static,
+    V: Send + Sync + 'static,
+    Ser: 'static,
+{
+    #[track_caller]
+    fn from(value: ArcQueryCache<K, V, Ser>) -> Self {
+        Self {
+            inner: ArenaItem::new_with_storage(value),
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+        }
+    }
+}
+
+impl<K, V> QueryCache<K, V, JsonSerdeCodec>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// Creates a new query cache, which loads data using the given `fetcher`, and serializes it
+    /// from the server to the client with the [`JsonSerdeCodec`] encoding.
+    #[track_caller]
+    pub fn new<Fut>(fetcher: impl Fn(K) -> Fut + Send + Sync + 'static) -> Self
+    where
+        Fut: Future<Output = V> + Send + 'static,
+    {
+        ArcQueryCache::new(fetcher).into()
+    }
+}
+
+impl<K, V, Ser> QueryCache<K, V, Ser>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    Ser: 'static,
+{
+    /// Creates a new query cache with the encoding `Ser`, which loads data using the given
+    /// `fetcher` and handles freshness and garbage collection according to `options`.
+    #[track_caller]
+    pub fn new_with_options<Fut>(
+        fetcher: impl Fn(K) -> Fut + Send + Sync + 'static,
+        options: QueryOptions,
+    ) -> Self
+    where
+        Fut: Future<Output = V> + Send + 'static,
+    {
+        ArcQueryCache::new_with_options(fetcher, options).into()
+    }
+
+    /// Starts loading the data for the given key in the background, if it is not already cached
+    /// or loading.
+    pub fn prefetch(&self, key: K) {
+        self.inner.try_with_value(|inner| inner.prefetch(key));
+    }
+
+    /// Returns the currently-cached data for the given key, if any.
+    pub fn get_data(&self, key: &K) -> Option<V> {
+        self.inner
+            .try_with_value(|inner| inner.get_data(key))
+            .flatten()
+    }
+
+    /// Replaces the cached data for the given key, notifying any resources that are reading it.
+    pub fn set_data(&self, key: K, value: V) {
+        self.inner
+            .try_with_value(|inner| inner.set_data(key, value));
+    }
+
+    /// Returns `true` if the data for the given key is missing, has expired, or has been
+    /// invalidated.
+    pub fn is_stale(&self, key: &K) -> bool {
+        self.inner
+            .try_with_value(|inner| inner.is_stale(key))
+            .unwrap_or(true)
+    }
+
+    /// Marks the data for the given key as stale.
+    pub fn invalidate(&self, key: &K) {
+        self.inner.try_with_value(|inner| inner.invalidate(key));
+    }
+
+    /// Marks the data for every key that begins with the given prefix as stale.
+    pub fn invalidate_prefix<P>(&self, prefix: &P)
+    where
+        K: KeyPrefix<P>,
+        P: ?Sized,
+    {
+        self.inner
+            .try_with_value(|inner| inner.invalidate_prefix(prefix));
+    }
+
+    /// Marks the data for every key in the cache as stale.
+    pub fn invalidate_all(&self) {
+        self.inner.try_with_value(|inner| inner.invalidate_all());
+    }
+
+    /// Removes the data for the given key from the cache.
+    pub fn remove(&self, key: &K) {
+        self.inner.try_with_value(|inner| inner.remove(key));
+    }
+
+    /// Removes every entry that has had no subscribers for longer than
+    /// [`QueryOptions::gc_time`].
+    pub fn collect_garbage(&self) {
+        self.inner.try_with_value(|inner| inner.collect_garbage());
+    }
+}
+
+impl<K, V, Ser> QueryCache<K, V, Ser>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    Ser: Encoder<V> + Decoder<V> + 'static,
+    <Ser as Encoder<V>>::Error: Debug,
+    <Ser as Decoder<V>>::Error: Debug,
+    <<Ser as Decoder<V>>::Encoded as FromEncodedStr>::DecodingError: Debug,
+    <Ser as Encoder<V>>::Encoded: IntoEncodedString,
+    <Ser as Decoder<V>>::Encoded: FromEncodedStr,
+{
+    /// Creates a resource that reads the data for the key returned by `key`.
+    ///
+    /// See [`ArcQueryCache::resource`] for more details.
+    #[track_caller]
+    pub fn resource(
+        &self,
+        key: impl Fn() -> K + Send + Sync + 'static,
+    ) -> Resource<V, Ser> {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.resource(key))
+            .unwrap_or_else(unwrap_signal!(self))
+            .into()
+    }
+
+    /// Creates a blocking resource that reads the data for the key returned by `key`.
+    ///
+    /// See [`ArcQueryCache::resource_blocking`] for more details.
+    #[track_caller]
+    pub fn resource_blocking(
+        &self,
+        key: impl Fn() -> K + Send + Sync + 'static,
+    ) -> Resource<V, Ser> {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.resource_blocking(key))
+            .unwrap_or_else(unwrap_signal!(self))
+            .into()
+    }
+}
+
+/// The current time, measured from the Unix epoch.
+fn now() -> Duration {
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    {
+        Duration::from_millis(js_sys::Date::now() as u64)
+    }
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        OnceLock,
+    };
+
+    /// Creates a cache whose fetcher returns the number of times it has been called.
+    fn counting_cache(
+        options: QueryOptions,
+    ) -> (ArcQueryCache<u32, usize>, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cache = ArcQueryCache::new_with_options(
+            {
+                let calls = Arc::clone(&calls);
+                move |_| {
+                    let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                    async move {
+                        tokio::task::yield_now().await;
+                        call
+                    }
+                }
+            },
+            options,
+        );
+        (cache, calls)
+    }
+
+    async fn settle() {
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_fetches_share_a_request() {
+        _ = any_spawner::Executor::init_tokio();
+        let (cache, calls) = counting_cache(QueryOptions::default());
+
+        let (a, b) = futures::join!(cache.fetch(1), cache.fetch(1));
+        assert_eq!((a, b), (1, 1));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn stale_data_is_returned_while_revalidating() {
+        _ = any_spawner::Executor::init_tokio();
+        let (cache, calls) = counting_cache(QueryOptions {
+            stale_time: Some(Duration::ZERO),
+            ..Default::default()
+        });
+
+        assert_eq!(cache.fetch(1).await, 1);
+        // the stale value is returned immediately, and replaced in the background
+        assert_eq!(cache.fetch(1).await, 1);
+        settle().await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.get_data(&1), Some(2));
+    }
+
+    #[tokio::test]
+    async fn fresh_data_is_reused_until_invalidated() {
+        _ = any_spawner::Executor::init_tokio();
+        let (cache, calls) = counting_cache(QueryOptions {
+            stale_time: None,
+            ..Default::default()
+        });
+
+        assert_eq!(cache.fetch(1).await, 1);
+        assert_eq!(cache.fetch(1).await, 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(!cache.is_stale(&1));
+
+        cache.invalidate(&1);
+        assert!(cache.is_stale(&1));
+        assert_eq!(cache.fetch(1).await, 1);
+        settle().await;
+        assert_eq!(cache.get_data(&1), Some(2));
+        assert!(!cache.is_stale(&1));
+    }
+
+    #[tokio::test]
+    async fn unused_entries_are_garbage_collected() {
+        _ = any_spawner::Executor::init_tokio();
+        let (cache, _) = counting_cache(QueryOptions {
+            gc_time: Duration::ZERO,
+            ..Default::default()
+        });
+
+        assert_eq!(cache.fetch(1).await, 1);
+        cache.collect_garbage();
+        assert_eq!(cache.get_data(&1), None);
+
+        // a request that is never polled does not keep its entry alive
+        drop(cache.fetch(2));
+        assert!(cache.inner.read().or_poisoned().entries.contains_key(&2));
+        cache.collect_garbage();
+        assert!(!cache.inner.read().or_poisoned().entries.contains_key(&2));
+    }
+
+    #[tokio::test]
+    async fn fetcher_can_use_the_cache() {
+        _ = any_spawner::Executor::init_tokio();
+        let this = Arc::new(OnceLock::<ArcQueryCache<u32, u32>>::new());
+        let cache = ArcQueryCache::new({
+            let this = Arc::clone(&this);
+            move |key| {
+                let cache = this.get().expect("cache was stored");
+                let prev = cache.get_data(&key).unwrap_or_default();
+                cache.invalidate(&(key + 1));
+                async move { prev + key }
+            }
+        });
+        _ = this.set(cache.clone());
+
+        assert_eq!(cache.fetch(1).await, 1);
+        // revalidating calls the fetcher as well
+        assert_eq!(cache.fetch(1).await, 1);
+        settle().await;
+        assert_eq!(cache.get_data(&1), Some(2));
+    }
+}
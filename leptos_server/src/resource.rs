@@ -22,7 +22,8 @@ use reactive_graph::{
     graph::{Source, ToAnySubscriber},
     owner::Owner,
     prelude::*,
-    signal::{ArcRwSignal, RwSignal},
+    retry::RetryPolicy,
+    signal::{ArcReadSignal, ArcRwSignal, ReadSignal, RwSignal},
 };
 use std::{
     future::{pending, IntoFuture},
@@ -73,6 +74,7 @@ impl Drop for SuppressResourceLoad {
 pub struct ArcResource<T, Ser = JsonSerdeCodec> {
     ser: PhantomData<Ser>,
     refetch: ArcRwSignal<usize>,
+    attempt: ArcRwSignal<usize>,
     data: ArcAsyncDerived<T>,
     #[cfg(any(debug_assertions, leptos_debuginfo))]
     defined_at: &'static Location<'static>,
@@ -98,6 +100,7 @@ where
             ser: PhantomData,
             data: arc_resource.data.into(),
             refetch: arc_resource.refetch.into(),
+            attempt: arc_resource.attempt.into(),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
         }
@@ -114,6 +117,7 @@ where
             ser: PhantomData,
             data: resource.data.into(),
             refetch: resource.refetch.into(),
+            attempt: resource.attempt.into(),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
         }
@@ -138,6 +142,7 @@ impl<T, Ser> Clone for ArcResource<T, Ser> {
         Self {
             ser: self.ser,
             refetch: self.refetch.clone(),
+            attempt: self.attempt.clone(),
             data: self.data.clone(),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: self.defined_at,
@@ -361,6 +366,7 @@ where
             ser: PhantomData,
             data,
             refetch,
+            attempt: ArcRwSignal::new(0),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
         }
@@ -380,6 +386,15 @@ where
     pub fn refetch(&self) {
         *self.refetch.write() += 1;
     }
+
+    /// The number of the attempt currently being made to load the resource, for a resource
+    /// created with [`new_with_retry`](ArcResource::new_with_retry).
+    ///
+    /// This is `1` during the first attempt, `2` during the first retry, and so on. It is always
+    /// `0` for resources that do not retry.
+    pub fn attempt(&self) -> ArcReadSignal<usize> {
+        self.attempt.read_only()
+    }
 }
 
 #[inline(always)]
@@ -452,6 +467,48 @@ where
     pub fn and_then<U>(&self, f: impl FnOnce(&T) -> U) -> Option<Result<U, E>> {
         self.map(|data| data.as_ref().map(f).map_err(|e| e.clone()))
     }
+
+    /// Creates a new resource with the encoding `Ser`, which retries the `fetcher` according to
+    /// the given [`RetryPolicy`] whenever it returns an error.
+    ///
+    /// The resource stays in its loading state until the last attempt has finished, and only the
+    /// result of that attempt is stored. The number of the attempt currently running is available
+    /// through [`attempt`](Self::attempt).
+    ///
+    /// See [`ArcResource::new_with_options`] for a description of the other arguments.
+    #[track_caller]
+    pub fn new_with_retry<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+        retry: RetryPolicy<E>,
+        blocking: bool,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        let attempt = ArcRwSignal::new(0);
+        let fetcher = Arc::new(fetcher);
+        let mut resource = ArcResource::new_with_options(
+            source,
+            {
+                let attempt = attempt.clone();
+                move |source: S| {
+                    let fetcher = Arc::clone(&fetcher);
+                    let retry = retry.clone();
+                    let attempt = attempt.clone();
+                    async move {
+                        retry
+                            .run(Some(&attempt), || fetcher(source.clone()))
+                            .await
+                    }
+                }
+            },
+            blocking,
+        );
+        resource.attempt = attempt;
+        resource
+    }
 }
 
 impl<T> ArcResource<T, JsonSerdeCodec>
@@ -843,6 +900,7 @@ where
     ser: PhantomData<Ser>,
     data: AsyncDerived<T>,
     refetch: RwSignal<usize>,
+    attempt: RwSignal<usize>,
     #[cfg(any(debug_assertions, leptos_debuginfo))]
     defined_at: &'static Location<'static>,
 }
@@ -1358,12 +1416,18 @@ where
         T: Send + Sync + 'static,
         Fut: Future<Output = T> + Send + 'static,
     {
-        let ArcResource { data, refetch, .. }: ArcResource<T, Ser> =
+        let ArcResource {
+            data,
+            refetch,
+            attempt,
+            ..
+        }: ArcResource<T, Ser> =
             ArcResource::new_with_options(source, fetcher, blocking);
         Resource {
             ser: PhantomData,
             data: data.into(),
             refetch: refetch.into(),
+            attempt: attempt.into(),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
         }
@@ -1381,6 +1445,15 @@ where
     pub fn refetch(&self) {
         self.refetch.try_update(|n| *n += 1);
     }
+
+    /// The number of the attempt currently being made to load the resource, for a resource
+    /// created with [`new_with_retry`](Resource::new_with_retry).
+    ///
+    /// This is `1` during the first attempt, `2` during the first retry, and so on. It is always
+    /// `0` for resources that do not retry.
+    pub fn attempt(&self) -> ReadSignal<usize> {
+        self.attempt.read_only()
+    }
 }
 
 impl<T, E, Ser> Resource<Result<T, E>, Ser>
@@ -1406,6 +1479,40 @@ where
     pub fn and_then<U>(&self, f: impl FnOnce(&T) -> U) -> Option<Result<U, E>> {
         self.map(|data| data.as_ref().map(f).map_err(|e| e.clone()))
     }
+
+    /// Creates a new resource with the encoding `Ser`, which retries the `fetcher` according to
+    /// the given [`RetryPolicy`] whenever it returns an error.
+    ///
+    /// See [`ArcResource::new_with_retry`] for more details.
+    #[track_caller]
+    pub fn new_with_retry<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+        retry: RetryPolicy<E>,
+        blocking: bool,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: 'static,
+        E: 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        let ArcResource {
+            data,
+            refetch,
+            attempt,
+            ..
+        }: ArcResource<Result<T, E>, Ser> =
+            ArcResource::new_with_retry(source, fetcher, retry, blocking);
+        Resource {
+            ser: PhantomData,
+            data: data.into(),
+            refetch: refetch.into(),
+            attempt: attempt.into(),
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+        }
+    }
 }
 
 impl<T, Ser> IntoFuture for Resource<T, Ser>
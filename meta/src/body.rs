@@ -4,7 +4,7 @@ use leptos::{
     component, html,
     reactive::owner::use_context,
     tachys::{
-        dom::document,
+        dom::body,
         html::attribute::Attribute,
         hydration::Cursor,
         view::{
@@ -67,7 +67,7 @@ where
     type State = BodyViewState<At>;
 
     fn build(self) -> Self::State {
-        let el = document().body().expect("there to be a <body> element");
+        let el = body();
         let attributes = self.attributes.build(&el);
 
         BodyViewState { attributes }
@@ -142,7 +142,7 @@ where
         _cursor: &Cursor,
         _position: &PositionState,
     ) -> Self::State {
-        let el = document().body().expect("there to be a <body> element");
+        let el = body();
         let attributes = self.attributes.hydrate::<FROM_SERVER>(&el);
 
         BodyViewState { attributes }
@@ -172,10 +172,9 @@ where
         false
     }
 
+    // with the mock DOM, `<body>` is already an `Element`
+    #[allow(clippy::useless_conversion)]
     fn elements(&self) -> Vec<leptos::tachys::renderer::types::Element> {
-        vec![document()
-            .body()
-            .expect("there to be a <body> element")
-            .into()]
+        vec![body().into()]
     }
 }
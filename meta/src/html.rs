@@ -4,9 +4,9 @@ use leptos::{
     component, html,
     reactive::owner::use_context,
     tachys::{
-        dom::document,
         html::attribute::Attribute,
         hydration::Cursor,
+        renderer::Rndr,
         view::{
             add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
             RenderHtml,
@@ -64,9 +64,7 @@ where
     type State = HtmlViewState<At>;
 
     fn build(self) -> Self::State {
-        let el = document()
-            .document_element()
-            .expect("there to be a <html> element");
+        let el = Rndr::document_element();
 
         let attributes = self.attributes.build(&el);
 
@@ -142,9 +140,7 @@ where
         _cursor: &Cursor,
         _position: &PositionState,
     ) -> Self::State {
-        let el = document()
-            .document_element()
-            .expect("there to be a <html> element");
+        let el = Rndr::document_element();
 
         let attributes = self.attributes.hydrate::<FROM_SERVER>(&el);
 
@@ -178,8 +174,6 @@ where
     }
 
     fn elements(&self) -> Vec<leptos::tachys::renderer::types::Element> {
-        vec![document()
-            .document_element()
-            .expect("there to be a <html> element")]
+        vec![Rndr::document_element()]
     }
 }
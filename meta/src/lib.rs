@@ -51,12 +51,12 @@ use leptos::{
     oco::Oco,
     reactive::owner::{provide_context, use_context},
     tachys::{
-        dom::document,
         html::{
             attribute::Attribute,
             element::{ElementType, HtmlElement},
         },
         hydration::Cursor,
+        renderer::Rndr,
         view::{
             add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
             RenderHtml,
@@ -72,8 +72,6 @@ use std::{
         Arc, LazyLock,
     },
 };
-use wasm_bindgen::JsCast;
-use web_sys::HtmlHeadElement;
 
 mod body;
 mod html;
@@ -119,9 +117,9 @@ const COMMENT_NODE: u16 = 8;
 impl Default for MetaContext {
     fn default() -> Self {
         let build_cursor: fn() -> SendWrapper<Cursor> = || {
-            let head = document().head().expect("missing <head> element");
+            let head = Rndr::head();
             let mut cursor = None;
-            let mut child = head.first_child();
+            let mut child = Rndr::first_child(&head);
             while let Some(this_child) = child {
                 if this_child.node_type() == COMMENT_NODE
                     && this_child.text_content().as_deref()
@@ -130,17 +128,16 @@ impl Default for MetaContext {
                     cursor = Some(this_child);
                     break;
                 }
-                child = this_child.next_sibling();
+                child = Rndr::next_sibling(&this_child);
             }
-            SendWrapper::new(Cursor::new(
-                cursor
-                    .expect(
-                        "no leptos_meta HEAD marker comment found. Did you \
-                         include the <MetaTags/> component in the <head> of \
-                         your server-rendered app?",
-                    )
-                    .unchecked_into(),
-            ))
+            let marker = cursor.expect(
+                "no leptos_meta HEAD marker comment found. Did you include \
+                 the <MetaTags/> component in the <head> of your \
+                 server-rendered app?",
+            );
+            let cursor = Cursor::new(head);
+            cursor.set(marker);
+            SendWrapper::new(cursor)
         };
 
         let cursor = Arc::new(LazyLock::new(build_cursor));
@@ -354,16 +351,6 @@ where
     }
 }
 
-fn document_head() -> HtmlHeadElement {
-    let document = document();
-    document.head().unwrap_or_else(|| {
-        let el = document.create_element("head").unwrap();
-        let document = document.document_element().unwrap();
-        _ = document.append_child(&el);
-        el.unchecked_into()
-    })
-}
-
 impl<E, At, Ch> Render for RegisteredMetaTag<E, At, Ch>
 where
     E: ElementType,
@@ -501,7 +488,7 @@ where
         // but this shouldn't warn about the parent being a regular element or being unused
         // because it will call "mount" with the parent where it is located in the component tree,
         // but actually be mounted to the <head>
-        self.state.mount(&document_head(), None);
+        self.state.mount(&Rndr::head(), None);
     }
 
     fn insert_before_this(&self, _child: &mut dyn Mountable) -> bool {
@@ -3,9 +3,15 @@ use crate::{
     diagnostics::is_suppressing_resource_load,
     graph::untrack,
     owner::{ArcStoredValue, ArenaItem, Owner},
+    retry::RetryPolicy,
     send_wrapper_ext::SendOption,
-    signal::{ArcMappedSignal, ArcRwSignal, MappedSignal, RwSignal},
-    traits::{DefinedAt, Dispose, Get, GetUntracked, GetValue, Update, Write},
+    signal::{
+        ArcMappedSignal, ArcReadSignal, ArcRwSignal, MappedSignal, ReadSignal,
+        RwSignal,
+    },
+    traits::{
+        DefinedAt, Dispose, Get, GetUntracked, GetValue, Set, Update, Write,
+    },
     unwrap_signal,
 };
 use any_spawner::Executor;
@@ -16,7 +22,10 @@ use std::{
     ops::{Deref, DerefMut},
     panic::Location,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 /// An action runs some asynchronous code when you dispatch a new value to it, and gives you
@@ -101,6 +110,7 @@ pub struct ArcAction<I, O> {
     input: ArcRwSignal<SendOption<I>>,
     value: ArcRwSignal<SendOption<O>>,
     version: ArcRwSignal<usize>,
+    attempt: ArcRwSignal<usize>,
     dispatched: ArcStoredValue<usize>,
     #[allow(clippy::complexity)]
     action_fn: Arc<
@@ -117,6 +127,7 @@ impl<I, O> Clone for ArcAction<I, O> {
             input: self.input.clone(),
             value: self.value.clone(),
             version: self.version.clone(),
+            attempt: self.attempt.clone(),
             dispatched: self.dispatched.clone(),
             action_fn: self.action_fn.clone(),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
@@ -206,6 +217,7 @@ where
             input: ArcRwSignal::new(SendOption::new(None)),
             value: ArcRwSignal::new(SendOption::new(value)),
             version: Default::default(),
+            attempt: Default::default(),
             dispatched: Default::default(),
             action_fn: Arc::new(move |input| {
                 let fut = untrack(|| action_fn(input));
@@ -234,6 +246,92 @@ where
     }
 }
 
+impl<I, T, E> ArcAction<I, Result<T, E>>
+where
+    I: Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    /// Creates a new action that retries the `async` function according to the given
+    /// [`RetryPolicy`] whenever it returns an error.
+    ///
+    /// The action remains [`pending`](Self::pending) until the last attempt has finished, and
+    /// only the result of that attempt is stored in [`value`](Self::value). The number of the
+    /// attempt currently running is available through [`attempt`](Self::attempt).
+    ///
+    /// ```rust
+    /// # use reactive_graph::{actions::*, retry::*};
+    /// # use reactive_graph::prelude::*;
+    /// # use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+    /// let calls = Arc::new(AtomicUsize::new(0));
+    /// let act = ArcAction::new_with_retry(
+    ///     RetryPolicy::new(3).backoff(Backoff::None),
+    ///     move |n: &u8| {
+    ///         let n = *n;
+    ///         let calls = Arc::clone(&calls);
+    ///         async move {
+    ///             // fails the first two times it is called
+    ///             if calls.fetch_add(1, Ordering::Relaxed) < 2 {
+    ///                 Err("flaky")
+    ///             } else {
+    ///                 Ok(n * 2)
+    ///             }
+    ///         }
+    ///     },
+    /// );
+    ///
+    /// act.dispatch(3);
+    /// # tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    /// // after it resolves
+    /// assert_eq!(act.value().get(), Some(Ok(6)));
+    /// assert_eq!(act.attempt().get(), 3);
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn new_with_retry<F, Fu>(policy: RetryPolicy<E>, action_fn: F) -> Self
+    where
+        F: Fn(&I) -> Fu + Send + Sync + 'static,
+        Fu: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        let attempt = ArcRwSignal::new(0);
+        let latest_dispatch = Arc::new(AtomicUsize::new(0));
+        let action_fn = Arc::new(action_fn);
+        let mut action = Self::new({
+            let attempt = attempt.clone();
+            move |input: &I| {
+                let input = input.clone();
+                let action_fn = Arc::clone(&action_fn);
+                let policy = policy.clone();
+                let attempt = attempt.clone();
+                let latest_dispatch = Arc::clone(&latest_dispatch);
+                let dispatch =
+                    latest_dispatch.fetch_add(1, Ordering::Relaxed) + 1;
+                async move {
+                    // each dispatch counts its own attempts, but only the latest one reports
+                    // them, so that overlapping dispatches don't overwrite each other's count
+                    let mut current = 0;
+                    policy
+                        .run(None, || {
+                            current += 1;
+                            if latest_dispatch.load(Ordering::Relaxed)
+                                == dispatch
+                            {
+                                attempt.set(current);
+                            }
+                            action_fn(&input)
+                        })
+                        .await
+                }
+            }
+        });
+        action.attempt = attempt;
+        action
+    }
+}
+
 /// A handle that allows aborting an in-flight action. It is returned from [`Action::dispatch`] or
 /// [`ArcAction::dispatch`].
 #[derive(Debug)]
@@ -247,6 +345,11 @@ impl ActionAbortHandle {
     /// effects created by that `Future` no longer run: for example, if the action dispatches an
     /// HTTP request, whether that request is actually canceled or not depends on whether the
     /// request library actually cancels a request when its `Future` is dropped.
+    ///
+    /// Server functions do cancel their requests when dropped, both in the browser and when
+    /// using `reqwest`.
+    ///
+    /// Dropping the handle without calling this method does not abort the action.
     pub fn abort(self) {
         let _ = self.0.send(());
     }
@@ -388,6 +491,7 @@ where
             input: ArcRwSignal::new(SendOption::new_local(None)),
             value: ArcRwSignal::new(SendOption::new_local(value)),
             version: Default::default(),
+            attempt: Default::default(),
             dispatched: Default::default(),
             action_fn: Arc::new(move |input| {
                 let fut = untrack(|| action_fn(input));
@@ -536,6 +640,20 @@ where
         let in_flight = self.in_flight.clone();
         ArcMemo::new(move |_| in_flight.get() > 0)
     }
+
+    /// The number of the attempt currently being made, for an action created with
+    /// [`new_with_retry`](Self::new_with_retry).
+    ///
+    /// This is `1` while the action is making its first attempt, `2` during the first retry, and
+    /// so on, and keeps the number of the last attempt once the action has resolved. It is always
+    /// `0` for actions that do not retry.
+    ///
+    /// If the action is dispatched again while an earlier dispatch is still running, this only
+    /// counts the attempts of the latest dispatch.
+    #[track_caller]
+    pub fn attempt(&self) -> ArcReadSignal<usize> {
+        self.attempt.read_only()
+    }
 }
 
 impl<I, O> DefinedAt for ArcAction<I, O>
@@ -729,6 +847,32 @@ where
     }
 }
 
+impl<I, T, E> Action<I, Result<T, E>>
+where
+    I: Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    /// Creates a new action that retries the `async` function according to the given
+    /// [`RetryPolicy`] whenever it returns an error.
+    ///
+    /// The action remains [`pending`](Self::pending) until the last attempt has finished, and
+    /// only the result of that attempt is stored in [`value`](Self::value). The number of the
+    /// attempt currently running is available through [`attempt`](Self::attempt).
+    #[track_caller]
+    pub fn new_with_retry<F, Fu>(policy: RetryPolicy<E>, action_fn: F) -> Self
+    where
+        F: Fn(&I) -> Fu + Send + Sync + 'static,
+        Fu: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        Self {
+            inner: ArenaItem::new(ArcAction::new_with_retry(policy, action_fn)),
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+        }
+    }
+}
+
 impl<I, O> Action<I, O>
 where
     I: 'static,
@@ -851,6 +995,24 @@ where
             .unwrap_or_else(unwrap_signal!(self));
         inner.into()
     }
+
+    /// The number of the attempt currently being made, for an action created with
+    /// [`new_with_retry`](Self::new_with_retry).
+    ///
+    /// This is `1` while the action is making its first attempt, `2` during the first retry, and
+    /// so on, and keeps the number of the last attempt once the action has resolved. It is always
+    /// `0` for actions that do not retry.
+    ///
+    /// If the action is dispatched again while an earlier dispatch is still running, this only
+    /// counts the attempts of the latest dispatch.
+    #[track_caller]
+    pub fn attempt(&self) -> ReadSignal<usize> {
+        let inner = self
+            .inner
+            .try_with_value(|inner| inner.attempt())
+            .unwrap_or_else(unwrap_signal!(self));
+        inner.into()
+    }
 }
 
 impl<I, O> Action<I, O>
@@ -8,7 +8,7 @@ use std::{
     task::{Context, Poll},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Sender(Arc<Inner>);
 
 #[derive(Debug)]
@@ -50,6 +50,10 @@ pub fn channel() -> (Sender, Receiver) {
 
 impl Sender {
     pub fn notify(&mut self) {
+        // inside a batch, this is held back and sent once the batch ends
+        if crate::effect::defer_notify(self) {
+            return;
+        }
         self.0.set.store(true, Relaxed);
         self.0.waker.wake();
     }
@@ -0,0 +1,335 @@
+//! A source of time and timers for reactive primitives that change over time, like
+//! [`Debounced`](crate::computed::Debounced).
+//!
+//! By default, these use the [`SystemClock`]. Tests can use a [`MockClock`] instead, which only
+//! moves forward when it is told to, so that they do not need to sleep:
+//!
+//! ```
+//! # use reactive_graph::prelude::*;
+//! # use reactive_graph::clock::{provide_clock, Clock, MockClock};
+//! # use std::{sync::{Arc, Mutex}, time::Duration};
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! let clock = MockClock::new();
+//! provide_clock(clock.clone());
+//!
+//! let fired = Arc::new(Mutex::new(false));
+//! let _handle = clock.set_timeout(Duration::from_millis(100), Box::new({
+//!     let fired = Arc::clone(&fired);
+//!     move || *fired.lock().unwrap() = true
+//! }));
+//!
+//! clock.advance(Duration::from_millis(99));
+//! assert!(!*fired.lock().unwrap());
+//! clock.advance(Duration::from_millis(1));
+//! assert!(*fired.lock().unwrap());
+//! ```
+
+use crate::owner::{provide_context, use_context};
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// A source of time, which can schedule callbacks to run in the future.
+pub trait Clock: Send + Sync + 'static {
+    /// The time that has passed since some fixed point, which does not change for the lifetime of
+    /// the clock.
+    fn now(&self) -> Duration;
+
+    /// Runs `callback` once `delay` has passed, unless the returned handle is canceled first.
+    fn set_timeout(
+        &self,
+        delay: Duration,
+        callback: Box<dyn FnOnce() + Send>,
+    ) -> TimeoutHandle;
+}
+
+/// A handle to a callback scheduled with [`Clock::set_timeout`].
+///
+/// Dropping the handle does not cancel the callback.
+#[must_use = "the timeout can only be canceled using its handle"]
+pub struct TimeoutHandle(Box<dyn FnOnce() + Send + Sync>);
+
+impl TimeoutHandle {
+    /// Creates a handle that runs `cancel` when it is canceled.
+    pub fn new(cancel: impl FnOnce() + Send + Sync + 'static) -> Self {
+        Self(Box::new(cancel))
+    }
+
+    /// Cancels the callback, if it has not run yet.
+    pub fn cancel(self) {
+        (self.0)()
+    }
+}
+
+impl Debug for TimeoutHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimeoutHandle").finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone)]
+struct ClockContext(Arc<dyn Clock>);
+
+/// Provides a [`Clock`] to every timed primitive created under the current [`Owner`](crate::owner::Owner).
+pub fn provide_clock(clock: impl Clock) {
+    provide_context(ClockContext(Arc::new(clock)));
+}
+
+/// Returns the [`Clock`] provided by [`provide_clock`], or the [`SystemClock`] if none has been
+/// provided.
+pub fn use_clock() -> Arc<dyn Clock> {
+    use_context::<ClockContext>()
+        .map(|clock| clock.0)
+        .unwrap_or_else(|| Arc::new(SystemClock))
+}
+
+/// The real clock.
+///
+/// In the browser, this uses `setTimeout`. Elsewhere, timers are run on a background thread.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod system {
+    use super::TimeoutHandle;
+    use std::time::Duration;
+    use wasm_bindgen::{closure::Closure, prelude::wasm_bindgen, JsValue};
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_name = setTimeout)]
+        fn set_timeout(callback: &JsValue, ms: f64) -> JsValue;
+
+        #[wasm_bindgen(js_name = clearTimeout)]
+        fn clear_timeout(id: &JsValue);
+    }
+
+    pub fn now() -> Duration {
+        Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+    }
+
+    pub fn timeout(
+        delay: Duration,
+        callback: Box<dyn FnOnce() + Send>,
+    ) -> TimeoutHandle {
+        let id = set_timeout(
+            &Closure::once_into_js(move || callback()),
+            delay.as_secs_f64() * 1000.0,
+        );
+        // the ID is a number, so it's fine to send it to another thread
+        let id = send_wrapper::SendWrapper::new(id);
+        TimeoutHandle::new(move || {
+            if id.valid() {
+                clear_timeout(&id)
+            }
+        })
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod system {
+    use super::{TimeoutHandle, Timers};
+    use or_poisoned::OrPoisoned;
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        sync::{Arc, Condvar, LazyLock, Mutex},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    static START: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+    static TIMERS: LazyLock<Arc<(Mutex<Timers>, Condvar)>> =
+        LazyLock::new(|| {
+            let timers =
+                Arc::new((Mutex::new(Timers::default()), Condvar::new()));
+            thread::Builder::new()
+                .name("reactive_graph timers".into())
+                .spawn({
+                    let timers = Arc::clone(&timers);
+                    move || run(&timers.0, &timers.1)
+                })
+                .expect("could not spawn the timer thread");
+            timers
+        });
+
+    fn run(timers: &Mutex<Timers>, condvar: &Condvar) {
+        let mut lock = timers.lock().or_poisoned();
+        loop {
+            let now = now();
+            if let Some(callback) = lock.pop_due(now) {
+                drop(lock);
+                // a callback that panics should not stop every other timer
+                _ = panic::catch_unwind(AssertUnwindSafe(callback));
+                lock = timers.lock().or_poisoned();
+                continue;
+            }
+            lock = match lock.next_deadline() {
+                Some(deadline) => {
+                    condvar
+                        .wait_timeout(lock, deadline - now)
+                        .expect("lock poisoned")
+                        .0
+                }
+                None => condvar.wait(lock).expect("lock poisoned"),
+            };
+        }
+    }
+
+    pub fn now() -> Duration {
+        START.elapsed()
+    }
+
+    pub fn timeout(
+        delay: Duration,
+        callback: Box<dyn FnOnce() + Send>,
+    ) -> TimeoutHandle {
+        let timers = Arc::clone(&TIMERS);
+        let key = timers
+            .0
+            .lock()
+            .or_poisoned()
+            .insert(now() + delay, callback);
+        // the timer thread may need to wake up earlier than it planned to
+        timers.1.notify_one();
+        TimeoutHandle::new(move || {
+            timers.0.lock().or_poisoned().remove(key);
+        })
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        system::now()
+    }
+
+    fn set_timeout(
+        &self,
+        delay: Duration,
+        callback: Box<dyn FnOnce() + Send>,
+    ) -> TimeoutHandle {
+        system::timeout(delay, callback)
+    }
+}
+
+/// A queue of callbacks, ordered by when they should run.
+#[derive(Default)]
+struct Timers {
+    next_id: u64,
+    queue: BTreeMap<(Duration, u64), Box<dyn FnOnce() + Send>>,
+}
+
+impl Timers {
+    fn insert(
+        &mut self,
+        deadline: Duration,
+        callback: Box<dyn FnOnce() + Send>,
+    ) -> (Duration, u64) {
+        let key = (deadline, self.next_id);
+        self.next_id += 1;
+        self.queue.insert(key, callback);
+        key
+    }
+
+    fn remove(&mut self, key: (Duration, u64)) {
+        self.queue.remove(&key);
+    }
+
+    fn next_deadline(&self) -> Option<Duration> {
+        self.queue
+            .first_key_value()
+            .map(|((deadline, _), _)| *deadline)
+    }
+
+    fn pop_due(&mut self, now: Duration) -> Option<Box<dyn FnOnce() + Send>> {
+        let entry = self.queue.first_entry()?;
+        (entry.key().0 <= now).then(|| entry.remove())
+    }
+}
+
+/// A [`Clock`] that only moves forward when [`advance`](MockClock::advance) is called, for use in
+/// tests.
+///
+/// Callbacks are run on the thread that advances the clock, in the order of their deadlines.
+#[derive(Clone, Default)]
+pub struct MockClock(Arc<Mutex<MockClockInner>>);
+
+#[derive(Default)]
+struct MockClockInner {
+    now: Duration,
+    timers: Timers,
+}
+
+impl Debug for MockClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.0.lock().or_poisoned();
+        f.debug_struct("MockClock")
+            .field("now", &inner.now)
+            .field("pending", &inner.timers.queue.len())
+            .finish()
+    }
+}
+
+impl MockClock {
+    /// Creates a new clock, starting at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the clock forward by `duration`, running every callback that becomes due along the
+    /// way.
+    pub fn advance(&self, duration: Duration) {
+        let target = self.0.lock().or_poisoned().now + duration;
+        loop {
+            let callback = {
+                let mut inner = self.0.lock().or_poisoned();
+                match inner.timers.next_deadline() {
+                    Some(deadline) if deadline <= target => {
+                        inner.now = inner.now.max(deadline);
+                        inner.timers.pop_due(deadline)
+                    }
+                    _ => {
+                        inner.now = target;
+                        None
+                    }
+                }
+            };
+            // callbacks can schedule more callbacks, so the lock is not held while they run
+            match callback {
+                Some(callback) => callback(),
+                None => break,
+            }
+        }
+    }
+
+    /// The number of callbacks that have been scheduled, but have not run or been canceled.
+    pub fn pending(&self) -> usize {
+        self.0.lock().or_poisoned().timers.queue.len()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Duration {
+        self.0.lock().or_poisoned().now
+    }
+
+    fn set_timeout(
+        &self,
+        delay: Duration,
+        callback: Box<dyn FnOnce() + Send>,
+    ) -> TimeoutHandle {
+        let mut inner = self.0.lock().or_poisoned();
+        let deadline = inner.now + delay;
+        let key = inner.timers.insert(deadline, callback);
+        let this = Arc::downgrade(&self.0);
+        TimeoutHandle::new(move || {
+            if let Some(this) = this.upgrade() {
+                this.lock().or_poisoned().timers.remove(key);
+            }
+        })
+    }
+}
@@ -162,6 +162,12 @@ where
 
             MemoInner::new(Arc::new(fun), subscriber)
         });
+        #[cfg(feature = "devtools")]
+        crate::devtools::register_node(
+            crate::devtools::NodeKind::Memo,
+            &inner,
+            Some(Location::caller()),
+        );
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
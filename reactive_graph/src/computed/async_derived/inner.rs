@@ -33,6 +33,19 @@ pub(crate) enum AsyncDerivedState {
     Notifying,
 }
 
+#[cfg(feature = "devtools")]
+impl crate::devtools::Inspect for RwLock<ArcAsyncDerivedInner> {
+    fn sources(&self) -> Vec<usize> {
+        let lock = self.read().or_poisoned();
+        (&lock.sources).into_iter().map(|source| source.0).collect()
+    }
+
+    fn subscribers(&self) -> Vec<usize> {
+        let lock = self.read().or_poisoned();
+        (&lock.subscribers).into_iter().map(|sub| sub.0).collect()
+    }
+}
+
 impl ReactiveNode for RwLock<ArcAsyncDerivedInner> {
     fn mark_dirty(&self) {
         let mut lock = self.write().or_poisoned();
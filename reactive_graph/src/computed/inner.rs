@@ -124,6 +124,9 @@ where
         }
 
         if needs_update(&self.reactivity) {
+            #[cfg(feature = "devtools")]
+            crate::devtools::record_run(self as *const Self as usize);
+
             // No deadlock risk, because we only hold the value lock.
             let value = self.value.write().or_poisoned().take();
 
@@ -174,6 +177,10 @@ where
                     drop(reactivity_lock);
                 }
             }
+            #[cfg(feature = "devtools")]
+            if changed {
+                crate::devtools::record_write(self as *const Self as usize);
+            }
             inner_2(changed, reactivity_lock);
 
             changed
@@ -189,6 +196,22 @@ where
     }
 }
 
+#[cfg(feature = "devtools")]
+impl<T: 'static, S> crate::devtools::Inspect for MemoInner<T, S>
+where
+    S: Storage<T>,
+{
+    fn sources(&self) -> Vec<usize> {
+        let lock = self.reactivity.read().or_poisoned();
+        (&lock.sources).into_iter().map(|source| source.0).collect()
+    }
+
+    fn subscribers(&self) -> Vec<usize> {
+        let lock = self.reactivity.read().or_poisoned();
+        (&lock.subscribers).into_iter().map(|sub| sub.0).collect()
+    }
+}
+
 impl<T: 'static, S> Source for MemoInner<T, S>
 where
     S: Storage<T>,
@@ -0,0 +1,596 @@
+use crate::{
+    clock::{use_clock, Clock, TimeoutHandle},
+    effect::ImmediateEffect,
+    graph::{untrack, SubscriberSet},
+    owner::{ArenaItem, Owner, Storage, SyncStorage},
+    signal::{
+        guards::{Plain, ReadGuard},
+        subscriber_traits::AsSubscriberSet,
+        ArcRwSignal,
+    },
+    traits::{DefinedAt, Dispose, IsDisposed, ReadUntracked, Set},
+    unwrap_signal,
+};
+use or_poisoned::OrPoisoned;
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    panic::Location,
+    sync::{Arc, Mutex, RwLock, Weak},
+    time::Duration,
+};
+
+/// How a timed value follows its source.
+#[derive(Debug, Clone, Copy)]
+enum Timing {
+    Debounce(Duration),
+    Throttle(Duration),
+    Sample(Duration),
+}
+
+struct TimedState<T> {
+    /// The latest value of the source that has not been emitted yet.
+    pending: Option<T>,
+    timer: Option<TimeoutHandle>,
+    /// Incremented whenever a timer is scheduled or canceled, so that a callback that was
+    /// already running when its timer was canceled can tell that it is stale.
+    generation: u64,
+    /// When the value was last updated, used for throttling.
+    last_emit: Option<Duration>,
+    /// When the value was created, used to line samples up with the sample period.
+    start: Duration,
+}
+
+/// The shared implementation of [`ArcDebounced`], [`ArcThrottled`] and [`ArcSampled`].
+struct TimedInner<T> {
+    timing: Timing,
+    clock: Arc<dyn Clock>,
+    value: ArcRwSignal<T>,
+    state: Mutex<TimedState<T>>,
+    /// Tracks the source, and is dropped to stop tracking it.
+    effect: Mutex<Option<ImmediateEffect>>,
+}
+
+impl<T> TimedInner<T>
+where
+    T: Send + Sync + 'static,
+{
+    #[track_caller]
+    fn new(
+        timing: Timing,
+        source: impl Fn() -> T + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        let clock = use_clock();
+        let this = Arc::new(Self {
+            timing,
+            value: ArcRwSignal::new(untrack(&source)),
+            state: Mutex::new(TimedState {
+                pending: None,
+                timer: None,
+                generation: 0,
+                last_emit: None,
+                start: clock.now(),
+            }),
+            clock,
+            effect: Mutex::new(None),
+        });
+
+        let effect = ImmediateEffect::new_isomorphic({
+            let this = Arc::downgrade(&this);
+            let first_run = Mutex::new(true);
+            move || {
+                let value = source();
+                // the value has already been read once to create the signal
+                if std::mem::take(&mut *first_run.lock().or_poisoned()) {
+                    return;
+                }
+                if let Some(this) = this.upgrade() {
+                    this.changed(value);
+                }
+            }
+        });
+        *this.effect.lock().or_poisoned() = Some(effect);
+
+        // stop the timers when the owner is cleaned up
+        let weak = Arc::downgrade(&this);
+        Owner::on_cleanup(move || {
+            if let Some(this) = weak.upgrade() {
+                this.stop();
+            }
+        });
+
+        this
+    }
+
+    fn changed(self: &Arc<Self>, value: T) {
+        let now = self.clock.now();
+        let mut state = self.state.lock().or_poisoned();
+        let delay = match self.timing {
+            Timing::Debounce(delay) => {
+                if let Some(timer) = state.timer.take() {
+                    timer.cancel();
+                }
+                delay
+            }
+            Timing::Throttle(interval) => {
+                let ready = state
+                    .last_emit
+                    .is_none_or(|last| now.saturating_sub(last) >= interval);
+                if ready && state.timer.is_none() {
+                    state.last_emit = Some(now);
+                    state.pending = None;
+                    drop(state);
+                    self.value.set(value);
+                    return;
+                }
+                let last = state.last_emit.unwrap_or(now);
+                (last + interval).saturating_sub(now)
+            }
+            Timing::Sample(period) => {
+                let elapsed = now.saturating_sub(state.start).as_nanos();
+                let period_nanos = period.as_nanos().max(1);
+                let remaining = period_nanos - elapsed % period_nanos;
+                Duration::from_nanos(remaining.try_into().unwrap_or(u64::MAX))
+            }
+        };
+
+        state.pending = Some(value);
+        if state.timer.is_none() {
+            state.generation += 1;
+            let generation = state.generation;
+            let this = Arc::downgrade(self);
+            state.timer = Some(self.clock.set_timeout(
+                delay,
+                Box::new(move || Self::fire(&this, generation)),
+            ));
+        }
+    }
+
+    fn fire(this: &Weak<Self>, generation: u64) {
+        let Some(this) = this.upgrade() else {
+            return;
+        };
+        let value = {
+            let mut state = this.state.lock().or_poisoned();
+            // a clock that runs callbacks on another thread may already have started running
+            // this one when it was canceled
+            if state.generation != generation {
+                return;
+            }
+            state.timer = None;
+            state.last_emit = Some(this.clock.now());
+            state.pending.take()
+        };
+        // the lock is not held while notifying, because this may cause the source to change
+        if let Some(value) = value {
+            this.value.set(value);
+        }
+    }
+
+    fn stop(&self) {
+        let effect = self.effect.lock().or_poisoned().take();
+        drop(effect);
+        let mut state = self.state.lock().or_poisoned();
+        state.pending = None;
+        state.generation += 1;
+        if let Some(timer) = state.timer.take() {
+            timer.cancel();
+        }
+    }
+}
+
+impl<T> Drop for TimedInner<T> {
+    fn drop(&mut self) {
+        let state = self.state.get_mut().expect("lock poisoned");
+        if let Some(timer) = state.timer.take() {
+            timer.cancel();
+        }
+    }
+}
+
+macro_rules! timed {
+    (
+        $(#[$arc_meta:meta])*
+        $arc:ident,
+        $(#[$meta:meta])*
+        $arena:ident,
+        $timing:ident,
+        $(#[$new_meta:meta])*
+        $param:ident
+    ) => {
+        $(#[$arc_meta])*
+        pub struct $arc<T> {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: &'static Location<'static>,
+            inner: Arc<TimedInner<T>>,
+        }
+
+        impl<T> $arc<T>
+        where
+            T: Send + Sync + 'static,
+        {
+            $(#[$new_meta])*
+            #[track_caller]
+            pub fn new(
+                source: impl Fn() -> T + Send + Sync + 'static,
+                $param: Duration,
+            ) -> Self {
+                Self {
+                    #[cfg(any(debug_assertions, leptos_debuginfo))]
+                    defined_at: Location::caller(),
+                    inner: TimedInner::new(Timing::$timing($param), source),
+                }
+            }
+        }
+
+        impl<T> Clone for $arc<T> {
+            #[track_caller]
+            fn clone(&self) -> Self {
+                Self {
+                    #[cfg(any(debug_assertions, leptos_debuginfo))]
+                    defined_at: self.defined_at,
+                    inner: Arc::clone(&self.inner),
+                }
+            }
+        }
+
+        impl<T> Debug for $arc<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($arc))
+                    .field("type", &std::any::type_name::<T>())
+                    .field("timing", &self.inner.timing)
+                    .field("data", &Arc::as_ptr(&self.inner))
+                    .finish()
+            }
+        }
+
+        impl<T> PartialEq for $arc<T> {
+            fn eq(&self, other: &Self) -> bool {
+                Arc::ptr_eq(&self.inner, &other.inner)
+            }
+        }
+
+        impl<T> Eq for $arc<T> {}
+
+        impl<T> Hash for $arc<T> {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                std::ptr::hash(&Arc::as_ptr(&self.inner), state);
+            }
+        }
+
+        impl<T> DefinedAt for $arc<T> {
+            #[inline(always)]
+            fn defined_at(&self) -> Option<&'static Location<'static>> {
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                {
+                    Some(self.defined_at)
+                }
+                #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+                {
+                    None
+                }
+            }
+        }
+
+        impl<T> IsDisposed for $arc<T> {
+            #[inline(always)]
+            fn is_disposed(&self) -> bool {
+                false
+            }
+        }
+
+        impl<T> AsSubscriberSet for $arc<T> {
+            type Output = Arc<RwLock<SubscriberSet>>;
+
+            #[inline(always)]
+            fn as_subscriber_set(&self) -> Option<Self::Output> {
+                Some(Arc::clone(&self.inner.value.inner))
+            }
+        }
+
+        impl<T: 'static> ReadUntracked for $arc<T> {
+            type Value = ReadGuard<T, Plain<T>>;
+
+            #[track_caller]
+            fn try_read_untracked(&self) -> Option<Self::Value> {
+                self.inner.value.try_read_untracked()
+            }
+        }
+
+        $(#[$meta])*
+        pub struct $arena<T, S = SyncStorage> {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: &'static Location<'static>,
+            inner: ArenaItem<$arc<T>, S>,
+        }
+
+        impl<T> $arena<T>
+        where
+            T: Send + Sync + 'static,
+        {
+            $(#[$new_meta])*
+            #[track_caller]
+            pub fn new(
+                source: impl Fn() -> T + Send + Sync + 'static,
+                $param: Duration,
+            ) -> Self {
+                Self {
+                    #[cfg(any(debug_assertions, leptos_debuginfo))]
+                    defined_at: Location::caller(),
+                    inner: ArenaItem::new_with_storage($arc::new(source, $param)),
+                }
+            }
+        }
+
+        impl<T> From<$arc<T>> for $arena<T>
+        where
+            T: Send + Sync + 'static,
+        {
+            #[track_caller]
+            fn from(value: $arc<T>) -> Self {
+                Self {
+                    #[cfg(any(debug_assertions, leptos_debuginfo))]
+                    defined_at: Location::caller(),
+                    inner: ArenaItem::new_with_storage(value),
+                }
+            }
+        }
+
+        impl<T, S> Dispose for $arena<T, S> {
+            fn dispose(self) {
+                self.inner.dispose()
+            }
+        }
+
+        impl<T, S> Copy for $arena<T, S> {}
+
+        impl<T, S> Clone for $arena<T, S> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<T, S> Debug for $arena<T, S>
+        where
+            S: Debug,
+        {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($arena))
+                    .field("type", &std::any::type_name::<T>())
+                    .field("store", &self.inner)
+                    .finish()
+            }
+        }
+
+        impl<T, S> PartialEq for $arena<T, S> {
+            fn eq(&self, other: &Self) -> bool {
+                self.inner == other.inner
+            }
+        }
+
+        impl<T, S> Eq for $arena<T, S> {}
+
+        impl<T, S> Hash for $arena<T, S> {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.inner.hash(state);
+            }
+        }
+
+        impl<T, S> DefinedAt for $arena<T, S> {
+            fn defined_at(&self) -> Option<&'static Location<'static>> {
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                {
+                    Some(self.defined_at)
+                }
+                #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+                {
+                    None
+                }
+            }
+        }
+
+        impl<T, S> IsDisposed for $arena<T, S> {
+            fn is_disposed(&self) -> bool {
+                self.inner.is_disposed()
+            }
+        }
+
+        impl<T, S> AsSubscriberSet for $arena<T, S>
+        where
+            S: Storage<$arc<T>>,
+        {
+            type Output = Arc<RwLock<SubscriberSet>>;
+
+            fn as_subscriber_set(&self) -> Option<Self::Output> {
+                self.inner
+                    .try_with_value(|inner| inner.as_subscriber_set())
+                    .flatten()
+            }
+        }
+
+        impl<T, S> ReadUntracked for $arena<T, S>
+        where
+            T: 'static,
+            S: Storage<$arc<T>>,
+        {
+            type Value = ReadGuard<T, Plain<T>>;
+
+            fn try_read_untracked(&self) -> Option<Self::Value> {
+                self.inner
+                    .try_get_value()
+                    .and_then(|inner| inner.try_read_untracked())
+            }
+        }
+
+        impl<T, S> From<$arena<T, S>> for $arc<T>
+        where
+            S: Storage<$arc<T>>,
+        {
+            #[track_caller]
+            fn from(value: $arena<T, S>) -> Self {
+                value
+                    .inner
+                    .try_get_value()
+                    .unwrap_or_else(unwrap_signal!(value))
+            }
+        }
+    };
+}
+
+timed! {
+    /// A reference-counted value that follows its source, but only updates once the source has
+    /// stopped changing for some period of time.
+    ///
+    /// This is useful for things like search boxes, where some expensive work should only be done
+    /// once the user has stopped typing. The value starts out as the current value of the source.
+    ///
+    /// Timers run on the [`Clock`] provided with [`provide_clock`](crate::clock::provide_clock),
+    /// and are canceled when the current [`Owner`] is cleaned up.
+    ///
+    /// This is a reference-counted type, which is `Clone` but not `Copy`. For an arena-allocated
+    /// `Copy` version, use [`Debounced`].
+    ///
+    /// ## Core Trait Implementations
+    /// - [`.get()`](crate::traits::Get) clones the current value of the debounced value.
+    ///   If you call it within an effect, it will cause that effect to subscribe
+    ///   to the value, and to re-run whenever it updates.
+    /// - [`.read()`](crate::traits::Read) returns a guard that allows accessing the value
+    ///   by reference.
+    /// - [`.with()`](crate::traits::With) allows you to reactively access the value without
+    ///   cloning by applying a callback function.
+    /// - [`.track()`](crate::traits::Track) subscribes to the value without reading it.
+    ArcDebounced,
+    /// An arena-allocated value that follows its source, but only updates once the source has
+    /// stopped changing for some period of time.
+    ///
+    /// This is useful for things like search boxes, where some expensive work should only be done
+    /// once the user has stopped typing. The value starts out as the current value of the source.
+    ///
+    /// Timers run on the [`Clock`] provided with [`provide_clock`](crate::clock::provide_clock),
+    /// and are canceled when the current [`Owner`] is cleaned up.
+    ///
+    /// ```
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::{clock::{provide_clock, MockClock}, computed::Debounced, signal::RwSignal};
+    /// # use std::time::Duration;
+    /// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let clock = MockClock::new(); provide_clock(clock.clone());
+    /// let search = RwSignal::new(String::new());
+    /// let query = Debounced::new(move || search.get(), Duration::from_millis(300));
+    ///
+    /// search.set("lep".to_string());
+    /// clock.advance(Duration::from_millis(200));
+    /// search.set("leptos".to_string());
+    /// clock.advance(Duration::from_millis(200));
+    /// // the search box has only been quiet for 200ms
+    /// assert_eq!(query.get(), "");
+    ///
+    /// clock.advance(Duration::from_millis(100));
+    /// assert_eq!(query.get(), "leptos");
+    /// ```
+    Debounced,
+    Debounce,
+    /// Creates a value that is updated to the latest value of `source` once it has not changed
+    /// for `delay`.
+    delay
+}
+
+timed! {
+    /// A reference-counted value that follows its source, but updates at most once per interval.
+    ///
+    /// The first change is applied immediately. Any changes during the following interval are
+    /// held back, and the latest of them is applied when the interval ends. This is useful for
+    /// things like resize or scroll handlers. The value starts out as the current value of the
+    /// source.
+    ///
+    /// Timers run on the [`Clock`] provided with [`provide_clock`](crate::clock::provide_clock),
+    /// and are canceled when the current [`Owner`] is cleaned up.
+    ///
+    /// This is a reference-counted type, which is `Clone` but not `Copy`. For an arena-allocated
+    /// `Copy` version, use [`Throttled`].
+    ArcThrottled,
+    /// An arena-allocated value that follows its source, but updates at most once per interval.
+    ///
+    /// The first change is applied immediately. Any changes during the following interval are
+    /// held back, and the latest of them is applied when the interval ends. This is useful for
+    /// things like resize or scroll handlers. The value starts out as the current value of the
+    /// source.
+    ///
+    /// Timers run on the [`Clock`] provided with [`provide_clock`](crate::clock::provide_clock),
+    /// and are canceled when the current [`Owner`] is cleaned up.
+    ///
+    /// ```
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::{clock::{provide_clock, MockClock}, computed::Throttled, signal::RwSignal};
+    /// # use std::time::Duration;
+    /// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let clock = MockClock::new(); provide_clock(clock.clone());
+    /// let width = RwSignal::new(800);
+    /// let throttled = Throttled::new(move || width.get(), Duration::from_millis(100));
+    ///
+    /// width.set(810);
+    /// assert_eq!(throttled.get(), 810);
+    ///
+    /// width.set(820);
+    /// width.set(830);
+    /// assert_eq!(throttled.get(), 810);
+    ///
+    /// clock.advance(Duration::from_millis(100));
+    /// assert_eq!(throttled.get(), 830);
+    /// ```
+    Throttled,
+    Throttle,
+    /// Creates a value that is updated to the latest value of `source` at most once per
+    /// `interval`.
+    interval
+}
+
+timed! {
+    /// A reference-counted value that follows its source, but only picks up changes at the end of
+    /// each sample period.
+    ///
+    /// Sample periods are counted from when the value is created. If the source has changed
+    /// during a period, the value is updated to its latest value when that period ends. This is
+    /// useful for things like autosaving, or showing progress that changes very often. The value
+    /// starts out as the current value of the source.
+    ///
+    /// Timers run on the [`Clock`] provided with [`provide_clock`](crate::clock::provide_clock),
+    /// and are canceled when the current [`Owner`] is cleaned up.
+    ///
+    /// This is a reference-counted type, which is `Clone` but not `Copy`. For an arena-allocated
+    /// `Copy` version, use [`Sampled`].
+    ArcSampled,
+    /// An arena-allocated value that follows its source, but only picks up changes at the end of
+    /// each sample period.
+    ///
+    /// Sample periods are counted from when the value is created. If the source has changed
+    /// during a period, the value is updated to its latest value when that period ends. This is
+    /// useful for things like autosaving, or showing progress that changes very often. The value
+    /// starts out as the current value of the source.
+    ///
+    /// Timers run on the [`Clock`] provided with [`provide_clock`](crate::clock::provide_clock),
+    /// and are canceled when the current [`Owner`] is cleaned up.
+    ///
+    /// ```
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::{clock::{provide_clock, MockClock}, computed::Sampled, signal::RwSignal};
+    /// # use std::time::Duration;
+    /// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let clock = MockClock::new(); provide_clock(clock.clone());
+    /// let progress = RwSignal::new(0);
+    /// let sampled = Sampled::new(move || progress.get(), Duration::from_millis(1000));
+    ///
+    /// clock.advance(Duration::from_millis(400));
+    /// progress.set(10);
+    /// progress.set(20);
+    /// assert_eq!(sampled.get(), 0);
+    ///
+    /// clock.advance(Duration::from_millis(600));
+    /// assert_eq!(sampled.get(), 20);
+    /// ```
+    Sampled,
+    Sample,
+    /// Creates a value that is updated to the latest value of `source` at the end of each
+    /// `period` in which it has changed.
+    period
+}
@@ -0,0 +1,459 @@
+//! Introspection of the reactive graph, for building developer tools.
+//!
+//! When the `devtools` feature is enabled, every owner, signal, memo, and effect that is created
+//! is registered here, and signal writes and memo and effect runs are recorded in a timeline.
+//! [`snapshot`] returns the current state of the graph, which can be serialized and consumed by an
+//! external inspector.
+//!
+//! Each owner and node is given an ID when it is created. IDs are never reused while the program
+//! runs, but they are only meant for debugging, and are not stable between runs.
+//!
+//! ```
+//! # use reactive_graph::prelude::*;
+//! # use reactive_graph::{devtools, computed::Memo, signal::RwSignal};
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! let count = RwSignal::new(0);
+//! let double = Memo::new(move |_| count.get() * 2);
+//! assert_eq!(double.get(), 0);
+//!
+//! devtools::clear_timeline();
+//! count.set(1);
+//! assert_eq!(double.get(), 2);
+//!
+//! let snapshot = devtools::snapshot();
+//! let memo = snapshot
+//!     .nodes
+//!     .iter()
+//!     .find(|node| node.kind == devtools::NodeKind::Memo)
+//!     .unwrap();
+//! // the memo reran because the signal it reads from was written to
+//! let run = snapshot
+//!     .timeline
+//!     .iter()
+//!     .find(|event| event.node == memo.id)
+//!     .unwrap();
+//! assert_eq!(run.kind, devtools::EventKind::Run { caused_by: memo.sources.clone() });
+//! ```
+
+use crate::{graph::SubscriberSet, owner::Owner};
+use or_poisoned::OrPoisoned;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    collections::VecDeque,
+    panic::Location,
+    sync::{Arc, LazyLock, Mutex, RwLock, Weak},
+};
+
+/// The number of events kept in the timeline by default.
+const DEFAULT_TIMELINE_CAPACITY: usize = 1000;
+
+/// The number of registered owners and nodes at which entries for dropped ones are first removed.
+const MIN_PRUNE_AT: usize = 256;
+
+static REGISTRY: LazyLock<Mutex<Registry>> = LazyLock::new(Default::default);
+
+/// Gives access to the edges of a node in the reactive graph.
+pub(crate) trait Inspect: Send + Sync {
+    /// The IDs of the nodes that this node depends on.
+    fn sources(&self) -> Vec<usize> {
+        Vec::new()
+    }
+
+    /// The IDs of the nodes that depend on this node.
+    fn subscribers(&self) -> Vec<usize> {
+        Vec::new()
+    }
+}
+
+impl Inspect for RwLock<SubscriberSet> {
+    fn subscribers(&self) -> Vec<usize> {
+        let subs = self.read().or_poisoned();
+        (&*subs).into_iter().map(|sub| sub.0).collect()
+    }
+}
+
+struct NodeEntry {
+    id: usize,
+    kind: NodeKind,
+    defined_at: Option<&'static Location<'static>>,
+    owner: Option<usize>,
+    node: Weak<dyn Inspect>,
+    /// The sequence number of the last time this node was written to or changed.
+    last_write: Option<u64>,
+    /// The sequence number of the last time this node ran.
+    last_run: Option<u64>,
+}
+
+struct OwnerEntry {
+    id: usize,
+    owner: Weak<dyn Any + Send + Sync>,
+    parent: Option<usize>,
+}
+
+/// Owners and nodes are keyed by their address, which is how the reactive graph refers to them.
+/// An address can be reused once the owner or node has been dropped, so entries are only used
+/// while they are still alive, and are replaced when something new is registered at the same
+/// address.
+struct Registry {
+    next_id: usize,
+    next_seq: u64,
+    nodes: FxHashMap<usize, NodeEntry>,
+    owners: FxHashMap<usize, OwnerEntry>,
+    /// The number of entries at which dropped owners and nodes are next removed.
+    prune_at: usize,
+    timeline: VecDeque<Event>,
+    timeline_capacity: usize,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            next_seq: 0,
+            nodes: Default::default(),
+            owners: Default::default(),
+            prune_at: MIN_PRUNE_AT,
+            timeline: Default::default(),
+            timeline_capacity: DEFAULT_TIMELINE_CAPACITY,
+        }
+    }
+}
+
+impl Registry {
+    fn next_id(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn node(&self, address: usize) -> Option<&NodeEntry> {
+        self.nodes
+            .get(&address)
+            .filter(|node| node.node.strong_count() > 0)
+    }
+
+    fn node_mut(&mut self, address: usize) -> Option<&mut NodeEntry> {
+        self.nodes
+            .get_mut(&address)
+            .filter(|node| node.node.strong_count() > 0)
+    }
+
+    fn owner(&self, address: usize) -> Option<&OwnerEntry> {
+        self.owners
+            .get(&address)
+            .filter(|owner| owner.owner.strong_count() > 0)
+    }
+
+    fn push(&mut self, node: usize, kind: EventKind) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        if self.timeline_capacity > 0 {
+            while self.timeline.len() >= self.timeline_capacity {
+                self.timeline.pop_front();
+            }
+            self.timeline.push_back(Event { seq, node, kind });
+        }
+        seq
+    }
+
+    fn prune(&mut self) {
+        self.nodes.retain(|_, node| node.node.strong_count() > 0);
+        self.owners
+            .retain(|_, owner| owner.owner.strong_count() > 0);
+    }
+
+    /// Removes dropped owners and nodes once the number of entries has doubled since the last
+    /// time, so that the registry does not grow without bound if [`snapshot`] is never called.
+    fn prune_if_needed(&mut self) {
+        if self.nodes.len() + self.owners.len() >= self.prune_at {
+            self.prune();
+            self.prune_at =
+                ((self.nodes.len() + self.owners.len()) * 2).max(MIN_PRUNE_AT);
+        }
+    }
+}
+
+/// The kind of a node in the reactive graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NodeKind {
+    /// A signal, like an [`ArcRwSignal`](crate::signal::ArcRwSignal).
+    Signal,
+    /// A trigger, like an [`ArcTrigger`](crate::signal::ArcTrigger).
+    Trigger,
+    /// A memo, like an [`ArcMemo`](crate::computed::ArcMemo).
+    Memo,
+    /// An async derived value, like an
+    /// [`ArcAsyncDerived`](crate::computed::ArcAsyncDerived).
+    AsyncDerived,
+    /// An [`Effect`](crate::effect::Effect).
+    Effect,
+    /// A [`RenderEffect`](crate::effect::RenderEffect).
+    RenderEffect,
+    /// An [`ImmediateEffect`](crate::effect::ImmediateEffect).
+    ImmediateEffect,
+}
+
+/// A live node in the reactive graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeSnapshot {
+    /// The ID of the node.
+    pub id: usize,
+    /// What kind of node this is.
+    pub kind: NodeKind,
+    /// Where the node was created, as `file:line:column`, if known.
+    pub defined_at: Option<String>,
+    /// The ID of the owner that was active when the node was created, if any.
+    pub owner: Option<usize>,
+    /// The IDs of the nodes that this node depends on.
+    pub sources: Vec<usize>,
+    /// The IDs of the nodes that depend on this node.
+    pub subscribers: Vec<usize>,
+}
+
+/// A live reactive [`Owner`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnerSnapshot {
+    /// The ID of the owner.
+    pub id: usize,
+    /// The owner's [`Owner::debug_id`], which can be used to find the snapshot of a given owner.
+    pub debug_id: usize,
+    /// The ID of the parent owner, if any.
+    pub parent: Option<usize>,
+}
+
+/// Something that happened to a node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventKind {
+    /// A signal or trigger was notified of a change, or a memo's value changed.
+    Write,
+    /// A memo or effect ran.
+    Run {
+        /// The IDs of the sources that had been written to since the last time the node ran,
+        /// which is why it ran again. This is empty for the first run.
+        caused_by: Vec<usize>,
+    },
+}
+
+/// An entry in the timeline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    /// The position of this event in the timeline, which increases with every event.
+    pub seq: u64,
+    /// The ID of the node this happened to.
+    pub node: usize,
+    /// What happened.
+    pub kind: EventKind,
+}
+
+/// The state of the reactive graph at one point in time.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Every live owner.
+    pub owners: Vec<OwnerSnapshot>,
+    /// Every live node.
+    pub nodes: Vec<NodeSnapshot>,
+    /// The most recent events, oldest first.
+    pub timeline: Vec<Event>,
+}
+
+/// Returns a snapshot of every live owner and node, and the recorded timeline.
+pub fn snapshot() -> Snapshot {
+    // the registry is not locked while inspecting nodes, because inspecting a node locks it
+    let (owners, nodes, ids, timeline) = {
+        let mut registry = REGISTRY.lock().or_poisoned();
+        registry.prune();
+        let owners = registry
+            .owners
+            .iter()
+            .map(|(address, owner)| OwnerSnapshot {
+                id: owner.id,
+                debug_id: *address,
+                parent: owner.parent,
+            })
+            .collect::<Vec<_>>();
+        let nodes = registry
+            .nodes
+            .values()
+            .map(|node| {
+                (
+                    node.id,
+                    node.kind,
+                    node.defined_at,
+                    node.owner,
+                    node.node.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let ids = registry
+            .nodes
+            .iter()
+            .map(|(address, node)| (*address, node.id))
+            .collect::<FxHashMap<_, _>>();
+        (
+            owners,
+            nodes,
+            ids,
+            registry.timeline.iter().cloned().collect(),
+        )
+    };
+    let to_ids = |addresses: Vec<usize>| {
+        addresses
+            .into_iter()
+            .filter_map(|address| ids.get(&address).copied())
+            .collect::<Vec<_>>()
+    };
+
+    let mut nodes = nodes
+        .into_iter()
+        .filter_map(|(id, kind, defined_at, owner, node)| {
+            let node = node.upgrade()?;
+            Some(NodeSnapshot {
+                id,
+                kind,
+                defined_at: defined_at.map(ToString::to_string),
+                owner,
+                sources: to_ids(node.sources()),
+                subscribers: to_ids(node.subscribers()),
+            })
+        })
+        .collect::<Vec<_>>();
+    nodes.sort_by_key(|node| node.id);
+    let mut owners = owners;
+    owners.sort_by_key(|owner| owner.id);
+
+    Snapshot {
+        owners,
+        nodes,
+        timeline,
+    }
+}
+
+/// Removes every event from the timeline.
+pub fn clear_timeline() {
+    REGISTRY.lock().or_poisoned().timeline.clear();
+}
+
+/// Sets the number of events that are kept in the timeline. Once there are more events than
+/// this, the oldest ones are discarded. Setting it to `0` stops recording events.
+///
+/// By default, the most recent 1000 events are kept.
+pub fn set_timeline_capacity(capacity: usize) {
+    let mut registry = REGISTRY.lock().or_poisoned();
+    registry.timeline_capacity = capacity;
+    while registry.timeline.len() > capacity {
+        registry.timeline.pop_front();
+    }
+}
+
+pub(crate) fn register_owner<T: Send + Sync + 'static>(
+    owner: &Arc<T>,
+    parent: Option<usize>,
+) {
+    let address = Arc::as_ptr(owner) as *const () as usize;
+    let owner = Arc::downgrade(owner) as Weak<dyn Any + Send + Sync>;
+    let mut registry = REGISTRY.lock().or_poisoned();
+    registry.prune_if_needed();
+    let id = registry.next_id();
+    let parent = parent
+        .and_then(|parent| registry.owner(parent))
+        .map(|parent| parent.id);
+    registry
+        .owners
+        .insert(address, OwnerEntry { id, owner, parent });
+}
+
+pub(crate) fn register_node<T: Inspect + 'static>(
+    kind: NodeKind,
+    node: &Arc<T>,
+    defined_at: Option<&'static Location<'static>>,
+) {
+    let address = Arc::as_ptr(node) as *const () as usize;
+    let owner = Owner::current().map(|owner| owner.debug_id());
+    let node = Arc::downgrade(node) as Weak<dyn Inspect>;
+    let mut registry = REGISTRY.lock().or_poisoned();
+    registry.prune_if_needed();
+    let id = registry.next_id();
+    let owner = owner
+        .and_then(|owner| registry.owner(owner))
+        .map(|owner| owner.id);
+    registry.nodes.insert(
+        address,
+        NodeEntry {
+            id,
+            kind,
+            defined_at,
+            owner,
+            node,
+            last_write: None,
+            last_run: None,
+        },
+    );
+}
+
+/// Records that the node at the given address was written to, or that its value changed.
+pub(crate) fn record_write(address: usize) {
+    let mut registry = REGISTRY.lock().or_poisoned();
+    let Some(id) = registry.node(address).map(|node| node.id) else {
+        return;
+    };
+    let seq = registry.push(id, EventKind::Write);
+    if let Some(node) = registry.node_mut(address) {
+        node.last_write = Some(seq);
+    }
+}
+
+/// Records that the node at the given address is about to run.
+///
+/// This must be called before the node's sources are cleared, because they are used to find out
+/// why it ran, and while the node is not locked.
+pub(crate) fn record_run(address: usize) {
+    let node = match REGISTRY.lock().or_poisoned().node(address) {
+        Some(node) => node.node.clone(),
+        None => return,
+    };
+    let sources = node
+        .upgrade()
+        .map(|node| node.sources())
+        .unwrap_or_default();
+
+    let mut registry = REGISTRY.lock().or_poisoned();
+    let Some((id, last_run)) =
+        registry.node(address).map(|node| (node.id, node.last_run))
+    else {
+        return;
+    };
+    let caused_by = match last_run {
+        None => Vec::new(),
+        Some(last_run) => sources
+            .into_iter()
+            .filter_map(|source| registry.node(source))
+            .filter(|source| {
+                source
+                    .last_write
+                    .is_some_and(|last_write| last_write > last_run)
+            })
+            .map(|source| source.id)
+            .collect(),
+    };
+    let seq = registry.push(id, EventKind::Run { caused_by });
+    if let Some(node) = registry.node_mut(address) {
+        node.last_run = Some(seq);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::REGISTRY;
+    use crate::signal::ArcTrigger;
+    use or_poisoned::OrPoisoned;
+
+    #[test]
+    fn dropped_nodes_are_removed_without_a_snapshot() {
+        for _ in 0..10_000 {
+            drop(ArcTrigger::new());
+        }
+        assert!(REGISTRY.lock().or_poisoned().nodes.len() < 10_000);
+    }
+}
@@ -1,5 +1,6 @@
 //! Side effects that run in response to changes in the reactive values they read from.
 
+mod batch;
 #[allow(clippy::module_inception)]
 mod effect;
 mod effect_function;
@@ -7,6 +8,8 @@ mod immediate;
 mod inner;
 mod render_effect;
 
+pub use batch::{batch, is_batching};
+pub(crate) use batch::{defer_notify, defer_run};
 pub use effect::*;
 pub use effect_function::*;
 pub use immediate::*;
@@ -0,0 +1,126 @@
+use crate::{
+    channel::Sender,
+    graph::{AnySubscriber, ReactiveNode},
+};
+use indexmap::IndexSet;
+use or_poisoned::OrPoisoned;
+use rustc_hash::FxHasher;
+use std::{hash::BuildHasherDefault, sync::Mutex, thread};
+
+type FxIndexSet<T> = IndexSet<T, BuildHasherDefault<FxHasher>>;
+
+/// The batch that is currently running, if any. Like the signals it holds back the effects of,
+/// this is shared between threads.
+static BATCH: Mutex<Option<Batch>> = Mutex::new(None);
+
+/// The work that has been held back until the outermost batch ends.
+#[derive(Default)]
+struct Batch {
+    /// [`ImmediateEffect`](super::ImmediateEffect)s, which are run when the batch ends.
+    effects: FxIndexSet<AnySubscriber>,
+    /// The notifiers for effects, render effects and async derived values, which are notified
+    /// when the batch ends so that each of them is scheduled to run once.
+    notifiers: FxIndexSet<Sender>,
+}
+
+/// Runs the given function as a single transaction, holding back every effect it triggers until
+/// it has finished.
+///
+/// Normally, each time a signal is updated, the effects that depend on it are scheduled to run.
+/// Inside `batch`, every kind of effect ([`Effect`](super::Effect),
+/// [`RenderEffect`](super::RenderEffect), [`ImmediateEffect`](super::ImmediateEffect)) and
+/// every async derived value is still marked dirty when one of its sources changes, but it is
+/// only notified once the outermost batch ends. Each of them then runs once, and only ever sees
+/// the final values of all the signals that were updated, rather than some inconsistent
+/// combination of old and new values.
+///
+/// Memos are still marked dirty immediately, so reading a memo inside the batch returns an
+/// up-to-date value.
+///
+/// Batches can be nested: nothing is notified until the outermost one ends. While a batch is
+/// running, it holds back the effects of updates made on any thread, not only the one it was
+/// started on.
+///
+/// ```
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::{effect::{batch, ImmediateEffect}, signal::RwSignal, owner::StoredValue};
+/// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let first = RwSignal::new("Ada".to_string());
+/// let last = RwSignal::new("Lovelace".to_string());
+/// let names = StoredValue::new(Vec::new());
+///
+/// let _effect = ImmediateEffect::new_isomorphic(move || {
+///     names.write_value().push(format!("{} {}", first.get(), last.get()));
+/// });
+///
+/// batch(move || {
+///     first.set("Grace".to_string());
+///     last.set("Hopper".to_string());
+/// });
+///
+/// // the effect never saw "Grace Lovelace"
+/// assert_eq!(names.get_value(), ["Ada Lovelace", "Grace Hopper"]);
+/// ```
+pub fn batch<T>(f: impl FnOnce() -> T) -> T {
+    struct ExecuteOnDrop;
+
+    impl Drop for ExecuteOnDrop {
+        fn drop(&mut self) {
+            let batch = BATCH.lock().or_poisoned().take().unwrap_or_default();
+            for mut notifier in batch.notifiers {
+                notifier.notify();
+            }
+            // the effects have already been marked dirty, so they will run the next time one
+            // of their sources changes, if they are skipped here
+            if thread::panicking() {
+                return;
+            }
+            for effect in batch.effects {
+                effect.update_if_necessary();
+            }
+        }
+    }
+
+    let execute_on_drop = {
+        let mut batch = BATCH.lock().or_poisoned();
+        if batch.is_none() {
+            *batch = Some(Batch::default());
+            Some(ExecuteOnDrop)
+        } else {
+            // only the outermost batch flushes the held-back work
+            None
+        }
+    };
+    let ret = f();
+    drop(execute_on_drop);
+    ret
+}
+
+/// Returns `true` if a [`batch`] is running.
+pub fn is_batching() -> bool {
+    BATCH.lock().or_poisoned().is_some()
+}
+
+/// Holds back running the given [`ImmediateEffect`](super::ImmediateEffect) until the end of the
+/// current batch. Returns `false` if there is no batch, in which case it should run now.
+pub(crate) fn defer_run(effect: &AnySubscriber) -> bool {
+    match &mut *BATCH.lock().or_poisoned() {
+        Some(batch) => {
+            batch.effects.insert(effect.clone());
+            true
+        }
+        None => false,
+    }
+}
+
+/// Holds back the given notification until the end of the current batch. Returns `false` if there
+/// is no batch, in which case it should be sent now.
+pub(crate) fn defer_notify(notifier: &Sender) -> bool {
+    match &mut *BATCH.lock().or_poisoned() {
+        Some(batch) => {
+            batch.notifiers.insert(notifier.clone());
+            true
+        }
+        None => false,
+    }
+}
@@ -13,6 +13,7 @@ use futures::StreamExt;
 use or_poisoned::OrPoisoned;
 use std::{
     mem,
+    panic::Location,
     sync::{atomic::AtomicBool, Arc, RwLock},
 };
 
@@ -92,7 +93,10 @@ impl<S> Dispose for Effect<S> {
     }
 }
 
-fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
+#[cfg_attr(not(feature = "devtools"), allow(unused_variables))]
+fn effect_base(
+    defined_at: &'static Location<'static>,
+) -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
     let (mut observer, rx) = channel();
 
     // spawn the effect asynchronously
@@ -106,6 +110,12 @@ fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
         observer,
         sources: SourceSet::new(),
     }));
+    #[cfg(feature = "devtools")]
+    crate::devtools::register_node(
+        crate::devtools::NodeKind::Effect,
+        &inner,
+        Some(defined_at),
+    );
 
     (rx, owner, inner)
 }
@@ -165,12 +175,14 @@ impl Effect<LocalStorage> {
     /// This spawns a task on the local thread using
     /// [`spawn_local`](any_spawner::Executor::spawn_local). For an effect that can be spawned on
     /// any thread, use [`new_sync`](Effect::new_sync).
+    #[track_caller]
     pub fn new<T, M>(mut fun: impl EffectFunction<T, M> + 'static) -> Self
     where
         T: 'static,
     {
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) = effect_base(defined_at);
             let value = Arc::new(RwLock::new(None::<T>));
             let mut first_run = true;
 
@@ -186,6 +198,8 @@ impl Effect<LocalStorage> {
                             }) || first_run)
                         {
                             first_run = false;
+                            #[cfg(feature = "devtools")]
+                            crate::devtools::record_run(subscriber.0);
                             subscriber.clear_sources(&subscriber);
 
                             let old_value =
@@ -310,6 +324,7 @@ impl Effect<LocalStorage> {
     /// # }).await;
     /// # });
     /// ```
+    #[track_caller]
     pub fn watch<D, T>(
         mut dependency_fn: impl FnMut() -> D + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T + 'static,
@@ -319,8 +334,9 @@ impl Effect<LocalStorage> {
         D: 'static,
         T: 'static,
     {
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) = effect_base(defined_at);
             let mut first_run = true;
             let dep_value = Arc::new(RwLock::new(None::<D>));
             let watch_value = Arc::new(RwLock::new(None::<T>));
@@ -337,6 +353,8 @@ impl Effect<LocalStorage> {
                                 subscriber.update_if_necessary()
                             }) || first_run)
                         {
+                            #[cfg(feature = "devtools")]
+                            crate::devtools::record_run(subscriber.0);
                             subscriber.clear_sources(&subscriber);
 
                             let old_dep_value = mem::take(
@@ -383,6 +401,7 @@ impl Effect<SyncStorage> {
     ///
     /// This spawns a task that can be run on any thread. For an effect that will be spawned on
     /// the current thread, use [`new`](Effect::new).
+    #[track_caller]
     pub fn new_sync<T, M>(
         fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
@@ -400,13 +419,14 @@ impl Effect<SyncStorage> {
     /// that are read inside it change.
     ///
     /// This will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic<T, M>(
         mut fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        let (mut rx, owner, inner) = effect_base(Location::caller());
         let mut first_run = true;
         let value = Arc::new(RwLock::new(None::<T>));
 
@@ -422,6 +442,8 @@ impl Effect<SyncStorage> {
                             || first_run)
                     {
                         first_run = false;
+                        #[cfg(feature = "devtools")]
+                        crate::devtools::record_run(subscriber.0);
                         subscriber.clear_sources(&subscriber);
 
                         let old_value =
@@ -445,6 +467,7 @@ impl Effect<SyncStorage> {
     }
 
     /// This is to [`Effect::watch`] what [`Effect::new_sync`] is to [`Effect::new`].
+    #[track_caller]
     pub fn watch_sync<D, T>(
         mut dependency_fn: impl FnMut() -> D + Send + Sync + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T
@@ -457,7 +480,7 @@ impl Effect<SyncStorage> {
         D: Send + Sync + 'static,
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        let (mut rx, owner, inner) = effect_base(Location::caller());
         let mut first_run = true;
         let dep_value = Arc::new(RwLock::new(None::<D>));
         let watch_value = Arc::new(RwLock::new(None::<T>));
@@ -475,6 +498,8 @@ impl Effect<SyncStorage> {
                                 subscriber.update_if_necessary()
                             }) || first_run)
                         {
+                            #[cfg(feature = "devtools")]
+                            crate::devtools::record_run(subscriber.0);
                             subscriber.clear_sources(&subscriber);
 
                             let old_dep_value = mem::take(
@@ -65,7 +65,7 @@ impl Dispose for ImmediateEffect {
 
 impl ImmediateEffect {
     /// Creates a new effect which runs immediately, then again as soon as any tracked signal changes.
-    /// (Unless [batch] is used.)
+    /// (Unless [batch](super::batch) is used.)
     ///
     /// NOTE: this requires a `Fn` function because it might recurse.
     /// Use [Self::new_mut] to pass a `FnMut` function, it'll panic on recursion.
@@ -83,7 +83,7 @@ impl ImmediateEffect {
         Self { inner: Some(inner) }
     }
     /// Creates a new effect which runs immediately, then again as soon as any tracked signal changes.
-    /// (Unless [batch] is used.)
+    /// (Unless [batch](super::batch) is used.)
     ///
     /// # Panics
     /// Panics on recursion or if triggered in parallel. Also see [Self::new]
@@ -95,7 +95,7 @@ impl ImmediateEffect {
         Self::new(move || fun.try_lock().expect(MSG)())
     }
     /// Creates a new effect which runs immediately, then again as soon as any tracked signal changes.
-    /// (Unless [batch] is used.)
+    /// (Unless [batch](super::batch) is used.)
     ///
     /// NOTE: this requires a `Fn` function because it might recurse.
     /// Use [Self::new_mut_scoped] to pass a `FnMut` function, it'll panic on recursion.
@@ -107,7 +107,7 @@ impl ImmediateEffect {
         on_cleanup(move || effect.dispose());
     }
     /// Creates a new effect which runs immediately, then again as soon as any tracked signal changes.
-    /// (Unless [batch] is used.)
+    /// (Unless [batch](super::batch) is used.)
     ///
     /// NOTE: this effect is automatically cleaned up when the current owner is cleared or disposed.
     ///
@@ -147,43 +147,9 @@ impl DefinedAt for ImmediateEffect {
     }
 }
 
-/// Defers any [ImmediateEffect]s from running until the end of the function.
-///
-/// NOTE: this affects only [ImmediateEffect]s, not other effects.
-///
-/// NOTE: this is rarely needed, but it is useful for example when multiple signals
-/// need to be updated atomically (for example a double-bound signal tree).
-pub fn batch<T>(f: impl FnOnce() -> T) -> T {
-    struct ExecuteOnDrop;
-    impl Drop for ExecuteOnDrop {
-        fn drop(&mut self) {
-            let effects = {
-                let mut batch = inner::BATCH.write().or_poisoned();
-                batch.take().unwrap().into_inner().expect("lock poisoned")
-            };
-            // TODO: Should we skip the effects if it's panicking?
-            for effect in effects {
-                effect.update_if_necessary();
-            }
-        }
-    }
-    let mut execute_on_drop = None;
-    {
-        let mut batch = inner::BATCH.write().or_poisoned();
-        if batch.is_none() {
-            execute_on_drop = Some(ExecuteOnDrop);
-        } else {
-            // Nested batching has no effect.
-        }
-        *batch = Some(batch.take().unwrap_or_default());
-    }
-    let ret = f();
-    drop(execute_on_drop);
-    ret
-}
-
 mod inner {
     use crate::{
+        effect::defer_run,
         graph::{
             AnySource, AnySubscriber, ReactiveNode, ReactiveNodeState,
             SourceSet, Subscriber, ToAnySubscriber, WithObserver,
@@ -192,7 +158,6 @@ mod inner {
         owner::Owner,
         traits::DefinedAt,
     };
-    use indexmap::IndexSet;
     use or_poisoned::OrPoisoned;
     use std::{
         panic::Location,
@@ -200,11 +165,6 @@ mod inner {
         thread::{self, ThreadId},
     };
 
-    /// Only the [super::batch] function ever writes to the outer RwLock.
-    /// While the effects will write to the inner one.
-    pub(super) static BATCH: RwLock<Option<RwLock<IndexSet<AnySubscriber>>>> =
-        RwLock::new(None);
-
     /// Handles subscription logic for effects.
     ///
     /// To handle parallelism and recursion we assign ordered (1..) ids to each run.
@@ -263,7 +223,7 @@ mod inner {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             let defined_at = Location::caller();
 
-            Arc::new_cyclic(|weak| {
+            let inner = Arc::new_cyclic(|weak| {
                 let any_subscriber = AnySubscriber(
                     weak.as_ptr() as usize,
                     Weak::clone(weak) as Weak<dyn Subscriber + Send + Sync>,
@@ -282,7 +242,22 @@ mod inner {
                     sources: SourceSet::new(),
                     any_subscriber,
                 })
-            })
+            });
+            #[cfg(feature = "devtools")]
+            crate::devtools::register_node(
+                crate::devtools::NodeKind::ImmediateEffect,
+                &inner,
+                Some(Location::caller()),
+            );
+            inner
+        }
+    }
+
+    #[cfg(feature = "devtools")]
+    impl crate::devtools::Inspect for RwLock<EffectInner> {
+        fn sources(&self) -> Vec<usize> {
+            let lock = self.read().or_poisoned();
+            (&lock.sources).into_iter().map(|source| source.0).collect()
         }
     }
 
@@ -321,17 +296,17 @@ mod inner {
             };
 
             {
-                if let Some(batch) = &*BATCH.read().or_poisoned() {
-                    let mut batch = batch.write().or_poisoned();
-                    let subscriber =
-                        self.read().or_poisoned().any_subscriber.clone();
-
-                    batch.insert(subscriber);
+                let subscriber =
+                    self.read().or_poisoned().any_subscriber.clone();
+                if defer_run(&subscriber) {
                     return needs_update;
                 }
             }
 
             if needs_update {
+                #[cfg(feature = "devtools")]
+                crate::devtools::record_run(self as *const Self as usize);
+
                 let mut guard = self.write().or_poisoned();
 
                 let owner = guard.owner.clone();
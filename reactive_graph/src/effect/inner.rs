@@ -56,6 +56,14 @@ impl ReactiveNode for RwLock<EffectInner> {
     }
 }
 
+#[cfg(feature = "devtools")]
+impl crate::devtools::Inspect for RwLock<EffectInner> {
+    fn sources(&self) -> Vec<usize> {
+        let lock = self.read().or_poisoned();
+        (&lock.sources).into_iter().map(|source| source.0).collect()
+    }
+}
+
 impl Subscriber for RwLock<EffectInner> {
     fn add_source(&self, source: AnySource) {
         self.write().or_poisoned().sources.insert(source);
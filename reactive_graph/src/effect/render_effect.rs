@@ -15,6 +15,7 @@ use std::{
     fmt::Debug,
     future::{Future, IntoFuture},
     mem,
+    panic::Location,
     pin::Pin,
     sync::{Arc, RwLock, Weak},
 };
@@ -59,6 +60,7 @@ where
     T: 'static,
 {
     /// Creates a new render effect, which immediately runs `fun`.
+    #[track_caller]
     pub fn new(fun: impl FnMut(Option<T>) -> T + 'static) -> Self {
         #[cfg(feature = "subsecond")]
         let (hot_fn_ptr, fun) = {
@@ -87,6 +89,7 @@ where
     }
 
     /// Creates a new render effect with an initial value.
+    #[track_caller]
     pub fn new_with_value(
         fun: impl FnMut(Option<T>) -> T + 'static,
         initial_value: Option<T>,
@@ -134,6 +137,7 @@ where
         .await
     }
 
+    #[track_caller]
     fn new_with_value_erased(
         #[allow(unused_mut)] mut fun: Box<dyn FnMut(Option<T>) -> T + 'static>,
         initial_value: Option<T>,
@@ -157,6 +161,12 @@ where
         }
 
         let (owner, inner, mut rx) = prep();
+        #[cfg(feature = "devtools")]
+        crate::devtools::register_node(
+            crate::devtools::NodeKind::RenderEffect,
+            &inner,
+            Some(Location::caller()),
+        );
 
         let value = Arc::new(RwLock::new(None::<T>));
 
@@ -215,6 +225,8 @@ where
                 move |prev| fun.call((prev,))
             };
 
+            #[cfg(feature = "devtools")]
+            crate::devtools::record_run(subscriber.0);
             *value.write().or_poisoned() = Some(
                 owner.with(|| subscriber.with_observer(|| fun(initial_value))),
             );
@@ -229,6 +241,8 @@ where
                                 subscriber.update_if_necessary()
                             })
                         {
+                            #[cfg(feature = "devtools")]
+                            crate::devtools::record_run(subscriber.0);
                             subscriber.clear_sources(&subscriber);
 
                             let old_value =
@@ -264,6 +278,12 @@ where
         }
 
         let (owner, inner, mut rx) = prep();
+        #[cfg(feature = "devtools")]
+        crate::devtools::register_node(
+            crate::devtools::NodeKind::RenderEffect,
+            &inner,
+            None,
+        );
 
         let value = Arc::new(RwLock::new(None::<T>));
 
@@ -296,6 +316,8 @@ where
                                 subscriber.update_if_necessary()
                             })
                         {
+                            #[cfg(feature = "devtools")]
+                            crate::devtools::record_run(subscriber.0);
                             subscriber.clear_sources(&subscriber);
 
                             let old_value =
@@ -332,6 +354,7 @@ where
     T: Send + Sync + 'static,
 {
     /// Creates a render effect that will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic(
         fun: impl FnMut(Option<T>) -> T + Send + Sync + 'static,
     ) -> Self {
@@ -340,8 +363,10 @@ where
         #[cfg(feature = "subsecond")]
         let fun = move |prev| fun.call((prev,));
 
+        #[cfg_attr(not(feature = "devtools"), allow(unused_variables))]
         fn erased<T: Send + Sync + 'static>(
             mut fun: Box<dyn FnMut(Option<T>) -> T + Send + Sync + 'static>,
+            defined_at: &'static Location<'static>,
         ) -> RenderEffect<T> {
             let (observer, mut rx) = channel();
             let value = Arc::new(RwLock::new(None::<T>));
@@ -351,6 +376,15 @@ where
                 observer,
                 sources: SourceSet::new(),
             }));
+            #[cfg(feature = "devtools")]
+            {
+                crate::devtools::register_node(
+                    crate::devtools::NodeKind::RenderEffect,
+                    &inner,
+                    Some(defined_at),
+                );
+                crate::devtools::record_run(Arc::as_ptr(&inner) as usize);
+            }
 
             let initial_value = owner
                 .with(|| inner.to_any_subscriber().with_observer(|| fun(None)));
@@ -367,6 +401,8 @@ where
                                 subscriber.update_if_necessary()
                             })
                         {
+                            #[cfg(feature = "devtools")]
+                            crate::devtools::record_run(subscriber.0);
                             subscriber.clear_sources(&subscriber);
 
                             let old_value =
@@ -383,7 +419,7 @@ where
             RenderEffect { value, inner }
         }
 
-        erased(Box::new(fun))
+        erased(Box::new(fun), Location::caller())
     }
 }
 
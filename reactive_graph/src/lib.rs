@@ -76,11 +76,15 @@ use std::{fmt::Arguments, future::Future};
 
 pub mod actions;
 pub(crate) mod channel;
+pub mod clock;
 pub mod computed;
+#[cfg(feature = "devtools")]
+pub mod devtools;
 pub mod diagnostics;
 pub mod effect;
 pub mod graph;
 pub mod owner;
+pub mod retry;
 pub mod send_wrapper_ext;
 #[cfg(feature = "serde")]
 mod serde;
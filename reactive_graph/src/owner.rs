@@ -179,6 +179,11 @@ impl Owner {
             #[cfg(feature = "hydration")]
             shared_context,
         };
+        #[cfg(feature = "devtools")]
+        crate::devtools::register_owner(
+            &this.inner,
+            parent.as_ref().map(|parent| parent.as_ptr() as usize),
+        );
         if let Some(parent) = parent.and_then(|n| n.upgrade()) {
             parent
                 .write()
@@ -213,6 +218,8 @@ impl Owner {
             #[cfg(feature = "hydration")]
             shared_context,
         };
+        #[cfg(feature = "devtools")]
+        crate::devtools::register_owner(&this.inner, None);
         this.set();
         this
     }
@@ -257,6 +264,8 @@ impl Owner {
             #[cfg(feature = "hydration")]
             shared_context: self.shared_context.clone(),
         };
+        #[cfg(feature = "devtools")]
+        crate::devtools::register_owner(&child.inner, Some(self.debug_id()));
         inner.children.push(Arc::downgrade(&child.inner));
         child
     }
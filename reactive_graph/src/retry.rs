@@ -0,0 +1,357 @@
+//! Policies for automatically retrying asynchronous work that has failed.
+//!
+//! A [`RetryPolicy`] describes how many times a fallible `Future` should be attempted, how long to
+//! wait between attempts, and which errors are worth retrying. It can be passed to
+//! [`ArcAction::new_with_retry`](crate::actions::ArcAction::new_with_retry) and
+//! [`Action::new_with_retry`](crate::actions::Action::new_with_retry), or used directly with
+//! [`RetryPolicy::run`].
+//!
+//! ```rust
+//! # use reactive_graph::retry::*;
+//! # use std::time::Duration;
+//! #[derive(Debug)]
+//! enum ApiError {
+//!     Network,
+//!     NotFound,
+//! }
+//!
+//! let policy = RetryPolicy::new(5)
+//!     .backoff(Backoff::Exponential {
+//!         initial: Duration::from_millis(100),
+//!         factor: 2.0,
+//!         max: Duration::from_secs(5),
+//!     })
+//!     .jitter(Jitter::Full)
+//!     // there's no point in retrying a request for something that doesn't exist
+//!     .retry_if(|err: &ApiError| matches!(err, ApiError::Network));
+//! ```
+
+use crate::{
+    clock::{use_clock, Clock},
+    signal::ArcRwSignal,
+    traits::Set,
+};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+use std::{fmt::Debug, future::Future, sync::Arc, time::Duration};
+
+/// Describes how long to wait between attempts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backoff {
+    /// Retry immediately.
+    None,
+    /// Wait the same amount of time before each retry.
+    Fixed(Duration),
+    /// Wait `initial` before the first retry, then multiply the delay by `factor` for each
+    /// subsequent retry, up to a maximum of `max`.
+    Exponential {
+        /// The delay before the first retry.
+        initial: Duration,
+        /// The factor by which the delay grows after each retry.
+        factor: f64,
+        /// The maximum delay between two attempts.
+        max: Duration,
+    },
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff::Exponential {
+            initial: Duration::from_millis(200),
+            factor: 2.0,
+            max: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Randomizes the delay between attempts, so that many clients that failed at the same time do
+/// not all retry at the same time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Jitter {
+    /// Use the delay given by the [`Backoff`] exactly.
+    #[default]
+    None,
+    /// Wait a random amount of time between zero and the delay given by the [`Backoff`].
+    Full,
+    /// Wait half of the delay given by the [`Backoff`], plus a random amount of time up to the
+    /// other half.
+    Equal,
+}
+
+/// Describes whether and how a failed asynchronous task should be retried.
+///
+/// See the [module-level documentation](crate::retry) for an example.
+pub struct RetryPolicy<E> {
+    max_attempts: usize,
+    backoff: Backoff,
+    jitter: Jitter,
+    #[allow(clippy::type_complexity)]
+    retry_if: Arc<dyn Fn(&E) -> bool + Send + Sync>,
+}
+
+impl<E> Clone for RetryPolicy<E> {
+    fn clone(&self) -> Self {
+        Self {
+            max_attempts: self.max_attempts,
+            backoff: self.backoff,
+            jitter: self.jitter,
+            retry_if: Arc::clone(&self.retry_if),
+        }
+    }
+}
+
+impl<E> Debug for RetryPolicy<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff", &self.backoff)
+            .field("jitter", &self.jitter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E> RetryPolicy<E> {
+    /// Creates a policy that makes at most `max_attempts` attempts in total (including the first
+    /// one), retrying every error with the default exponential [`Backoff`].
+    pub fn new(max_attempts: usize) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            backoff: Backoff::default(),
+            jitter: Jitter::default(),
+            retry_if: Arc::new(|_| true),
+        }
+    }
+
+    /// Sets how long to wait between attempts.
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets how the delay between attempts is randomized.
+    pub fn jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Only retries errors for which `predicate` returns `true`. Any other error is returned
+    /// immediately.
+    pub fn retry_if(
+        mut self,
+        predicate: impl Fn(&E) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.retry_if = Arc::new(predicate);
+        self
+    }
+
+    /// The maximum number of attempts, including the first one.
+    pub fn max_attempts(&self) -> usize {
+        self.max_attempts
+    }
+
+    /// Returns `true` if a task that failed with `error` on attempt number `attempt` (starting at
+    /// `1`) should be attempted again.
+    pub fn should_retry(&self, attempt: usize, error: &E) -> bool {
+        attempt < self.max_attempts && (self.retry_if)(error)
+    }
+
+    /// Returns how long to wait after attempt number `attempt` (starting at `1`) has failed,
+    /// before making the next attempt.
+    pub fn delay(&self, attempt: usize) -> Duration {
+        let delay = match self.backoff {
+            Backoff::None => Duration::ZERO,
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential {
+                initial,
+                factor,
+                max,
+            } => {
+                let exponent = attempt.saturating_sub(1).min(i32::MAX as usize);
+                initial
+                    .mul_f64(factor.max(1.0).powi(exponent as i32))
+                    .min(max)
+            }
+        };
+        match self.jitter {
+            Jitter::None => delay,
+            Jitter::Full => delay.mul_f64(random_fraction()),
+            Jitter::Equal => {
+                let half = delay / 2;
+                half + half.mul_f64(random_fraction())
+            }
+        }
+    }
+
+    /// Runs the `Future` created by `task`, creating and running a new one according to this
+    /// policy each time it fails.
+    ///
+    /// If `attempt` is given, it is set to the number of the attempt currently running, starting
+    /// at `1`.
+    ///
+    /// The delays between attempts are timed by the [`Clock`] returned by
+    /// [`use_clock`](crate::clock::use_clock) when the returned `Future` is first polled.
+    pub async fn run<T, F, Fut>(
+        &self,
+        attempt: Option<&ArcRwSignal<usize>>,
+        mut task: F,
+    ) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let clock = use_clock();
+        let mut current = 1;
+        loop {
+            if let Some(attempt) = attempt {
+                attempt.set(current);
+            }
+            match task().await {
+                Err(e) if self.should_retry(current, &e) => {
+                    sleep(&*clock, self.delay(current)).await;
+                    current += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Returns a pseudo-random number in the range `0.0..=1.0`.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+fn random_fraction() -> f64 {
+    // `RandomState` uses fixed keys on this target, so every client would get the same jitter
+    js_sys::Math::random()
+}
+
+/// Returns a pseudo-random number in the range `0.0..=1.0`.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn random_fraction() -> f64 {
+    // each `RandomState` is seeded differently, so this is enough randomness for jitter without
+    // pulling in a dependency on `rand`
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u8(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Waits for the given duration, using the timers of `clock` rather than those of a particular
+/// async runtime.
+async fn sleep(clock: &dyn Clock, duration: Duration) {
+    if duration.is_zero() {
+        return;
+    }
+    let (tx, rx) = futures::channel::oneshot::channel();
+    // if this future is dropped, the timer still fires, but there is no one left to wake
+    _ = clock.set_timeout(duration, Box::new(move || _ = tx.send(())));
+    _ = rx.await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Backoff, Jitter, RetryPolicy};
+    use crate::{
+        clock::{provide_clock, MockClock},
+        owner::Owner,
+    };
+    use std::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll},
+        time::Duration,
+    };
+
+    #[test]
+    fn exponential_backoff_is_capped() {
+        let policy = RetryPolicy::<()>::new(10).backoff(Backoff::Exponential {
+            initial: Duration::from_millis(100),
+            factor: 2.0,
+            max: Duration::from_millis(500),
+        });
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(400));
+        assert_eq!(policy.delay(4), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let policy = RetryPolicy::<()>::new(10)
+            .backoff(Backoff::Fixed(Duration::from_millis(100)))
+            .jitter(Jitter::Equal);
+        for _ in 0..100 {
+            let delay = policy.delay(1);
+            assert!(delay >= Duration::from_millis(50));
+            assert!(delay <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn jitter_differs_between_delays() {
+        let policy = RetryPolicy::<()>::new(10)
+            .backoff(Backoff::Fixed(Duration::from_secs(1)))
+            .jitter(Jitter::Full);
+        let delays = (0..10).map(|_| policy.delay(1)).collect::<Vec<_>>();
+        assert!(delays.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn only_retries_matching_errors() {
+        let policy = RetryPolicy::new(3).retry_if(|err: &u8| *err > 0);
+        assert!(policy.should_retry(1, &1));
+        assert!(!policy.should_retry(1, &0));
+        assert!(!policy.should_retry(3, &1));
+    }
+
+    #[test]
+    fn runs_until_success() {
+        let policy = RetryPolicy::new(5).backoff(Backoff::None);
+        let mut calls = 0;
+        let result = futures::executor::block_on(policy.run(None, || {
+            calls += 1;
+            let n = calls;
+            async move {
+                if n < 3 {
+                    Err(n)
+                } else {
+                    Ok(n)
+                }
+            }
+        }));
+        assert_eq!(result, Ok(3));
+    }
+
+    #[test]
+    fn waits_on_the_clock_between_attempts() {
+        let owner = Owner::new();
+        owner.set();
+        let clock = MockClock::new();
+        provide_clock(clock.clone());
+
+        let policy = RetryPolicy::new(3)
+            .backoff(Backoff::Fixed(Duration::from_millis(100)))
+            .jitter(Jitter::None);
+        let mut calls = 0;
+        let mut result = pin!(policy.run(None, || {
+            calls += 1;
+            let n = calls;
+            async move {
+                if n < 3 {
+                    Err(n)
+                } else {
+                    Ok(n)
+                }
+            }
+        }));
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        assert_eq!(result.as_mut().poll(&mut cx), Poll::Pending);
+        clock.advance(Duration::from_millis(99));
+        assert_eq!(result.as_mut().poll(&mut cx), Poll::Pending);
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(result.as_mut().poll(&mut cx), Poll::Pending);
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(result.as_mut().poll(&mut cx), Poll::Ready(Ok(3)));
+    }
+}
@@ -7,9 +7,11 @@ mod arc_trigger;
 mod arc_write;
 pub mod guards;
 mod mapped;
+#[cfg(feature = "persist")]
+pub mod persisted;
 mod read;
 mod rw;
-mod subscriber_traits;
+pub(crate) mod subscriber_traits;
 mod trigger;
 mod write;
 
@@ -19,6 +21,8 @@ pub use arc_rw::*;
 pub use arc_trigger::*;
 pub use arc_write::*;
 pub use mapped::*;
+#[cfg(feature = "persist")]
+pub use persisted::{ArcPersistedSignal, PersistedSignal};
 pub use read::*;
 pub use rw::*;
 pub use trigger::*;
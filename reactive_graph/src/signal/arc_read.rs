@@ -4,9 +4,13 @@ use super::{
 };
 use crate::{
     graph::SubscriberSet,
-    traits::{DefinedAt, IntoInner, IsDisposed, ReadUntracked},
+    signal::arc_signal,
+    traits::{
+        drive_with_stream, DefinedAt, IntoInner, IsDisposed, ReadUntracked,
+    },
 };
 use core::fmt::{Debug, Formatter, Result};
+use futures::Stream;
 use std::{
     hash::Hash,
     panic::Location,
@@ -42,6 +46,8 @@ use std::{
 ///   stream of values.
 /// - [`::from_stream()`](crate::traits::FromStream) converts an `async` stream
 ///   of values into a signal containing the latest value.
+///   - [`::from_stream_with_initial()`](ArcReadSignal::from_stream_with_initial)
+///     does the same, starting from an initial value rather than `None`.
 ///
 /// ## Examples
 /// ```
@@ -60,6 +66,56 @@ pub struct ArcReadSignal<T> {
     pub(crate) inner: Arc<RwLock<SubscriberSet>>,
 }
 
+impl<T> ArcReadSignal<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates a signal that starts with `initial`, and is set to each new value of the stream.
+    ///
+    /// The stream is spawned on the [`Executor`](any_spawner::Executor). It is polled until it
+    /// ends, or until the current [`Owner`](crate::owner::Owner) is cleaned up.
+    ///
+    /// ```
+    /// # use reactive_graph::prelude::*; use reactive_graph::signal::*;
+    /// # use reactive_graph::owner::Owner;
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = Owner::new(); owner.set();
+    /// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+    /// let messages = futures::stream::iter(["hello", "world"]);
+    /// let latest = ArcReadSignal::from_stream_with_initial(messages, "");
+    /// assert_eq!(latest.get(), "");
+    ///
+    /// # any_spawner::Executor::tick().await;
+    /// assert_eq!(latest.get(), "world");
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn from_stream_with_initial(
+        stream: impl Stream<Item = T> + Send + 'static,
+        initial: T,
+    ) -> Self {
+        let (read, write) = arc_signal(initial);
+        crate::spawn(drive_with_stream(stream, write));
+        read
+    }
+
+    /// Creates a signal that starts with `initial`, and is set to each new value of a stream that
+    /// is not `Send`.
+    ///
+    /// The stream is spawned with
+    /// [`Executor::spawn_local`](any_spawner::Executor::spawn_local). It is polled until it ends,
+    /// or until the current [`Owner`](crate::owner::Owner) is cleaned up.
+    #[track_caller]
+    pub fn from_stream_with_initial_unsync(
+        stream: impl Stream<Item = T> + 'static,
+        initial: T,
+    ) -> Self {
+        let (read, write) = arc_signal(initial);
+        crate::spawn_local(drive_with_stream(stream, write));
+        read
+    }
+}
+
 impl<T> Clone for ArcReadSignal<T> {
     #[track_caller]
     fn clone(&self) -> Self {
@@ -153,11 +153,18 @@ impl<T> ArcRwSignal<T> {
     )]
     #[track_caller]
     pub fn new(value: T) -> Self {
+        let inner = Arc::new(RwLock::new(SubscriberSet::new()));
+        #[cfg(feature = "devtools")]
+        crate::devtools::register_node(
+            crate::devtools::NodeKind::Signal,
+            &inner,
+            Some(Location::caller()),
+        );
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
             value: Arc::new(RwLock::new(value)),
-            inner: Arc::new(RwLock::new(SubscriberSet::new())),
+            inner,
         }
     }
 
@@ -0,0 +1,1001 @@
+//! Signals that persist their value to storage, like `localStorage` or a cookie.
+
+use super::{
+    guards::{Plain, ReadGuard, UntrackedWriteGuard, WriteGuard},
+    subscriber_traits::AsSubscriberSet,
+    ArcRwSignal,
+};
+use crate::{
+    graph::{ReactiveNode, SubscriberSet},
+    owner::{ArenaItem, Storage, SyncStorage},
+    traits::{
+        DefinedAt, Dispose, IsDisposed, Notify, ReadUntracked, Set,
+        UntrackableGuard, Write,
+    },
+    unwrap_signal,
+};
+use codee::{string::JsonSerdeCodec, Decoder, Encoder};
+use core::fmt::Debug;
+use guardian::ArcRwLockWriteGuardian;
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    marker::PhantomData,
+    panic::Location,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock, Weak,
+    },
+    time::Duration,
+};
+
+/// A function that is called with the new value when a stored value changes.
+pub type StorageListener = Arc<dyn Fn(Option<String>) + Send + Sync>;
+
+/// A function that stops listening for changes to a stored value.
+pub type Unsubscribe = Box<dyn FnOnce() + Send + Sync>;
+
+/// A place where a persisted signal stores its serialized value.
+pub trait SignalStorage: Send + Sync + 'static {
+    /// Returns the value stored under the given key, if any.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Stores a value under the given key.
+    fn set(&self, key: &str, value: &str);
+
+    /// Removes the value stored under the given key.
+    fn remove(&self, key: &str);
+
+    /// Calls `on_change` whenever the value stored under the given key is changed somewhere
+    /// else, like in another browser tab.
+    ///
+    /// By default, changes made elsewhere are not observed.
+    fn subscribe(&self, key: &str, on_change: StorageListener) -> Unsubscribe {
+        _ = (key, on_change);
+        Box::new(|| {})
+    }
+
+    /// Whether this storage can be read while rendering on the server.
+    ///
+    /// A signal whose storage can't be read on the server starts with its initial value while
+    /// the page is hydrating, and only loads its stored value once hydration is complete, so
+    /// that the first render in the browser matches the HTML from the server.
+    fn available_on_server(&self) -> bool {
+        false
+    }
+}
+
+/// An in-memory [`SignalStorage`], which is useful for testing.
+///
+/// Clones share the same values. Every persisted signal that uses the same storage is notified
+/// when one of them changes a value, in the same way that browser tabs are notified when one of
+/// them changes a value in `localStorage`.
+#[derive(Clone, Default)]
+pub struct MemoryStorage {
+    inner: Arc<Mutex<MemoryStorageInner>>,
+}
+
+#[derive(Default)]
+struct MemoryStorageInner {
+    values: HashMap<String, String>,
+    next_id: usize,
+    listeners: Vec<(usize, String, StorageListener)>,
+}
+
+impl Debug for MemoryStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryStorage")
+            .field("values", &self.inner.lock().or_poisoned().values)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MemoryStorage {
+    /// Creates an empty storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn notify(&self, key: &str, value: Option<&str>) {
+        // listeners are cloned out so they can access the storage themselves
+        let listeners = self
+            .inner
+            .lock()
+            .or_poisoned()
+            .listeners
+            .iter()
+            .filter(|(_, listening_to, _)| listening_to == key)
+            .map(|(_, _, listener)| Arc::clone(listener))
+            .collect::<Vec<_>>();
+        for listener in listeners {
+            listener(value.map(ToString::to_string));
+        }
+    }
+}
+
+impl SignalStorage for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.inner.lock().or_poisoned().values.get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        self.inner
+            .lock()
+            .or_poisoned()
+            .values
+            .insert(key.to_string(), value.to_string());
+        self.notify(key, Some(value));
+    }
+
+    fn remove(&self, key: &str) {
+        self.inner.lock().or_poisoned().values.remove(key);
+        self.notify(key, None);
+    }
+
+    fn subscribe(&self, key: &str, on_change: StorageListener) -> Unsubscribe {
+        let id = {
+            let mut inner = self.inner.lock().or_poisoned();
+            let id = inner.next_id;
+            inner.next_id += 1;
+            inner.listeners.push((id, key.to_string(), on_change));
+            id
+        };
+        let storage = Arc::downgrade(&self.inner);
+        Box::new(move || {
+            if let Some(storage) = storage.upgrade() {
+                storage
+                    .lock()
+                    .or_poisoned()
+                    .listeners
+                    .retain(|(listener, _, _)| *listener != id);
+            }
+        })
+    }
+
+    fn available_on_server(&self) -> bool {
+        true
+    }
+}
+
+/// Browser [Web Storage](https://developer.mozilla.org/en-US/docs/Web/API/Web_Storage_API).
+///
+/// Changes made in other tabs are observed through the `storage` event. On the server, and
+/// anywhere else that Web Storage isn't available, nothing is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WebStorage {
+    /// `localStorage`, which is shared by every tab of the same origin.
+    Local,
+    /// `sessionStorage`, which lasts for the lifetime of one tab.
+    Session,
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl WebStorage {
+    fn storage(&self) -> Option<web_sys::Storage> {
+        let window = web_sys::window()?;
+        match self {
+            WebStorage::Local => window.local_storage(),
+            WebStorage::Session => window.session_storage(),
+        }
+        .ok()
+        .flatten()
+    }
+}
+
+impl SignalStorage for WebStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        {
+            self.storage()?.get_item(key).ok().flatten()
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        {
+            _ = key;
+            None
+        }
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        if let Some(storage) = self.storage() {
+            _ = storage.set_item(key, value);
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        {
+            _ = (key, value);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        if let Some(storage) = self.storage() {
+            _ = storage.remove_item(key);
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        {
+            _ = key;
+        }
+    }
+
+    fn subscribe(&self, key: &str, on_change: StorageListener) -> Unsubscribe {
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        {
+            use wasm_bindgen::{closure::Closure, JsCast};
+
+            let Some(window) = web_sys::window() else {
+                return Box::new(|| {});
+            };
+            let storage = self.storage();
+            let key = key.to_string();
+            let listener = Closure::<dyn Fn(web_sys::StorageEvent)>::new(
+                move |ev: web_sys::StorageEvent| {
+                    // the key is `None` when the whole storage area was cleared
+                    let same_key = ev.key().map(|k| k == key).unwrap_or(true);
+                    if same_key && ev.storage_area() == storage {
+                        on_change(ev.new_value());
+                    }
+                },
+            );
+            _ = window.add_event_listener_with_callback(
+                "storage",
+                listener.as_ref().unchecked_ref(),
+            );
+
+            // the browser only ever runs this on the main thread
+            let remove = send_wrapper::SendWrapper::new(move || {
+                _ = window.remove_event_listener_with_callback(
+                    "storage",
+                    listener.as_ref().unchecked_ref(),
+                );
+            });
+            Box::new(move || (remove.take())())
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        {
+            _ = (key, on_change);
+            Box::new(|| {})
+        }
+    }
+}
+
+/// The `Cookie` header of the request that is currently being rendered on the server.
+///
+/// Server integrations provide this as context when their `persist` feature is enabled, so that
+/// values in a [`CookieStorage`] can be read while rendering on the server.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestCookies(pub String);
+
+/// The `SameSite` attribute of a cookie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SameSite {
+    /// The cookie is only sent with requests from the same site.
+    Strict,
+    /// The cookie is also sent when navigating to the site from elsewhere.
+    Lax,
+    /// The cookie is sent with every request. This requires the cookie to be `Secure`.
+    None,
+}
+
+/// Stores values in cookies.
+///
+/// Unlike Web Storage, cookies can be read while rendering on the server, through the
+/// [`RequestCookies`] provided by the server integration, so the server renders the same value
+/// that the browser will have. Writes made on the server are not sent back to the browser.
+///
+/// Values are percent-encoded, so they can contain any characters.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CookieStorage {
+    path: String,
+    max_age: Option<Duration>,
+    same_site: SameSite,
+    secure: bool,
+}
+
+impl Default for CookieStorage {
+    fn default() -> Self {
+        Self {
+            path: "/".to_string(),
+            max_age: None,
+            same_site: SameSite::Lax,
+            secure: false,
+        }
+    }
+}
+
+impl CookieStorage {
+    /// Creates a storage for session cookies that are available on every path, with
+    /// `SameSite=Lax`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the path the cookies are available on.
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets how long the cookies are kept. By default, they're removed when the browser is
+    /// closed.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets the `SameSite` attribute of the cookies.
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = same_site;
+        self
+    }
+
+    /// Sets whether the cookies are only sent over HTTPS.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Builds the string that sets a cookie, in the format of `document.cookie`.
+    fn cookie_string(
+        &self,
+        key: &str,
+        value: &str,
+        max_age: Option<Duration>,
+    ) -> String {
+        let mut cookie = format!(
+            "{}={}; Path={}",
+            encode_cookie_value(key),
+            encode_cookie_value(value),
+            self.path
+        );
+        if let Some(max_age) = max_age {
+            cookie.push_str(&format!("; Max-Age={}", max_age.as_secs()));
+        }
+        cookie.push_str(match self.same_site {
+            SameSite::Strict => "; SameSite=Strict",
+            SameSite::Lax => "; SameSite=Lax",
+            SameSite::None => "; SameSite=None",
+        });
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+        cookie
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    fn document() -> Option<web_sys::HtmlDocument> {
+        use wasm_bindgen::JsCast;
+
+        web_sys::window()?.document()?.dyn_into().ok()
+    }
+}
+
+impl SignalStorage for CookieStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        let cookies = Self::document()?.cookie().ok()?;
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        let cookies = crate::owner::use_context::<RequestCookies>()?.0;
+
+        find_cookie(&cookies, key)
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        let cookie = self.cookie_string(key, value, self.max_age);
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        if let Some(document) = Self::document() {
+            _ = document.set_cookie(&cookie);
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        {
+            _ = cookie;
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let cookie = self.cookie_string(key, "", Some(Duration::ZERO));
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        if let Some(document) = Self::document() {
+            _ = document.set_cookie(&cookie);
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        {
+            _ = cookie;
+        }
+    }
+
+    fn available_on_server(&self) -> bool {
+        true
+    }
+}
+
+/// Finds the value of the cookie with the given name in a `Cookie` header.
+fn find_cookie(cookies: &str, key: &str) -> Option<String> {
+    cookies.split(';').find_map(|cookie| {
+        let (name, value) = cookie.trim().split_once('=')?;
+        (decode_cookie_value(name) == key).then(|| decode_cookie_value(value))
+    })
+}
+
+fn encode_cookie_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'!'
+            | b'*'
+            | b'~'
+            | b'('
+            | b')' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+fn decode_cookie_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+struct PersistedInner {
+    key: String,
+    storage: Arc<dyn SignalStorage>,
+    /// Set while this signal writes to its storage, so that it doesn't receive its own change.
+    writing: AtomicBool,
+    unsubscribe: Mutex<Option<Unsubscribe>>,
+}
+
+impl Drop for PersistedInner {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.lock().or_poisoned().take()
+        {
+            unsubscribe();
+        }
+    }
+}
+
+/// A reference-counted signal that persists its value to a [`SignalStorage`], like
+/// `localStorage` or a cookie.
+///
+/// When it is created, the signal loads the value that is stored under its key, and falls back
+/// to the initial value if nothing is stored or the stored value can't be decoded. Every time
+/// the signal is written to, the new value is encoded with the codec `C` (JSON by default) and
+/// stored. If the value is changed elsewhere, like in another browser tab, the signal is updated
+/// to match.
+///
+/// Storage that can't be read on the server (see [`SignalStorage::available_on_server`]) is
+/// only read once the page has hydrated, so that the server-rendered HTML and the first render
+/// in the browser agree.
+///
+/// ```
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::{persisted::MemoryStorage, ArcPersistedSignal};
+/// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let storage = MemoryStorage::new();
+///
+/// let theme = ArcPersistedSignal::new("theme", storage.clone(), "light".to_string());
+/// theme.set("dark".to_string());
+///
+/// // a signal created later (for example, on the next visit) starts with the stored value
+/// let restored = ArcPersistedSignal::new("theme", storage, "light".to_string());
+/// assert_eq!(restored.get(), "dark");
+/// ```
+pub struct ArcPersistedSignal<T, C = JsonSerdeCodec> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    signal: ArcRwSignal<T>,
+    inner: Arc<PersistedInner>,
+    ty: PhantomData<fn() -> C>,
+}
+
+impl<T, C> Clone for ArcPersistedSignal<T, C> {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            signal: self.signal.clone(),
+            inner: Arc::clone(&self.inner),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<T, C> Debug for ArcPersistedSignal<T, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArcPersistedSignal")
+            .field("type", &std::any::type_name::<T>())
+            .field("key", &self.inner.key)
+            .field("value", &Arc::as_ptr(&self.signal.value))
+            .finish()
+    }
+}
+
+impl<T, C> PartialEq for ArcPersistedSignal<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.signal == other.signal
+    }
+}
+
+impl<T, C> Eq for ArcPersistedSignal<T, C> {}
+
+impl<T, C> Hash for ArcPersistedSignal<T, C> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.signal.hash(state);
+    }
+}
+
+impl<T> ArcPersistedSignal<T>
+where
+    T: Send + Sync + 'static,
+    JsonSerdeCodec: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    /// Creates a signal that persists its value as JSON under `key` in the given storage,
+    /// starting with the stored value if there is one, and with `initial` otherwise.
+    #[track_caller]
+    pub fn new(
+        key: impl Into<String>,
+        storage: impl SignalStorage,
+        initial: T,
+    ) -> Self {
+        Self::new_with_codec(key, storage, initial)
+    }
+}
+
+impl<T, C> ArcPersistedSignal<T, C>
+where
+    T: Send + Sync + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    /// Creates a signal that persists its value under `key` in the given storage, encoded with
+    /// the codec `C`, starting with the stored value if there is one, and with `initial`
+    /// otherwise.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new_with_codec(
+        key: impl Into<String>,
+        storage: impl SignalStorage,
+        initial: T,
+    ) -> Self {
+        let key = key.into();
+        let storage: Arc<dyn SignalStorage> = Arc::new(storage);
+
+        #[cfg(feature = "hydration")]
+        let defer_load = !storage.available_on_server()
+            && crate::owner::Owner::current_shared_context()
+                .map(|sc| sc.during_hydration())
+                .unwrap_or(false);
+        #[cfg(not(feature = "hydration"))]
+        let defer_load = false;
+
+        let stored = if defer_load { None } else { storage.get(&key) };
+        let signal = ArcRwSignal::new(
+            stored
+                .and_then(|value| C::decode(&value).ok())
+                .unwrap_or(initial),
+        );
+
+        let inner = Arc::new(PersistedInner {
+            key,
+            storage,
+            writing: AtomicBool::new(false),
+            unsubscribe: Mutex::new(None),
+        });
+        let on_change: StorageListener = Arc::new({
+            let inner = Arc::downgrade(&inner);
+            let signal = signal.clone();
+            move |value| Self::receive(&inner, &signal, value)
+        });
+        let unsubscribe = inner.storage.subscribe(&inner.key, on_change);
+        *inner.unsubscribe.lock().or_poisoned() = Some(unsubscribe);
+
+        if defer_load {
+            let inner = Arc::downgrade(&inner);
+            let signal = signal.clone();
+            any_spawner::Executor::spawn_local(async move {
+                let value = inner
+                    .upgrade()
+                    .and_then(|inner| inner.storage.get(&inner.key));
+                Self::receive(&inner, &signal, value);
+            });
+        }
+
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            signal,
+            inner,
+            ty: PhantomData,
+        }
+    }
+
+    /// Updates the signal with a value that was read from storage, without writing it back.
+    fn receive(
+        inner: &Weak<PersistedInner>,
+        signal: &ArcRwSignal<T>,
+        value: Option<String>,
+    ) {
+        let Some(inner) = inner.upgrade() else {
+            return;
+        };
+        if inner.writing.load(Ordering::Relaxed) {
+            return;
+        }
+        // if the value was removed elsewhere, the signal keeps its current value
+        if let Some(value) = value.and_then(|value| C::decode(&value).ok()) {
+            signal.set(value);
+        }
+    }
+}
+
+impl<T, C> ArcPersistedSignal<T, C> {
+    /// The key that the value is stored under.
+    pub fn key(&self) -> &str {
+        &self.inner.key
+    }
+
+    /// Removes the stored value. The signal keeps its current value, which is stored again the
+    /// next time the signal is written to.
+    pub fn remove_stored(&self) {
+        self.inner.writing.store(true, Ordering::Relaxed);
+        self.inner.storage.remove(&self.inner.key);
+        self.inner.writing.store(false, Ordering::Relaxed);
+    }
+}
+
+impl<T, C> ArcPersistedSignal<T, C>
+where
+    C: Encoder<T, Encoded = String>,
+{
+    fn persist(&self) {
+        let encoded = match self.signal.value.read() {
+            Ok(value) => C::encode(&value),
+            Err(_) => return,
+        };
+        if let Ok(encoded) = encoded {
+            self.inner.writing.store(true, Ordering::Relaxed);
+            self.inner.storage.set(&self.inner.key, &encoded);
+            self.inner.writing.store(false, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<T, C> DefinedAt for ArcPersistedSignal<T, C> {
+    #[inline(always)]
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<T, C> IsDisposed for ArcPersistedSignal<T, C> {
+    #[inline(always)]
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<T, C> AsSubscriberSet for ArcPersistedSignal<T, C> {
+    type Output = Arc<RwLock<SubscriberSet>>;
+
+    #[inline(always)]
+    fn as_subscriber_set(&self) -> Option<Self::Output> {
+        self.signal.as_subscriber_set()
+    }
+}
+
+impl<T: 'static, C> ReadUntracked for ArcPersistedSignal<T, C> {
+    type Value = ReadGuard<T, Plain<T>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.signal.try_read_untracked()
+    }
+}
+
+impl<T, C> Notify for ArcPersistedSignal<T, C>
+where
+    C: Encoder<T, Encoded = String>,
+{
+    fn notify(&self) {
+        self.persist();
+        self.mark_dirty();
+    }
+}
+
+impl<T: 'static, C> Write for ArcPersistedSignal<T, C>
+where
+    C: Encoder<T, Encoded = String>,
+{
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.signal
+            .value
+            .write()
+            .ok()
+            .map(|guard| WriteGuard::new(self.clone(), guard))
+    }
+
+    #[allow(refining_impl_trait)]
+    fn try_write_untracked(&self) -> Option<UntrackedWriteGuard<Self::Value>> {
+        self.signal.try_write_untracked()
+    }
+}
+
+/// An arena-allocated signal that persists its value to a [`SignalStorage`], like
+/// `localStorage` or a cookie.
+///
+/// This is the arena-allocated, `Copy` equivalent of an [`ArcPersistedSignal`]. It is disposed
+/// when its reactive owner is disposed.
+pub struct PersistedSignal<T, C = JsonSerdeCodec, S = SyncStorage> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    inner: ArenaItem<ArcPersistedSignal<T, C>, S>,
+}
+
+impl<T, C, S> Dispose for PersistedSignal<T, C, S> {
+    fn dispose(self) {
+        self.inner.dispose()
+    }
+}
+
+impl<T> PersistedSignal<T>
+where
+    T: Send + Sync + 'static,
+    JsonSerdeCodec: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    /// Creates a signal that persists its value as JSON under `key` in the given storage,
+    /// starting with the stored value if there is one, and with `initial` otherwise.
+    #[track_caller]
+    pub fn new(
+        key: impl Into<String>,
+        storage: impl SignalStorage,
+        initial: T,
+    ) -> Self {
+        Self::new_with_codec(key, storage, initial)
+    }
+}
+
+impl<T, C> PersistedSignal<T, C>
+where
+    T: Send + Sync + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+{
+    /// Creates a signal that persists its value under `key` in the given storage, encoded with
+    /// the codec `C`, starting with the stored value if there is one, and with `initial`
+    /// otherwise.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new_with_codec(
+        key: impl Into<String>,
+        storage: impl SignalStorage,
+        initial: T,
+    ) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(
+                ArcPersistedSignal::new_with_codec(key, storage, initial),
+            ),
+        }
+    }
+}
+
+impl<T, C, S> PersistedSignal<T, C, S>
+where
+    T: 'static,
+    S: Storage<ArcPersistedSignal<T, C>>,
+{
+    /// The key that the value is stored under.
+    pub fn key(&self) -> Option<String> {
+        self.inner.try_with_value(|inner| inner.key().to_string())
+    }
+
+    /// Removes the stored value. The signal keeps its current value, which is stored again the
+    /// next time the signal is written to.
+    pub fn remove_stored(&self) {
+        self.inner.try_with_value(|inner| inner.remove_stored());
+    }
+}
+
+impl<T, C, S> Copy for PersistedSignal<T, C, S> {}
+
+impl<T, C, S> Clone for PersistedSignal<T, C, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, C, S> Debug for PersistedSignal<T, C, S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistedSignal")
+            .field("type", &std::any::type_name::<T>())
+            .field("store", &self.inner)
+            .finish()
+    }
+}
+
+impl<T, C, S> PartialEq for PersistedSignal<T, C, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T, C, S> Eq for PersistedSignal<T, C, S> {}
+
+impl<T, C, S> Hash for PersistedSignal<T, C, S> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
+impl<T, C, S> DefinedAt for PersistedSignal<T, C, S> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<T: 'static, C, S> IsDisposed for PersistedSignal<T, C, S> {
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<T, C, S> AsSubscriberSet for PersistedSignal<T, C, S>
+where
+    S: Storage<ArcPersistedSignal<T, C>>,
+{
+    type Output = Arc<RwLock<SubscriberSet>>;
+
+    fn as_subscriber_set(&self) -> Option<Self::Output> {
+        self.inner
+            .try_with_value(|inner| inner.as_subscriber_set())
+            .flatten()
+    }
+}
+
+impl<T, C, S> ReadUntracked for PersistedSignal<T, C, S>
+where
+    T: 'static,
+    S: Storage<ArcPersistedSignal<T, C>>,
+{
+    type Value = ReadGuard<T, Plain<T>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.inner
+            .try_with_value(|inner| inner.try_read_untracked())
+            .flatten()
+    }
+}
+
+impl<T, C, S> Notify for PersistedSignal<T, C, S>
+where
+    C: Encoder<T, Encoded = String>,
+    S: Storage<ArcPersistedSignal<T, C>>,
+{
+    fn notify(&self) {
+        self.inner.try_with_value(|inner| inner.notify());
+    }
+}
+
+impl<T, C, S> Write for PersistedSignal<T, C, S>
+where
+    T: 'static,
+    C: Encoder<T, Encoded = String>,
+    S: Storage<ArcPersistedSignal<T, C>>,
+{
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        let guard = self.inner.try_with_value(|n| {
+            ArcRwLockWriteGuardian::take(Arc::clone(&n.signal.value)).ok()
+        })??;
+        Some(WriteGuard::new(*self, guard))
+    }
+
+    #[allow(refining_impl_trait)]
+    fn try_write_untracked(&self) -> Option<UntrackedWriteGuard<Self::Value>> {
+        self.inner
+            .try_with_value(|n| n.try_write_untracked())
+            .flatten()
+    }
+}
+
+impl<T, C> From<ArcPersistedSignal<T, C>> for PersistedSignal<T, C>
+where
+    T: Send + Sync + 'static,
+    C: 'static,
+{
+    #[track_caller]
+    fn from(value: ArcPersistedSignal<T, C>) -> Self {
+        PersistedSignal {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(value),
+        }
+    }
+}
+
+impl<T, C, S> From<PersistedSignal<T, C, S>> for ArcPersistedSignal<T, C>
+where
+    T: 'static,
+    S: Storage<ArcPersistedSignal<T, C>>,
+{
+    #[track_caller]
+    fn from(value: PersistedSignal<T, C, S>) -> Self {
+        value
+            .inner
+            .try_get_value()
+            .unwrap_or_else(unwrap_signal!(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_cookie_value, encode_cookie_value, find_cookie};
+
+    #[test]
+    fn cookie_values_round_trip() {
+        let value = r#"{"theme": "dark; contrast=high", "size": 1.5}"#;
+        let encoded = encode_cookie_value(value);
+        assert!(!encoded.contains([';', ',', ' ', '"', '=']));
+        assert_eq!(decode_cookie_value(&encoded), value);
+    }
+
+    #[test]
+    fn cookies_are_found_by_name() {
+        let header = format!(
+            "session=abc; theme={}; other=1",
+            encode_cookie_value("\"dark\"")
+        );
+        assert_eq!(find_cookie(&header, "theme").as_deref(), Some("\"dark\""));
+        assert_eq!(find_cookie(&header, "other").as_deref(), Some("1"));
+        assert_eq!(find_cookie(&header, "missing"), None);
+    }
+}
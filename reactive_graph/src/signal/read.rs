@@ -10,6 +10,7 @@ use crate::{
     unwrap_signal,
 };
 use core::fmt::Debug;
+use futures::Stream;
 use std::{
     hash::Hash,
     panic::Location,
@@ -46,6 +47,8 @@ use std::{
 ///   stream of values.
 /// - [`::from_stream()`](crate::traits::FromStream) converts an `async` stream
 ///   of values into a signal containing the latest value.
+///   - [`::from_stream_with_initial()`](ReadSignal::from_stream_with_initial)
+///     does the same, starting from an initial value rather than `None`.
 ///
 /// ## Examples
 /// ```
@@ -69,6 +72,34 @@ impl<T, S> Dispose for ReadSignal<T, S> {
     }
 }
 
+impl<T> ReadSignal<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates a signal that starts with `initial`, and is set to each new value of the stream.
+    ///
+    /// See [`ArcReadSignal::from_stream_with_initial`].
+    #[track_caller]
+    pub fn from_stream_with_initial(
+        stream: impl Stream<Item = T> + Send + 'static,
+        initial: T,
+    ) -> Self {
+        ArcReadSignal::from_stream_with_initial(stream, initial).into()
+    }
+
+    /// Creates a signal that starts with `initial`, and is set to each new value of a stream that
+    /// is not `Send`.
+    ///
+    /// See [`ArcReadSignal::from_stream_with_initial_unsync`].
+    #[track_caller]
+    pub fn from_stream_with_initial_unsync(
+        stream: impl Stream<Item = T> + 'static,
+        initial: T,
+    ) -> Self {
+        ArcReadSignal::from_stream_with_initial_unsync(stream, initial).into()
+    }
+}
+
 impl<T, S> Copy for ReadSignal<T, S> {}
 
 impl<T, S> Clone for ReadSignal<T, S> {
@@ -53,11 +53,17 @@ use crate::{
     effect::Effect,
     graph::{Observer, Source, Subscriber, ToAnySource},
     owner::Owner,
-    signal::{arc_signal, guards::UntrackedWriteGuard, ArcReadSignal},
+    signal::{
+        arc_signal, guards::UntrackedWriteGuard, ArcReadSignal, ArcWriteSignal,
+    },
 };
 use any_spawner::Executor;
-use futures::{Stream, StreamExt};
+use futures::{
+    future::{AbortHandle, Abortable},
+    Stream, StreamExt,
+};
 use std::{
+    future::Future,
     ops::{Deref, DerefMut},
     panic::Location,
 };
@@ -576,6 +582,9 @@ pub trait ToStream<T> {
     /// Generates a [`Stream`] that emits the new value of the signal
     /// whenever it changes.
     ///
+    /// The current value is emitted first. The stream ends when the current [`Owner`] is
+    /// cleaned up.
+    ///
     /// # Panics
     /// Panics if you try to access a signal that is owned by a reactive node that has been disposed.
     #[track_caller]
@@ -606,6 +615,10 @@ where
 }
 
 /// Allows creating a signal from an async [`Stream`].
+///
+/// The stream is polled until it ends, or until the [`Owner`] that was current when the signal
+/// was created is cleaned up. To start with a value other than `None`, use
+/// [`ArcReadSignal::from_stream_with_initial`].
 pub trait FromStream<T> {
     /// Creates a signal that contains the latest value of the stream.
     #[track_caller]
@@ -623,27 +636,34 @@ where
 {
     fn from_stream(stream: impl Stream<Item = T> + Send + 'static) -> Self {
         let (read, write) = arc_signal(None);
-        let mut stream = Box::pin(stream);
-        crate::spawn(async move {
-            while let Some(value) = stream.next().await {
-                write.set(Some(value));
-            }
-        });
+        crate::spawn(drive_with_stream(stream.map(Some), write));
         read.into()
     }
 
     fn from_stream_unsync(stream: impl Stream<Item = T> + 'static) -> Self {
         let (read, write) = arc_signal(None);
-        let mut stream = Box::pin(stream);
-        Executor::spawn_local(async move {
-            while let Some(value) = stream.next().await {
-                write.set(Some(value));
-            }
-        });
+        Executor::spawn_local(drive_with_stream(stream.map(Some), write));
         read.into()
     }
 }
 
+/// Sets the signal to each value of the stream, until the stream ends or the current [`Owner`]
+/// is cleaned up.
+pub(crate) fn drive_with_stream<T: 'static>(
+    stream: impl Stream<Item = T> + 'static,
+    write: ArcWriteSignal<T>,
+) -> impl Future<Output = ()> {
+    let (abort_handle, abort_registration) = AbortHandle::new_pair();
+    Owner::on_cleanup(move || abort_handle.abort());
+
+    let mut stream = Box::pin(Abortable::new(stream, abort_registration));
+    async move {
+        while let Some(value) = stream.next().await {
+            write.set(value);
+        }
+    }
+}
+
 /// Checks whether a signal has already been disposed.
 pub trait IsDisposed {
     /// If `true`, the signal cannot be accessed without a panic.
@@ -0,0 +1,55 @@
+// a batch holds back the effects of every thread, so this is kept in its own test binary, where
+// it can't hold back the effects of tests running at the same time
+#[cfg(feature = "effects")]
+#[test]
+fn batch_holds_back_effects_until_it_ends() {
+    use reactive_graph::{
+        effect::{batch, is_batching, ImmediateEffect},
+        owner::Owner,
+        prelude::*,
+        signal::{ArcRwSignal, RwSignal},
+    };
+    use std::{
+        sync::{Arc, RwLock},
+        thread,
+    };
+
+    let owner = Owner::new();
+    owner.set();
+    let a = RwSignal::new(0);
+    let b = ArcRwSignal::new(0);
+    let seen = Arc::new(RwLock::new(Vec::new()));
+
+    let _effect = ImmediateEffect::new({
+        let b = b.clone();
+        let seen = Arc::clone(&seen);
+        move || seen.write().unwrap().push((a.get(), b.get()))
+    });
+    assert_eq!(*seen.read().unwrap(), [(0, 0)]);
+
+    batch(|| {
+        a.set(1);
+        batch(|| {
+            b.set(1);
+            assert_eq!(*seen.read().unwrap(), [(0, 0)]);
+        });
+        // only the outermost batch runs the effects it held back
+        assert!(is_batching());
+        assert_eq!(*seen.read().unwrap(), [(0, 0)]);
+    });
+    assert!(!is_batching());
+    assert_eq!(*seen.read().unwrap(), [(0, 0), (1, 1)]);
+
+    // writes made on other threads are held back as well
+    batch(|| {
+        thread::spawn({
+            let b = b.clone();
+            move || b.set(2)
+        })
+        .join()
+        .unwrap();
+        a.set(2);
+        assert_eq!(*seen.read().unwrap(), [(0, 0), (1, 1)]);
+    });
+    assert_eq!(*seen.read().unwrap(), [(0, 0), (1, 1), (2, 2)]);
+}
@@ -0,0 +1,202 @@
+#![cfg(feature = "devtools")]
+
+use reactive_graph::{
+    computed::ArcMemo,
+    devtools::{self, EventKind, NodeKind, NodeSnapshot, Snapshot},
+    effect::ImmediateEffect,
+    owner::Owner,
+    prelude::*,
+    signal::ArcRwSignal,
+};
+
+fn owner_id(snapshot: &Snapshot, owner: &Owner) -> usize {
+    snapshot
+        .owners
+        .iter()
+        .find(|snapshot| snapshot.debug_id == owner.debug_id())
+        .map(|snapshot| snapshot.id)
+        .expect("owner should be registered")
+}
+
+fn node(snapshot: &Snapshot, owner: &Owner, kind: NodeKind) -> NodeSnapshot {
+    let owner = owner_id(snapshot, owner);
+    snapshot
+        .nodes
+        .iter()
+        .find(|node| node.owner == Some(owner) && node.kind == kind)
+        .cloned()
+        .expect("node should be registered")
+}
+
+#[test]
+fn snapshot_lists_nodes_and_edges() {
+    let owner = Owner::new();
+    owner.set();
+
+    let count = ArcRwSignal::new(1);
+    let double = ArcMemo::new({
+        let count = count.clone();
+        move |_| count.get() * 2
+    });
+    let _effect = ImmediateEffect::new_isomorphic({
+        let double = double.clone();
+        move || _ = double.get()
+    });
+
+    let snapshot = devtools::snapshot();
+    let signal = node(&snapshot, &owner, NodeKind::Signal);
+    let memo = node(&snapshot, &owner, NodeKind::Memo);
+    let effect = node(&snapshot, &owner, NodeKind::ImmediateEffect);
+
+    assert_eq!(signal.subscribers, vec![memo.id]);
+    assert_eq!(memo.sources, vec![signal.id]);
+    assert_eq!(memo.subscribers, vec![effect.id]);
+    assert_eq!(effect.sources, vec![memo.id]);
+    for node in [signal, memo, effect] {
+        assert!(node.defined_at.unwrap().contains("devtools.rs"));
+    }
+}
+
+#[test]
+fn timeline_explains_why_effects_reran() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = ArcRwSignal::new(0);
+    let b = ArcRwSignal::new(0);
+    let _effect = ImmediateEffect::new_isomorphic({
+        let (a, b) = (a.clone(), b.clone());
+        move || _ = a.get() + b.get()
+    });
+
+    let before = devtools::snapshot();
+    let owner_id = owner_id(&before, &owner);
+    let signals = before
+        .nodes
+        .iter()
+        .filter(|node| {
+            node.owner == Some(owner_id) && node.kind == NodeKind::Signal
+        })
+        .map(|node| node.id)
+        .collect::<Vec<_>>();
+    let effect_id = node(&before, &owner, NodeKind::ImmediateEffect).id;
+    let last_seq = before.timeline.last().map(|event| event.seq);
+    assert_eq!(signals.len(), 2);
+
+    b.set(1);
+
+    let events = devtools::snapshot()
+        .timeline
+        .into_iter()
+        .filter(|event| Some(event.seq) > last_seq)
+        .filter(|event| {
+            signals.contains(&event.node) || event.node == effect_id
+        })
+        .map(|event| (event.node, event.kind))
+        .collect::<Vec<_>>();
+    // only the signal that was written to is blamed for the rerun
+    let (b_id, _) = events[0];
+    assert!(signals.contains(&b_id));
+    assert_eq!(
+        events,
+        vec![
+            (b_id, EventKind::Write),
+            (
+                effect_id,
+                EventKind::Run {
+                    caused_by: vec![b_id]
+                }
+            )
+        ]
+    );
+}
+
+#[test]
+fn memos_record_runs_and_changes() {
+    let owner = Owner::new();
+    owner.set();
+
+    let count = ArcRwSignal::new(1);
+    let is_even = ArcMemo::new({
+        let count = count.clone();
+        move |_| count.get() % 2 == 0
+    });
+    assert!(!is_even.get());
+
+    let before = devtools::snapshot();
+    let memo = node(&before, &owner, NodeKind::Memo);
+    let last_seq = before.timeline.last().map(|event| event.seq);
+
+    // the memo reruns, but its value does not change
+    count.set(3);
+    assert!(!is_even.get());
+    // the memo reruns, and its value changes
+    count.set(4);
+    assert!(is_even.get());
+
+    let events = devtools::snapshot()
+        .timeline
+        .into_iter()
+        .filter(|event| Some(event.seq) > last_seq && event.node == memo.id)
+        .map(|event| event.kind)
+        .collect::<Vec<_>>();
+    let run = EventKind::Run {
+        caused_by: memo.sources.clone(),
+    };
+    assert_eq!(events, vec![run.clone(), run, EventKind::Write]);
+}
+
+#[test]
+fn owners_are_listed_with_their_parents() {
+    let parent = Owner::new();
+    parent.set();
+    let child = parent.child();
+    let nested = Owner::new();
+
+    let snapshot = devtools::snapshot();
+    let parent_of = |owner: &Owner| {
+        snapshot
+            .owners
+            .iter()
+            .find(|snapshot| snapshot.debug_id == owner.debug_id())
+            .map(|snapshot| snapshot.parent)
+    };
+    let parent_id = owner_id(&snapshot, &parent);
+    assert_eq!(parent_of(&child), Some(Some(parent_id)));
+    assert_eq!(parent_of(&nested), Some(Some(parent_id)));
+
+    let child_id = owner_id(&snapshot, &child);
+    drop(child);
+    assert!(devtools::snapshot()
+        .owners
+        .iter()
+        .all(|owner| owner.id != child_id));
+}
+
+#[test]
+fn dropped_nodes_are_removed() {
+    let owner = Owner::new();
+    owner.set();
+
+    let trigger = reactive_graph::signal::ArcTrigger::new();
+    let id = node(&devtools::snapshot(), &owner, NodeKind::Trigger).id;
+
+    drop(trigger);
+    assert!(devtools::snapshot().nodes.iter().all(|node| node.id != id));
+}
+
+#[test]
+fn ids_are_not_reused() {
+    let owner = Owner::new();
+    owner.set();
+
+    let mut ids = Vec::new();
+    for _ in 0..10 {
+        let trigger = reactive_graph::signal::ArcTrigger::new();
+        ids.push(node(&devtools::snapshot(), &owner, NodeKind::Trigger).id);
+        drop(trigger);
+    }
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 10);
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "persist")]
+
+use reactive_graph::{
+    owner::{provide_context, Owner},
+    signal::{
+        persisted::{
+            CookieStorage, MemoryStorage, RequestCookies, SignalStorage,
+            WebStorage,
+        },
+        ArcPersistedSignal, PersistedSignal,
+    },
+    traits::{Get, Set, Update, Write},
+};
+
+#[test]
+fn persisted_signal_starts_with_stored_value() {
+    let storage = MemoryStorage::new();
+    storage.set("count", "5");
+
+    let count = ArcPersistedSignal::new("count", storage.clone(), 0);
+    assert_eq!(count.get(), 5);
+
+    let missing = ArcPersistedSignal::new("missing", storage, 1);
+    assert_eq!(missing.get(), 1);
+}
+
+#[test]
+fn persisted_signal_falls_back_when_value_cant_be_decoded() {
+    let storage = MemoryStorage::new();
+    storage.set("count", "not a number");
+
+    let count = ArcPersistedSignal::new("count", storage, 3);
+    assert_eq!(count.get(), 3);
+}
+
+#[test]
+fn writes_are_persisted() {
+    let storage = MemoryStorage::new();
+    let names = ArcPersistedSignal::new("names", storage.clone(), Vec::new());
+
+    names.set(vec!["Alice".to_string()]);
+    assert_eq!(storage.get("names").as_deref(), Some(r#"["Alice"]"#));
+
+    names.update(|names| names.push("Bob".to_string()));
+    assert_eq!(storage.get("names").as_deref(), Some(r#"["Alice","Bob"]"#));
+
+    names.write().clear();
+    assert_eq!(storage.get("names").as_deref(), Some("[]"));
+
+    names.remove_stored();
+    assert_eq!(storage.get("names"), None);
+    assert_eq!(names.get(), Vec::<String>::new());
+}
+
+#[test]
+fn signals_sharing_a_key_stay_in_sync() {
+    let storage = MemoryStorage::new();
+    let a =
+        ArcPersistedSignal::new("theme", storage.clone(), "light".to_string());
+    let b = ArcPersistedSignal::new("theme", storage, "light".to_string());
+
+    a.set("dark".to_string());
+    assert_eq!(b.get(), "dark");
+
+    b.set("contrast".to_string());
+    assert_eq!(a.get(), "contrast");
+}
+
+#[test]
+fn dropped_signals_stop_listening() {
+    let storage = MemoryStorage::new();
+    let a = ArcPersistedSignal::new("count", storage.clone(), 0);
+    let b = ArcPersistedSignal::new("count", storage.clone(), 0);
+    drop(b);
+
+    a.set(1);
+    assert_eq!(storage.get("count").as_deref(), Some("1"));
+}
+
+#[test]
+fn arena_persisted_signal() {
+    let owner = Owner::new();
+    owner.set();
+
+    let storage = MemoryStorage::new();
+    let count = PersistedSignal::new("count", storage.clone(), 0);
+    count.set(2);
+    assert_eq!(count.get(), 2);
+    assert_eq!(storage.get("count").as_deref(), Some("2"));
+    assert_eq!(count.key().as_deref(), Some("count"));
+}
+
+#[test]
+fn web_storage_is_empty_on_the_server() {
+    let count = ArcPersistedSignal::new("count", WebStorage::Local, 7);
+    count.set(8);
+    assert_eq!(count.get(), 8);
+    assert_eq!(WebStorage::Local.get("count"), None);
+}
+
+#[test]
+fn cookies_are_read_from_the_request_on_the_server() {
+    let owner = Owner::new();
+    owner.set();
+    provide_context(RequestCookies(
+        "session=abc; theme=%22dark%22".to_string(),
+    ));
+
+    let theme =
+        ArcPersistedSignal::new("theme", CookieStorage::new(), String::new());
+    assert_eq!(theme.get(), "dark");
+}
@@ -0,0 +1,92 @@
+use any_spawner::Executor;
+use futures::{channel::mpsc, SinkExt};
+use reactive_graph::{
+    owner::Owner,
+    prelude::*,
+    signal::{ArcReadSignal, ReadSignal},
+};
+
+#[tokio::test]
+async fn from_stream_with_initial_follows_the_stream() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let (mut tx, rx) = mpsc::unbounded();
+    let signal = ArcReadSignal::from_stream_with_initial(rx, 0);
+    assert_eq!(signal.get_untracked(), 0);
+
+    tx.send(1).await.unwrap();
+    Executor::tick().await;
+    assert_eq!(signal.get_untracked(), 1);
+
+    tx.send(2).await.unwrap();
+    tx.send(3).await.unwrap();
+    Executor::tick().await;
+    assert_eq!(signal.get_untracked(), 3);
+}
+
+#[tokio::test]
+async fn from_stream_stops_when_the_owner_is_cleaned_up() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let (mut tx, rx) = mpsc::unbounded();
+    let initial = ArcReadSignal::from_stream_with_initial(rx, 0);
+    let (mut tx_opt, rx_opt) = mpsc::unbounded();
+    let optional = ArcReadSignal::<Option<i32>>::from_stream(rx_opt);
+
+    tx.send(1).await.unwrap();
+    tx_opt.send(1).await.unwrap();
+    Executor::tick().await;
+    assert_eq!(initial.get_untracked(), 1);
+    assert_eq!(optional.get_untracked(), Some(1));
+
+    owner.cleanup();
+    Executor::tick().await;
+
+    // the streams are no longer being polled, so their receivers have been dropped
+    assert!(tx.send(2).await.is_err());
+    assert!(tx_opt.send(2).await.is_err());
+    assert_eq!(initial.get_untracked(), 1);
+    assert_eq!(optional.get_untracked(), Some(1));
+}
+
+#[tokio::test]
+async fn arena_signal_from_stream_with_initial() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let signal = ReadSignal::from_stream_with_initial(
+        futures::stream::iter(["a", "b"]),
+        "",
+    );
+    assert_eq!(signal.get_untracked(), "");
+    Executor::tick().await;
+    assert_eq!(signal.get_untracked(), "b");
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn to_stream_yields_each_new_value() {
+    use futures::StreamExt;
+    use reactive_graph::signal::RwSignal;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(0);
+    let mut stream = count.to_stream();
+    assert_eq!(stream.next().await, Some(0));
+
+    count.set(1);
+    assert_eq!(stream.next().await, Some(1));
+    count.set(2);
+    assert_eq!(stream.next().await, Some(2));
+
+    owner.cleanup();
+    assert_eq!(stream.next().await, None);
+}
@@ -0,0 +1,228 @@
+use reactive_graph::{
+    clock::{provide_clock, Clock, MockClock, TimeoutHandle},
+    computed::{ArcDebounced, ArcSampled, ArcThrottled, Debounced},
+    effect::ImmediateEffect,
+    owner::{Owner, StoredValue},
+    prelude::*,
+    signal::ArcRwSignal,
+};
+use std::time::Duration;
+
+const MS: Duration = Duration::from_millis(1);
+
+fn setup() -> (Owner, MockClock) {
+    let owner = Owner::new();
+    owner.set();
+    let clock = MockClock::new();
+    provide_clock(clock.clone());
+    (owner, clock)
+}
+
+#[test]
+fn debounced_waits_for_quiet() {
+    let (_owner, clock) = setup();
+
+    let source = ArcRwSignal::new(0);
+    let debounced = ArcDebounced::new(
+        {
+            let source = source.clone();
+            move || source.get()
+        },
+        100 * MS,
+    );
+    assert_eq!(debounced.get(), 0);
+
+    source.set(1);
+    clock.advance(60 * MS);
+    source.set(2);
+    clock.advance(60 * MS);
+    source.set(3);
+    clock.advance(99 * MS);
+    assert_eq!(debounced.get(), 0);
+
+    clock.advance(MS);
+    assert_eq!(debounced.get(), 3);
+    assert_eq!(clock.pending(), 0);
+}
+
+#[test]
+fn throttled_applies_first_and_last_change() {
+    let (_owner, clock) = setup();
+
+    let source = ArcRwSignal::new(0);
+    let throttled = ArcThrottled::new(
+        {
+            let source = source.clone();
+            move || source.get()
+        },
+        100 * MS,
+    );
+
+    source.set(1);
+    assert_eq!(throttled.get(), 1);
+    source.set(2);
+    clock.advance(50 * MS);
+    source.set(3);
+    assert_eq!(throttled.get(), 1);
+
+    clock.advance(50 * MS);
+    assert_eq!(throttled.get(), 3);
+
+    // the trailing update starts a new interval
+    source.set(4);
+    assert_eq!(throttled.get(), 3);
+    clock.advance(100 * MS);
+    assert_eq!(throttled.get(), 4);
+
+    // after a quiet interval, changes are applied immediately again
+    clock.advance(200 * MS);
+    source.set(5);
+    assert_eq!(throttled.get(), 5);
+}
+
+#[test]
+fn sampled_updates_at_the_end_of_each_period() {
+    let (_owner, clock) = setup();
+
+    let source = ArcRwSignal::new(0);
+    let sampled = ArcSampled::new(
+        {
+            let source = source.clone();
+            move || source.get()
+        },
+        100 * MS,
+    );
+
+    clock.advance(30 * MS);
+    source.set(1);
+    clock.advance(60 * MS);
+    source.set(2);
+    assert_eq!(sampled.get(), 0);
+    clock.advance(10 * MS);
+    assert_eq!(sampled.get(), 2);
+
+    // nothing is scheduled while the source is unchanged
+    clock.advance(500 * MS);
+    assert_eq!(clock.pending(), 0);
+
+    source.set(3);
+    clock.advance(99 * MS);
+    assert_eq!(sampled.get(), 2);
+    clock.advance(MS);
+    assert_eq!(sampled.get(), 3);
+}
+
+#[test]
+fn timed_values_notify_subscribers() {
+    let (_owner, clock) = setup();
+
+    let source = ArcRwSignal::new("a");
+    let debounced = Debounced::new(
+        {
+            let source = source.clone();
+            move || source.get()
+        },
+        10 * MS,
+    );
+    let seen = StoredValue::new(Vec::new());
+    let _effect = ImmediateEffect::new_isomorphic(move || {
+        seen.write_value().push(debounced.get());
+    });
+
+    source.set("b");
+    source.set("c");
+    clock.advance(10 * MS);
+    assert_eq!(seen.get_value(), vec!["a", "c"]);
+    assert_eq!(debounced.with(|value| value.len()), 1);
+}
+
+#[test]
+fn timers_are_canceled_when_the_owner_is_cleaned_up() {
+    let (owner, clock) = setup();
+
+    let source = ArcRwSignal::new(0);
+    let debounced = ArcDebounced::new(
+        {
+            let source = source.clone();
+            move || source.get()
+        },
+        100 * MS,
+    );
+
+    source.set(1);
+    assert_eq!(clock.pending(), 1);
+
+    owner.cleanup();
+    assert_eq!(clock.pending(), 0);
+
+    // the source is no longer tracked
+    source.set(2);
+    clock.advance(100 * MS);
+    assert_eq!(clock.pending(), 0);
+    assert_eq!(debounced.get_untracked(), 0);
+}
+
+#[test]
+fn timers_are_canceled_when_dropped() {
+    let (_owner, clock) = setup();
+
+    let source = ArcRwSignal::new(0);
+    let debounced = ArcDebounced::new(
+        {
+            let source = source.clone();
+            move || source.get()
+        },
+        100 * MS,
+    );
+
+    source.set(1);
+    assert_eq!(clock.pending(), 1);
+    drop(debounced);
+    assert_eq!(clock.pending(), 0);
+}
+
+/// A clock whose callbacks cannot be canceled, like a clock that runs them on another thread
+/// when one has already started running by the time it is canceled.
+struct UncancelableClock(MockClock);
+
+impl Clock for UncancelableClock {
+    fn now(&self) -> Duration {
+        self.0.now()
+    }
+
+    fn set_timeout(
+        &self,
+        delay: Duration,
+        callback: Box<dyn FnOnce() + Send>,
+    ) -> TimeoutHandle {
+        _ = self.0.set_timeout(delay, callback);
+        TimeoutHandle::new(|| {})
+    }
+}
+
+#[test]
+fn canceled_callbacks_that_still_run_are_ignored() {
+    let owner = Owner::new();
+    owner.set();
+    let clock = MockClock::new();
+    provide_clock(UncancelableClock(clock.clone()));
+
+    let source = ArcRwSignal::new(0);
+    let debounced = ArcDebounced::new(
+        {
+            let source = source.clone();
+            move || source.get()
+        },
+        100 * MS,
+    );
+
+    source.set(1);
+    clock.advance(60 * MS);
+    source.set(2);
+    // the first timer still runs, even though it was canceled
+    clock.advance(40 * MS);
+    assert_eq!(debounced.get(), 0);
+
+    clock.advance(60 * MS);
+    assert_eq!(debounced.get(), 2);
+}
@@ -1,4 +1,5 @@
 use crate::{
+    changes::ReportWrite,
     path::{StorePath, StorePathSegment},
     ArcStore, AtIndex, AtKeyed, DerefedField, KeyMap, KeyedAccess,
     KeyedSubfield, Store, StoreField, StoreFieldTrigger, Subfield,
@@ -473,7 +474,7 @@ impl<T> Write for ArcField<T> {
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        (self.write)()
+        (self.write)().map(|writer| ReportWrite::new(self, writer))
     }
 
     fn try_write_untracked(
@@ -0,0 +1,135 @@
+use crate::{path::StorePath, StoreField};
+use futures::{
+    channel::mpsc::{self, UnboundedSender},
+    Stream,
+};
+use or_poisoned::OrPoisoned;
+use reactive_graph::traits::UntrackableGuard;
+use std::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+    sync::{Arc, RwLock},
+};
+
+/// Describes how a field of a store was changed. See
+/// [`ArcStore::subscribe_changes`](crate::ArcStore::subscribe_changes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// The field was written to directly, e.g., with `.set()`, `.update()`, or `.write()`, or it
+    /// was notified manually. Any of its children may have changed as well.
+    Write,
+    /// The field was changed by patching it or one of its ancestors (see [`Patch`](crate::Patch)).
+    Patch,
+}
+
+type ChangeSender = UnboundedSender<(StorePath, ChangeKind)>;
+
+/// The subscribers to the changes made to a store.
+#[derive(Clone, Default)]
+pub(crate) struct ChangeSubscribers(Arc<RwLock<Vec<ChangeSender>>>);
+
+impl Debug for ChangeSubscribers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ChangeSubscribers")
+            .field(&self.0.read().or_poisoned().len())
+            .finish()
+    }
+}
+
+impl ChangeSubscribers {
+    pub fn subscribe(
+        &self,
+    ) -> impl Stream<Item = (StorePath, ChangeKind)> + Send + Sync + Unpin + 'static
+    {
+        let (tx, rx) = mpsc::unbounded();
+        self.0.write().or_poisoned().push(tx);
+        rx
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.read().or_poisoned().is_empty()
+    }
+
+    pub fn report(&self, path: &StorePath, kind: ChangeKind) {
+        if self.is_empty() {
+            return;
+        }
+        // drop the senders for any streams that have been dropped
+        self.0
+            .write()
+            .or_poisoned()
+            .retain(|tx| tx.unbounded_send((path.clone(), kind)).is_ok());
+    }
+
+    /// Reports a change to the given field, only calculating its path if anyone is subscribed.
+    pub fn report_field(&self, field: &impl StoreField, kind: ChangeKind) {
+        if !self.is_empty() {
+            let path = field.path_unkeyed().into_iter().collect();
+            self.report(&path, kind);
+        }
+    }
+}
+
+/// Returns the change subscribers of the store that contains this field.
+pub(crate) fn change_subscribers(field: &impl StoreField) -> ChangeSubscribers {
+    field.get_trigger(StorePath::new()).changes
+}
+
+/// Wraps the write guard for a store field, and reports the write to anyone who is subscribed to
+/// the store's changes once it has been dropped, unless it was untracked.
+pub(crate) struct ReportWrite<G> {
+    guard: Option<G>,
+    change: Option<(ChangeSubscribers, StorePath)>,
+}
+
+impl<G> ReportWrite<G> {
+    pub fn new(field: &impl StoreField, guard: G) -> Self {
+        let changes = change_subscribers(field);
+        let change = (!changes.is_empty()).then(|| {
+            let path = field.path_unkeyed().into_iter().collect();
+            (changes, path)
+        });
+        Self {
+            guard: Some(guard),
+            change,
+        }
+    }
+}
+
+impl<G: Deref> Deref for ReportWrite<G> {
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard
+            .as_ref()
+            .expect("should be Some(_) until dropped")
+    }
+}
+
+impl<G: DerefMut> DerefMut for ReportWrite<G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard
+            .as_mut()
+            .expect("should be Some(_) until dropped")
+    }
+}
+
+impl<G: UntrackableGuard> UntrackableGuard for ReportWrite<G> {
+    fn untrack(&mut self) {
+        self.change = None;
+        if let Some(guard) = self.guard.as_mut() {
+            guard.untrack();
+        }
+    }
+}
+
+impl<G> Drop for ReportWrite<G> {
+    fn drop(&mut self) {
+        // release the write lock and notify the field's triggers before reporting the change, so
+        // that subscribers can read the new value
+        drop(self.guard.take());
+        if let Some((changes, path)) = &self.change {
+            changes.report(path, ChangeKind::Write);
+        }
+    }
+}
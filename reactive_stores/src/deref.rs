@@ -1,4 +1,5 @@
 use crate::{
+    changes::{ChangeKind, ReportWrite},
     path::{StorePath, StorePathSegment},
     store_field::StoreField,
     KeyMap, StoreFieldTrigger,
@@ -130,6 +131,7 @@ where
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
         trigger.children.notify();
+        trigger.changes.report_field(self, ChangeKind::Write);
     }
 }
 impl<S> Track for DerefedField<S>
@@ -162,7 +164,7 @@ where
     type Value = <S::Value as Deref>::Target;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer()
+        self.writer().map(|writer| ReportWrite::new(self, writer))
     }
     fn try_write_untracked(
         &self,
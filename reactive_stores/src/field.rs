@@ -1,5 +1,6 @@
 use crate::{
     arc_field::{StoreFieldReader, StoreFieldWriter},
+    changes::ReportWrite,
     path::{StorePath, StorePathSegment},
     ArcField, ArcStore, AtIndex, AtKeyed, DerefedField, KeyMap, KeyedAccess,
     KeyedSubfield, Store, StoreField, StoreFieldTrigger, Subfield,
@@ -273,7 +274,9 @@ impl<T> Write for Field<T> {
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.inner.try_get_value().and_then(|inner| (inner.write)())
+        self.inner.try_get_value().and_then(|inner| {
+            (inner.write)().map(|writer| ReportWrite::new(&inner, writer))
+        })
     }
 
     fn try_write_untracked(
@@ -0,0 +1,389 @@
+use crate::{ArcStore, Patch, PatchField, Store};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    owner::{ArenaItem, Storage, SyncStorage},
+    signal::ArcTrigger,
+    traits::{DefinedAt, IsDisposed, Notify, Track},
+};
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    panic::Location,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Weak,
+    },
+};
+
+/// A function that is called with the current value of a store, before it is written to.
+pub(crate) type BeforeWrite<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
+struct HistoryState<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    max_depth: usize,
+    /// How many transactions are currently open.
+    transaction_depth: usize,
+    /// Whether the current transaction has already recorded its step.
+    transaction_recorded: bool,
+}
+
+/// Records the changes made to an [`ArcStore`], so that they can be undone and redone.
+///
+/// Every write to the store or one of its fields, including each call to
+/// [`Patch::patch`](crate::Patch::patch), records a snapshot of the value from before the write
+/// as one undo step. Several writes can be grouped into a single step with
+/// [`transaction`](Self::transaction).
+///
+/// Undoing or redoing patches the store with the saved value, so only the fields that actually
+/// change are notified.
+///
+/// A store can only have one history at a time: creating a new history for a store stops the
+/// previous one from recording any further changes.
+pub struct ArcStoreHistory<T> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    store: ArcStore<T>,
+    state: Arc<Mutex<HistoryState<T>>>,
+    /// Set while undoing or redoing, so that the patch is not recorded as a new step.
+    applying: Arc<AtomicBool>,
+    trigger: ArcTrigger,
+}
+
+impl<T> Clone for ArcStoreHistory<T> {
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            store: self.store.clone(),
+            state: Arc::clone(&self.state),
+            applying: Arc::clone(&self.applying),
+            trigger: self.trigger.clone(),
+        }
+    }
+}
+
+impl<T> Debug for ArcStoreHistory<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut f = f.debug_struct("ArcStoreHistory");
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let f = f.field("defined_at", &self.defined_at);
+        f.finish_non_exhaustive()
+    }
+}
+
+impl<T> DefinedAt for ArcStoreHistory<T> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<T> ArcStoreHistory<T>
+where
+    T: Clone + PatchField + Send + Sync + 'static,
+{
+    /// Starts recording the changes made to the store, keeping at most `max_depth` undo steps.
+    /// Once there are more steps than that, the oldest ones are discarded.
+    #[track_caller]
+    pub fn new(store: ArcStore<T>, max_depth: usize) -> Self {
+        let state = Arc::new(Mutex::new(HistoryState {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            max_depth,
+            transaction_depth: 0,
+            transaction_recorded: false,
+        }));
+        let applying = Arc::new(AtomicBool::new(false));
+        let trigger = ArcTrigger::new();
+
+        // the hook only holds a weak reference, so that once the history has been dropped, writes
+        // stop cloning the value to record it
+        let record: BeforeWrite<T> = Arc::new({
+            let state = Arc::downgrade(&state);
+            let applying = Arc::clone(&applying);
+            let trigger = trigger.clone();
+            move |value: &T| {
+                let Some(state) = Weak::upgrade(&state) else {
+                    return;
+                };
+                if applying.load(Ordering::Relaxed) {
+                    return;
+                }
+                {
+                    let mut state = state.lock().or_poisoned();
+                    if state.transaction_depth > 0 {
+                        if state.transaction_recorded {
+                            return;
+                        }
+                        state.transaction_recorded = true;
+                    }
+                    state.undo.push_back(value.clone());
+                    while state.undo.len() > state.max_depth {
+                        state.undo.pop_front();
+                    }
+                    state.redo.clear();
+                }
+                trigger.notify();
+            }
+        });
+        *store.before_write.write().or_poisoned() = Some(record);
+
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            store,
+            state,
+            applying,
+            trigger,
+        }
+    }
+
+    /// Restores the value from before the most recent step, if there is one. Returns `true` if a
+    /// step was undone.
+    pub fn undo(&self) -> bool {
+        let prev = self.state.lock().or_poisoned().undo.pop_back();
+        match prev {
+            Some(prev) => {
+                let current = self.apply(prev);
+                self.state.lock().or_poisoned().redo.push(current);
+                self.trigger.notify();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Restores the value from before the most recent [`undo`](Self::undo), if there is one.
+    /// Returns `true` if a step was redone.
+    pub fn redo(&self) -> bool {
+        let next = self.state.lock().or_poisoned().redo.pop();
+        match next {
+            Some(next) => {
+                let current = self.apply(next);
+                self.state.lock().or_poisoned().undo.push_back(current);
+                self.trigger.notify();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Patches the store with the given value without recording it, and returns the value that
+    /// it replaced.
+    fn apply(&self, value: T) -> T {
+        let current = self.store.value.read().or_poisoned().clone();
+        self.applying.store(true, Ordering::Relaxed);
+        self.store.patch(value);
+        self.applying.store(false, Ordering::Relaxed);
+        current
+    }
+
+    /// Runs the function, recording all the writes made inside it as a single undo step.
+    ///
+    /// Transactions can be nested, in which case the outermost transaction is the step.
+    pub fn transaction<U>(&self, fun: impl FnOnce() -> U) -> U {
+        struct EndOnDrop<'a, T>(&'a Mutex<HistoryState<T>>);
+
+        impl<T> Drop for EndOnDrop<'_, T> {
+            fn drop(&mut self) {
+                // this also runs if `fun` panics, so that later writes are not all merged into
+                // a step that never ends
+                let mut state = self.0.lock().or_poisoned();
+                state.transaction_depth -= 1;
+                if state.transaction_depth == 0 {
+                    state.transaction_recorded = false;
+                }
+            }
+        }
+
+        self.state.lock().or_poisoned().transaction_depth += 1;
+        let _end = EndOnDrop(&self.state);
+        fun()
+    }
+}
+
+impl<T> ArcStoreHistory<T> {
+    /// Whether there is a step that can be undone. This is tracked reactively.
+    pub fn can_undo(&self) -> bool {
+        self.trigger.track();
+        !self.state.lock().or_poisoned().undo.is_empty()
+    }
+
+    /// Whether there is a step that can be redone. This is tracked reactively.
+    pub fn can_redo(&self) -> bool {
+        self.trigger.track();
+        !self.state.lock().or_poisoned().redo.is_empty()
+    }
+
+    /// Discards all the recorded steps.
+    pub fn clear(&self) {
+        {
+            let mut state = self.state.lock().or_poisoned();
+            state.undo.clear();
+            state.redo.clear();
+        }
+        self.trigger.notify();
+    }
+}
+
+/// An arena-allocated [`ArcStoreHistory`], which records the changes made to a [`Store`] so that
+/// they can be undone and redone.
+///
+/// This follows the same ownership rules as arena-allocated types like
+/// [`RwSignal`](reactive_graph::signal::RwSignal).
+pub struct StoreHistory<T, S = SyncStorage> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    inner: ArenaItem<ArcStoreHistory<T>, S>,
+}
+
+impl<T, S> Clone for StoreHistory<T, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, S> Copy for StoreHistory<T, S> {}
+
+impl<T, S> Debug for StoreHistory<T, S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut f = f.debug_struct("StoreHistory");
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let f = f.field("defined_at", &self.defined_at);
+        f.field("inner", &self.inner).finish()
+    }
+}
+
+impl<T, S> DefinedAt for StoreHistory<T, S> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<T, S> IsDisposed for StoreHistory<T, S>
+where
+    T: 'static,
+{
+    #[inline(always)]
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<T> StoreHistory<T>
+where
+    T: Clone + PatchField + Send + Sync + 'static,
+{
+    /// Starts recording the changes made to the store, keeping at most `max_depth` undo steps.
+    ///
+    /// # Panics
+    /// Panics if the store has already been disposed.
+    #[track_caller]
+    pub fn new(store: Store<T>, max_depth: usize) -> Self {
+        let store = store
+            .inner
+            .try_get_value()
+            .expect("tried to record the history of a disposed store");
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(ArcStoreHistory::new(
+                store, max_depth,
+            )),
+        }
+    }
+}
+
+impl<T, S> StoreHistory<T, S>
+where
+    T: Clone + PatchField + Send + Sync + 'static,
+    S: Storage<ArcStoreHistory<T>>,
+{
+    /// Restores the value from before the most recent step, if there is one. Returns `true` if a
+    /// step was undone.
+    pub fn undo(&self) -> bool {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.undo())
+            .unwrap_or(false)
+    }
+
+    /// Restores the value from before the most recent [`undo`](Self::undo), if there is one.
+    /// Returns `true` if a step was redone.
+    pub fn redo(&self) -> bool {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.redo())
+            .unwrap_or(false)
+    }
+
+    /// Runs the function, recording all the writes made inside it as a single undo step.
+    ///
+    /// Transactions can be nested, in which case the outermost transaction is the step.
+    pub fn transaction<U>(&self, fun: impl FnOnce() -> U) -> U {
+        match self.inner.try_get_value() {
+            Some(inner) => inner.transaction(fun),
+            None => fun(),
+        }
+    }
+}
+
+impl<T, S> StoreHistory<T, S>
+where
+    T: 'static,
+    S: Storage<ArcStoreHistory<T>>,
+{
+    /// Whether there is a step that can be undone. This is tracked reactively.
+    pub fn can_undo(&self) -> bool {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.can_undo())
+            .unwrap_or(false)
+    }
+
+    /// Whether there is a step that can be redone. This is tracked reactively.
+    pub fn can_redo(&self) -> bool {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.can_redo())
+            .unwrap_or(false)
+    }
+
+    /// Discards all the recorded steps.
+    pub fn clear(&self) {
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.clear();
+        }
+    }
+}
+
+impl<T> From<ArcStoreHistory<T>> for StoreHistory<T>
+where
+    T: Send + Sync + 'static,
+{
+    #[track_caller]
+    fn from(value: ArcStoreHistory<T>) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(value),
+        }
+    }
+}
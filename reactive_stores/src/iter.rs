@@ -1,4 +1,5 @@
 use crate::{
+    changes::{ChangeKind, ReportWrite},
     len::Len,
     path::{StorePath, StorePathSegment},
     store_field::StoreField,
@@ -173,6 +174,7 @@ where
     fn notify(&self) {
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
+        trigger.changes.report_field(self, ChangeKind::Write);
     }
 }
 
@@ -209,7 +211,7 @@ where
     type Value = Prev::Output;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer()
+        self.writer().map(|writer| ReportWrite::new(self, writer))
     }
 
     fn try_write_untracked(
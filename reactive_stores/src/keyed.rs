@@ -1,4 +1,5 @@
 use crate::{
+    changes::{ChangeKind, ReportWrite},
     path::{StorePath, StorePathSegment},
     store_field::StoreField,
     KeyMap, StoreFieldTrigger,
@@ -400,6 +401,7 @@ where
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
         trigger.children.notify();
+        trigger.changes.report_field(self, ChangeKind::Write);
     }
 }
 
@@ -718,6 +720,7 @@ where
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
         trigger.children.notify();
+        trigger.changes.report_field(self, ChangeKind::Write);
     }
 }
 
@@ -766,7 +769,7 @@ where
     type Value = T::Value;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer()
+        self.writer().map(|writer| ReportWrite::new(self, writer))
     }
 
     fn try_write_untracked(
@@ -1507,4 +1510,36 @@ mod tests {
         assert_eq!(b_count.load(Ordering::Relaxed), 2);
         assert_eq!(c_count.load(Ordering::Relaxed), 2);
     }
+    #[tokio::test]
+    async fn patching_the_store_updates_keys_of_keyed_fields() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(TodoVec::test_data());
+        let a = AtKeyed::new(store.todos(), 10);
+        let a_count = Arc::new(AtomicUsize::new(0));
+        Effect::new_sync({
+            let a_count = Arc::clone(&a_count);
+            move || {
+                a.track();
+                a_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        assert_eq!(a_count.load(Ordering::Relaxed), 1);
+
+        let mut new_data = TodoVec::test_data();
+        new_data.todos.swap(0, 2);
+        store.patch(new_data);
+        tick().await;
+        assert_eq!(a_count.load(Ordering::Relaxed), 2);
+
+        // the keys were updated, so the item with this key is found at its new index
+        a.label().set("Bar".into());
+        assert_eq!(
+            store.todos().get_untracked(),
+            vec![Todo::new(12, "C"), Todo::new(11, "B"), Todo::new(10, "Bar")]
+        );
+        tick().await;
+        assert_eq!(a_count.load(Ordering::Relaxed), 3);
+    }
 }
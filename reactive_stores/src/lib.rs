@@ -20,10 +20,17 @@
 //!
 //! The [`Patch`](macro@Patch) macro allows you to annotate a struct such that stores and fields have a
 //! [`.patch()`](Patch::patch) method, which allows you to provide an entirely new value, but only
-//! notify fields that have changed.
+//! notify fields that have changed. It can also be derived for an enum: if the new value is the
+//! same variant, its fields are patched one by one; otherwise, the enum itself is notified.
 //!
 //! Updating a field will notify its parents and children, but not its siblings.
 //!
+//! A [`StoreHistory`] records the changes made to a store, so that they can be undone and
+//! redone.
+//!
+//! [`ArcStore::subscribe_changes`] returns a stream of the paths of the fields that change, which
+//! can be used to persist or sync a store's state without tracking every field in an effect.
+//!
 //! Stores can therefore
 //! 1) work with plain Rust data types, and
 //! 2) provide reactive access to individual fields
@@ -239,6 +246,8 @@
 //! field in the signal inner `Arc<RwLock<_>>`, and tracks the trigger that corresponds with its
 //! path; calling `.write()` returns a writeable guard, and notifies that same trigger.
 
+use changes::ChangeSubscribers;
+use futures::Stream;
 use or_poisoned::OrPoisoned;
 use reactive_graph::{
     owner::{ArenaItem, LocalStorage, Storage, SyncStorage},
@@ -263,8 +272,10 @@ use std::{
 };
 
 mod arc_field;
+mod changes;
 mod deref;
 mod field;
+mod history;
 mod iter;
 mod keyed;
 mod len;
@@ -279,8 +290,10 @@ mod store_field;
 mod subfield;
 
 pub use arc_field::ArcField;
+pub use changes::ChangeKind;
 pub use deref::*;
 pub use field::Field;
+pub use history::{ArcStoreHistory, StoreHistory};
 pub use iter::*;
 pub use keyed::*;
 pub use len::Len;
@@ -291,13 +304,15 @@ pub use store_field::StoreField;
 pub use subfield::Subfield;
 
 #[derive(Debug, Default)]
-struct TriggerMap(FxHashMap<StorePath, StoreFieldTrigger>);
+struct TriggerMap(FxHashMap<StorePath, StoreFieldTrigger>, ChangeSubscribers);
 
 /// The reactive trigger that can be used to track updates to a store field.
 #[derive(Debug, Clone, Default)]
 pub struct StoreFieldTrigger {
     pub(crate) this: ArcTrigger,
     pub(crate) children: ArcTrigger,
+    /// The subscribers to the changes made to the store that contains this field.
+    pub(crate) changes: ChangeSubscribers,
 }
 
 impl StoreFieldTrigger {
@@ -312,7 +327,10 @@ impl TriggerMap {
         if let Some(trigger) = self.0.get(&key) {
             trigger.clone()
         } else {
-            let new = StoreFieldTrigger::new();
+            let new = StoreFieldTrigger {
+                changes: self.1.clone(),
+                ..Default::default()
+            };
             self.0.insert(key, new.clone());
             new
         }
@@ -521,6 +539,53 @@ impl KeyMap {
         Some(result)
     }
 
+    /// Returns the path to the value identified by `key` in the keyed field at `path`, using its
+    /// index, if keys have been registered for that field.
+    ///
+    /// # Usage
+    ///
+    /// You should not call this method directly from your code, as it's
+    /// an implementation detail of `reactive_stores`. This method was exposed
+    /// to implement the derive `Patch` macro for keyed fields.
+    #[doc(hidden)]
+    pub fn path_at_key<K>(&self, path: &StorePath, key: &K) -> Option<StorePath>
+    where
+        K: Hash + PartialEq + Eq + 'static,
+    {
+        let (_, idx) = self
+            .0
+            .read()
+            .or_poisoned()
+            .get(path)?
+            .downcast_ref::<FieldKeys<K>>()?
+            .get(key)?;
+        let mut path = path.clone();
+        path.push(idx);
+        Some(path)
+    }
+
+    /// Replaces the keys of the keyed field at `path` with `latest`, if keys have been
+    /// registered for that field.
+    ///
+    /// # Usage
+    ///
+    /// You should not call this method directly from your code, as it's
+    /// an implementation detail of `reactive_stores`. This method was exposed
+    /// to implement the derive `Patch` macro for keyed fields.
+    #[doc(hidden)]
+    pub fn update_field_keys<K>(&self, path: StorePath, latest: Vec<K>)
+    where
+        K: Debug + Hash + PartialEq + Eq + Send + Sync + 'static,
+    {
+        if self.contains_key(&path) {
+            self.with_field_keys(
+                path,
+                |keys| ((), keys.update(latest)),
+                Vec::new,
+            );
+        }
+    }
+
     fn contains_key(&self, key: &StorePath) -> bool {
         self.0.read().or_poisoned().contains_key(key)
     }
@@ -545,6 +610,7 @@ pub struct ArcStore<T> {
     pub(crate) value: Arc<RwLock<T>>,
     signals: Arc<RwLock<TriggerMap>>,
     keys: KeyMap,
+    pub(crate) before_write: Arc<RwLock<Option<history::BeforeWrite<T>>>>,
 }
 
 impl<T> ArcStore<T> {
@@ -556,10 +622,43 @@ impl<T> ArcStore<T> {
             value: Arc::new(RwLock::new(value)),
             signals: Default::default(),
             keys: Default::default(),
+            before_write: Default::default(),
         }
     }
 }
 
+impl<T> ArcStore<T> {
+    /// Returns a stream of the changes made to this store, as the path of each field that
+    /// changed and how it was changed.
+    ///
+    /// Every write to the store or to one of its fields is reported, including writes made by
+    /// [`Patch::patch`], which reports each field that has actually changed. Use
+    /// [`field_name`](Self::field_name) to turn a path into a readable name.
+    ///
+    /// Only writes that notify the reactive system are reported, so untracked writes (as with
+    /// `.write_untracked()`) are not. The stream ends when the store is dropped.
+    pub fn subscribe_changes(
+        &self,
+    ) -> impl Stream<Item = (StorePath, ChangeKind)> + Send + Sync + Unpin + 'static
+    {
+        self.signals.read().or_poisoned().1.subscribe()
+    }
+
+    /// Returns a readable name for the field of this store at the given path, such as
+    /// `todos[2].label`.
+    ///
+    /// Fields are named using the [`Patch`](macro@Patch) implementations of their types, so
+    /// any part of the path within a type that does not derive `Patch` is shown as indices.
+    pub fn field_name(&self, path: &StorePath) -> String
+    where
+        T: PatchField,
+    {
+        let mut name = String::new();
+        T::field_name(&path.0, &mut name);
+        name
+    }
+}
+
 impl<T: Default> Default for ArcStore<T> {
     fn default() -> Self {
         Self::new(T::default())
@@ -585,6 +684,7 @@ impl<T> Clone for ArcStore<T> {
             value: Arc::clone(&self.value),
             signals: Arc::clone(&self.signals),
             keys: self.keys.clone(),
+            before_write: Arc::clone(&self.before_write),
         }
     }
 }
@@ -651,6 +751,7 @@ impl<T: 'static> Notify for ArcStore<T> {
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
         trigger.children.notify();
+        trigger.changes.report(&StorePath::new(), ChangeKind::Write);
     }
 }
 
@@ -683,6 +784,42 @@ where
     }
 }
 
+impl<T, S> Store<T, S>
+where
+    T: 'static,
+    S: Storage<ArcStore<T>>,
+{
+    /// Returns a stream of the changes made to this store, as the path of each field that
+    /// changed and how it was changed.
+    ///
+    /// See [`ArcStore::subscribe_changes`] for details. If the store has already been disposed,
+    /// the stream ends immediately.
+    pub fn subscribe_changes(
+        &self,
+    ) -> impl Stream<Item = (StorePath, ChangeKind)> + Send + Sync + Unpin + 'static
+    {
+        self.inner
+            .try_with_value(|inner| {
+                inner.signals.read().or_poisoned().1.clone()
+            })
+            .unwrap_or_default()
+            .subscribe()
+    }
+
+    /// Returns a readable name for the field of this store at the given path, such as
+    /// `todos[2].label`.
+    ///
+    /// See [`ArcStore::field_name`] for details.
+    pub fn field_name(&self, path: &StorePath) -> String
+    where
+        T: PatchField,
+    {
+        let mut name = String::new();
+        T::field_name(&path.0, &mut name);
+        name
+    }
+}
+
 impl<T, S> PartialEq for Store<T, S> {
     fn eq(&self, other: &Self) -> bool {
         self.inner == other.inner
@@ -851,11 +988,17 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::{self as reactive_stores, Patch, Store, StoreFieldIterator};
+    use crate::{
+        self as reactive_stores, ArcStore, ArcStoreHistory, Field, Patch,
+        Store, StoreFieldIterator, StoreHistory,
+    };
     use reactive_graph::{
         effect::Effect,
         owner::StoredValue,
-        traits::{Read, ReadUntracked, Set, Track, Update, Write},
+        traits::{
+            GetUntracked, Notify, Read, ReadUntracked, Set, Track, Update,
+            Write,
+        },
     };
     use std::sync::{
         atomic::{AtomicUsize, Ordering},
@@ -866,13 +1009,13 @@ mod tests {
         tokio::time::sleep(std::time::Duration::from_micros(1)).await;
     }
 
-    #[derive(Debug, Store, Patch, Default)]
+    #[derive(Debug, Clone, Store, Patch, Default)]
     struct Todos {
         user: String,
         todos: Vec<Todo>,
     }
 
-    #[derive(Debug, Store, Patch, Default)]
+    #[derive(Debug, Clone, Store, Patch, Default)]
     struct Todo {
         label: String,
         completed: bool,
@@ -1204,6 +1347,122 @@ mod tests {
         assert_eq!(combined_count.load(Ordering::Relaxed), 3);
     }
 
+    #[tokio::test]
+    async fn patching_enum_only_notifies_changed_fields_of_same_variant() {
+        _ = any_spawner::Executor::init_tokio();
+
+        #[derive(Debug, Store, Patch)]
+        enum Loadable {
+            Loading,
+            Loaded(Todo),
+            Failed { message: String },
+        }
+
+        let variant_count = Arc::new(AtomicUsize::new(0));
+        let label_count = Arc::new(AtomicUsize::new(0));
+
+        let store = Store::new(Loadable::Loading);
+
+        Effect::new_sync({
+            let variant_count = Arc::clone(&variant_count);
+            move |_| {
+                println!("loaded: {}", store.loaded());
+                variant_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        assert_eq!(variant_count.load(Ordering::Relaxed), 1);
+
+        // changing the variant notifies its subscribers
+        store.patch(Loadable::Failed {
+            message: "timed out".into(),
+        });
+        tick().await;
+        assert_eq!(variant_count.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            store
+                .failed_message()
+                .map(|message| message.read_untracked().clone()),
+            Some("timed out".to_string())
+        );
+
+        store.patch(Loadable::Loaded(Todo::new("a")));
+        tick().await;
+        assert_eq!(variant_count.load(Ordering::Relaxed), 3);
+
+        let label = store.loaded_0().unwrap().label();
+        Effect::new_sync({
+            let label_count = Arc::clone(&label_count);
+            move |_| {
+                println!("label: {}", label.read());
+                label_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        assert_eq!(label_count.load(Ordering::Relaxed), 1);
+
+        // patching another field of the same variant does not notify the label
+        store.patch(Loadable::Loaded(Todo {
+            label: "a".into(),
+            completed: true,
+        }));
+        tick().await;
+        assert_eq!(label_count.load(Ordering::Relaxed), 1);
+        assert!(matches!(
+            &*store.read_untracked(),
+            Loadable::Loaded(todo) if todo.completed
+        ));
+
+        store.patch(Loadable::Loaded(Todo {
+            label: "b".into(),
+            completed: true,
+        }));
+        tick().await;
+        assert_eq!(label_count.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn patching_enum_uses_keyed_patch_for_keyed_fields() {
+        _ = any_spawner::Executor::init_tokio();
+
+        #[derive(Debug, Store, Patch)]
+        enum Loadable {
+            Loaded {
+                #[store(key: String = |todo| todo.label.clone())]
+                todos: Vec<Todo>,
+            },
+        }
+
+        let completed_count = Arc::new(AtomicUsize::new(0));
+
+        let store = Store::new(Loadable::Loaded {
+            todos: vec![Todo::new("a"), Todo::new("b")],
+        });
+
+        let completed = store.loaded_todos().unwrap().at_unkeyed(1).completed();
+        Effect::new_sync({
+            let completed_count = Arc::clone(&completed_count);
+            move |_| {
+                println!("completed: {}", completed.read());
+                completed_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        assert_eq!(completed_count.load(Ordering::Relaxed), 1);
+
+        // reordering the todos changes the structure of the keyed field, so every item is
+        // notified, rather than only the labels that differ at each index
+        store.patch(Loadable::Loaded {
+            todos: vec![Todo::new("b"), Todo::new("a")],
+        });
+        tick().await;
+        assert_eq!(completed_count.load(Ordering::Relaxed), 2);
+        assert!(matches!(
+            &*store.read_untracked(),
+            Loadable::Loaded { todos } if todos[0].label == "b"
+        ));
+    }
+
     // regression test for https://github.com/leptos-rs/leptos/issues/3523
     #[tokio::test]
     async fn notifying_all_descendants() {
@@ -1427,4 +1686,234 @@ mod tests {
         tick().await;
         assert_eq!(name_count.load(Ordering::Relaxed), 2);
     }
+
+    #[test]
+    fn batched_field_setters_notify_once() {
+        use reactive_graph::{
+            effect::{batch, ImmediateEffect},
+            traits::{Get, GetValue, WriteValue},
+        };
+
+        let store = Store::new(data());
+        let seen = StoredValue::new(Vec::new());
+
+        let _effect = ImmediateEffect::new_isomorphic(move || {
+            let user = store.user().get();
+            let completed = store
+                .todos()
+                .iter_unkeyed()
+                .filter(|todo| todo.completed().get())
+                .count();
+            seen.write_value().push((user, completed));
+        });
+
+        batch(move || {
+            store.user().set("Alice".into());
+            for todo in store.todos().iter_unkeyed() {
+                todo.completed().set(true);
+            }
+        });
+
+        assert_eq!(
+            seen.get_value(),
+            vec![("Bob".to_string(), 1), ("Alice".to_string(), 3)]
+        );
+    }
+
+    #[tokio::test]
+    async fn undo_and_redo_only_notify_changed_fields() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let user_count = Arc::new(AtomicUsize::new(0));
+        let todos_count = Arc::new(AtomicUsize::new(0));
+
+        let store = Store::new(data());
+        let history = StoreHistory::new(store, 10);
+        assert!(!history.can_undo());
+
+        Effect::new_sync({
+            let user_count = Arc::clone(&user_count);
+            move |_| {
+                store.user().track();
+                user_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Effect::new_sync({
+            let todos_count = Arc::clone(&todos_count);
+            move |_| {
+                store.todos().track();
+                todos_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+
+        store.user().set("Alice".into());
+        tick().await;
+        store.todos().write().push(Todo::new("Undo"));
+        tick().await;
+        assert_eq!(user_count.load(Ordering::Relaxed), 2);
+        assert_eq!(todos_count.load(Ordering::Relaxed), 2);
+        assert!(history.can_undo());
+        assert!(!history.can_redo());
+
+        // undoing the push only changes the todos
+        assert!(history.undo());
+        tick().await;
+        assert_eq!(store.todos().read_untracked().len(), 3);
+        assert_eq!(user_count.load(Ordering::Relaxed), 2);
+        assert_eq!(todos_count.load(Ordering::Relaxed), 3);
+
+        assert!(history.undo());
+        tick().await;
+        assert_eq!(*store.user().read_untracked(), "Bob");
+        assert_eq!(user_count.load(Ordering::Relaxed), 3);
+        assert_eq!(todos_count.load(Ordering::Relaxed), 3);
+        assert!(!history.can_undo());
+        assert!(!history.undo());
+
+        assert!(history.redo());
+        assert!(history.redo());
+        tick().await;
+        assert_eq!(*store.user().read_untracked(), "Alice");
+        assert_eq!(store.todos().read_untracked().len(), 4);
+        assert!(!history.can_redo());
+
+        // a new write discards the redo steps
+        assert!(history.undo());
+        store.user().set("Carol".into());
+        assert!(!history.can_redo());
+    }
+
+    #[tokio::test]
+    async fn history_transactions_and_max_depth() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let history = StoreHistory::new(store, 2);
+
+        history.transaction(|| {
+            store.user().set("Alice".into());
+            history.transaction(|| {
+                store.todos().write().clear();
+            });
+        });
+        assert!(history.undo());
+        assert_eq!(*store.user().read_untracked(), "Bob");
+        assert_eq!(store.todos().read_untracked().len(), 3);
+        assert!(!history.can_undo());
+
+        store.user().set("Carol".into());
+        store.user().set("Dave".into());
+        store.user().set("Eve".into());
+        assert!(history.undo());
+        assert!(history.undo());
+        assert!(!history.undo());
+        assert_eq!(*store.user().read_untracked(), "Carol");
+
+        history.clear();
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn history_transaction_ends_if_it_panics() {
+        let store = ArcStore::new(data());
+        let history = ArcStoreHistory::new(store.clone(), 10);
+
+        let panicked =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                history.transaction(|| {
+                    store.clone().user().set("Alice".into());
+                    panic!("the transaction failed");
+                })
+            }));
+        assert!(panicked.is_err());
+
+        // later writes are recorded as steps of their own again
+        store.clone().user().set("Carol".into());
+        assert!(history.undo());
+        assert_eq!(*store.clone().user().read_untracked(), "Alice");
+        assert!(history.undo());
+        assert_eq!(*store.clone().user().read_untracked(), "Bob");
+    }
+
+    #[test]
+    fn dropped_history_stops_recording() {
+        static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug, Store, Patch)]
+        struct Counted {
+            count: i32,
+        }
+
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                CLONES.fetch_add(1, Ordering::Relaxed);
+                Self { count: self.count }
+            }
+        }
+
+        let store = ArcStore::new(Counted { count: 0 });
+        let history = ArcStoreHistory::new(store.clone(), 10);
+        store.clone().count().set(1);
+        assert!(history.can_undo());
+        assert_eq!(CLONES.load(Ordering::Relaxed), 1);
+
+        drop(history);
+        store.clone().count().set(2);
+        assert_eq!(CLONES.load(Ordering::Relaxed), 1);
+        assert_eq!(store.count().get_untracked(), 2);
+    }
+
+    #[tokio::test]
+    async fn subscribing_to_changes_reports_changed_paths() {
+        use crate::ChangeKind;
+        use futures::{FutureExt, StreamExt};
+
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let mut changes = store.subscribe_changes();
+        let mut next_change = move || {
+            changes
+                .next()
+                .now_or_never()
+                .flatten()
+                .map(|(path, kind)| (store.field_name(&path), kind))
+        };
+
+        store.user().set("Alice".into());
+        assert_eq!(next_change(), Some(("user".into(), ChangeKind::Write)));
+
+        store.todos().at_unkeyed(1).label().set("Pay bills".into());
+        assert_eq!(
+            next_change(),
+            Some(("todos[1].label".into(), ChangeKind::Write))
+        );
+        assert_eq!(next_change(), None);
+
+        // writes through a type-erased field are reported as well
+        let user: Field<String> = store.user().into();
+        user.set("Bob".into());
+        assert_eq!(next_change(), Some(("user".into(), ChangeKind::Write)));
+
+        // untracked writes don't notify anything, so they aren't reported either
+        store.user().write_untracked().push('!');
+        assert_eq!(next_change(), None);
+        store.user().set("Alice".into());
+        _ = next_change();
+
+        let mut new = data();
+        new.user = "Alice".into();
+        new.todos[0].completed = false;
+        new.todos[1].label = "Pay bills".into();
+        store.patch(new);
+        assert_eq!(
+            next_change(),
+            Some(("todos[0].completed".into(), ChangeKind::Patch))
+        );
+        assert_eq!(next_change(), None);
+
+        store.notify();
+        assert_eq!(next_change(), Some(("".into(), ChangeKind::Write)));
+    }
 }
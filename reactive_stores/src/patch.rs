@@ -1,11 +1,15 @@
-use crate::{path::StorePath, KeyMap, KeyedAccess, KeyedSubfield, StoreField};
+use crate::{
+    changes::{change_subscribers, ChangeKind},
+    path::{StorePath, StorePathSegment},
+    KeyMap, KeyedAccess, KeyedSubfield, StoreField,
+};
 use indexmap::IndexMap;
 use itertools::{EitherOrBoth, Itertools};
 use reactive_graph::traits::{Notify, UntrackableGuard};
 use std::{
     borrow::Cow,
     collections::{BTreeMap, HashMap},
-    fmt::Debug,
+    fmt::{Debug, Write},
     hash::Hash,
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     num::{
@@ -40,8 +44,10 @@ where
         if let Some(mut writer) = self.writer() {
             // don't track the writer for the whole store
             writer.untrack();
+            let changes = change_subscribers(self);
             let mut notify = |path: &StorePath| {
                 self.triggers_for_path_unkeyed(path.to_owned()).notify();
+                changes.report(path, ChangeKind::Patch);
             };
             writer.patch_field(new, &path, &mut notify, keys.as_ref());
         }
@@ -70,8 +76,10 @@ where
         let structure_changed = if let Some(mut writer) = self.writer() {
             // don't track the writer for the whole store
             writer.untrack();
+            let changes = change_subscribers(self);
             let mut notify = |path: &StorePath| {
                 self.triggers_for_path_unkeyed(path.to_owned()).notify();
+                changes.report(path, ChangeKind::Patch);
             };
             writer.patch_field_keyed(
                 new,
@@ -85,6 +93,8 @@ where
         };
 
         if structure_changed {
+            change_subscribers(self).report(&path, ChangeKind::Patch);
+
             // Only notify `children` (not `this`) at the collection path, so that
             // individual keyed items — which track `this` on all ancestor paths —
             // are not spuriously notified when only the collection order has changed.
@@ -120,6 +130,20 @@ pub trait PatchField {
         notify: &mut dyn FnMut(&StorePath),
         keys: Option<&KeyMap>,
     );
+
+    /// Appends a readable name for the field at the given path within this type to `name`,
+    /// as in `todos[2].label`.
+    ///
+    /// By default, each segment of the path is shown as an index. This is overridden by the
+    /// [`Patch`](macro@crate::Patch) derive macro to use the names of struct fields.
+    fn field_name(path: &[StorePathSegment], name: &mut String)
+    where
+        Self: Sized,
+    {
+        for segment in path {
+            _ = write!(name, "[{segment}]");
+        }
+    }
 }
 
 /// Allows patching a collection in a store field with a new value, after doing a keyed diff.
@@ -250,6 +274,13 @@ where
             }
         }
     }
+
+    fn field_name(path: &[StorePathSegment], name: &mut String) {
+        // the inner value has its own path segment, but not its own name
+        if let [_, rest @ ..] = path {
+            T::field_name(rest, name);
+        }
+    }
 }
 
 impl<T> PatchField for Vec<T>
@@ -304,6 +335,13 @@ where
             }
         }
     }
+
+    fn field_name(path: &[StorePathSegment], name: &mut String) {
+        if let [idx, rest @ ..] = path {
+            _ = write!(name, "[{idx}]");
+            T::field_name(rest, name);
+        }
+    }
 }
 
 impl<K, T> PatchFieldKeyed<K> for Vec<T>
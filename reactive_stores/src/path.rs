@@ -1,6 +1,8 @@
+use std::fmt::{self, Display};
+
 /// The path of a field within some store.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
-pub struct StorePath(Vec<StorePathSegment>);
+pub struct StorePath(pub(crate) Vec<StorePathSegment>);
 
 impl IntoIterator for StorePath {
     type Item = StorePathSegment;
@@ -69,6 +71,12 @@ impl StorePath {
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct StorePathSegment(pub(crate) usize);
 
+impl Display for StorePathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 impl From<usize> for StorePathSegment {
     fn from(value: usize) -> Self {
         Self(value)
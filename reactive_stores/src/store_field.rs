@@ -183,6 +183,9 @@ where
     fn writer(&self) -> Option<Self::Writer> {
         let trigger = self.get_trigger(Default::default());
         let guard = UntrackedWriteGuard::try_new(Arc::clone(&self.value))?;
+        if let Some(before_write) = &*self.before_write.read().or_poisoned() {
+            before_write(&guard);
+        }
         Some(WriteGuard::new(trigger.children, guard))
     }
 
@@ -1,4 +1,5 @@
 use crate::{
+    changes::{ChangeKind, ReportWrite},
     path::{StorePath, StorePathSegment},
     store_field::StoreField,
     KeyMap, StoreFieldTrigger,
@@ -176,6 +177,7 @@ where
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
         trigger.children.notify();
+        trigger.changes.report_field(self, ChangeKind::Write);
     }
 }
 
@@ -212,7 +214,7 @@ where
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer()
+        self.writer().map(|writer| ReportWrite::new(self, writer))
     }
 
     fn try_write_untracked(
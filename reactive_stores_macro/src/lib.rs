@@ -6,8 +6,9 @@ use syn::{
     parse::{Parse, ParseStream, Parser},
     punctuated::Punctuated,
     token::Comma,
-    ExprClosure, Field, Fields, GenericParam, Generics, Ident, Index, Meta,
-    Result, Token, Type, TypeParam, Variant, Visibility, WhereClause,
+    Attribute, ExprClosure, Field, Fields, GenericParam, Generics, Ident,
+    Index, Meta, Result, Token, Type, TypeParam, Variant, Visibility,
+    WhereClause,
 };
 
 #[proc_macro_error]
@@ -653,13 +654,8 @@ struct PatchModel {
 }
 
 enum PatchModelTy {
-    Struct {
-        fields: Vec<Field>,
-    },
-    #[allow(dead_code)]
-    Enum {
-        variants: Vec<Variant>,
-    },
+    Struct { fields: Vec<Field> },
+    Enum { variants: Vec<Variant> },
 }
 
 impl Parse for PatchModel {
@@ -682,17 +678,12 @@ impl Parse for PatchModel {
 
                 PatchModelTy::Struct { fields }
             }
-            syn::Data::Enum(_e) => {
-                abort_call_site!("only structs can be used with `Patch`");
-
-                // TODO: support enums later on
-                // PatchModelTy::Enum {
-                //     variants: e.variants.into_iter().collect(),
-                // }
-            }
+            syn::Data::Enum(e) => PatchModelTy::Enum {
+                variants: e.variants.into_iter().collect(),
+            },
             _ => {
                 abort_call_site!(
-                    "only structs and enums can be used with `Store`"
+                    "only structs and enums can be used with `Patch`"
                 );
             }
         };
@@ -710,9 +701,58 @@ impl ToTokens for PatchModel {
         let library_path = quote! { reactive_stores };
         let PatchModel { name, generics, ty } = &self;
 
-        let fields = match ty {
+        // only structs name their fields: the fields of every variant of an enum share the
+        // same path segment, so they use the default implementation
+        let field_name = match ty {
             PatchModelTy::Struct { fields } => {
-                fields.iter().enumerate().map(|(idx, field)| {
+                let arms = fields.iter().enumerate().map(|(idx, field)| {
+                    let Field { attrs, ident, ty, .. } = &field;
+                    let field_name = match ident {
+                        Some(ident) => ident.to_string(),
+                        None => idx.to_string(),
+                    };
+                    // fields with a custom or keyed patch don't need to implement `PatchField`
+                    let custom_patch = patch_closure(attrs).is_some()
+                        || keyed_by(attrs).is_some();
+                    let rest = if custom_patch {
+                        quote! {
+                            for segment in rest {
+                                name.push_str(&::std::format!("[{segment}]"));
+                            }
+                        }
+                    } else {
+                        quote! {
+                            <#ty as #library_path::PatchField>::field_name(rest, name);
+                        }
+                    };
+                    quote! {
+                        if *segment == #library_path::StorePathSegment::from(#idx) {
+                            if !name.is_empty() {
+                                name.push('.');
+                            }
+                            name.push_str(#field_name);
+                            #rest
+                            return;
+                        }
+                    }
+                });
+                Some(quote! {
+                    fn field_name(
+                        path: &[#library_path::StorePathSegment],
+                        name: &mut ::std::string::String,
+                    ) {
+                        if let [segment, rest @ ..] = path {
+                            #(#arms)*
+                        }
+                    }
+                })
+            }
+            PatchModelTy::Enum { .. } => None,
+        };
+
+        let body = match ty {
+            PatchModelTy::Struct { fields } => {
+                let fields = fields.iter().enumerate().map(|(idx, field)| {
                     let Field {
                         attrs, ident, ..
                     } = &field;
@@ -720,61 +760,8 @@ impl ToTokens for PatchModel {
                         Some(ident) => Either::Left(ident),
                         None => Either::Right(Index::from(idx)),
                     };
-                    let closure = attrs
-                        .iter()
-                        .find_map(|attr| {
-                            attr.meta.path().is_ident("patch").then(
-                                || match &attr.meta {
-                                    Meta::List(list) => {
-                                        match Punctuated::<
-                                                ExprClosure,
-                                                Comma,
-                                            >::parse_terminated
-                                                .parse2(list.tokens.clone())
-                                            {
-                                                Ok(closures) => {
-                                                    let closure = closures.iter().next().cloned().expect_or_abort("should have ONE closure");
-                                                    if closure.inputs.len() != 2 {
-                                                        abort!(closure.inputs, "patch closure should have TWO params as in #[patch(|this, new| ...)]");
-                                                    }
-                                                    closure
-                                                },
-                                                Err(e) => abort!(list, e),
-                                            }
-                                    }
-                                    _ => abort!(attr.meta, "needs to be as `#[patch(|this, new| ...)]`"),
-                                },
-                            )
-                        });
-                    let keyed = attrs
-                        .iter()
-                        .find_map(|attr| {
-                            attr.meta.path().is_ident("store").then(|| {
-                                match &attr.meta {
-                                    Meta::List(list) => {
-                                        let subfields = match Punctuated::<
-                                                SubfieldMode,
-                                                Comma,
-                                            >::parse_terminated
-                                                .parse2(list.tokens.clone())
-                                            {
-                                                Ok(modes) => Some(
-                                                    modes
-                                                        .iter()
-                                                        .cloned()
-                                                        .collect::<Vec<_>>(),
-                                                ),
-                                                Err(e) => abort!(list, e),
-                                            }.unwrap_or_default();
-                                            subfields.into_iter().find_map(|subfield| match subfield {
-                                                SubfieldMode::Keyed(closure, _ty) => Some(closure),
-                                                SubfieldMode::Skip => None,
-                                            })
-                                    }
-                                    _ => None,
-                                }
-                            })
-                        }).flatten();
+                    let closure = patch_closure(attrs);
+                    let keyed = keyed_by(attrs);
 
                     if let Some(closure) = closure {
                         let params = closure.inputs;
@@ -790,31 +777,14 @@ impl ToTokens for PatchModel {
                             new_path.replace_last(#idx + 1);
                         }
                     } else if let Some(closure) = keyed {
+                        let patch = keyed_patch(
+                            &library_path,
+                            &closure,
+                            quote! { self.#locator },
+                            quote! { new.#locator },
+                        );
                         quote! {
-                            let structure_changed = #library_path::PatchFieldKeyed::patch_field_keyed(
-                                &mut self.#locator,
-                                new.#locator,
-                                notify,
-                                keys,
-                                #closure,
-                                |key| {
-                                    let keys = keys.as_ref()?;
-                                    let segment = keys
-                                        .with_field_keys(
-                                            path.clone(),
-                                            |keys| (keys.get(key), vec![]),
-                                            || vec![],
-                                        )
-                                        .flatten()
-                                        .map(|(_, idx)| idx)?;
-                                    let mut path = path.clone();
-                                    path.push(segment);
-                                    Some(path)
-                                }
-                            );
-                            if structure_changed {
-                                notify(&new_path);
-                            }
+                            #patch
                             new_path.replace_last(#idx + 1);
                         }
                     } else {
@@ -829,10 +799,109 @@ impl ToTokens for PatchModel {
                             new_path.replace_last(#idx + 1);
                         }
                     }
-                }).collect::<Vec<_>>()
+                });
+                quote! {
+                    let mut new_path = path.clone();
+                    new_path.push(0);
+                    #(#fields)*
+                }
             }
-            PatchModelTy::Enum { variants: _ } => {
-                unreachable!("not implemented currently")
+            PatchModelTy::Enum { variants } => {
+                // the fields of every variant share the same path segment (see
+                // `variant_to_tokens`), so a changed field notifies that segment
+                let arms = variants.iter().map(|variant| {
+                    let Variant { ident, fields, .. } = variant;
+                    let this_fields = (0..fields.len())
+                        .map(|idx| {
+                            Ident::new(
+                                &format!("this_{idx}"),
+                                Span::call_site(),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    let new_fields = (0..fields.len())
+                        .map(|idx| {
+                            Ident::new(&format!("new_{idx}"), Span::call_site())
+                        })
+                        .collect::<Vec<_>>();
+                    let (this_pat, new_pat) = match fields {
+                        Fields::Unit => (quote! {}, quote! {}),
+                        Fields::Named(named) => {
+                            let names = named
+                                .named
+                                .iter()
+                                .map(|field| &field.ident)
+                                .collect::<Vec<_>>();
+                            (
+                                quote! { { #(#names: #this_fields),* } },
+                                quote! { { #(#names: #new_fields),* } },
+                            )
+                        }
+                        Fields::Unnamed(_) => (
+                            quote! { ( #(#this_fields),* ) },
+                            quote! { ( #(#new_fields),* ) },
+                        ),
+                    };
+                    let patch_fields = fields
+                        .iter()
+                        .zip(this_fields.iter().zip(&new_fields))
+                        .map(|(field, (this, new))| {
+                            if let Some(closure) = patch_closure(&field.attrs) {
+                                let params = closure.inputs;
+                                let body = closure.body;
+                                quote! {
+                                    if #new != *#this {
+                                        _ = {
+                                            let (#params) = (#this, #new);
+                                            #body
+                                        };
+                                        notify(&new_path);
+                                    }
+                                }
+                            } else if let Some(closure) = keyed_by(&field.attrs)
+                            {
+                                keyed_patch(
+                                    &library_path,
+                                    &closure,
+                                    quote! { *#this },
+                                    quote! { #new },
+                                )
+                            } else {
+                                quote! {
+                                    #library_path::PatchField::patch_field(
+                                        #this,
+                                        #new,
+                                        &new_path,
+                                        notify,
+                                        keys
+                                    );
+                                }
+                            }
+                        });
+                    let new_path = (!fields.is_empty()).then(|| {
+                        quote! {
+                            let mut new_path = path.clone();
+                            new_path.push(0);
+                        }
+                    });
+                    quote! {
+                        (#name::#ident #this_pat, #name::#ident #new_pat) => {
+                            #new_path
+                            #(#patch_fields)*
+                        }
+                    }
+                });
+                quote! {
+                    match (self, new) {
+                        #(#arms)*
+                        // the variant has changed, so everything that depends on it is notified
+                        #[allow(unreachable_patterns)]
+                        (this, new) => {
+                            *this = new;
+                            notify(path);
+                        }
+                    }
+                }
             }
         };
 
@@ -853,15 +922,115 @@ impl ToTokens for PatchModel {
                     notify: &mut dyn FnMut(&#library_path::StorePath),
                     keys: Option<&#library_path::KeyMap>,
                 ) {
-                    let mut new_path = path.clone();
-                    new_path.push(0);
-                    #(#fields)*
+                    #body
                 }
+
+                #field_name
             }
         });
     }
 }
 
+/// Patches the keyed field at `field` (a place expression) with [`PatchFieldKeyed`], notifying
+/// `new_path` if the structure of the collection has changed.
+fn keyed_patch(
+    library_path: &TokenStream,
+    closure: &ExprClosure,
+    field: TokenStream,
+    new: TokenStream,
+) -> TokenStream {
+    quote! {
+        let structure_changed = #library_path::PatchFieldKeyed::patch_field_keyed(
+            &mut #field,
+            #new,
+            notify,
+            keys,
+            #closure,
+            |key| keys?.path_at_key(&new_path, key)
+        );
+        if let Some(keys) = keys {
+            keys.update_field_keys(
+                new_path.clone(),
+                ::std::iter::IntoIterator::into_iter(&#field)
+                    .map(#closure)
+                    .collect::<::std::vec::Vec<_>>(),
+            );
+        }
+        if structure_changed {
+            notify(&new_path);
+        }
+    }
+}
+
+/// Finds the key function for a keyed field, as in `#[store(key: T = |item| ...)]`.
+fn keyed_by(attrs: &[Attribute]) -> Option<Box<ExprClosure>> {
+    attrs
+        .iter()
+        .find_map(|attr| {
+            attr.meta
+                .path()
+                .is_ident("store")
+                .then(|| match &attr.meta {
+                    Meta::List(list) => {
+                        let subfields = match Punctuated::<
+                                SubfieldMode,
+                                Comma,
+                            >::parse_terminated
+                                .parse2(list.tokens.clone())
+                            {
+                                Ok(modes) => Some(
+                                    modes
+                                        .iter()
+                                        .cloned()
+                                        .collect::<Vec<_>>(),
+                                ),
+                                Err(e) => abort!(list, e),
+                            }.unwrap_or_default();
+                        subfields.into_iter().find_map(
+                            |subfield| match subfield {
+                                SubfieldMode::Keyed(closure, _ty) => {
+                                    Some(closure)
+                                }
+                                SubfieldMode::Skip => None,
+                            },
+                        )
+                    }
+                    _ => None,
+                })
+        })
+        .flatten()
+}
+
+/// Finds the custom patch closure for a field, as in `#[patch(|this, new| ...)]`.
+fn patch_closure(attrs: &[Attribute]) -> Option<ExprClosure> {
+    attrs.iter().find_map(|attr| {
+        attr.meta.path().is_ident("patch").then(|| match &attr.meta {
+            Meta::List(list) => {
+                match Punctuated::<ExprClosure, Comma>::parse_terminated
+                    .parse2(list.tokens.clone())
+                {
+                    Ok(closures) => {
+                        let closure = closures
+                            .iter()
+                            .next()
+                            .cloned()
+                            .expect_or_abort("should have ONE closure");
+                        if closure.inputs.len() != 2 {
+                            abort!(closure.inputs, "patch closure should have TWO params as in #[patch(|this, new| ...)]");
+                        }
+                        closure
+                    }
+                    Err(e) => abort!(list, e),
+                }
+            }
+            _ => abort!(
+                attr.meta,
+                "needs to be as `#[patch(|this, new| ...)]`"
+            ),
+        })
+    })
+}
+
 enum Either<A, B> {
     Left(A),
     Right(B),
@@ -6,13 +6,15 @@ use crate::{
     flat_router::FlatRoutesView,
     hooks::{use_matched, use_navigate},
     location::{
-        BrowserUrl, Location, LocationChange, LocationProvider, State, Url,
+        AnyLocationProvider, BrowserUrl, Location, LocationChange,
+        LocationProvider, State, Url,
     },
-    navigate::NavigateOptions,
+    navigate::{NavigateOptions, NavigationBlockers},
     nested_router::NestedRoutesView,
+    prefetch::RoutePrefetcher,
     resolve_path::resolve_path,
     ChooseView, MatchNestedRoutes, NestedRoute, PossibleRouteMatch, RouteDefs,
-    SsrMode,
+    RouteLoader, SsrMode,
 };
 use either_of::EitherOf3;
 use leptos::{children, prelude::*};
@@ -59,6 +61,14 @@ pub fn Router<Chil>(
     /// A signal that will be set while the navigation process is underway.
     #[prop(optional, into)]
     set_is_routing: Option<SignalSetter<bool>>,
+    /// The [`LocationProvider`] used to read and update the current URL.
+    ///
+    /// In the browser, this defaults to [`BrowserUrl`], which uses the path of the URL. Use
+    /// [`HashUrl`](crate::location::HashUrl) to route on the hash fragment instead, or
+    /// [`MemoryUrl`](crate::location::MemoryUrl) to keep the location in memory (for example, in
+    /// tests or embedded widgets).
+    #[prop(optional, into)]
+    location: Option<AnyLocationProvider>,
     // TODO trailing slashes
     ///// How trailing slashes should be handled in [`Route`] paths.
     //#[prop(optional)]
@@ -72,32 +82,30 @@ where
     Chil: IntoView,
 {
     #[cfg(feature = "ssr")]
-    let (location_provider, current_url, redirect_hook) = {
-        let req = use_context::<RequestUrl>().expect("no RequestUrl provided");
-        let parsed = req.parse().expect("could not parse RequestUrl");
-        let current_url = ArcRwSignal::new(parsed);
-
-        (None, current_url, Box::new(move |_: &str| {}))
+    let (location_provider, current_url, redirect_hook) = match location {
+        Some(location) => init_location_provider(location, base.clone()),
+        None => {
+            let req =
+                use_context::<RequestUrl>().expect("no RequestUrl provided");
+            let parsed = req.parse().expect("could not parse RequestUrl");
+            let current_url = ArcRwSignal::new(parsed);
+
+            (None, current_url, Box::new(move |_: &str| {}) as Box<_>)
+        }
     };
 
     #[cfg(not(feature = "ssr"))]
     let (location_provider, current_url, redirect_hook) = {
-        let owner = Owner::current();
-        let location =
-            BrowserUrl::new().expect("could not access browser navigation"); // TODO options here
-        location.init(base.clone());
-        provide_context(location.clone());
-        let current_url = location.as_url().clone();
-
-        let redirect_hook = Box::new(move |loc: &str| {
-            if let Some(owner) = &owner {
-                owner.with(|| BrowserUrl::redirect(loc));
-            }
+        let location = location.unwrap_or_else(|| {
+            BrowserUrl::new()
+                .expect("could not access browser navigation")
+                .into()
         });
-
-        (Some(location), current_url, redirect_hook)
+        init_location_provider(location, base.clone())
     };
     // provide router context
+    let blockers = use_context::<NavigationBlockers>()
+        .unwrap_or_else(|| NavigationBlockers::new(current_url.clone()));
     let state = ArcRwSignal::new(State::new(None));
     let location = Location::new(current_url.read_only(), state.read_only());
 
@@ -112,12 +120,43 @@ where
         set_is_routing,
         query_mutations: Default::default(),
         location_provider,
+        blockers,
+        prefetcher: Default::default(),
     });
 
     let children = children.into_inner();
     children()
 }
 
+#[allow(clippy::type_complexity)]
+fn init_location_provider(
+    location: AnyLocationProvider,
+    base: Option<Cow<'static, str>>,
+) -> (
+    Option<AnyLocationProvider>,
+    ArcRwSignal<Url>,
+    Box<dyn Fn(&str) + Send + Sync>,
+) {
+    let owner = Owner::current();
+    let current_url = location.as_url().clone();
+    // the location provider checks any blockers before handling link clicks or back/forward
+    // navigations, so they need to be available when it is initialized
+    provide_context(NavigationBlockers::new(current_url.clone()));
+    location.init(base);
+    provide_context(location.clone());
+
+    let redirect_hook = Box::new({
+        let location = location.clone();
+        move |loc: &str| {
+            if let Some(owner) = &owner {
+                owner.with(|| location.redirect(loc));
+            }
+        }
+    });
+
+    (Some(location), current_url, redirect_hook)
+}
+
 #[derive(Clone)]
 pub(crate) struct RouterContext {
     pub base: Option<Cow<'static, str>>,
@@ -127,7 +166,9 @@ pub(crate) struct RouterContext {
     pub set_is_routing: Option<SignalSetter<bool>>,
     pub query_mutations:
         ArcStoredValue<Vec<(Oco<'static, str>, Option<String>)>>,
-    pub location_provider: Option<BrowserUrl>,
+    pub location_provider: Option<AnyLocationProvider>,
+    pub blockers: NavigationBlockers,
+    pub prefetcher: ArcStoredValue<Option<RoutePrefetcher>>,
 }
 
 impl RouterContext {
@@ -144,7 +185,13 @@ impl RouterContext {
             resolve_path("", path, None)
         };
 
-        let mut url = match BrowserUrl::parse(&resolved_to) {
+        let parsed = match &self.location_provider {
+            Some(location_provider) => location_provider.parse(&resolved_to),
+            None => {
+                BrowserUrl::parse(&resolved_to).map_err(|e| format!("{e:?}"))
+            }
+        };
+        let mut url = match parsed {
             Ok(url) => url,
             Err(e) => {
                 leptos::logging::error!("Error parsing URL: {e:?}");
@@ -167,7 +214,27 @@ impl RouterContext {
                 .trim_start_matches('?')
                 .into()
         }
+        drop(current);
+
+        let blocked = self.blockers.check(&url, {
+            let this = self.clone();
+            let path = path.to_string();
+            let url = url.clone();
+            let options = options.clone();
+            move || this.navigate_unblocked(&path, url, options)
+        });
+        if !blocked {
+            self.navigate_unblocked(path, url, options);
+        }
+    }
 
+    fn navigate_unblocked(
+        &self,
+        path: &str,
+        url: Url,
+        options: NavigateOptions,
+    ) {
+        let current = self.current_url.read_untracked();
         if url.origin() != current.origin() {
             window().location().set_href(path).unwrap();
             return;
@@ -195,6 +262,23 @@ impl RouterContext {
         }
     }
 
+    /// Prefetches the routes that match the given path, if it is on the same origin.
+    pub fn prefetch(&self, path: &str) {
+        let Some(prefetcher) = self.prefetcher.read_value().clone() else {
+            return;
+        };
+        let parsed = match &self.location_provider {
+            Some(location_provider) => location_provider.parse(path),
+            None => BrowserUrl::parse(path).map_err(|e| format!("{e:?}")),
+        };
+        let Ok(url) = parsed else {
+            return;
+        };
+        if url.origin() == self.current_url.read_untracked().origin() {
+            prefetcher.prefetch(url);
+        }
+    }
+
     pub fn resolve_path<'a>(
         &'a self,
         path: &'a str,
@@ -220,6 +304,12 @@ pub fn Routes<Defs, FallbackFn, Fallback>(
     /// A function that returns the view that should be shown if no route is matched.
     fallback: FallbackFn,
     /// Whether to use the View Transition API during navigation.
+    ///
+    /// The old page stays in place until the new route's resources have loaded, and the DOM is
+    /// then swapped inside `document.startViewTransition`. While the transition runs, the root
+    /// element has a `router-outlet-{n}` class for the depth of the route that changed, and a
+    /// `router-back` class for back navigations, for use in CSS. In browsers that don't support
+    /// view transitions, navigation works as usual, without the transition.
     #[prop(optional)]
     transition: bool,
     /// The route definitions. This should consist of one or more [`ParentRoute`] or [`Route`]
@@ -231,11 +321,12 @@ where
     FallbackFn: FnOnce() -> Fallback + Clone + Send + 'static,
     Fallback: IntoView + 'static,
 {
-    let location = use_context::<BrowserUrl>();
+    let location = use_context::<AnyLocationProvider>();
     let RouterContext {
         current_url,
         base,
         set_is_routing,
+        prefetcher,
         ..
     } = use_context()
         .expect("<Routes> should be used inside a <Router> component");
@@ -250,6 +341,8 @@ where
     );
     let outer_owner =
         Owner::current().expect("creating Routes, but no Owner was found");
+    *prefetcher.write_value() =
+        Some(RoutePrefetcher::new(routes.clone(), outer_owner.clone()));
     move || {
         current_url.track();
         outer_owner.with(|| {
@@ -273,6 +366,12 @@ pub fn FlatRoutes<Defs, FallbackFn, Fallback>(
     /// A function that returns the view that should be shown if no route is matched.
     fallback: FallbackFn,
     /// Whether to use the View Transition API during navigation.
+    ///
+    /// The old page stays in place until the new route's resources have loaded, and the DOM is
+    /// then swapped inside `document.startViewTransition`. While the transition runs, the root
+    /// element has a `router-outlet-{n}` class for the depth of the route that changed, and a
+    /// `router-back` class for back navigations, for use in CSS. In browsers that don't support
+    /// view transitions, navigation works as usual, without the transition.
     #[prop(optional)]
     transition: bool,
     /// The route definitions. This should consist of one or more [`ParentRoute`] or [`Route`]
@@ -284,11 +383,12 @@ where
     FallbackFn: FnOnce() -> Fallback + Clone + Send + 'static,
     Fallback: IntoView + 'static,
 {
-    let location = use_context::<BrowserUrl>();
+    let location = use_context::<AnyLocationProvider>();
     let RouterContext {
         current_url,
         base,
         set_is_routing,
+        prefetcher,
         ..
     } = use_context()
         .expect("<FlatRoutes> should be used inside a <Router> component");
@@ -307,6 +407,8 @@ where
 
     let outer_owner =
         Owner::current().expect("creating Router, but no Owner was found");
+    *prefetcher.write_value() =
+        Some(RoutePrefetcher::new(routes.clone(), outer_owner.clone()));
 
     move || {
         current_url.track();
@@ -340,14 +442,21 @@ pub fn Route<Segments, View>(
     /// Defaults to out-of-order streaming.
     #[prop(optional)]
     ssr: SsrMode,
+    /// Loads the data for this route as soon as it is matched. The data can be read with
+    /// [`use_loader_data`](crate::hooks::use_loader_data).
+    #[prop(optional, into)]
+    loader: Option<RouteLoader>,
 ) -> <NestedRoute<Segments, (), (), View> as IntoMaybeErased>::Output
 where
     View: ChooseView + Clone + 'static,
     Segments: PossibleRouteMatch + Clone + Send + 'static,
 {
-    NestedRoute::new(path, view)
-        .ssr_mode(ssr)
-        .into_maybe_erased()
+    let route = NestedRoute::new(path, view).ssr_mode(ssr);
+    match loader {
+        Some(loader) => route.loader(loader),
+        None => route,
+    }
+    .into_maybe_erased()
 }
 
 /// Describes a portion of the nested layout of the app, specifying the route it should match
@@ -367,6 +476,11 @@ pub fn ParentRoute<Segments, View, Children>(
     /// Defaults to out-of-order streaming.
     #[prop(optional)]
     ssr: SsrMode,
+    /// Loads the data for this route as soon as it is matched, in parallel with the loaders of
+    /// its child routes. The data can be read with
+    /// [`use_loader_data`](crate::hooks::use_loader_data).
+    #[prop(optional, into)]
+    loader: Option<RouteLoader>,
 ) -> <NestedRoute<Segments, Children, (), View> as IntoMaybeErased>::Output
 where
     View: ChooseView + Clone + 'static,
@@ -374,10 +488,13 @@ where
     Segments: PossibleRouteMatch + Clone + Send + 'static,
 {
     let children = children.into_inner();
-    NestedRoute::new(path, view)
-        .ssr_mode(ssr)
-        .child(children)
-        .into_maybe_erased()
+    let route = NestedRoute::new(path, view).ssr_mode(ssr);
+    match loader {
+        Some(loader) => route.loader(loader),
+        None => route,
+    }
+    .child(children)
+    .into_maybe_erased()
 }
 
 /// With the `impl Fn` in the return signature, IntoMaybeErased::Output isn't accepted by the compiler, so changing return type depending on the erasure flag.
@@ -1,6 +1,6 @@
 use crate::{
     hooks::Matched,
-    location::{LocationProvider, Url},
+    location::{AnyLocationProvider, Url},
     matching::{MatchParams, RouteDefs},
     params::ParamsMap,
     view_transition::start_view_transition,
@@ -31,9 +31,9 @@ use tachys::{
     },
 };
 
-pub(crate) struct FlatRoutesView<Loc, Defs, FalFn> {
+pub(crate) struct FlatRoutesView<Defs, FalFn> {
     pub current_url: ArcRwSignal<Url>,
-    pub location: Option<Loc>,
+    pub location: Option<AnyLocationProvider>,
     pub routes: RouteDefs<Defs>,
     pub fallback: FalFn,
     pub outer_owner: Owner,
@@ -75,9 +75,8 @@ impl Mountable for FlatRoutesViewState {
     }
 }
 
-impl<Loc, Defs, FalFn, Fal> Render for FlatRoutesView<Loc, Defs, FalFn>
+impl<Defs, FalFn, Fal> Render for FlatRoutesView<Defs, FalFn>
 where
-    Loc: LocationProvider,
     Defs: MatchNestedRoutes + 'static,
     FalFn: FnOnce() -> Fal + Send,
     Fal: IntoAny,
@@ -288,6 +287,13 @@ where
                     .as_ref()
                     .map(|nav| nav.is_back().get_untracked())
                     .unwrap_or(false);
+                // when restoring a scroll position, wait for the new route's resources to
+                // resolve, so that the position is not restored against a half-rendered page;
+                // likewise, a view transition should only start once the new page is complete
+                let wait_for_resources = transition
+                    || location
+                        .as_ref()
+                        .is_some_and(|loc| loc.wait_for_resources());
                 Executor::spawn_local(owner.with(|| {
                     provide_context(url);
                     provide_context(params_memo);
@@ -304,6 +310,8 @@ where
                                             .await;
                                     set_is_routing.set(false);
                                     value
+                                } else if wait_for_resources {
+                                    AsyncTransition::run(|| view.choose()).await
                                 } else {
                                     view.choose().await
                                 },
@@ -319,7 +327,12 @@ where
                                         .rebuild(&mut state.borrow_mut().view);
                                 };
                                 if transition {
-                                    start_view_transition(0, is_back, rebuild);
+                                    start_view_transition(
+                                        0,
+                                        is_back,
+                                        rebuild,
+                                        async {},
+                                    );
                                 } else {
                                     rebuild();
                                 }
@@ -340,15 +353,14 @@ where
     }
 }
 
-impl<Loc, Defs, FalFn, Fal> AddAnyAttr for FlatRoutesView<Loc, Defs, FalFn>
+impl<Defs, FalFn, Fal> AddAnyAttr for FlatRoutesView<Defs, FalFn>
 where
-    Loc: LocationProvider + Send,
     Defs: MatchNestedRoutes + Send + 'static,
     FalFn: FnOnce() -> Fal + Send + 'static,
     Fal: RenderHtml + 'static,
 {
     type Output<SomeNewAttr: leptos::attr::Attribute> =
-        FlatRoutesView<Loc, Defs, FalFn>;
+        FlatRoutesView<Defs, FalFn>;
 
     fn add_any_attr<NewAttr: leptos::attr::Attribute>(
         self,
@@ -489,9 +501,8 @@ impl RenderHtml for MatchedRoute {
     }
 }
 
-impl<Loc, Defs, FalFn, Fal> FlatRoutesView<Loc, Defs, FalFn>
+impl<Defs, FalFn, Fal> FlatRoutesView<Defs, FalFn>
 where
-    Loc: LocationProvider + Send,
     Defs: MatchNestedRoutes + Send + 'static,
     FalFn: FnOnce() -> Fal + Send,
     Fal: RenderHtml + 'static,
@@ -542,9 +553,8 @@ where
     }
 }
 
-impl<Loc, Defs, FalFn, Fal> RenderHtml for FlatRoutesView<Loc, Defs, FalFn>
+impl<Defs, FalFn, Fal> RenderHtml for FlatRoutesView<Defs, FalFn>
 where
-    Loc: LocationProvider + Send,
     Defs: MatchNestedRoutes + Send + 'static,
     FalFn: FnOnce() -> Fal + Send + 'static,
     Fal: RenderHtml + 'static,
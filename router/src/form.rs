@@ -4,7 +4,15 @@ use crate::{
     location::{BrowserUrl, LocationProvider},
     NavigateOptions,
 };
-use leptos::{ev, html::form, logging::*, prelude::*, task::spawn_local};
+use leptos::{
+    csrf::{use_csrf_token, CsrfInput},
+    ev,
+    html::form,
+    logging::*,
+    prelude::*,
+    server_fn::csrf::CSRF_HEADER,
+    task::spawn_local,
+};
 use std::{error::Error, sync::Arc};
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 use web_sys::{FormData, RequestRedirect, Response};
@@ -58,11 +66,20 @@ pub fn Form<A>(
 where
     A: ToHref + Send + Sync + 'static,
 {
+    fn with_csrf_token(
+        builder: gloo_net::http::RequestBuilder,
+    ) -> gloo_net::http::RequestBuilder {
+        match use_csrf_token() {
+            Some(token) => builder.header(CSRF_HEADER, &token),
+            None => builder,
+        }
+    }
+
     async fn post_form_data(
         action: &str,
         form_data: FormData,
     ) -> Result<gloo_net::http::Response, gloo_net::Error> {
-        gloo_net::http::Request::post(action)
+        with_csrf_token(gloo_net::http::Request::post(action))
             .header("Accept", "application/json")
             .redirect(RequestRedirect::Follow)
             .body(form_data)?
@@ -75,7 +92,7 @@ where
         enctype: &str,
         params: web_sys::UrlSearchParams,
     ) -> Result<gloo_net::http::Response, gloo_net::Error> {
-        gloo_net::http::Request::post(action)
+        with_csrf_token(gloo_net::http::Request::post(action))
             .header("Accept", "application/json")
             .header("Content-Type", enctype)
             .redirect(RequestRedirect::Follow)
@@ -307,7 +324,7 @@ where
             .attr("action", move || action.get())
             .attr("enctype", enctype)
             .on(ev::submit, on_submit)
-            .child(children())
+            .child(((method == "post").then(CsrfInput), children()))
     }
 
     let has_router = has_router();
@@ -1,20 +1,32 @@
 use crate::{
-    components::RouterContext,
+    components::{RouterContext, ToHref},
     location::{Location, Url},
-    navigate::NavigateOptions,
+    matching::LoaderData,
+    navigate::{Blocker, NavigateOptions, NavigationAttempt},
     params::{Params, ParamsError, ParamsMap},
+    TypedRoute,
+};
+use leptos::{
+    ev,
+    leptos_dom::helpers::{request_animation_frame, window_event_listener},
+    oco::Oco,
+    server::Resource,
 };
-use leptos::{leptos_dom::helpers::request_animation_frame, oco::Oco};
 use reactive_graph::{
     computed::{ArcMemo, Memo},
-    owner::{expect_context, use_context},
+    effect::Effect,
+    graph::untrack,
+    owner::{expect_context, on_cleanup, use_context},
     signal::{ArcRwSignal, ReadSignal},
     traits::{Get, GetUntracked, ReadUntracked, With, WriteValue},
     wrappers::write::SignalSetter,
 };
 use std::{
     str::FromStr,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 /// See [`query_signal`].
@@ -177,7 +189,7 @@ pub fn use_location() -> Location {
 pub(crate) type RawParamsMap = ArcMemo<ParamsMap>;
 
 #[track_caller]
-fn use_params_raw() -> RawParamsMap {
+pub(crate) fn use_params_raw() -> RawParamsMap {
     use_context().expect(
         "Tried to access params outside the context of a matched <Route>.",
     )
@@ -200,8 +212,79 @@ where
     Memo::new(move |_| params.with(T::from_map))
 }
 
+/// Returns the params of the current route, including its query params, parsed into the given
+/// [`TypedRoute`], or an error.
+#[track_caller]
+pub fn use_typed_params<T>() -> Memo<Result<T, ParamsError>>
+where
+    T: TypedRoute + PartialEq + Send + Sync + 'static,
+{
+    let params = use_params_raw();
+    let url = use_url_raw();
+    Memo::new(move |_| {
+        params.with(|params| {
+            url.with(|url| T::from_maps(params, url.search_params()))
+        })
+    })
+}
+
+/// Returns the data loaded by the [`RouteLoader`](crate::RouteLoader) of the
+/// current route, or of the nearest parent route whose loader returns a `T`.
+///
+/// The loader starts as soon as the route is matched, so the data is usually already loading
+/// by the time the view is rendered. Read it inside a `Suspense` or `Transition`, as with any
+/// other resource.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::{
+///     components::{Route, Router, Routes},
+///     hooks::use_loader_data,
+///     path, LoaderArgs,
+/// };
+///
+/// async fn load_user(args: LoaderArgs) -> String {
+///     let id = args.params.get("id").unwrap_or_default();
+///     format!("User {id}")
+/// }
+///
+/// #[component]
+/// fn UserPage() -> impl IntoView {
+///     let user = use_loader_data::<String>();
+///     view! {
+///         <Suspense>
+///             {move || Suspend::new(async move { user.await })}
+///         </Suspense>
+///     }
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///         <Router>
+///             <Routes fallback=|| "Not found.">
+///                 <Route path=path!("/users/:id") view=UserPage loader=load_user/>
+///             </Routes>
+///         </Router>
+///     }
+/// }
+/// ```
 #[track_caller]
-fn use_url_raw() -> ArcRwSignal<Url> {
+pub fn use_loader_data<T>() -> Resource<T>
+where
+    T: Send + Sync + 'static,
+{
+    use_context::<LoaderData<T>>()
+        .expect(
+            "Tried to access loader data outside a matched <Route> whose \
+             loader returns this type.",
+        )
+        .0
+        .into()
+}
+
+#[track_caller]
+pub(crate) fn use_url_raw() -> ArcRwSignal<Url> {
     use_context().unwrap_or_else(|| {
         let RouterContext { current_url, .. } = use_context().expect(
             "Tried to access reactive URL outside a <Router> component.",
@@ -278,6 +361,97 @@ pub fn use_navigate() -> impl Fn(&str, NavigateOptions) + Clone {
     move |path: &str, options: NavigateOptions| cx.navigate(path, options)
 }
 
+/// Prefetches the route at `path`, so that its code-split views and the data from its loaders
+/// are ready before it is navigated to.
+///
+/// The path is resolved relative to the current route, like the `href` of an
+/// [`<A/>`](crate::components::A). It is prefetched once the component has rendered, and again
+/// whenever a reactive path changes. This does nothing during server rendering.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::hooks::use_prefetch;
+/// #[component]
+/// fn Checkout() -> impl IntoView {
+///     // the user is likely to go on to pay next
+///     use_prefetch("/checkout/payment");
+///
+///     view! { <p>"Your basket"</p> }
+/// }
+/// ```
+#[track_caller]
+pub fn use_prefetch(path: impl ToHref + Send + Sync + 'static) {
+    let router = use_context::<RouterContext>()
+        .expect("You cannot call `use_prefetch` outside a <Router>.");
+    let path = use_resolved_path(move || path.to_href()());
+    Effect::new(move |_| path.with(|path| router.prefetch(path)));
+}
+
+/// Blocks navigations for which `when` returns `true`, until the app decides whether to continue
+/// with them.
+///
+/// This applies to navigations from [`<A/>`](crate::components::A) and other links, from
+/// [`use_navigate`], from [`<Form/>`](crate::components::Form), and to the browser's back and
+/// forward buttons. The returned [`Blocker`] holds the blocked navigation, which can be
+/// continued with [`Blocker::proceed`] or cancelled with [`Blocker::reset`]. The blocker is
+/// removed when the component that created it is unmounted.
+///
+/// Leaving the app entirely (by closing the tab, reloading the page, or following an external
+/// link) can't be blocked this way; use [`use_before_unload`] to ask the browser to confirm
+/// those.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::hooks::{use_before_unload, use_blocker};
+///
+/// #[component]
+/// fn EditPost() -> impl IntoView {
+///     let (unsaved, set_unsaved) = signal(false);
+///     let blocker = use_blocker(move |_| unsaved.get());
+///     use_before_unload(move || unsaved.get());
+///
+///     view! {
+///         <textarea on:input=move |_| set_unsaved.set(true)/>
+///         <Show when=move || blocker.is_blocked()>
+///             <p>"You have unsaved changes. Leave anyway?"</p>
+///             <button on:click=move |_| blocker.proceed()>"Leave"</button>
+///             <button on:click=move |_| blocker.reset()>"Stay"</button>
+///         </Show>
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_blocker(
+    when: impl Fn(&NavigationAttempt) -> bool + Send + Sync + 'static,
+) -> Blocker {
+    let RouterContext { blockers, .. } = use_context()
+        .expect("You cannot call `use_blocker` outside a <Router>.");
+    let blocker = Blocker::new();
+    let id = blockers.add(Arc::new(when), blocker);
+    on_cleanup(move || blockers.remove(id));
+    blocker
+}
+
+/// Asks the browser to confirm before the page is closed or reloaded, or before the user leaves
+/// the app by following a link to another site, whenever `when` returns `true`.
+///
+/// Most browsers show their own generic message in the confirmation dialog.
+pub fn use_before_unload(when: impl Fn() -> bool + Send + Sync + 'static) {
+    let when = Arc::new(when);
+    // effects only run in the browser
+    Effect::new(move |_| {
+        let when = Arc::clone(&when);
+        let handle = window_event_listener(ev::beforeunload, move |ev| {
+            if untrack(|| when()) {
+                ev.prevent_default();
+                // required by some older browsers
+                ev.set_return_value("");
+            }
+        });
+        on_cleanup(move || handle.remove());
+    });
+}
+
 /// Returns a reactive string that contains the route that was matched for
 /// this [`Route`](crate::components::Route).
 #[track_caller]
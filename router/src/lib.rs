@@ -140,27 +140,45 @@ mod navigate;
 pub mod nested_router;
 /// Support for maps of parameters in the path or in the query.
 pub mod params;
+mod prefetch;
 mod ssr_mode;
 /// Support for static routing.
 pub mod static_routes;
+mod typed_route;
 
 pub use generate_route_list::*;
 #[doc(inline)]
-pub use leptos_router_macro::{lazy_route, path};
+pub use leptos_router_macro::{lazy_route, path, TypedRoute};
 pub use matching::*;
 pub use method::*;
 pub use navigate::*;
 pub use ssr_mode::*;
+pub use typed_route::*;
 
 pub(crate) mod view_transition {
+    use any_spawner::Executor;
     use js_sys::{Function, Promise, Reflect};
     use leptos::leptos_dom::helpers::document;
+    use std::{
+        cell::Cell,
+        future::Future,
+        rc::Rc,
+        sync::atomic::{AtomicBool, Ordering},
+    };
     use wasm_bindgen::{closure::Closure, intern, JsCast, JsValue};
 
+    static WARNED_UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
+    /// Runs `fun` to update the DOM inside a view transition, if the browser supports them.
+    ///
+    /// The browser keeps showing the old page until `swapped` resolves, so that the new route
+    /// can finish rendering before the transition to it begins. If view transitions are not
+    /// supported, `fun` is simply called.
     pub fn start_view_transition(
         level: u8,
         is_back_navigation: bool,
         fun: impl FnOnce() + 'static,
+        swapped: impl Future<Output = ()> + 'static,
     ) {
         let document = document();
         let document_element = document.document_element().unwrap();
@@ -170,21 +188,49 @@ pub(crate) mod view_transition {
             &JsValue::from_str(intern("startViewTransition")),
         )
         .and_then(|svt| svt.dyn_into::<Function>());
-        _ = class_list.add_1(&format!("router-outlet-{level}"));
-        if is_back_navigation {
-            _ = class_list.add_1("router-back");
-        }
         match svt {
             Ok(svt) => {
-                let cb = Closure::once_into_js(Box::new(move || {
-                    fun();
-                }));
+                _ = class_list.add_1(&format!("router-outlet-{level}"));
+                if is_back_navigation {
+                    _ = class_list.add_1("router-back");
+                }
+                let remove_classes = {
+                    let class_list = class_list.clone();
+                    move || {
+                        if is_back_navigation {
+                            _ = class_list.remove_1("router-back");
+                        }
+                        _ = class_list
+                            .remove_1(&format!("router-outlet-{level}"));
+                    }
+                };
+
+                // the update may need to run here instead, if the transition can't be started
+                let update = Rc::new(Cell::new(Some((fun, swapped))));
+                let cb = Closure::once_into_js({
+                    let update = Rc::clone(&update);
+                    move || -> JsValue {
+                        let Some((fun, swapped)) = update.take() else {
+                            return JsValue::UNDEFINED;
+                        };
+                        fun();
+                        let mut swapped = Some(swapped);
+                        Promise::new(&mut |resolve, _| {
+                            if let Some(swapped) = swapped.take() {
+                                Executor::spawn_local(async move {
+                                    swapped.await;
+                                    _ = resolve.call0(&JsValue::UNDEFINED);
+                                });
+                            }
+                        })
+                        .into()
+                    }
+                });
                 match svt.call1(
                     document.unchecked_ref(),
                     cb.as_ref().unchecked_ref(),
                 ) {
                     Ok(view_transition) => {
-                        let class_list = document_element.class_list();
                         let finished = Reflect::get(
                             &view_transition,
                             &JsValue::from_str("finished"),
@@ -192,12 +238,7 @@ pub(crate) mod view_transition {
                         .expect("no `finished` property on ViewTransition")
                         .unchecked_into::<Promise>();
                         let cb = Closure::new(Box::new(move |_| {
-                            if is_back_navigation {
-                                class_list.remove_1("router-back").unwrap();
-                            }
-                            class_list
-                                .remove_1(&format!("router-outlet-{level}"))
-                                .unwrap();
+                            remove_classes();
                         })
                             as Box<dyn FnMut(JsValue)>);
                         _ = finished.then(&cb);
@@ -205,15 +246,21 @@ pub(crate) mod view_transition {
                     }
                     Err(e) => {
                         web_sys::console::log_1(&e);
+                        remove_classes();
+                        if let Some((fun, _)) = update.take() {
+                            fun();
+                        }
                     }
                 }
             }
             Err(_) => {
-                leptos::logging::warn!(
-                    "NOTE: View transitions are not supported in this \
-                     browser; unless you provide a polyfill, view transitions \
-                     will not be applied."
-                );
+                if !WARNED_UNSUPPORTED.swap(true, Ordering::Relaxed) {
+                    leptos::logging::warn!(
+                        "NOTE: View transitions are not supported in this \
+                         browser; unless you provide a polyfill, view \
+                         transitions will not be applied."
+                    );
+                }
                 fun();
             }
         }
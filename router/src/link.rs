@@ -1,7 +1,13 @@
 use crate::{components::RouterContext, hooks::use_resolved_path};
-use leptos::{children::Children, oco::Oco, prelude::*};
-use reactive_graph::{computed::ArcMemo, owner::use_context};
+use leptos::{children::Children, html, oco::Oco, prelude::*};
+use reactive_graph::{
+    computed::ArcMemo,
+    owner::{on_cleanup, use_context},
+};
+use send_wrapper::SendWrapper;
 use std::{borrow::Cow, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{IntersectionObserver, IntersectionObserverEntry};
 
 /// Describes a value that is either a static or a reactive URL, i.e.,
 /// a [`String`], a [`&str`], or a reactive `Fn() -> String`.
@@ -95,6 +101,21 @@ where
 /// Previously, this component took these as component props. Now, they can be added using the
 /// `prop:` syntax, and will be added directly to the DOM. They can work with either `<a>` elements
 /// or the `<A/>` component.
+///
+/// ### Prefetching
+///
+/// The `prefetch` prop starts loading the route that the link points to before it is clicked:
+/// any code-split route views are loaded, and the loaders of the matched routes are started, so
+/// that both are ready by the time the user navigates. See [`Prefetch`] for the options.
+///
+/// ```rust
+/// # use leptos::prelude::*; use leptos_router::components::{A, Prefetch};
+/// # fn prefetch_example() -> impl IntoView {
+/// view! {
+///   <A href="/settings" prefetch=Prefetch::Hover>"Settings"</A>
+/// }
+/// # }
+/// ```
 #[component]
 pub fn A<H>(
     /// Used to calculate the link's `href` attribute. Will be resolved relative
@@ -114,6 +135,9 @@ pub fn A<H>(
     /// If `true`, the router will scroll to the top of the window at the end of navigation. Defaults to `true`.
     #[prop(default = true)]
     scroll: bool,
+    /// When to prefetch the route that the link points to, if at all.
+    #[prop(optional, into)]
+    prefetch: Option<Prefetch>,
     /// The nodes or elements to be shown inside the link.
     children: Children,
 ) -> impl IntoView + 'static
@@ -127,9 +151,19 @@ where
         children: Children,
         strict_trailing_slash: bool,
         scroll: bool,
+        prefetch: Option<Prefetch>,
     ) -> impl IntoView {
-        let RouterContext { current_url, .. } =
-            use_context().expect("tried to use <A/> outside a <Router/>.");
+        let router = use_context::<RouterContext>()
+            .expect("tried to use <A/> outside a <Router/>.");
+        let node_ref = NodeRef::<html::A>::new();
+        if let Some(prefetch) = prefetch {
+            prefetch_link(prefetch, &router, node_ref, href.clone());
+        }
+        let RouterContext {
+            current_url,
+            location_provider,
+            ..
+        } = router;
         let is_active = {
             let href = href.clone();
             move || {
@@ -147,7 +181,11 @@ where
 
         view! {
             <a
-                href=move || href.get()
+                node_ref=node_ref
+                href=move || match &location_provider {
+                    Some(location_provider) => location_provider.href(&href.read()),
+                    None => href.get(),
+                }
                 target=target
                 aria-current=move || if is_active() { Some("page") } else { None }
                 data-noscroll=!scroll
@@ -159,7 +197,102 @@ where
     }
 
     let href = use_resolved_path(move || href.to_href()());
-    inner(href, target, exact, children, strict_trailing_slash, scroll)
+    inner(
+        href,
+        target,
+        exact,
+        children,
+        strict_trailing_slash,
+        scroll,
+        prefetch,
+    )
+}
+
+/// When the route that an [`A`] links to should be prefetched.
+///
+/// Prefetching only happens in the browser, and only for links to routes inside the same
+/// router.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Prefetch {
+    /// When the user hovers over the link, or focuses it.
+    Hover,
+    /// When the link is scrolled into the viewport.
+    Viewport,
+    /// As soon as the link has been rendered.
+    Render,
+}
+
+fn prefetch_link(
+    prefetch: Prefetch,
+    router: &RouterContext,
+    node_ref: NodeRef<html::A>,
+    href: ArcMemo<String>,
+) {
+    let router = router.clone();
+    match prefetch {
+        Prefetch::Render => {
+            Effect::new(move |_| router.prefetch(&href.read()));
+        }
+        Prefetch::Hover => node_ref.on_load(move |el| {
+            // only prefetch again if the link has changed since the last time
+            let mut prev = None::<String>;
+            let cb = Closure::<dyn FnMut()>::new(move || {
+                let href = href.get_untracked();
+                if prev.as_ref() != Some(&href) {
+                    router.prefetch(&href);
+                    prev = Some(href);
+                }
+            });
+            for ev in ["mouseenter", "focus"] {
+                _ = el.add_event_listener_with_callback(
+                    ev,
+                    cb.as_ref().unchecked_ref(),
+                );
+            }
+            let el = SendWrapper::new(el);
+            let cb = SendWrapper::new(cb);
+            on_cleanup(move || {
+                for ev in ["mouseenter", "focus"] {
+                    _ = el.remove_event_listener_with_callback(
+                        ev,
+                        cb.as_ref().unchecked_ref(),
+                    );
+                }
+            });
+        }),
+        Prefetch::Viewport => node_ref.on_load(move |el| {
+            let cb =
+                Closure::<dyn Fn(js_sys::Array, IntersectionObserver)>::new({
+                    let router = router.clone();
+                    let href = href.clone();
+                    move |entries: js_sys::Array,
+                          observer: IntersectionObserver| {
+                        let visible = entries.iter().any(|entry| {
+                            entry
+                                .unchecked_into::<IntersectionObserverEntry>()
+                                .is_intersecting()
+                        });
+                        if visible {
+                            observer.disconnect();
+                            router.prefetch(&href.get_untracked());
+                        }
+                    }
+                });
+            match IntersectionObserver::new(cb.as_ref().unchecked_ref()) {
+                Ok(observer) => {
+                    observer.observe(&el);
+                    let observer = SendWrapper::new(observer);
+                    let cb = SendWrapper::new(cb);
+                    on_cleanup(move || {
+                        observer.disconnect();
+                        drop(cb);
+                    });
+                }
+                // if the browser can't tell us when the link is visible, prefetch it right away
+                Err(_) => router.prefetch(&href.get_untracked()),
+            }
+        }),
+    }
 }
 
 // Test if `href` is active for `location`.  Assumes _both_ `href` and `location` begin with a `'/'`.
@@ -0,0 +1,326 @@
+use super::{
+    handle_anchor_click, history::search_params_from_web_url, BrowserUrl,
+    LocationChange, LocationProvider, Url,
+};
+use crate::{hooks::use_navigate, navigate::NavigationBlockers};
+use core::fmt;
+use leptos::{ev, prelude::*};
+use reactive_graph::{
+    signal::ArcRwSignal,
+    traits::{ReadUntracked, Set},
+};
+use std::borrow::Cow;
+use tachys::dom::window;
+use wasm_bindgen::JsValue;
+
+/// A [`LocationProvider`] that stores the router's location in the hash fragment of the
+/// browser's URL, so that `/about?tab=team` is shown as `#/about?tab=team`.
+///
+/// This allows client-side routing for apps that are served as static files, where the server
+/// cannot be configured to respond to every path with the app.
+///
+/// Every same-origin link is treated as a route. Links whose `href` is a hash route
+/// (`<a href="#/about">`) resolve to that route; any other link resolves to its path, as with
+/// [`BrowserUrl`]. Use `rel="external"` for links that should leave the app.
+#[derive(Clone)]
+pub struct HashUrl {
+    url: ArcRwSignal<Url>,
+    path_stack: ArcStoredValue<Vec<Url>>,
+    is_back: ArcRwSignal<bool>,
+}
+
+impl fmt::Debug for HashUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashUrl").finish_non_exhaustive()
+    }
+}
+
+impl HashUrl {
+    /// Returns the route stored in a hash fragment, if it contains one.
+    fn route_from_hash(hash: &str) -> Option<&str> {
+        hash.strip_prefix('#')
+            .filter(|route| route.starts_with('/'))
+    }
+
+    /// Returns the route stored in the hash fragment of a URL, if it contains one.
+    fn route_from_url(url: &str) -> Option<&str> {
+        Self::route_from_hash(&url[url.find('#')?..])
+    }
+
+    fn scroll_to_el(url: &Url, loc_scroll: bool) {
+        if !url.hash.is_empty() {
+            let hash = js_sys::decode_uri(&url.hash[1..])
+                .ok()
+                .and_then(|decoded| decoded.as_string())
+                .unwrap_or_else(|| url.hash[1..].to_string());
+            if let Some(el) = document().get_element_by_id(&hash) {
+                el.scroll_into_view();
+                return;
+            }
+        }
+
+        // scroll to top
+        if loc_scroll {
+            window().scroll_to_with_x_and_y(0.0, 0.0);
+        }
+    }
+}
+
+impl LocationProvider for HashUrl {
+    type Error = JsValue;
+
+    fn new() -> Result<Self, JsValue> {
+        let current = Self::current()?;
+        Ok(Self {
+            url: ArcRwSignal::new(current.clone()),
+            path_stack: ArcStoredValue::new(vec![current]),
+            is_back: Default::default(),
+        })
+    }
+
+    fn as_url(&self) -> &ArcRwSignal<Url> {
+        &self.url
+    }
+
+    fn current() -> Result<Url, Self::Error> {
+        let location = window().location();
+        let hash = location.hash()?;
+        let route = Self::route_from_hash(&hash).unwrap_or("/");
+        BrowserUrl::parse_with_base(route, &location.origin()?)
+    }
+
+    fn parse(url: &str) -> Result<Url, Self::Error> {
+        let base = window().location().origin()?;
+        Self::parse_with_base(url, &base)
+    }
+
+    fn parse_with_base(url: &str, base: &str) -> Result<Url, Self::Error> {
+        // a URL with a hash route is parsed as that route, whatever the rest of the URL is
+        if let Some(route) = Self::route_from_url(url) {
+            return BrowserUrl::parse_with_base(route, base);
+        }
+
+        let location = web_sys::Url::new_with_base(url, base)?;
+        Ok(Url {
+            origin: location.origin(),
+            path: location.pathname(),
+            search: location
+                .search()
+                .strip_prefix('?')
+                .map(String::from)
+                .unwrap_or_default(),
+            search_params: search_params_from_web_url(
+                &location.search_params(),
+            )?,
+            hash: location.hash(),
+        })
+    }
+
+    fn init(&self, base: Option<Cow<'static, str>>) {
+        let navigate = {
+            let url = self.url.clone();
+            let this = self.clone();
+            move |new_url: Url, loc: LocationChange| {
+                url.set(new_url);
+                this.complete_navigation(&loc);
+                async {}
+            }
+        };
+
+        let handle_anchor_click =
+            handle_anchor_click(base, Self::parse_with_base, navigate);
+
+        let click_handle = window_event_listener(ev::click, move |ev| {
+            if let Err(e) = handle_anchor_click(ev) {
+                #[cfg(feature = "tracing")]
+                tracing::error!("{e:?}");
+                #[cfg(not(feature = "tracing"))]
+                web_sys::console::error_1(&e);
+            }
+        });
+
+        // handle popstate event (forward/back navigation, or editing the hash by hand)
+        let popstate_cb = {
+            let url = self.url.clone();
+            let path_stack = self.path_stack.clone();
+            let is_back = self.is_back.clone();
+            let blockers = use_context::<NavigationBlockers>();
+            move || match Self::current() {
+                Ok(new_url) => {
+                    // the browser has returned to the current URL, after a navigation was blocked
+                    if *url.read_untracked() == new_url {
+                        return;
+                    }
+
+                    let mut stack = path_stack.write_value();
+                    let is_navigating_back = stack.len() == 1
+                        || (stack.len() >= 2
+                            && stack.get(stack.len() - 2) == Some(&new_url));
+
+                    if blockers.as_ref().is_some_and(|blockers| {
+                        blockers.check_popstate(&new_url, is_navigating_back)
+                    }) {
+                        return;
+                    }
+
+                    if is_navigating_back {
+                        stack.pop();
+                    } else {
+                        stack.push(new_url.clone());
+                    }
+
+                    is_back.set(is_navigating_back);
+
+                    url.set(new_url);
+                }
+                Err(e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!("{e:?}");
+                    #[cfg(not(feature = "tracing"))]
+                    web_sys::console::error_1(&e);
+                }
+            }
+        };
+
+        let popstate_handle =
+            window_event_listener(ev::popstate, move |_| popstate_cb());
+
+        on_cleanup(|| {
+            click_handle.remove();
+            popstate_handle.remove();
+        });
+    }
+
+    fn ready_to_complete(&self) {}
+
+    fn complete_navigation(&self, loc: &LocationChange) {
+        let history = window().history().unwrap();
+
+        let current_path = self
+            .path_stack
+            .read_value()
+            .last()
+            .map(|url| url.to_full_path());
+        let add_to_stack = current_path.as_ref() != Some(&loc.value);
+        let href = self.href(&loc.value);
+
+        if loc.replace {
+            history
+                .replace_state_with_url(
+                    &loc.state.to_js_value(),
+                    "",
+                    Some(&href),
+                )
+                .unwrap();
+        } else if add_to_stack {
+            let state = &loc.state.to_js_value();
+            history.push_state_with_url(state, "", Some(&href)).unwrap();
+        }
+
+        if let Ok(url) = Self::current() {
+            if add_to_stack {
+                self.path_stack.write_value().push(url.clone());
+            }
+            self.is_back.set(false);
+            Self::scroll_to_el(&url, loc.scroll);
+        }
+    }
+
+    fn redirect(loc: &str) {
+        let navigate = use_navigate();
+        let Some(url) = super::resolve_redirect_url(loc) else {
+            return; // resolve_redirect_url() already logs an error
+        };
+        let current_origin = location().origin().unwrap();
+        if url.origin() == current_origin {
+            // delay by a tick here, so that the Action updates *before* the redirect
+            request_animation_frame(move || {
+                navigate(&url.href(), Default::default());
+            });
+        } else if let Err(e) = location().set_href(&url.href()) {
+            leptos::logging::error!("Failed to redirect: {e:#?}");
+        }
+    }
+
+    fn is_back(&self) -> ReadSignal<bool> {
+        self.is_back.read_only().into()
+    }
+
+    fn href(&self, path: &str) -> String {
+        if path.starts_with('/') {
+            format!("#{path}")
+        } else {
+            path.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HashUrl;
+    use crate::location::{LocationProvider, Url};
+    use leptos::prelude::*;
+    use reactive_graph::signal::ArcRwSignal;
+
+    fn hash_url() -> HashUrl {
+        HashUrl {
+            url: ArcRwSignal::new(Url::default()),
+            path_stack: ArcStoredValue::new(Vec::new()),
+            is_back: Default::default(),
+        }
+    }
+
+    #[test]
+    fn urls_with_hash_routes_are_parsed_as_the_route() {
+        assert_eq!(HashUrl::route_from_url("#/about"), Some("/about"));
+        assert_eq!(
+            HashUrl::route_from_url("https://example.com/app/#/about?tab=team"),
+            Some("/about?tab=team")
+        );
+        assert_eq!(
+            HashUrl::route_from_url("#/users/1#bio"),
+            Some("/users/1#bio")
+        );
+        // a fragment that isn't a route links to an element on the page
+        assert_eq!(HashUrl::route_from_url("/about#team"), None);
+        assert_eq!(HashUrl::route_from_url("/about"), None);
+    }
+
+    #[test]
+    fn href_round_trips_through_parse() {
+        let location = hash_url();
+        for path in ["/", "/about", "/about?tab=team", "/users/1#bio"] {
+            let href = location.href(path);
+            assert_eq!(href, format!("#{path}"));
+            assert_eq!(HashUrl::route_from_url(&href), Some(path));
+        }
+        // anything that isn't a path within the app is left alone
+        assert_eq!(location.href("#team"), "#team");
+        assert_eq!(
+            location.href("https://example.com/about"),
+            "https://example.com/about"
+        );
+    }
+
+    #[test]
+    fn redirects_round_trip_through_parse() {
+        // `redirect` resolves its location against the origin, as the browser does, then
+        // navigates to the result, which is parsed by `parse`
+        let origin = url::Url::parse("https://example.com").unwrap();
+
+        let resolved = origin.join("#/login?next=%2Fdashboard").unwrap();
+        assert_eq!(
+            HashUrl::route_from_url(resolved.as_str()),
+            Some("/login?next=%2Fdashboard")
+        );
+
+        // a redirect to a path navigates to that path, which is then stored in the hash
+        let resolved = origin.join("/login?next=%2Fdashboard").unwrap();
+        assert_eq!(HashUrl::route_from_url(resolved.as_str()), None);
+        let path = format!("{}?{}", resolved.path(), resolved.query().unwrap());
+        assert_eq!(
+            HashUrl::route_from_url(&hash_url().href(&path)),
+            Some("/login?next=%2Fdashboard")
+        );
+    }
+}
@@ -1,29 +1,233 @@
 use super::{handle_anchor_click, LocationChange, LocationProvider, Url};
-use crate::{hooks::use_navigate, params::ParamsMap};
+use crate::{
+    hooks::use_navigate, navigate::NavigationBlockers, params::ParamsMap,
+};
 use core::fmt;
 use futures::channel::oneshot;
-use js_sys::{try_iter, Array, JsString};
+use js_sys::{try_iter, Array, JsString, Object, Reflect};
 use leptos::{ev, prelude::*};
 use or_poisoned::OrPoisoned;
 use reactive_graph::{
     signal::ArcRwSignal,
     traits::{ReadUntracked, Set},
 };
+use send_wrapper::SendWrapper;
 use std::{
     borrow::Cow,
+    collections::{HashMap, VecDeque},
     string::String,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
 };
 use tachys::dom::{document, window};
-use wasm_bindgen::{JsCast, JsValue};
-use web_sys::UrlSearchParams;
-
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{Element, UrlSearchParams};
+
+/// The attribute that opts a scroll container into scroll restoration. Its value identifies the
+/// container, so that its position can be restored after it has been rendered again.
+const SCROLL_RESTORE_ATTR: &str = "data-scroll-restore";
+
+/// A [`LocationProvider`] that reads the router's location from, and writes it to, the browser's
+/// URL and history.
+///
+/// The scroll position of the window is saved for each history entry, and restored when the user
+/// returns to that entry with the browser's back or forward buttons. Restoration waits until the
+/// resources read by the matched route have resolved. Scroll containers can opt in by setting the
+/// `data-scroll-restore` attribute to a name that identifies them:
+/// `<ul data-scroll-restore="results">`.
+///
+/// To tell apart history entries with the same URL, a key is added to the `history.state` of
+/// each entry that the router creates, as long as that state is empty or a plain object.
 #[derive(Clone)]
 pub struct BrowserUrl {
     url: ArcRwSignal<Url>,
     pub(crate) pending_navigation: Arc<Mutex<Option<oneshot::Sender<()>>>>,
     pub(crate) path_stack: ArcStoredValue<Vec<Url>>,
     pub(crate) is_back: ArcRwSignal<bool>,
+    scroll: ScrollRestoration,
+}
+
+/// The scroll positions saved for a single history entry.
+#[derive(Debug, Clone, Default)]
+struct ScrollPositions {
+    window: (f64, f64),
+    containers: HashMap<String, (i32, i32)>,
+}
+
+/// The property of `history.state` that holds the key identifying a history entry.
+const SCROLL_KEY: &str = "__leptosScrollKey";
+
+/// The number of history entries whose scroll positions are kept.
+const MAX_SCROLL_ENTRIES: usize = 100;
+
+/// The scroll positions saved for each history entry, keyed by a key that is unique to the
+/// entry, so that entries with the same URL are kept apart.
+#[derive(Debug, Default)]
+struct ScrollEntries {
+    positions: HashMap<String, ScrollPositions>,
+    /// The keys of the entries, from least to most recently added.
+    order: VecDeque<String>,
+}
+
+impl ScrollEntries {
+    fn entry(&mut self, key: String) -> &mut ScrollPositions {
+        if !self.positions.contains_key(&key) {
+            if self.order.len() == MAX_SCROLL_ENTRIES {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.positions.remove(&oldest);
+                }
+            }
+            self.order.push_back(key.clone());
+        }
+        self.positions.entry(key).or_default()
+    }
+
+    fn get(&self, key: &str) -> Option<&ScrollPositions> {
+        self.positions.get(key)
+    }
+}
+
+/// Saves the scroll positions of each history entry, and restores them on back/forward
+/// navigations.
+#[derive(Debug, Clone, Default)]
+struct ScrollRestoration {
+    entries: Arc<Mutex<ScrollEntries>>,
+    /// The history entry whose positions will be restored once its route is ready.
+    pending: Arc<Mutex<Option<String>>>,
+}
+
+impl ScrollRestoration {
+    fn is_pending(&self) -> bool {
+        self.pending.lock().or_poisoned().is_some()
+    }
+
+    fn cancel(&self) {
+        self.pending.lock().or_poisoned().take();
+    }
+
+    /// Returns the key that identifies the current history entry.
+    ///
+    /// This is stored in `history.state` when the entry is created. If the state could not hold
+    /// it, the entry is identified by its URL instead.
+    fn current_key() -> Option<String> {
+        Self::stored_key().or_else(|| window().location().href().ok())
+    }
+
+    /// Returns the key stored in the state of the current history entry, if any.
+    fn stored_key() -> Option<String> {
+        window()
+            .history()
+            .and_then(|history| history.state())
+            .ok()
+            .filter(JsValue::is_object)
+            .and_then(|state| Reflect::get(&state, &SCROLL_KEY.into()).ok())
+            .and_then(|key| key.as_string())
+    }
+
+    /// Returns `state` with a new key that identifies a history entry added to it.
+    fn with_new_key(state: JsValue) -> JsValue {
+        static NEXT_KEY: AtomicUsize = AtomicUsize::new(0);
+
+        // history entries outlive the page, so the keys are made unique across page loads
+        let key = format!(
+            "{}-{}",
+            js_sys::Date::now(),
+            NEXT_KEY.fetch_add(1, Ordering::Relaxed)
+        );
+        Self::with_key(state, key)
+    }
+
+    /// Returns `state` with the key of the current history entry, for a state that replaces it,
+    /// so that the scroll positions saved for the entry are kept.
+    fn with_current_key(state: JsValue) -> JsValue {
+        match Self::stored_key() {
+            Some(key) => Self::with_key(state, key),
+            None => Self::with_new_key(state),
+        }
+    }
+
+    /// Returns `state` with the given key.
+    ///
+    /// The key is only added to states that are plain objects (or empty), which are copied;
+    /// any other state is stored as it is.
+    fn with_key(state: JsValue, key: String) -> JsValue {
+        let copy = if state.is_undefined() || state.is_null() {
+            Object::new()
+        } else if state.is_object() && !Array::is_array(&state) {
+            Object::assign(&Object::new(), state.unchecked_ref())
+        } else {
+            return state;
+        };
+        _ = Reflect::set(&copy, &SCROLL_KEY.into(), &key.into());
+        copy.into()
+    }
+
+    /// Saves the position of the window, or of an opted-in scroll container, for the current
+    /// history entry.
+    fn save(&self, target: Option<Element>) {
+        let Some(key) = Self::current_key() else {
+            return;
+        };
+        let mut entries = self.entries.lock().or_poisoned();
+        let entry = entries.entry(key);
+        match target {
+            // scroll events for the window are fired at the document
+            None => {
+                entry.window = (
+                    window().scroll_x().unwrap_or_default(),
+                    window().scroll_y().unwrap_or_default(),
+                );
+            }
+            Some(el) => {
+                if let Some(name) = el.get_attribute(SCROLL_RESTORE_ATTR) {
+                    entry
+                        .containers
+                        .insert(name, (el.scroll_left(), el.scroll_top()));
+                }
+            }
+        }
+    }
+
+    /// Restores the saved positions for the current history entry, on the next frame.
+    fn restore(&self) {
+        let pending = Arc::clone(&self.pending);
+        let positions = Self::current_key().and_then(|key| {
+            self.entries.lock().or_poisoned().get(&key).cloned()
+        });
+        request_animation_frame(move || {
+            // scroll events fired before this point were caused by rendering the new route,
+            // so they are ignored until restoration is complete
+            pending.lock().or_poisoned().take();
+            let Some(positions) = positions else {
+                BrowserUrl::scroll_to_el(true);
+                return;
+            };
+            let (x, y) = positions.window;
+            window().scroll_to_with_x_and_y(x, y);
+            let Ok(containers) = document()
+                .query_selector_all(&format!("[{SCROLL_RESTORE_ATTR}]"))
+            else {
+                return;
+            };
+            for idx in 0..containers.length() {
+                let Some(el) = containers
+                    .get(idx)
+                    .and_then(|el| el.dyn_into::<Element>().ok())
+                else {
+                    continue;
+                };
+                let position = el
+                    .get_attribute(SCROLL_RESTORE_ATTR)
+                    .and_then(|name| positions.containers.get(&name));
+                if let Some((left, top)) = position {
+                    el.set_scroll_left(*left);
+                    el.set_scroll_top(*top);
+                }
+            }
+        });
+    }
 }
 
 impl fmt::Debug for BrowserUrl {
@@ -68,6 +272,7 @@ impl LocationProvider for BrowserUrl {
             pending_navigation: Default::default(),
             path_stack,
             is_back: Default::default(),
+            scroll: Default::default(),
         })
     }
 
@@ -120,6 +325,7 @@ impl LocationProvider for BrowserUrl {
             let pending = Arc::clone(&self.pending_navigation);
             let this = self.clone();
             move |new_url: Url, loc| {
+                this.scroll.cancel();
                 let same_path = {
                     let curr = url.read_untracked();
                     curr.origin() == new_url.origin()
@@ -167,24 +373,91 @@ impl LocationProvider for BrowserUrl {
             }
         });
 
+        // the router restores scroll positions itself, once the new route has rendered
+        if let Ok(history) = window().history() {
+            _ = history
+                .set_scroll_restoration(web_sys::ScrollRestoration::Manual);
+            // the entry the page was loaded in needs a key too, unless it already has one
+            // from an earlier visit
+            if let Ok(state) = history.state() {
+                let has_key = state.is_object()
+                    && Reflect::has(&state, &SCROLL_KEY.into())
+                        .unwrap_or_default();
+                if !has_key {
+                    _ = history.replace_state(
+                        &ScrollRestoration::with_new_key(state),
+                        "",
+                    );
+                }
+            }
+        }
+
+        // save scroll positions as they change; scroll events on elements do not bubble, so this
+        // listens during the capture phase
+        let scroll_cb = {
+            let scroll = self.scroll.clone();
+            let pending = Arc::clone(&self.pending_navigation);
+            Closure::<dyn Fn(web_sys::Event)>::new(move |ev: web_sys::Event| {
+                // while navigating, the page may scroll because the current route is being
+                // replaced, which should not overwrite the saved position
+                if pending.lock().or_poisoned().is_some() || scroll.is_pending()
+                {
+                    return;
+                }
+                scroll.save(
+                    ev.target().and_then(|target| target.dyn_into().ok()),
+                );
+            })
+            .into_js_value()
+        };
+        _ = window().add_event_listener_with_callback_and_bool(
+            "scroll",
+            scroll_cb.unchecked_ref(),
+            true,
+        );
+        let scroll_cb = SendWrapper::new(scroll_cb);
+
         // handle popstate event (forward/back navigation)
         let popstate_cb = {
             let url = self.url.clone();
             let path_stack = self.path_stack.clone();
             let is_back = self.is_back.clone();
+            let scroll = self.scroll.clone();
+            let blockers = use_context::<NavigationBlockers>();
             move || match Self::current() {
                 Ok(new_url) => {
+                    // the browser has returned to the current URL, after a navigation was blocked
+                    if *url.read_untracked() == new_url {
+                        return;
+                    }
+
                     let mut stack = path_stack.write_value();
                     let is_navigating_back = stack.len() == 1
                         || (stack.len() >= 2
                             && stack.get(stack.len() - 2) == Some(&new_url));
 
+                    if blockers.as_ref().is_some_and(|blockers| {
+                        blockers.check_popstate(&new_url, is_navigating_back)
+                    }) {
+                        return;
+                    }
+
                     if is_navigating_back {
                         stack.pop();
                     }
 
                     is_back.set(is_navigating_back);
 
+                    // if the path is the same, the route does not need to render again, so the
+                    // position can be restored right away; otherwise, wait until it is ready
+                    let same_path =
+                        url.read_untracked().path() == new_url.path();
+                    *scroll.pending.lock().or_poisoned() =
+                        ScrollRestoration::current_key();
+                    if same_path {
+                        scroll.restore();
+                    }
+
                     url.set(new_url);
                 }
                 Err(e) => {
@@ -199,9 +472,14 @@ impl LocationProvider for BrowserUrl {
         let popstate_handle =
             window_event_listener(ev::popstate, move |_| popstate_cb());
 
-        on_cleanup(|| {
+        on_cleanup(move || {
             click_handle.remove();
             popstate_handle.remove();
+            _ = window().remove_event_listener_with_callback_and_bool(
+                "scroll",
+                scroll_cb.unchecked_ref(),
+                true,
+            );
         });
     }
 
@@ -209,9 +487,13 @@ impl LocationProvider for BrowserUrl {
         if let Some(tx) = self.pending_navigation.lock().or_poisoned().take() {
             _ = tx.send(());
         }
+        if self.scroll.is_pending() {
+            self.scroll.restore();
+        }
     }
 
     fn complete_navigation(&self, loc: &LocationChange) {
+        self.scroll.cancel();
         let history = window().history().unwrap();
 
         let current_path = self
@@ -222,18 +504,18 @@ impl LocationProvider for BrowserUrl {
         let add_to_stack = current_path.as_ref() != Some(&loc.value);
 
         if loc.replace {
+            // the entry is still the same one, so it keeps its saved scroll positions
+            let state =
+                ScrollRestoration::with_current_key(loc.state.to_js_value());
             history
-                .replace_state_with_url(
-                    &loc.state.to_js_value(),
-                    "",
-                    Some(&loc.value),
-                )
+                .replace_state_with_url(&state, "", Some(&loc.value))
                 .unwrap();
         } else if add_to_stack {
             // push the "forward direction" marker
-            let state = &loc.state.to_js_value();
+            let state =
+                ScrollRestoration::with_new_key(loc.state.to_js_value());
             history
-                .push_state_with_url(state, "", Some(&loc.value))
+                .push_state_with_url(&state, "", Some(&loc.value))
                 .unwrap();
         }
 
@@ -271,9 +553,13 @@ impl LocationProvider for BrowserUrl {
     fn is_back(&self) -> ReadSignal<bool> {
         self.is_back.read_only().into()
     }
+
+    fn wait_for_resources(&self) -> bool {
+        self.scroll.is_pending()
+    }
 }
 
-fn search_params_from_web_url(
+pub(super) fn search_params_from_web_url(
     params: &web_sys::UrlSearchParams,
 ) -> Result<ParamsMap, JsValue> {
     try_iter(params)?
@@ -315,3 +601,43 @@ pub(crate) fn resolve_redirect_url(loc: &str) -> Option<web_sys::Url> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ScrollEntries, MAX_SCROLL_ENTRIES};
+
+    #[test]
+    fn scroll_positions_are_kept_per_history_entry() {
+        let mut entries = ScrollEntries::default();
+        // two history entries for the same URL have different keys
+        entries.entry("1-0".into()).window = (0.0, 100.0);
+        entries.entry("1-1".into()).window = (0.0, 200.0);
+        entries
+            .entry("1-0".into())
+            .containers
+            .insert("results".into(), (0, 50));
+
+        let first = entries.get("1-0").unwrap();
+        assert_eq!(first.window, (0.0, 100.0));
+        assert_eq!(first.containers.get("results"), Some(&(0, 50)));
+        let second = entries.get("1-1").unwrap();
+        assert_eq!(second.window, (0.0, 200.0));
+        assert!(second.containers.is_empty());
+        assert!(entries.get("1-2").is_none());
+    }
+
+    #[test]
+    fn scroll_positions_are_bounded() {
+        let mut entries = ScrollEntries::default();
+        for idx in 0..=MAX_SCROLL_ENTRIES {
+            entries.entry(idx.to_string()).window = (0.0, idx as f64);
+        }
+        assert!(entries.get("0").is_none());
+        assert!(entries.get("1").is_some());
+        assert_eq!(
+            entries.get(&MAX_SCROLL_ENTRIES.to_string()).unwrap().window,
+            (0.0, MAX_SCROLL_ENTRIES as f64)
+        );
+        assert_eq!(entries.positions.len(), MAX_SCROLL_ENTRIES);
+    }
+}
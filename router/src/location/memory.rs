@@ -0,0 +1,297 @@
+use super::{
+    handle_anchor_click, BrowserUrl, LocationChange, LocationProvider, Url,
+    BASE,
+};
+use crate::{hooks::use_navigate, params::ParamsMap};
+use core::fmt;
+use leptos::{ev, prelude::*};
+use reactive_graph::{
+    owner::ArcStoredValue,
+    signal::ArcRwSignal,
+    traits::{ReadValue, Set, WriteValue},
+};
+use std::borrow::Cow;
+
+/// A [`LocationProvider`] that keeps the current location and the history stack in memory,
+/// rather than reading them from or writing them to the browser.
+///
+/// This is useful for tests, for running the router outside the browser, and for widgets that are
+/// embedded in a page whose URL they should not change.
+#[derive(Clone)]
+pub struct MemoryUrl {
+    url: ArcRwSignal<Url>,
+    history: ArcStoredValue<MemoryHistory>,
+    is_back: ArcRwSignal<bool>,
+}
+
+#[derive(Debug)]
+struct MemoryHistory {
+    entries: Vec<Url>,
+    index: usize,
+}
+
+impl fmt::Debug for MemoryUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryUrl").finish_non_exhaustive()
+    }
+}
+
+impl MemoryUrl {
+    /// Creates an in-memory location, starting at the given path.
+    pub fn new_with_path(path: &str) -> Result<Self, url::ParseError> {
+        let url = Self::parse(path)?;
+        Ok(Self {
+            url: ArcRwSignal::new(url.clone()),
+            history: ArcStoredValue::new(MemoryHistory {
+                entries: vec![url],
+                index: 0,
+            }),
+            is_back: Default::default(),
+        })
+    }
+
+    /// Moves `delta` entries through the history stack: backward if it is negative, and forward
+    /// if it is positive. Does nothing if there is no entry at that position.
+    pub fn go(&self, delta: isize) {
+        let url = {
+            let mut history = self.history.write_value();
+            let Some(index) = history
+                .index
+                .checked_add_signed(delta)
+                .filter(|index| *index < history.entries.len())
+            else {
+                return;
+            };
+            history.index = index;
+            history.entries[index].clone()
+        };
+        self.is_back.set(delta < 0);
+        self.url.set(url);
+    }
+
+    /// Moves back one entry in the history stack, like the browser's back button.
+    pub fn back(&self) {
+        self.go(-1);
+    }
+
+    /// Moves forward one entry in the history stack, like the browser's forward button.
+    pub fn forward(&self) {
+        self.go(1);
+    }
+
+    /// Whether there is an entry before the current one in the history stack.
+    pub fn can_go_back(&self) -> bool {
+        self.history.read_value().index > 0
+    }
+
+    /// Whether there is an entry after the current one in the history stack.
+    pub fn can_go_forward(&self) -> bool {
+        let history = self.history.read_value();
+        history.index + 1 < history.entries.len()
+    }
+
+    /// The number of entries in the history stack.
+    pub fn history_len(&self) -> usize {
+        self.history.read_value().entries.len()
+    }
+}
+
+impl LocationProvider for MemoryUrl {
+    type Error = url::ParseError;
+
+    fn new() -> Result<Self, Self::Error> {
+        Self::new_with_path("/")
+    }
+
+    fn as_url(&self) -> &ArcRwSignal<Url> {
+        &self.url
+    }
+
+    /// An in-memory location has no global state to read from, so this always returns the root
+    /// path. Use [`as_url`](LocationProvider::as_url) to read the location of a particular
+    /// `MemoryUrl`.
+    fn current() -> Result<Url, Self::Error> {
+        Self::parse("/")
+    }
+
+    fn parse_with_base(url: &str, base: &str) -> Result<Url, Self::Error> {
+        let base = url::Url::parse(base)?;
+        let url = url::Url::options().base_url(Some(&base)).parse(url)?;
+
+        let search_params = url
+            .query_pairs()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<ParamsMap>();
+
+        Ok(Url {
+            origin: url.origin().unicode_serialization(),
+            path: url.path().to_string(),
+            search: url.query().unwrap_or_default().to_string(),
+            search_params,
+            hash: url
+                .fragment()
+                .map(|hash| format!("#{hash}"))
+                .unwrap_or_default(),
+        })
+    }
+
+    fn init(&self, base: Option<Cow<'static, str>>) {
+        // in the browser, links still need to be intercepted so that they update the in-memory
+        // location rather than the page's URL
+        if cfg!(target_arch = "wasm32") {
+            let navigate = {
+                let this = self.clone();
+                move |new_url: Url, loc: LocationChange| {
+                    let mut new_url = new_url;
+                    new_url.origin = BASE.to_string();
+                    this.url.set(new_url);
+                    this.complete_navigation(&loc);
+                    async {}
+                }
+            };
+            let handle_anchor_click = handle_anchor_click(
+                base,
+                BrowserUrl::parse_with_base,
+                navigate,
+            );
+            let click_handle = window_event_listener(ev::click, move |ev| {
+                if let Err(e) = handle_anchor_click(ev) {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!("{e:?}");
+                    #[cfg(not(feature = "tracing"))]
+                    web_sys::console::error_1(&e);
+                }
+            });
+            on_cleanup(move || click_handle.remove());
+        }
+    }
+
+    fn ready_to_complete(&self) {}
+
+    fn complete_navigation(&self, loc: &LocationChange) {
+        let url = match Self::parse(&loc.value) {
+            Ok(url) => url,
+            Err(e) => {
+                leptos::logging::error!("Error parsing URL: {e:?}");
+                return;
+            }
+        };
+
+        let mut history = self.history.write_value();
+        let index = history.index;
+        if loc.replace {
+            history.entries[index] = url;
+        } else if history.entries[index].to_full_path() != loc.value {
+            // navigating to a new location discards any "forward" entries
+            history.entries.truncate(index + 1);
+            history.entries.push(url);
+            history.index += 1;
+        }
+        drop(history);
+
+        self.is_back.set(false);
+    }
+
+    fn redirect(loc: &str) {
+        let navigate = use_navigate();
+        navigate(loc, Default::default());
+    }
+
+    fn is_back(&self) -> ReadSignal<bool> {
+        self.is_back.read_only().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MemoryUrl;
+    use crate::{
+        components::{Router, RouterProps},
+        hooks::use_navigate,
+        location::{LocationChange, LocationProvider},
+    };
+    use leptos::prelude::*;
+
+    fn push(location: &MemoryUrl, path: &str) {
+        location.complete_navigation(&LocationChange {
+            value: path.to_string(),
+            replace: false,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    pub fn should_parse_hash() {
+        let url = MemoryUrl::parse("/foo/bar#baz").unwrap();
+        assert_eq!(url.path(), "/foo/bar");
+        assert_eq!(url.hash(), "#baz");
+    }
+
+    #[test]
+    pub fn should_move_through_history() {
+        let owner = Owner::new();
+        owner.set();
+        let location = MemoryUrl::new_with_path("/a").unwrap();
+        push(&location, "/b");
+        push(&location, "/c");
+        assert_eq!(location.history_len(), 3);
+        assert!(!location.can_go_forward());
+
+        location.back();
+        location.back();
+        assert_eq!(location.as_url().get_untracked().path(), "/a");
+        assert!(location.is_back().get_untracked());
+        assert!(!location.can_go_back());
+
+        location.forward();
+        assert_eq!(location.as_url().get_untracked().path(), "/b");
+        assert!(!location.is_back().get_untracked());
+
+        // going past the end of the stack does nothing
+        location.go(5);
+        assert_eq!(location.as_url().get_untracked().path(), "/b");
+    }
+
+    #[test]
+    pub fn should_discard_forward_entries() {
+        let location = MemoryUrl::new_with_path("/a").unwrap();
+        push(&location, "/b");
+        location.back();
+        push(&location, "/c");
+        assert_eq!(location.history_len(), 2);
+        assert!(!location.can_go_forward());
+    }
+
+    #[test]
+    pub fn should_replace_current_entry() {
+        let location = MemoryUrl::new_with_path("/a").unwrap();
+        location.complete_navigation(&LocationChange {
+            value: "/b".to_string(),
+            replace: true,
+            ..Default::default()
+        });
+        assert_eq!(location.history_len(), 1);
+    }
+
+    #[test]
+    pub fn should_navigate_inside_router() {
+        let owner = Owner::new();
+        owner.set();
+
+        let location = MemoryUrl::new().unwrap();
+        let navigated = location.clone();
+        _ = Router(
+            RouterProps::builder()
+                .location(location.clone())
+                .children(ToChildren::to_children(move || {
+                    let navigate = use_navigate();
+                    navigate("/foo", Default::default());
+                }))
+                .build(),
+        );
+
+        let url = navigated.as_url().get_untracked();
+        assert_eq!(url.path(), "/foo");
+        assert!(location.can_go_back());
+    }
+}
@@ -6,20 +6,24 @@ use js_sys::Reflect;
 use leptos::server::ServerActionError;
 use reactive_graph::{
     computed::Memo,
-    owner::provide_context,
+    owner::{provide_context, use_context},
     signal::{ArcRwSignal, ReadSignal},
     traits::With,
 };
 use send_wrapper::SendWrapper;
-use std::{borrow::Cow, future::Future};
+use std::{borrow::Cow, future::Future, rc::Rc, sync::Arc};
 use tachys::dom::window;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{HtmlAnchorElement, MouseEvent};
 
+mod hash;
 mod history;
+mod memory;
 mod server;
-use crate::params::ParamsMap;
+use crate::{navigate::NavigationBlockers, params::ParamsMap};
+pub use hash::*;
 pub use history::*;
+pub use memory::*;
 pub use server::*;
 
 pub(crate) const BASE: &str = "https://leptos.dev";
@@ -261,6 +265,157 @@ pub trait LocationProvider: Clone + 'static {
 
     /// Whether we are currently in a "back" navigation.
     fn is_back(&self) -> ReadSignal<bool>;
+
+    /// Whether the router should wait for the resources read by the next matched route to
+    /// resolve before calling [`ready_to_complete`](Self::ready_to_complete), for example so that
+    /// a saved scroll position can be restored once the page has fully rendered.
+    fn wait_for_resources(&self) -> bool {
+        false
+    }
+
+    /// Converts a router path into the value that should be used as the `href` of a link to it.
+    fn href(&self, path: &str) -> String {
+        path.to_string()
+    }
+}
+
+/// A type-erased [`LocationProvider`], which can be passed to the `location` prop of
+/// [`Router`](crate::components::Router).
+#[derive(Clone)]
+pub struct AnyLocationProvider(Arc<dyn DynLocationProvider>);
+
+impl Debug for AnyLocationProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AnyLocationProvider").finish_non_exhaustive()
+    }
+}
+
+impl<T> From<T> for AnyLocationProvider
+where
+    T: LocationProvider + Send + Sync,
+{
+    fn from(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+}
+
+impl AnyLocationProvider {
+    /// Wraps the given location provider.
+    pub fn new(provider: impl LocationProvider + Send + Sync) -> Self {
+        provider.into()
+    }
+
+    /// The signal that holds the current URL.
+    pub fn as_url(&self) -> &ArcRwSignal<Url> {
+        self.0.as_url()
+    }
+
+    /// Sets up any global event listeners or other initialization needed, such as intercepting
+    /// clicks on links within `base`.
+    pub fn init(&self, base: Option<Cow<'static, str>>) {
+        self.0.init(base)
+    }
+
+    /// Called after a navigation when all route components and data have been loaded and the URL
+    /// can be updated.
+    pub fn ready_to_complete(&self) {
+        self.0.ready_to_complete()
+    }
+
+    /// Updates the browser's history to reflect a new location.
+    pub fn complete_navigation(&self, loc: &LocationChange) {
+        self.0.complete_navigation(loc)
+    }
+
+    /// Parses a URL, such as the `href` of a link, into the location it routes to.
+    ///
+    /// Returns the provider's error, formatted with [`Debug`], if the URL is not valid.
+    pub fn parse(&self, url: &str) -> Result<Url, String> {
+        self.0.parse(url)
+    }
+
+    /// Navigates to `loc`, for example when a server function redirects.
+    pub fn redirect(&self, loc: &str) {
+        self.0.redirect(loc)
+    }
+
+    /// Whether we are currently in a "back" navigation.
+    pub fn is_back(&self) -> ReadSignal<bool> {
+        self.0.is_back()
+    }
+
+    /// Whether the router should wait for the resources read by the next matched route to
+    /// resolve before calling [`ready_to_complete`](Self::ready_to_complete).
+    pub fn wait_for_resources(&self) -> bool {
+        self.0.wait_for_resources()
+    }
+
+    /// Converts a router path into the value that should be used as the `href` of a link to it,
+    /// such as `#/about` for `/about` with a [`HashUrl`].
+    pub fn href(&self, path: &str) -> String {
+        self.0.href(path)
+    }
+}
+
+trait DynLocationProvider: Send + Sync {
+    fn as_url(&self) -> &ArcRwSignal<Url>;
+
+    fn init(&self, base: Option<Cow<'static, str>>);
+
+    fn ready_to_complete(&self);
+
+    fn complete_navigation(&self, loc: &LocationChange);
+
+    fn parse(&self, url: &str) -> Result<Url, String>;
+
+    fn redirect(&self, loc: &str);
+
+    fn is_back(&self) -> ReadSignal<bool>;
+
+    fn wait_for_resources(&self) -> bool;
+
+    fn href(&self, path: &str) -> String;
+}
+
+impl<T> DynLocationProvider for T
+where
+    T: LocationProvider + Send + Sync,
+{
+    fn as_url(&self) -> &ArcRwSignal<Url> {
+        LocationProvider::as_url(self)
+    }
+
+    fn init(&self, base: Option<Cow<'static, str>>) {
+        LocationProvider::init(self, base)
+    }
+
+    fn ready_to_complete(&self) {
+        LocationProvider::ready_to_complete(self)
+    }
+
+    fn complete_navigation(&self, loc: &LocationChange) {
+        LocationProvider::complete_navigation(self, loc)
+    }
+
+    fn parse(&self, url: &str) -> Result<Url, String> {
+        T::parse(url).map_err(|e| format!("{e:?}"))
+    }
+
+    fn redirect(&self, loc: &str) {
+        T::redirect(loc)
+    }
+
+    fn is_back(&self) -> ReadSignal<bool> {
+        LocationProvider::is_back(self)
+    }
+
+    fn wait_for_resources(&self) -> bool {
+        LocationProvider::wait_for_resources(self)
+    }
+
+    fn href(&self, path: &str) -> String {
+        LocationProvider::href(self, path)
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -305,6 +460,8 @@ where
     NavFut: Future<Output = ()> + 'static,
 {
     let router_base = router_base.unwrap_or_default();
+    let blockers = use_context::<NavigationBlockers>();
+    let navigate = Rc::new(navigate);
 
     Box::new(move |ev: MouseEvent| {
         let origin = window().location().origin()?;
@@ -386,6 +543,18 @@ where
                 state: State::new(state),
             };
 
+            if let Some(blockers) = &blockers {
+                let proceed = SendWrapper::new({
+                    let navigate = Rc::clone(&navigate);
+                    let url = url.clone();
+                    let change = change.clone();
+                    move || Executor::spawn_local(navigate(url, change))
+                });
+                if blockers.check(&url, move || proceed.take()()) {
+                    return Ok(());
+                }
+            }
+
             Executor::spawn_local(navigate(url, change));
         }
 
@@ -10,6 +10,7 @@ pub struct AnyChooseView {
     #[allow(clippy::type_complexity)]
     choose: fn(Erased) -> Pin<Box<dyn Future<Output = AnyView>>>,
     preload: for<'a> fn(&'a Erased) -> Pin<Box<dyn Future<Output = ()> + 'a>>,
+    prefetch: for<'a> fn(&'a Erased) -> Pin<Box<dyn Future<Output = ()> + 'a>>,
 }
 
 impl Clone for AnyChooseView {
@@ -36,11 +37,18 @@ impl AnyChooseView {
             value.get_ref::<T>().preload().boxed_local()
         }
 
+        fn prefetch<'a, T: ChooseView>(
+            value: &'a Erased,
+        ) -> Pin<Box<dyn Future<Output = ()> + 'a>> {
+            value.get_ref::<T>().prefetch().boxed_local()
+        }
+
         Self {
             value: Erased::new(value),
             clone: clone::<T>,
             choose: choose::<T>,
             preload: preload::<T>,
+            prefetch: prefetch::<T>,
         }
     }
 }
@@ -53,4 +61,8 @@ impl ChooseView for AnyChooseView {
     async fn preload(&self) {
         (self.preload)(&self.value).await;
     }
+
+    async fn prefetch(&self) {
+        (self.prefetch)(&self.value).await;
+    }
 }
@@ -10,6 +10,17 @@ where
     fn choose(self) -> impl Future<Output = AnyView>;
 
     fn preload(&self) -> impl Future<Output = ()>;
+
+    /// Fetches whatever this view will need ahead of time, without creating any of the
+    /// reactive state that belongs to the route once it is rendered.
+    ///
+    /// This is used to prefetch a route before it is navigated to, for example when the user
+    /// hovers over a link.
+    // TODO 0.9: remove this default implementation, which is provided so that adding the method
+    // is not a breaking change
+    fn prefetch(&self) -> impl Future<Output = ()> {
+        async {}
+    }
 }
 
 impl<F, View> ChooseView for F
@@ -37,6 +48,12 @@ where
         *self.data.write_value() = Some(T::data());
         T::preload().await;
     }
+
+    async fn prefetch(&self) {
+        // the route's data is created by its owner when it is matched, so only the code for
+        // the view is loaded ahead of time
+        T::preload().await;
+    }
 }
 
 pub trait LazyRoute: Send + 'static {
@@ -105,6 +122,13 @@ where
             Either::Right(f) => f.preload().await,
         }
     }
+
+    async fn prefetch(&self) {
+        match self {
+            Either::Left(f) => f.prefetch().await,
+            Either::Right(f) => f.prefetch().await,
+        }
+    }
 }
 
 macro_rules! tuples {
@@ -126,6 +150,12 @@ macro_rules! tuples {
                     $($either::$ty(f) => f.preload().await,)*
                 }
             }
+
+            async fn prefetch(&self) {
+                match self {
+                    $($either::$ty(f) => f.prefetch().await,)*
+                }
+            }
         }
     };
 }
@@ -0,0 +1,330 @@
+use crate::{
+    hooks::{use_params_raw, use_url_raw},
+    params::ParamsMap,
+    ChooseView,
+};
+use any_spawner::Executor;
+use core::fmt;
+use futures::channel::oneshot;
+use leptos::server::ArcResource;
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    clock::use_clock,
+    owner::{provide_context, ArcStoredValue},
+    traits::{Get, GetUntracked, ReadValue, With, WithUntracked, WriteValue},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tachys::view::any_view::AnyView;
+
+/// The arguments that are passed to a route's [`RouteLoader`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoaderArgs {
+    /// The params matched by this route and all of its parent routes.
+    pub params: ParamsMap,
+    /// The query params of the current URL.
+    pub query: ParamsMap,
+}
+
+type ProvideLoaderData = Arc<dyn Fn() + Send + Sync>;
+
+/// The maximum number of prefetched results that are held for a single loader.
+const MAX_PREFETCHED: usize = 8;
+
+/// How long a prefetched result can be used for after it was started. After this, the data may
+/// be out of date, so navigating to the route runs the loader again.
+const PREFETCH_TTL: Duration = Duration::from_secs(30);
+
+/// Results of a loader that have been started by prefetching a route, but have not yet been
+/// used by a route that was navigated to.
+type Prefetched<T> = Arc<Mutex<Vec<PrefetchedEntry<T>>>>;
+
+struct PrefetchedEntry<T> {
+    args: LoaderArgs,
+    /// When the loader was started, as given by the [`Clock`](reactive_graph::clock::Clock).
+    started_at: Duration,
+    data: oneshot::Receiver<T>,
+}
+
+/// Removes the prefetched results that have expired.
+fn remove_expired<T>(prefetched: &mut Vec<PrefetchedEntry<T>>, now: Duration) {
+    prefetched
+        .retain(|entry| now.saturating_sub(entry.started_at) < PREFETCH_TTL);
+}
+
+/// Loads the data for a [`Route`](crate::components::Route) or
+/// [`ParentRoute`](crate::components::ParentRoute).
+///
+/// The loaders of every matched route start as soon as the URL is matched, before any of the
+/// route views are rendered, so nested routes load their data in parallel rather than one after
+/// another. A loader runs again whenever its [`LoaderArgs`] change.
+///
+/// The loaded data is stored in a resource, which is serialized from the server to the client
+/// like any other resource, and can be read in the route's view (or any of its children) with
+/// [`use_loader_data`](crate::hooks::use_loader_data).
+///
+/// When a route is prefetched (see [`Prefetch`](crate::components::Prefetch)), its loader is
+/// started with the arguments it would receive for that URL. If the route is then navigated to
+/// with the same arguments within 30 seconds, the loader uses the prefetched result rather than
+/// running again.
+///
+/// Any function that takes [`LoaderArgs`] and returns a `Future` can be converted into a loader.
+#[derive(Clone)]
+pub struct RouteLoader {
+    load: Arc<dyn Fn() -> ProvideLoaderData + Send + Sync>,
+    prefetch: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl RouteLoader {
+    /// Creates a loader from an async function.
+    pub fn new<F, Fut, T>(loader: F) -> Self
+    where
+        F: Fn(LoaderArgs) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        T: Serialize + DeserializeOwned + Send + Sync + 'static,
+    {
+        let loader = Arc::new(loader);
+        let prefetched: Prefetched<T> = Default::default();
+
+        let load = Arc::new({
+            let loader = Arc::clone(&loader);
+            let prefetched = Arc::clone(&prefetched);
+            move || {
+                let params = use_params_raw();
+                let url = use_url_raw();
+                let loader = Arc::clone(&loader);
+                let prefetched = Arc::clone(&prefetched);
+                let clock = use_clock();
+                let data = ArcResource::new(
+                    move || LoaderArgs {
+                        params: params.get(),
+                        query: url.with(|url| url.search_params().clone()),
+                    },
+                    move |args| {
+                        let prefetched = {
+                            let mut prefetched =
+                                prefetched.lock().or_poisoned();
+                            remove_expired(&mut prefetched, clock.now());
+                            prefetched
+                                .iter()
+                                .position(|entry| entry.args == args)
+                                .map(|idx| prefetched.remove(idx).data)
+                        };
+                        let loader = Arc::clone(&loader);
+                        async move {
+                            if let Some(prefetched) = prefetched {
+                                if let Ok(data) = prefetched.await {
+                                    return data;
+                                }
+                            }
+                            loader(args).await
+                        }
+                    },
+                );
+                Arc::new(move || provide_context(LoaderData(data.clone())))
+                    as ProvideLoaderData
+            }
+        });
+
+        let prefetch = Arc::new(move || {
+            let args = LoaderArgs {
+                params: use_params_raw().get_untracked(),
+                query: use_url_raw()
+                    .with_untracked(|url| url.search_params().clone()),
+            };
+            let now = use_clock().now();
+            let mut prefetched = prefetched.lock().or_poisoned();
+            remove_expired(&mut prefetched, now);
+            if prefetched.iter().any(|entry| entry.args == args) {
+                return;
+            }
+
+            let (tx, rx) = oneshot::channel();
+            let fut = loader(args.clone());
+            Executor::spawn(async move {
+                _ = tx.send(fut.await);
+            });
+
+            if prefetched.len() >= MAX_PREFETCHED {
+                _ = prefetched.remove(0);
+            }
+            prefetched.push(PrefetchedEntry {
+                args,
+                started_at: now,
+                data: rx,
+            });
+        });
+
+        Self { load, prefetch }
+    }
+
+    /// Starts loading, and returns a function that provides the data as context.
+    fn load(&self) -> ProvideLoaderData {
+        (self.load)()
+    }
+
+    /// Starts loading ahead of time, holding on to the result until the route is loaded with
+    /// the same arguments.
+    fn prefetch(&self) {
+        (self.prefetch)()
+    }
+}
+
+impl<F, Fut, T> From<F> for RouteLoader
+where
+    F: Fn(LoaderArgs) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = T> + Send + 'static,
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    fn from(loader: F) -> Self {
+        Self::new(loader)
+    }
+}
+
+impl fmt::Debug for RouteLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RouteLoader").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for RouteLoader {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.load, &other.load)
+    }
+}
+
+impl Eq for RouteLoader {}
+
+/// The data loaded by a [`RouteLoader`], as provided to the route's view.
+pub(crate) struct LoaderData<T>(pub ArcResource<T>);
+
+impl<T> Clone for LoaderData<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Wraps the view of a matched route, so that its loader is started when the route is
+/// preloaded or prefetched, and its data is provided when the view is chosen.
+#[derive(Clone)]
+pub(crate) struct WithLoader<View> {
+    view: View,
+    loader: Option<RouteLoader>,
+    data: ArcStoredValue<Option<ProvideLoaderData>>,
+}
+
+impl<View> WithLoader<View> {
+    pub fn new(view: View, loader: Option<RouteLoader>) -> Self {
+        Self {
+            view,
+            loader,
+            data: ArcStoredValue::new(None),
+        }
+    }
+}
+
+impl<View> ChooseView for WithLoader<View>
+where
+    View: ChooseView,
+{
+    async fn choose(self) -> AnyView {
+        if let Some(loader) = &self.loader {
+            // routers that don't preload their routes start loading here instead
+            let provide = self
+                .data
+                .read_value()
+                .clone()
+                .unwrap_or_else(|| loader.load());
+            provide();
+        }
+        self.view.choose().await
+    }
+
+    async fn preload(&self) {
+        if let Some(loader) = &self.loader {
+            *self.data.write_value() = Some(loader.load());
+        }
+        self.view.preload().await;
+    }
+
+    async fn prefetch(&self) {
+        if let Some(loader) = &self.loader {
+            loader.prefetch();
+        }
+        self.view.prefetch().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::location::Url;
+    use reactive_graph::{
+        clock::{provide_clock, MockClock},
+        computed::ArcMemo,
+        owner::{use_context, Owner},
+        signal::ArcRwSignal,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn loading_uses_prefetched_result() {
+        _ = Executor::init_futures_executor();
+        let owner = Owner::new();
+        owner.set();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let loader = RouteLoader::new({
+            let calls = Arc::clone(&calls);
+            move |_: LoaderArgs| {
+                let call = calls.fetch_add(1, Ordering::Relaxed);
+                async move { call }
+            }
+        });
+        provide_context(ArcMemo::new(|_| ParamsMap::new()));
+        provide_context(ArcRwSignal::new(Url::default()));
+
+        loader.prefetch();
+        loader.prefetch();
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        loader.load()();
+        let LoaderData(data) = use_context::<LoaderData<usize>>().unwrap();
+        let data = futures::executor::block_on(async move { data.await });
+        assert_eq!(data, 0);
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn expired_prefetched_results_are_not_used() {
+        _ = Executor::init_futures_executor();
+        let owner = Owner::new();
+        owner.set();
+        let clock = MockClock::new();
+        provide_clock(clock.clone());
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let loader = RouteLoader::new({
+            let calls = Arc::clone(&calls);
+            move |_: LoaderArgs| {
+                let call = calls.fetch_add(1, Ordering::Relaxed);
+                async move { call }
+            }
+        });
+        provide_context(ArcMemo::new(|_| ParamsMap::new()));
+        provide_context(ArcRwSignal::new(Url::default()));
+
+        loader.prefetch();
+        clock.advance(PREFETCH_TTL);
+
+        loader.load()();
+        let LoaderData(data) = use_context::<LoaderData<usize>>().unwrap();
+        let data = futures::executor::block_on(async move { data.await });
+        assert_eq!(data, 1);
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+}
@@ -14,8 +14,13 @@ use tachys::prelude::IntoMaybeErased;
 
 pub mod any_nested_match;
 pub mod any_nested_route;
+mod loader;
 mod tuples;
 
+pub(crate) use loader::LoaderData;
+use loader::WithLoader;
+pub use loader::{LoaderArgs, RouteLoader};
+
 pub(crate) static ROUTE_ID: AtomicU16 = AtomicU16::new(1);
 
 #[derive(Debug, PartialEq, Eq)]
@@ -25,6 +30,7 @@ pub struct NestedRoute<Segments, Children, Data, View> {
     children: Option<Children>,
     data: Data,
     view: View,
+    loader: Option<RouteLoader>,
     methods: HashSet<Method>,
     ssr_mode: SsrMode,
 }
@@ -69,6 +75,7 @@ where
             children: self.children.clone(),
             data: self.data.clone(),
             view: self.view.clone(),
+            loader: self.loader.clone(),
             methods: self.methods.clone(),
             ssr_mode: self.ssr_mode.clone(),
         }
@@ -94,6 +101,7 @@ impl<Segments, View> NestedRoute<Segments, (), (), View> {
             children: None,
             data: (),
             view: view.into_maybe_erased(),
+            loader: None,
             methods: [Method::Get].into(),
             ssr_mode: Default::default(),
         }
@@ -110,6 +118,7 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
             segments,
             data,
             view,
+            loader,
             ssr_mode,
             methods,
             ..
@@ -120,6 +129,7 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
             children: Some(child),
             data,
             view,
+            loader,
             ssr_mode,
             methods,
         }
@@ -129,6 +139,12 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
         self.ssr_mode = ssr_mode;
         self
     }
+
+    /// Sets the loader that fetches the data for this route whenever it is matched.
+    pub fn loader(mut self, loader: impl Into<RouteLoader>) -> Self {
+        self.loader = Some(loader.into());
+        self
+    }
 }
 
 #[derive(PartialEq, Eq)]
@@ -141,6 +157,7 @@ pub struct NestedMatch<Child, View> {
     /// The nested route.
     child: Option<Child>,
     view_fn: View,
+    loader: Option<RouteLoader>,
 }
 
 impl<Child, View> fmt::Debug for NestedMatch<Child, View>
@@ -179,7 +196,7 @@ where
     }
 
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
-        (self.view_fn, self.child)
+        (WithLoader::new(self.view_fn, self.loader), self.child)
     }
 }
 
@@ -295,6 +312,7 @@ where
                                 params,
                                 child,
                                 view_fn: self.view.clone(),
+                                loader: self.loader.clone(),
                             },
                         )),
                         remaining,
@@ -1,4 +1,18 @@
-use crate::location::State;
+use crate::location::{State, Url};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    graph::untrack,
+    owner::StoredValue,
+    signal::{ArcRwSignal, RwSignal},
+    traits::{Get, GetUntracked, Set, SetValue, UpdateValue, With},
+};
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
 
 /// Options that can be used to configure a navigation. Used with [use_navigate](crate::hooks::use_navigate).
 #[derive(Clone, Debug)]
@@ -26,3 +40,233 @@ impl Default for NavigateOptions {
         }
     }
 }
+
+/// A navigation that a [`Blocker`] is deciding whether to allow.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NavigationAttempt {
+    /// The current location.
+    pub from: Url,
+    /// The location that is being navigated to.
+    pub to: Url,
+}
+
+/// A handle to a navigation blocker, created with [`use_blocker`](crate::hooks::use_blocker).
+///
+/// When a navigation is blocked, it is held until the app either calls [`Blocker::proceed`] to
+/// continue it, or [`Blocker::reset`] to cancel it.
+#[derive(Clone, Copy)]
+pub struct Blocker {
+    attempt: RwSignal<Option<NavigationAttempt>>,
+    #[allow(clippy::type_complexity)]
+    proceed: StoredValue<Option<Box<dyn FnOnce() + Send + Sync>>>,
+}
+
+impl Debug for Blocker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Blocker")
+            .field("attempt", &self.attempt)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Blocker {
+    pub(crate) fn new() -> Self {
+        Self {
+            attempt: RwSignal::new(None),
+            proceed: StoredValue::new(None),
+        }
+    }
+
+    /// Whether a navigation is currently being blocked. This is reactive.
+    pub fn is_blocked(&self) -> bool {
+        self.attempt.with(Option::is_some)
+    }
+
+    /// The navigation that is currently being blocked, if any. This is reactive.
+    pub fn attempt(&self) -> Option<NavigationAttempt> {
+        self.attempt.get()
+    }
+
+    /// Continues the blocked navigation, if any.
+    pub fn proceed(&self) {
+        self.attempt.set(None);
+        if let Some(proceed) =
+            self.proceed.try_update_value(Option::take).flatten()
+        {
+            proceed();
+        }
+    }
+
+    /// Cancels the blocked navigation, if any, and stays at the current location.
+    pub fn reset(&self) {
+        self.attempt.set(None);
+        self.proceed.set_value(None);
+    }
+
+    fn block(
+        &self,
+        attempt: NavigationAttempt,
+        proceed: Box<dyn FnOnce() + Send + Sync>,
+    ) {
+        self.proceed.set_value(Some(proceed));
+        self.attempt.set(Some(attempt));
+    }
+}
+
+type BlockerFn = Arc<dyn Fn(&NavigationAttempt) -> bool + Send + Sync>;
+
+/// The navigation blockers that are registered for a router.
+#[derive(Clone)]
+pub(crate) struct NavigationBlockers {
+    current_url: ArcRwSignal<Url>,
+    blockers: Arc<RwLock<Vec<(usize, BlockerFn, Blocker)>>>,
+    next_id: Arc<AtomicUsize>,
+    skip_next: Arc<AtomicBool>,
+}
+
+impl NavigationBlockers {
+    pub fn new(current_url: ArcRwSignal<Url>) -> Self {
+        Self {
+            current_url,
+            blockers: Default::default(),
+            next_id: Default::default(),
+            skip_next: Default::default(),
+        }
+    }
+
+    /// Registers a blocker, returning an ID that can be used to remove it.
+    pub fn add(&self, when: BlockerFn, blocker: Blocker) -> usize {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.blockers
+            .write()
+            .or_poisoned()
+            .push((id, when, blocker));
+        id
+    }
+
+    pub fn remove(&self, id: usize) {
+        self.blockers
+            .write()
+            .or_poisoned()
+            .retain(|(blocker_id, _, _)| *blocker_id != id);
+    }
+
+    /// Lets the next navigation through without checking the blockers, because it is a
+    /// navigation that a blocker has already allowed to proceed.
+    pub fn skip_next(&self) {
+        self.skip_next.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks whether a navigation to `to` should be blocked. If so, holds on to `proceed`
+    /// until the blocker decides to continue, and returns `true`.
+    pub fn check(
+        &self,
+        to: &Url,
+        proceed: impl FnOnce() + Send + Sync + 'static,
+    ) -> bool {
+        if self.skip_next.swap(false, Ordering::Relaxed) {
+            return false;
+        }
+
+        let attempt = NavigationAttempt {
+            from: self.current_url.get_untracked(),
+            to: to.clone(),
+        };
+        let blocker = untrack(|| {
+            self.blockers
+                .read()
+                .or_poisoned()
+                .iter()
+                .find(|(_, when, _)| when(&attempt))
+                .map(|(_, _, blocker)| *blocker)
+        });
+        match blocker {
+            Some(blocker) => {
+                blocker.block(attempt, Box::new(proceed));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Checks whether a `popstate` navigation to `to` should be blocked, given whether it went
+    /// back or forward in the history. If so, returns `true`.
+    ///
+    /// The browser has already changed the URL by the time the `popstate` event fires, so a
+    /// blocked navigation is undone while the blocker decides, and redone if it proceeds.
+    pub fn check_popstate(&self, to: &Url, is_navigating_back: bool) -> bool {
+        fn go(delta: i32) {
+            _ = tachys::dom::window()
+                .history()
+                .and_then(|history| history.go_with_delta(delta));
+        }
+
+        let delta = if is_navigating_back { 1 } else { -1 };
+        let proceed = {
+            let blockers = self.clone();
+            move || {
+                blockers.skip_next();
+                go(-delta);
+            }
+        };
+        let blocked = self.check(to, proceed);
+        if blocked {
+            go(delta);
+        }
+        blocked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::{Router, RouterProps},
+        hooks::{use_blocker, use_navigate},
+        location::{LocationProvider, MemoryUrl},
+    };
+    use leptos::prelude::*;
+
+    #[test]
+    fn blocks_navigation_until_proceeding() {
+        let owner = Owner::new();
+        owner.set();
+
+        let location = MemoryUrl::new().unwrap();
+        let url = location.as_url().clone();
+        _ = Router(
+            RouterProps::builder()
+                .location(location)
+                .children(ToChildren::to_children(move || {
+                    let block = RwSignal::new(true);
+                    let blocker = use_blocker(move |attempt| {
+                        block.get() && attempt.to.path() != "/allowed"
+                    });
+                    let navigate = use_navigate();
+
+                    navigate("/allowed", Default::default());
+                    assert_eq!(url.get_untracked().path(), "/allowed");
+                    assert!(!blocker.is_blocked());
+
+                    navigate("/foo", Default::default());
+                    assert_eq!(url.get_untracked().path(), "/allowed");
+                    let attempt = blocker.attempt().unwrap();
+                    assert_eq!(attempt.from.path(), "/allowed");
+                    assert_eq!(attempt.to.path(), "/foo");
+
+                    blocker.reset();
+                    assert!(!blocker.is_blocked());
+                    assert_eq!(url.get_untracked().path(), "/allowed");
+
+                    navigate("/bar", Default::default());
+                    blocker.proceed();
+                    assert!(!blocker.is_blocked());
+                    assert_eq!(url.get_untracked().path(), "/bar");
+
+                    block.set(false);
+                    navigate("/baz", Default::default());
+                    assert_eq!(url.get_untracked().path(), "/baz");
+                }))
+                .build(),
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     flat_router::MatchedRoute,
     hooks::Matched,
-    location::{LocationProvider, Url},
+    location::{AnyLocationProvider, Url},
     matching::RouteDefs,
     params::ParamsMap,
     view_transition::start_view_transition,
@@ -52,8 +52,8 @@ use tachys::{
     },
 };
 
-pub(crate) struct NestedRoutesView<Loc, Defs, FalFn> {
-    pub location: Option<Loc>,
+pub(crate) struct NestedRoutesView<Defs, FalFn> {
+    pub location: Option<AnyLocationProvider>,
     pub routes: RouteDefs<Defs>,
     pub outer_owner: Owner,
     pub current_url: ArcRwSignal<Url>,
@@ -80,9 +80,8 @@ where
     abort_navigation: ArcStoredValue<Option<AbortHandle>>,
 }
 
-impl<Loc, Defs, FalFn, Fal> Render for NestedRoutesView<Loc, Defs, FalFn>
+impl<Defs, FalFn, Fal> Render for NestedRoutesView<Defs, FalFn>
 where
-    Loc: LocationProvider,
     Defs: MatchNestedRoutes,
     FalFn: FnOnce() -> Fal,
     Fal: Render + 'static,
@@ -183,6 +182,16 @@ where
                     set_is_routing.set(true);
                 }
 
+                // when restoring a scroll position, wait for the new route's resources to
+                // resolve, so that the position is not restored against a half-rendered page;
+                // likewise, a view transition should only start once the new page is complete
+                let wait_for_resources = self.set_is_routing.is_some()
+                    || self.transition
+                    || self
+                        .location
+                        .as_ref()
+                        .is_some_and(|loc| loc.wait_for_resources());
+
                 let mut preloaders = Vec::new();
                 let mut full_loaders = Vec::new();
                 let different_level = route.rebuild_nested_route(
@@ -192,7 +201,7 @@ where
                     &mut preloaders,
                     &mut full_loaders,
                     &mut state.outlets,
-                    self.set_is_routing.is_some(),
+                    wait_for_resources,
                     0,
                     &self.outer_owner,
                 );
@@ -211,6 +220,7 @@ where
                     .as_ref()
                     .map(|nav| nav.is_back().get_untracked())
                     .unwrap_or(false);
+                let (swapped_tx, swapped_rx) = oneshot::channel::<()>();
                 Executor::spawn_local(async move {
                     let triggers = Abortable::new(
                         join_all(preloaders),
@@ -228,6 +238,9 @@ where
                                 different_level,
                                 is_back,
                                 notify,
+                                async move {
+                                    _ = swapped_rx.await;
+                                },
                             );
                         } else {
                             notify();
@@ -238,6 +251,8 @@ where
                 let abort_navigation = state.abort_navigation.clone();
                 Executor::spawn_local(async move {
                     join_all(full_loaders).await;
+                    // the new views have been rendered by now, so a view transition can begin
+                    _ = swapped_tx.send(());
                     _ = abort_navigation.write_value().take();
                     if let Some(set_is_routing) = self.set_is_routing {
                         set_is_routing.set(false);
@@ -260,15 +275,14 @@ where
     }
 }
 
-impl<Loc, Defs, Fal, FalFn> AddAnyAttr for NestedRoutesView<Loc, Defs, FalFn>
+impl<Defs, Fal, FalFn> AddAnyAttr for NestedRoutesView<Defs, FalFn>
 where
-    Loc: LocationProvider + Send,
     Defs: MatchNestedRoutes + Send + 'static,
     FalFn: FnOnce() -> Fal + Send + 'static,
     Fal: RenderHtml + 'static,
 {
     type Output<SomeNewAttr: leptos::attr::Attribute> =
-        NestedRoutesView<Loc, Defs, FalFn>;
+        NestedRoutesView<Defs, FalFn>;
 
     fn add_any_attr<NewAttr: leptos::attr::Attribute>(
         self,
@@ -281,9 +295,8 @@ where
     }
 }
 
-impl<Loc, Defs, FalFn, Fal> RenderHtml for NestedRoutesView<Loc, Defs, FalFn>
+impl<Defs, FalFn, Fal> RenderHtml for NestedRoutesView<Defs, FalFn>
 where
-    Loc: LocationProvider + Send,
     Defs: MatchNestedRoutes + Send + 'static,
     FalFn: FnOnce() -> Fal + Send + 'static,
     Fal: RenderHtml + 'static,
@@ -636,7 +649,7 @@ trait AddNestedRoute {
         loaders: &mut Vec<Pin<Box<dyn Future<Output = ArcTrigger>>>>,
         full_loaders: &mut Vec<oneshot::Receiver<Option<Owner>>>,
         outlets: &mut Vec<RouteContext>,
-        set_is_routing: bool,
+        wait_for_resources: bool,
         level: u8,
         outer_owner: &Owner,
     ) -> u8;
@@ -819,7 +832,7 @@ where
         preloaders: &mut Vec<Pin<Box<dyn Future<Output = ArcTrigger>>>>,
         full_loaders: &mut Vec<oneshot::Receiver<Option<Owner>>>,
         outlets: &mut Vec<RouteContext>,
-        set_is_routing: bool,
+        wait_for_resources: bool,
         level: u8,
         outer_owner: &Owner,
     ) -> u8 {
@@ -939,7 +952,7 @@ where
                                     provide_context(url.clone());
                                     provide_context(matched.clone());
                                     ScopedFuture::new(async {
-                                        if set_is_routing {
+                                        if wait_for_resources {
                                             AsyncTransition::run(|| {
                                                 view.preload()
                                             })
@@ -972,7 +985,7 @@ where
                                                 provide_context(url);
                                                 provide_context(matched);
                                                 ScopedFuture::new(async move {
-                                                    if set_is_routing {
+                                                    if wait_for_resources {
                                                         AsyncTransition::run(
                                                             || view.choose(),
                                                         )
@@ -1037,7 +1050,7 @@ where
                         preloaders,
                         full_loaders,
                         outlets,
-                        set_is_routing,
+                        wait_for_resources,
                         level + 1,
                         outer_owner,
                     )
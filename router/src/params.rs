@@ -240,7 +240,7 @@ where
 /// Helpers for the `Params` derive macro to allow specialization without nightly.
 pub mod macro_helpers {
     use crate::params::{IntoParam, ParamsError};
-    use std::{str::FromStr, sync::Arc};
+    use std::{fmt::Display, str::FromStr, sync::Arc};
 
     /// This struct is never actually created; it just exists so that we can impl associated
     /// functions on it.
@@ -286,6 +286,32 @@ pub mod macro_helpers {
         <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
     {
     }
+
+    impl<T: Display> Wrapper<Option<T>> {
+        /// Converts an optional field into a param, which is omitted if the field is `None`.
+        /// Because it is directly on the struct, the compiler will pick this over the impl from
+        /// the `ToParamFallback` trait.
+        #[inline]
+        pub fn __to_param(value: &Option<T>) -> Option<String> {
+            value.as_ref().map(ToString::to_string)
+        }
+    }
+
+    /// If the ToParamFallback trait is in scope, then the compiler will use it to convert any
+    /// field that is not an `Option` into a param.
+    pub trait ToParamFallback<T>: Sized
+    where
+        T: Display,
+    {
+        /// Fallback function in case the inherent impl on the Wrapper struct does not exist for
+        /// `T`
+        #[inline]
+        fn __to_param(value: &T) -> Option<String> {
+            Some(value.to_string())
+        }
+    }
+
+    impl<T> ToParamFallback<T> for Wrapper<T> where T: Display {}
 }
 /// Errors that can occur while parsing params using [`Params`].
 #[derive(Error, Debug, Clone)]
@@ -0,0 +1,92 @@
+use crate::{
+    location::Url, params::ParamsMap, ChooseView, MatchInterface,
+    MatchNestedRoutes, MatchParams, RouteDefs,
+};
+use any_spawner::Executor;
+use futures::future::join_all;
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    computed::{ArcMemo, ScopedFuture},
+    owner::{provide_context, Owner},
+    signal::ArcRwSignal,
+};
+use std::{
+    borrow::Cow,
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+type PrefetchFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Prefetches the views of the routes that match a URL, ahead of navigating to it.
+///
+/// This erases the type of the route definitions, so that it can be stored in the router
+/// context and used by any link inside the router.
+#[derive(Clone)]
+pub(crate) struct RoutePrefetcher(Arc<dyn Fn(Url) + Send + Sync>);
+
+impl RoutePrefetcher {
+    pub fn new<Defs>(routes: RouteDefs<Defs>, outer_owner: Owner) -> Self
+    where
+        Defs: MatchNestedRoutes + Send + 'static,
+    {
+        let routes = Mutex::new(routes);
+        Self(Arc::new(move |url| {
+            let matched = routes.lock().or_poisoned().match_route(url.path());
+            if let Some(matched) = matched {
+                let mut prefetchers = Vec::new();
+                prefetch_match(
+                    matched,
+                    &url,
+                    &outer_owner,
+                    Vec::new(),
+                    &mut prefetchers,
+                );
+                Executor::spawn_local(async move {
+                    join_all(prefetchers).await;
+                });
+            }
+        }))
+    }
+
+    pub fn prefetch(&self, url: Url) {
+        (self.0)(url)
+    }
+}
+
+impl Debug for RoutePrefetcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RoutePrefetcher").finish_non_exhaustive()
+    }
+}
+
+/// Prefetches the view of a matched route and of each of its children, with the params and
+/// URL that they would be given if the route were navigated to.
+fn prefetch_match<Match>(
+    matched: Match,
+    url: &Url,
+    outer_owner: &Owner,
+    mut params: Vec<(Cow<'static, str>, String)>,
+    prefetchers: &mut Vec<PrefetchFuture>,
+) where
+    Match: MatchInterface + MatchParams,
+{
+    params.extend(matched.to_params());
+    let (view, child) = matched.into_view_and_child();
+
+    let params_including_parents =
+        params.iter().cloned().collect::<ParamsMap>();
+    prefetchers.push(Box::pin(outer_owner.child().with(|| {
+        provide_context(ArcMemo::new(move |_| {
+            params_including_parents.clone()
+        }));
+        provide_context(ArcRwSignal::new(url.to_owned()));
+        ScopedFuture::new(async move { view.prefetch().await })
+    })));
+
+    if let Some(child) = child {
+        prefetch_match(child, url, outer_owner, params, prefetchers);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    location::Url,
+    params::{ParamsError, ParamsMap},
+    PossibleRouteMatch,
+};
+use std::fmt::Display;
+
+/// A route whose path params and query params are described by the fields of a type.
+///
+/// This is usually implemented with `#[derive(TypedRoute)]`, which checks at compile time that
+/// every param in the path has a matching field, and vice versa. The derived type can then be
+/// used in three places:
+/// 1. As the `path` of a [`Route`](crate::components::Route), with [`TypedRoute::path`].
+/// 2. As the `href` of an [`A`](crate::components::A), or converted to a URL with `to_href()` or
+///    [`Display`].
+/// 3. To read the params of the current route, with
+///    [`use_typed_params`](crate::hooks::use_typed_params).
+///
+/// Fields marked with `#[route(query)]` are read from and written to the query string. An
+/// optional param (`:name?`) must be an `Option`.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::{
+///     components::{Route, Router, Routes, A},
+///     hooks::use_typed_params,
+///     TypedRoute,
+/// };
+///
+/// #[derive(TypedRoute, Debug, Clone, PartialEq)]
+/// #[route(path = "/users/:id")]
+/// struct UserRoute {
+///     id: u32,
+///     #[route(query)]
+///     tab: Option<String>,
+/// }
+///
+/// #[component]
+/// fn UserPage() -> impl IntoView {
+///     let params = use_typed_params::<UserRoute>();
+///     move || params.get().map(|route| route.id)
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///         <Router>
+///             // renaming the `:id` param without renaming the field would fail to compile
+///             <A href=UserRoute { id: 42, tab: None }>"User 42"</A>
+///             <Routes fallback=|| "Not found.">
+///                 <Route path=UserRoute::path() view=UserPage/>
+///             </Routes>
+///         </Router>
+///     }
+/// }
+///
+/// let route = UserRoute {
+///     id: 42,
+///     tab: Some("posts".to_string()),
+/// };
+/// assert_eq!(route.to_href(), "/users/42?tab=posts");
+/// ```
+///
+/// The URL is built from the path exactly as it is written, so the path of a typed route
+/// should be the full path from the root of the app, rather than a path relative to some
+/// [`ParentRoute`](crate::components::ParentRoute).
+pub trait TypedRoute: Display + Sized {
+    /// The path segments that match this route.
+    type Segments: PossibleRouteMatch + Clone + Send + Sync + 'static;
+
+    /// The path of this route, as passed to `#[route(path = ...)]`.
+    const PATH: &'static str;
+
+    /// Returns the path segments that match this route, which can be used as the `path` of a
+    /// [`Route`](crate::components::Route).
+    fn path() -> Self::Segments;
+
+    /// Builds the route from the params of a matched path and the query params of the URL.
+    fn from_maps(
+        params: &ParamsMap,
+        query: &ParamsMap,
+    ) -> Result<Self, ParamsError>;
+}
+
+/// Builds the URL of a [`TypedRoute`]. This is used by the code generated by
+/// `#[derive(TypedRoute)]`.
+#[derive(Debug, Default)]
+pub struct HrefBuilder {
+    path: String,
+    query: String,
+}
+
+impl HrefBuilder {
+    /// Creates an empty URL.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a static segment to the path.
+    pub fn push_static(&mut self, segment: &str) {
+        // a `/` segment marks a trailing slash
+        if segment != "/" {
+            self.path.push('/');
+        }
+        self.path.push_str(segment);
+    }
+
+    /// Adds a param segment to the path, if it has a value.
+    pub fn push_param(&mut self, value: Option<String>) {
+        if let Some(value) = value {
+            self.path.push('/');
+            self.path.push_str(&escape(&value));
+        }
+    }
+
+    /// Adds a wildcard segment to the path, if it has a value. Unlike a param, the value may
+    /// contain several segments separated by `/`.
+    pub fn push_wildcard(&mut self, value: Option<String>) {
+        if let Some(value) = value {
+            for segment in value.split('/').filter(|s| !s.is_empty()) {
+                self.path.push('/');
+                self.path.push_str(&escape(segment));
+            }
+        }
+    }
+
+    /// Adds a key-value pair to the query string, if it has a value.
+    pub fn push_query(&mut self, key: &str, value: Option<String>) {
+        if let Some(value) = value {
+            self.query
+                .push(if self.query.is_empty() { '?' } else { '&' });
+            self.query.push_str(&escape(key));
+            self.query.push('=');
+            self.query.push_str(&escape(&value));
+        }
+    }
+
+    /// Returns the complete URL.
+    pub fn finish(mut self) -> String {
+        if self.path.is_empty() {
+            self.path.push('/');
+        }
+        self.path.push_str(&self.query);
+        self.path
+    }
+}
+
+fn escape(s: &str) -> String {
+    // most params don't need to be escaped, and this avoids a call into JS for each one
+    if s.bytes().all(|b| {
+        b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~')
+    }) {
+        s.to_string()
+    } else {
+        Url::escape(s)
+    }
+}
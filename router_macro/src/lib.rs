@@ -12,6 +12,8 @@ use syn::{
     spanned::Spanned, FnArg, Ident, ImplItem, ItemImpl, Path, Type, TypePath,
 };
 
+mod typed_route;
+
 const RFC3986_UNRESERVED: [char; 4] = ['-', '.', '_', '~'];
 const RFC3986_PCHAR_OTHER: [char; 1] = ['@'];
 
@@ -48,6 +50,42 @@ pub fn path(tokens: TokenStream) -> TokenStream {
     segments.into_token_stream().into()
 }
 
+/// Derives [`TypedRoute`] for a struct, from the route path given in a `#[route(path = "...")]`
+/// attribute.
+///
+/// Each param in the path must have a field with the same name, and each field must either be a
+/// param in the path or be marked with `#[route(query)]`, so that renaming a param or a field
+/// without updating the other is a compile-time error. Fields are parsed with [`FromStr`] and
+/// converted to URLs with [`Display`](std::fmt::Display). Optional params (`:name?`) must be an
+/// `Option`, and `Option` query params are left out of the URL when they are `None`.
+///
+/// ```rust
+/// use leptos_router::{
+///     ParamSegment, StaticSegment, TypedRoute,
+/// };
+///
+/// #[derive(TypedRoute)]
+/// #[route(path = "/posts/:id")]
+/// struct PostRoute {
+///     id: u32,
+///     #[route(query)]
+///     page: Option<u32>,
+/// }
+///
+/// assert_eq!(PostRoute::path(), (StaticSegment("posts"), ParamSegment("id")));
+///
+/// let route = PostRoute { id: 3, page: Some(2) };
+/// assert_eq!(route.to_href(), "/posts/3?page=2");
+/// ```
+///
+/// [`TypedRoute`]: https://docs.rs/leptos_router/latest/leptos_router/trait.TypedRoute.html
+/// [`FromStr`]: std::str::FromStr
+#[proc_macro_error]
+#[proc_macro_derive(TypedRoute, attributes(route))]
+pub fn typed_route(input: TokenStream) -> TokenStream {
+    typed_route::typed_route_impl(input)
+}
+
 #[derive(Debug, PartialEq)]
 struct Segments(pub Vec<Segment>);
 
@@ -79,20 +117,7 @@ impl SegmentParser {
             match input {
                 TokenTree::Literal(lit) => {
                     let lit = lit.to_string();
-                    if lit.contains("//") {
-                        abort!(
-                            proc_macro2::Span::call_site(),
-                            "Consecutive '/' is not allowed"
-                        );
-                    }
-                    Self::parse_str(
-                        &mut self.segments,
-                        lit.trim_start_matches(['"', '/'])
-                            .trim_end_matches(['"', '/']),
-                    );
-                    if lit.ends_with(r#"/""#) && lit != r#""/""# {
-                        self.segments.push(Segment::Static("/".to_string()));
-                    }
+                    Self::parse_path(&mut self.segments, lit.trim_matches('"'));
                 }
                 TokenTree::Group(_) => unimplemented!(),
                 TokenTree::Ident(_) => unimplemented!(),
@@ -101,6 +126,19 @@ impl SegmentParser {
         }
     }
 
+    pub fn parse_path(segments: &mut Vec<Segment>, path: &str) {
+        if path.contains("//") {
+            abort!(
+                proc_macro2::Span::call_site(),
+                "Consecutive '/' is not allowed"
+            );
+        }
+        Self::parse_str(segments, path.trim_matches('/'));
+        if path.ends_with('/') && path != "/" {
+            segments.push(Segment::Static("/".to_string()));
+        }
+    }
+
     pub fn parse_str(segments: &mut Vec<Segment>, current_str: &str) {
         if ["", "*"].contains(&current_str) {
             return;
@@ -0,0 +1,279 @@
+use crate::{Segment, SegmentParser, Segments};
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use proc_macro_error2::abort;
+use quote::quote;
+use syn::{
+    spanned::Spanned, Data, DataStruct, DeriveInput, Field, Fields, LitStr,
+    Type, TypePath,
+};
+
+struct RouteField<'a> {
+    field: &'a Field,
+    name: String,
+    query: bool,
+}
+
+pub(crate) fn typed_route_impl(input: TokenStream) -> TokenStream {
+    let ast = syn::parse::<DeriveInput>(input).unwrap_or_else(|e| {
+        abort!(e.span(), "`TypedRoute` can only be derived for a struct")
+    });
+
+    let Some(path) = route_path(&ast) else {
+        abort!(
+            ast.ident.span(),
+            "missing `#[route(path = \"...\")]` attribute"
+        )
+    };
+    let mut segments = Vec::new();
+    SegmentParser::parse_path(&mut segments, &path.value());
+
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => fields.named.iter().map(route_field).collect::<Vec<_>>(),
+        Data::Struct(DataStruct {
+            fields: Fields::Unit,
+            ..
+        }) => Vec::new(),
+        _ => abort!(
+            ast.span(),
+            "`TypedRoute` can only be derived for a struct with named fields"
+        ),
+    };
+
+    // every param in the path needs a field, and every field that is not a query param needs
+    // to appear in the path
+    for segment in &segments {
+        let (Segment::Param(name)
+        | Segment::OptionalParam(name)
+        | Segment::Wildcard(name)) = segment
+        else {
+            continue;
+        };
+        let field = fields
+            .iter()
+            .find(|field| !field.query && &field.name == name)
+            .unwrap_or_else(|| {
+                abort!(
+                    path.span(),
+                    "the path param `{}` does not have a matching field",
+                    name
+                )
+            });
+        if matches!(segment, Segment::OptionalParam(_))
+            && !is_option(&field.field.ty)
+        {
+            abort!(
+                field.field.ty.span(),
+                "the optional path param `{}` must be an `Option`",
+                name
+            );
+        }
+    }
+    for field in fields.iter().filter(|field| !field.query) {
+        let in_path = segments.iter().any(|segment| {
+            matches!(
+                segment,
+                Segment::Param(name)
+                | Segment::OptionalParam(name)
+                | Segment::Wildcard(name) if name == &field.name
+            )
+        });
+        if !in_path {
+            abort!(
+                field.field.span(),
+                "the field `{}` is not a param in the path `{}`; mark it with \
+                 `#[route(query)]` to read it from the query string instead",
+                field.name,
+                path.value()
+            );
+        }
+    }
+
+    let segment_types = segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Static(_) => {
+                quote! { ::leptos_router::StaticSegment<&'static str> }
+            }
+            Segment::Param(_) => quote! { ::leptos_router::ParamSegment },
+            Segment::OptionalParam(_) => {
+                quote! { ::leptos_router::OptionalParamSegment }
+            }
+            Segment::Wildcard(_) => quote! { ::leptos_router::WildcardSegment },
+        })
+        .collect::<Vec<_>>();
+    let segments_ty = match segments.len() {
+        1 => quote! { (#(#segment_types,)*) },
+        _ => quote! { (#(#segment_types),*) },
+    };
+
+    let push_segments = segments.iter().map(|segment| match segment {
+        Segment::Static(s) => quote! { href.push_static(#s); },
+        Segment::Param(name) | Segment::OptionalParam(name) => {
+            let field = find_field(&fields, name);
+            let ident = &field.field.ident;
+            let ty = &field.field.ty;
+            quote! {
+                href.push_param(::leptos_router::params::macro_helpers::Wrapper::<#ty>::__to_param(&self.#ident));
+            }
+        }
+        Segment::Wildcard(name) => {
+            let field = find_field(&fields, name);
+            let ident = &field.field.ident;
+            let ty = &field.field.ty;
+            quote! {
+                href.push_wildcard(::leptos_router::params::macro_helpers::Wrapper::<#ty>::__to_param(&self.#ident));
+            }
+        }
+    }).collect::<Vec<_>>();
+    let push_query = fields.iter().filter(|field| field.query).map(|field| {
+        let ident = &field.field.ident;
+        let ty = &field.field.ty;
+        let name = &field.name;
+        quote! {
+            href.push_query(#name, ::leptos_router::params::macro_helpers::Wrapper::<#ty>::__to_param(&self.#ident));
+        }
+    });
+
+    let from_maps = fields.iter().map(|field| {
+        let ident = &field.field.ident;
+        let ty = &field.field.ty;
+        let name = &field.name;
+        let map = if field.query {
+            quote! { query }
+        } else {
+            quote! { params }
+        };
+        quote! {
+            #ident: ::leptos_router::params::macro_helpers::Wrapper::<#ty>::__into_param(
+                #map.get_str(#name),
+                #name
+            )?
+        }
+    });
+
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        ast.generics.split_for_impl();
+    let segments = Segments(segments);
+
+    quote! {
+        impl #impl_generics ::leptos_router::TypedRoute for #name #ty_generics #where_clause {
+            type Segments = #segments_ty;
+
+            const PATH: &'static str = #path;
+
+            #[allow(clippy::unused_unit)]
+            fn path() -> Self::Segments {
+                #segments
+            }
+
+            fn from_maps(
+                params: &::leptos_router::params::ParamsMap,
+                query: &::leptos_router::params::ParamsMap,
+            ) -> ::core::result::Result<Self, ::leptos_router::params::ParamsError> {
+                use ::leptos_router::params::macro_helpers::Fallback as _;
+
+                ::core::result::Result::Ok(Self {
+                    #(#from_maps,)*
+                })
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Builds the URL of this route.
+            pub fn to_href(&self) -> ::std::string::String {
+                use ::leptos_router::params::macro_helpers::ToParamFallback as _;
+
+                let mut href = ::leptos_router::HrefBuilder::new();
+                #(#push_segments)*
+                #(#push_query)*
+                href.finish()
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(&self.to_href())
+            }
+        }
+
+        impl #impl_generics ::leptos_router::components::ToHref for #name #ty_generics #where_clause {
+            fn to_href(&self) -> ::std::boxed::Box<dyn Fn() -> ::std::string::String + '_> {
+                let href = #name::to_href(self);
+                ::std::boxed::Box::new(move || href.clone())
+            }
+        }
+    }
+    .into()
+}
+
+fn route_path(ast: &DeriveInput) -> Option<LitStr> {
+    let mut path = None;
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("route"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("path") {
+                path = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `path = \"...\"`"))
+            }
+        })
+        .unwrap_or_else(|e| abort!(e.span(), "{}", e));
+    }
+    path
+}
+
+fn route_field(field: &Field) -> RouteField<'_> {
+    let mut query = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("route"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("query") {
+                query = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `query`"))
+            }
+        })
+        .unwrap_or_else(|e| abort!(e.span(), "{}", e));
+    }
+    let name = field
+        .ident
+        .as_ref()
+        .expect("expected named struct fields")
+        .to_string()
+        .trim_start_matches("r#")
+        .to_owned();
+    RouteField { field, name, query }
+}
+
+fn find_field<'a>(
+    fields: &'a [RouteField<'a>],
+    name: &str,
+) -> &'a RouteField<'a> {
+    fields
+        .iter()
+        .find(|field| !field.query && field.name == name)
+        .unwrap_or_else(|| {
+            abort!(Span::call_site(), "no field for path param `{}`", name)
+        })
+}
+
+fn is_option(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Path(TypePath { path, .. })
+            if path.segments.last().is_some_and(|segment| segment.ident == "Option")
+    )
+}
@@ -0,0 +1,94 @@
+use leptos_router::{
+    params::{ParamsError, ParamsMap},
+    path, OptionalParamSegment, ParamSegment, StaticSegment, TypedRoute,
+    WildcardSegment,
+};
+
+#[derive(TypedRoute, Debug, PartialEq)]
+#[route(path = "/")]
+struct HomeRoute;
+
+#[derive(TypedRoute, Debug, PartialEq)]
+#[route(path = "/users/:id/posts/:post?")]
+struct UserPostsRoute {
+    id: u32,
+    post: Option<u32>,
+    #[route(query)]
+    page: Option<u32>,
+    #[route(query)]
+    sort: String,
+}
+
+#[derive(TypedRoute, Debug, PartialEq)]
+#[route(path = "/files/*path")]
+struct FilesRoute {
+    path: String,
+}
+
+#[derive(TypedRoute, Debug, PartialEq)]
+#[route(path = "/about/")]
+struct AboutRoute {}
+
+#[test]
+fn builds_matching_segments() {
+    assert_eq!(HomeRoute::path(), ());
+    assert_eq!(UserPostsRoute::path(), path!("/users/:id/posts/:post?"));
+    assert_eq!(
+        UserPostsRoute::path(),
+        (
+            StaticSegment("users"),
+            ParamSegment("id"),
+            StaticSegment("posts"),
+            OptionalParamSegment("post")
+        )
+    );
+    assert_eq!(
+        FilesRoute::path(),
+        (StaticSegment("files"), WildcardSegment("path"))
+    );
+    assert_eq!(AboutRoute::path(), path!("/about/"));
+    assert_eq!(UserPostsRoute::PATH, "/users/:id/posts/:post?");
+}
+
+#[test]
+fn builds_href() {
+    assert_eq!(HomeRoute.to_href(), "/");
+    assert_eq!(AboutRoute {}.to_href(), "/about/");
+    assert_eq!(
+        UserPostsRoute {
+            id: 1,
+            post: None,
+            page: None,
+            sort: "new".to_string(),
+        }
+        .to_href(),
+        "/users/1/posts?sort=new"
+    );
+    assert_eq!(
+        UserPostsRoute {
+            id: 1,
+            post: Some(2),
+            page: Some(3),
+            sort: "new".to_string(),
+        }
+        .to_string(),
+        "/users/1/posts/2?page=3&sort=new"
+    );
+    assert_eq!(
+        FilesRoute {
+            path: "docs/intro.md".to_string()
+        }
+        .to_href(),
+        "/files/docs/intro.md"
+    );
+}
+
+#[test]
+fn parses_params_and_query() {
+    let empty = ParamsMap::new();
+    assert_eq!(HomeRoute::from_maps(&empty, &empty), Ok(HomeRoute));
+    assert_eq!(
+        UserPostsRoute::from_maps(&empty, &empty),
+        Err(ParamsError::MissingParam("id".to_string()))
+    );
+}
@@ -5,7 +5,7 @@
 //! See the [server_fn_macro] crate for more information.
 
 use proc_macro::TokenStream;
-use server_fn_macro::server_macro_impl;
+use server_fn_macro::{server_macro_impl, validate_derive_impl};
 use syn::__private::ToTokens;
 
 /// Declares that a function is a [server function](https://docs.rs/server_fn/).
@@ -82,3 +82,24 @@ pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
         Ok(s) => s.to_token_stream().into(),
     }
 }
+
+/// Derives [`Validate`](https://docs.rs/server_fn/latest/server_fn/validate/trait.Validate.html)
+/// for a struct, using the `#[validate(...)]` rules on each of its fields.
+///
+/// ```rust,ignore
+/// #[derive(Deserialize, Serialize, Validate)]
+/// pub struct NewUser {
+///     #[validate(required, length(min = 3, max = 32))]
+///     name: String,
+///     #[validate(range(min = 13, message = "You must be at least 13."))]
+///     age: u8,
+/// }
+/// ```
+#[proc_macro_derive(Validate, attributes(validate))]
+pub fn validate(input: TokenStream) -> TokenStream {
+    match validate_derive_impl(input.into(), Some(syn::parse_quote!(server_fn)))
+    {
+        Err(e) => e.to_compile_error().into(),
+        Ok(s) => s.into(),
+    }
+}
@@ -0,0 +1,1173 @@
+use crate::{
+    client::Client,
+    error::{FromServerFnError, IntoAppError, ServerFnErrorErr},
+    redirect::REDIRECT_HEADER,
+    request::ClientReq,
+    response::ClientRes,
+    ServerFnError,
+};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use futures::{
+    channel::{mpsc, oneshot},
+    future::{self, Either},
+    stream, FutureExt, Stream, StreamExt, TryFutureExt,
+};
+use http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use or_poisoned::OrPoisoned;
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    fmt::Display,
+    future::Future,
+    marker::PhantomData,
+    pin::pin,
+    sync::{LazyLock, Mutex, OnceLock},
+    task::Poll,
+};
+
+/// The MIME type of the body of a batch request or response.
+pub(crate) const CONTENT_TYPE: &str = "application/x-server-fn-batch";
+
+const DEFAULT_PATH: &str = "/api/_batch";
+
+static PATH: OnceLock<&'static str> = OnceLock::new();
+
+/// Sets the path that batches of server function calls are sent to.
+///
+/// This needs to be set to the same path on the client and on the server, before any batches
+/// are sent or routes are registered. If it is not set, it defaults to `/api/_batch`.
+///
+/// # Panics
+///
+/// Panics if the path has already been set.
+pub fn set_batch_path(path: &'static str) {
+    PATH.set(path).unwrap();
+}
+
+/// Returns the path that batches of server function calls are sent to.
+pub fn batch_path() -> &'static str {
+    PATH.get().copied().unwrap_or(DEFAULT_PATH)
+}
+
+/// Limits on the batches of server function calls that are accepted.
+///
+/// The server rejects a batch that exceeds either limit, without running any of its calls. A
+/// [`BatchClient`] splits the calls it sends into batches that fit within them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchLimits {
+    /// The largest body of a batch request, in bytes. Defaults to 2 MiB.
+    pub max_body_size: usize,
+    /// The largest number of calls in a single batch. Defaults to 32.
+    pub max_calls: usize,
+}
+
+impl Default for BatchLimits {
+    fn default() -> Self {
+        Self {
+            max_body_size: 2 * 1024 * 1024,
+            max_calls: 32,
+        }
+    }
+}
+
+static LIMITS: OnceLock<BatchLimits> = OnceLock::new();
+
+/// Sets the limits on the batches of server function calls that are accepted.
+///
+/// Like the [batch path](set_batch_path), these should be set to the same limits on the client
+/// and on the server, before any batches are sent or received. If they are not set, the
+/// [defaults](BatchLimits::default) are used.
+///
+/// # Panics
+///
+/// Panics if the limits have already been set.
+pub fn set_batch_limits(limits: BatchLimits) {
+    LIMITS.set(limits).unwrap();
+}
+
+/// Returns the limits on the batches of server function calls that are accepted.
+pub fn batch_limits() -> BatchLimits {
+    LIMITS.get().copied().unwrap_or_default()
+}
+
+// Each frame is a kind, the ID of the call it belongs to, and a length-prefixed payload.
+const CALL: u8 = 0;
+const HEAD: u8 = 1;
+const DATA: u8 = 2;
+const ERROR: u8 = 3;
+const END: u8 = 4;
+const FRAME_HEADER_LEN: usize = 9;
+
+fn encode_frame(kind: u8, id: u32, payload: &[u8]) -> Bytes {
+    let mut buf = BytesMut::with_capacity(FRAME_HEADER_LEN + payload.len());
+    buf.put_u8(kind);
+    buf.put_u32(id);
+    buf.put_u32(payload.len() as u32);
+    buf.put_slice(payload);
+    buf.freeze()
+}
+
+fn put_field(buf: &mut BytesMut, field: &[u8]) {
+    buf.put_u32(field.len() as u32);
+    buf.put_slice(field);
+}
+
+fn take_field(buf: &mut Bytes) -> Result<Bytes, String> {
+    if buf.remaining() < 4 {
+        return Err("unexpected end of frame".into());
+    }
+    let len = buf.get_u32() as usize;
+    if buf.remaining() < len {
+        return Err("unexpected end of frame".into());
+    }
+    Ok(buf.split_to(len))
+}
+
+fn take_str(buf: &mut Bytes) -> Result<String, String> {
+    String::from_utf8(take_field(buf)?.into()).map_err(|e| e.to_string())
+}
+
+struct Frame {
+    kind: u8,
+    id: u32,
+    payload: Bytes,
+}
+
+/// Splits a stream of bytes into frames, however it happens to be chunked.
+#[derive(Default)]
+struct Decoder {
+    buf: BytesMut,
+}
+
+impl Decoder {
+    fn feed(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        if self.buf.len() < FRAME_HEADER_LEN {
+            return None;
+        }
+        let len = u32::from_be_bytes(self.buf[5..9].try_into().unwrap());
+        let len = FRAME_HEADER_LEN + len as usize;
+        if self.buf.len() < len {
+            return None;
+        }
+        let mut frame = self.buf.split_to(len).freeze();
+        let kind = frame.get_u8();
+        let id = frame.get_u32();
+        frame.advance(4);
+        Some(Frame {
+            kind,
+            id,
+            payload: frame,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+}
+
+/// A single server function call, sent as part of a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchedCall {
+    /// The HTTP method of the call.
+    pub method: Method,
+    /// The path of the server function.
+    pub path: String,
+    /// The query string, for calls that send their arguments in the URL.
+    pub query: Option<String>,
+    /// The `Content-Type` of the call.
+    pub content_type: String,
+    /// The `Accept` header of the call.
+    pub accepts: String,
+    /// The body of the call, which is empty if the arguments are in the query string.
+    pub body: Bytes,
+}
+
+impl BatchedCall {
+    /// The path and query string of the call.
+    pub fn uri(&self) -> String {
+        match &self.query {
+            Some(query) => format!("{}?{query}", self.path),
+            None => self.path.clone(),
+        }
+    }
+
+    /// Builds a request for this call, with the same headers and extensions as the request for
+    /// the whole batch, so that things like cookies and authentication apply to every call.
+    pub fn into_request(
+        self,
+        batch: &http::request::Parts,
+    ) -> http::Request<Bytes> {
+        let mut req = http::Request::new(Bytes::new());
+        *req.method_mut() = self.method.clone();
+        *req.uri_mut() = self.uri().parse().unwrap_or_default();
+        *req.version_mut() = batch.version;
+        *req.extensions_mut() = batch.extensions.clone();
+
+        let headers = req.headers_mut();
+        *headers = batch.headers.clone();
+        headers.remove(header::CONTENT_LENGTH);
+        for (name, value) in [
+            (header::CONTENT_TYPE, &self.content_type),
+            (header::ACCEPT, &self.accepts),
+        ] {
+            match HeaderValue::from_str(value) {
+                Ok(value) => {
+                    headers.insert(name, value);
+                }
+                Err(_) => {
+                    headers.remove(name);
+                }
+            }
+        }
+
+        *req.body_mut() = self.body;
+        req
+    }
+
+    /// The length of the frame that [`encode`](Self::encode) creates for this call.
+    fn encoded_len(&self) -> usize {
+        let uri_len = self.path.len()
+            + self.query.as_ref().map_or(0, |query| query.len() + 1);
+        let fields = [
+            self.method.as_str().len(),
+            uri_len,
+            self.content_type.len(),
+            self.accepts.len(),
+            self.body.len(),
+        ];
+        FRAME_HEADER_LEN + fields.iter().map(|len| 4 + len).sum::<usize>()
+    }
+
+    fn encode(&self, id: u32) -> Bytes {
+        let mut payload = BytesMut::new();
+        put_field(&mut payload, self.method.as_str().as_bytes());
+        put_field(&mut payload, self.uri().as_bytes());
+        put_field(&mut payload, self.content_type.as_bytes());
+        put_field(&mut payload, self.accepts.as_bytes());
+        put_field(&mut payload, &self.body);
+        encode_frame(CALL, id, &payload)
+    }
+
+    fn decode(mut payload: Bytes) -> Result<Self, String> {
+        let method = Method::from_bytes(&take_field(&mut payload)?)
+            .map_err(|e| e.to_string())?;
+        let uri = take_str(&mut payload)?;
+        let (path, query) = match uri.split_once('?') {
+            Some((path, query)) => (path.to_string(), Some(query.to_string())),
+            None => (uri, None),
+        };
+        Ok(Self {
+            method,
+            path,
+            query,
+            content_type: take_str(&mut payload)?,
+            accepts: take_str(&mut payload)?,
+            body: take_field(&mut payload)?,
+        })
+    }
+}
+
+/// Decodes the calls in the body of a batch request, which may contain at most `max_calls` calls.
+pub fn decode_calls(
+    body: Bytes,
+    max_calls: usize,
+) -> Result<Vec<BatchedCall>, ServerFnErrorErr> {
+    let mut decoder = Decoder::default();
+    decoder.feed(&body);
+    let mut calls = Vec::new();
+    while let Some(frame) = decoder.next_frame() {
+        if calls.len() == max_calls {
+            return Err(ServerFnErrorErr::Args(format!(
+                "a batch may contain at most {max_calls} server function calls"
+            )));
+        }
+        if frame.kind != CALL || frame.id as usize != calls.len() {
+            return Err(ServerFnErrorErr::Args(
+                "invalid batch of server function calls".into(),
+            ));
+        }
+        calls.push(
+            BatchedCall::decode(frame.payload)
+                .map_err(ServerFnErrorErr::Args)?,
+        );
+    }
+    if !decoder.is_empty() {
+        return Err(ServerFnErrorErr::Args(
+            "incomplete batch of server function calls".into(),
+        ));
+    }
+    Ok(calls)
+}
+
+/// Runs each call in a batch with `run`, and encodes their responses into the body of a single
+/// response.
+///
+/// The calls run concurrently. Each response is sent as soon as it is available, and its body is
+/// streamed, so a call that takes a long time (or returns a stream) does not hold back the others.
+pub fn encode_responses<F, Fut, S, E>(
+    calls: Vec<BatchedCall>,
+    mut run: F,
+) -> impl Stream<Item = Bytes>
+where
+    F: FnMut(BatchedCall) -> Fut,
+    Fut: Future<Output = http::Response<S>>,
+    S: Stream<Item = Result<Bytes, E>>,
+    E: Display,
+{
+    stream::select_all(calls.into_iter().enumerate().map(|(id, call)| {
+        let id = id as u32;
+        Box::pin(run(call).into_stream().flat_map(move |res| {
+            let (parts, body) = res.into_parts();
+
+            let mut head = BytesMut::new();
+            put_field(&mut head, parts.status.as_str().as_bytes());
+            for (name, value) in &parts.headers {
+                put_field(&mut head, name.as_str().as_bytes());
+                put_field(&mut head, value.as_bytes());
+            }
+
+            stream::once(future::ready(encode_frame(HEAD, id, &head)))
+                .chain(body.map(move |chunk| match chunk {
+                    Ok(chunk) => encode_frame(DATA, id, &chunk),
+                    Err(e) => encode_frame(ERROR, id, e.to_string().as_bytes()),
+                }))
+                .chain(stream::once(future::ready(encode_frame(END, id, &[]))))
+        }))
+    }))
+}
+
+/// A [`Client`] that sends server function calls that are made at the same time as a single
+/// request, using another client `C`.
+///
+/// Calls are collected until the current task yields, and are then sent together to the
+/// [batch path](batch_path), where the server runs each of them as usual. The responses are
+/// streamed back as they become available, so each call still resolves on its own, with its own
+/// result or error.
+///
+/// Only calls with a plain text or binary body (or arguments in the URL) are batched. Calls with
+/// form data, multipart or streaming bodies, and websockets, are sent with `C` as usual, as are
+/// calls that are too large to fit in a batch on their own.
+///
+/// Responses to batched calls are received by this client, not by the browser, so any cookies
+/// they set are ignored. Server functions that set cookies should not use this client.
+///
+/// ```rust, no_run
+/// # use server_fn_macro_default::server;
+/// # #[cfg(feature = "browser")] {
+/// use server_fn::{
+///     batch::BatchClient, client::browser::BrowserClient, ServerFnError,
+/// };
+///
+/// #[server(client = BatchClient<BrowserClient>)]
+/// async fn get_count() -> Result<u32, ServerFnError> {
+///     Ok(0)
+/// }
+/// # }
+/// ```
+pub struct BatchClient<C>(PhantomData<C>);
+
+/// A request sent by a [`BatchClient`].
+pub struct BatchRequest<R>(RequestInner<R>);
+
+enum RequestInner<R> {
+    Batched(BatchedCall),
+    Direct(R),
+}
+
+impl<R> BatchRequest<R> {
+    /// Batches a call, unless its frame could never fit in a batch, in which case it is sent on
+    /// its own.
+    fn batched<E>(
+        method: Method,
+        path: &str,
+        query: Option<&str>,
+        content_type: &str,
+        accepts: &str,
+        body: Bytes,
+    ) -> Result<Self, E>
+    where
+        R: ClientReq<E>,
+    {
+        let call = BatchedCall {
+            method,
+            path: path.to_string(),
+            query: query.map(ToString::to_string),
+            content_type: content_type.to_string(),
+            accepts: accepts.to_string(),
+            body,
+        };
+        let fits = call.encoded_len() <= batch_limits().max_body_size;
+        let req = Self(RequestInner::Batched(call));
+        if fits {
+            Ok(req)
+        } else {
+            req.into_direct().map(Self::direct)
+        }
+    }
+
+    fn direct(req: R) -> Self {
+        Self(RequestInner::Direct(req))
+    }
+
+    /// Converts this into a request that is sent on its own.
+    fn into_direct<E>(self) -> Result<R, E>
+    where
+        R: ClientReq<E>,
+    {
+        match self.0 {
+            RequestInner::Direct(req) => Ok(req),
+            RequestInner::Batched(call) => match call.query {
+                Some(query) => R::try_new_req_query(
+                    &call.path,
+                    &call.content_type,
+                    &call.accepts,
+                    &query,
+                    call.method,
+                ),
+                None => R::try_new_req_bytes(
+                    &call.path,
+                    &call.content_type,
+                    &call.accepts,
+                    call.body,
+                    call.method,
+                ),
+            },
+        }
+    }
+}
+
+impl<R, E> ClientReq<E> for BatchRequest<R>
+where
+    R: ClientReq<E>,
+{
+    type FormData = R::FormData;
+
+    fn try_new_req_query(
+        path: &str,
+        content_type: &str,
+        accepts: &str,
+        query: &str,
+        method: Method,
+    ) -> Result<Self, E> {
+        Self::batched(
+            method,
+            path,
+            Some(query),
+            content_type,
+            accepts,
+            Bytes::new(),
+        )
+    }
+
+    fn try_new_req_text(
+        path: &str,
+        content_type: &str,
+        accepts: &str,
+        body: String,
+        method: Method,
+    ) -> Result<Self, E> {
+        Self::try_new_req_bytes(
+            path,
+            content_type,
+            accepts,
+            body.into(),
+            method,
+        )
+    }
+
+    fn try_new_req_bytes(
+        path: &str,
+        content_type: &str,
+        accepts: &str,
+        body: Bytes,
+        method: Method,
+    ) -> Result<Self, E> {
+        Self::batched(method, path, None, content_type, accepts, body)
+    }
+
+    fn try_new_req_form_data(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: Self::FormData,
+        method: Method,
+    ) -> Result<Self, E> {
+        R::try_new_req_form_data(path, accepts, content_type, body, method)
+            .map(Self::direct)
+    }
+
+    fn try_new_req_multipart(
+        path: &str,
+        accepts: &str,
+        body: Self::FormData,
+        method: Method,
+    ) -> Result<Self, E> {
+        R::try_new_req_multipart(path, accepts, body, method).map(Self::direct)
+    }
+
+    fn try_new_req_streaming(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: impl Stream<Item = Bytes> + Send + 'static,
+        method: Method,
+    ) -> Result<Self, E> {
+        R::try_new_req_streaming(path, accepts, content_type, body, method)
+            .map(Self::direct)
+    }
+}
+
+/// A response received by a [`BatchClient`].
+pub struct BatchResponse<R>(ResponseInner<R>);
+
+enum ResponseInner<R> {
+    Batched(BatchedResponse),
+    Direct(R),
+}
+
+/// The response to one call in a batch, whose body is still being received.
+struct BatchedResponse {
+    path: String,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: mpsc::UnboundedReceiver<Result<Bytes, String>>,
+}
+
+impl BatchedResponse {
+    async fn collect(mut self) -> Result<Bytes, ServerFnErrorErr> {
+        let mut buf = BytesMut::new();
+        while let Some(chunk) = self.body.next().await {
+            buf.extend_from_slice(&chunk.map_err(ServerFnErrorErr::Response)?);
+        }
+        Ok(buf.freeze())
+    }
+}
+
+impl<R, E> ClientRes<E> for BatchResponse<R>
+where
+    R: ClientRes<E> + Send,
+    E: FromServerFnError + Send,
+{
+    fn try_into_string(self) -> impl Future<Output = Result<String, E>> + Send {
+        match self.0 {
+            ResponseInner::Direct(res) => Either::Left(res.try_into_string()),
+            ResponseInner::Batched(res) => Either::Right(async move {
+                let bytes =
+                    res.collect().await.map_err(E::from_server_fn_error)?;
+                String::from_utf8(bytes.into()).map_err(|e| {
+                    ServerFnErrorErr::Deserialization(e.to_string())
+                        .into_app_error()
+                })
+            }),
+        }
+    }
+
+    fn try_into_bytes(self) -> impl Future<Output = Result<Bytes, E>> + Send {
+        match self.0 {
+            ResponseInner::Direct(res) => Either::Left(res.try_into_bytes()),
+            ResponseInner::Batched(res) => {
+                Either::Right(res.collect().map_err(E::from_server_fn_error))
+            }
+        }
+    }
+
+    fn try_into_stream(
+        self,
+    ) -> Result<
+        impl Stream<Item = Result<Bytes, Bytes>> + Send + Sync + 'static,
+        E,
+    > {
+        match self.0 {
+            ResponseInner::Direct(res) => {
+                res.try_into_stream().map(Either::Left)
+            }
+            ResponseInner::Batched(res) => {
+                Ok(Either::Right(res.body.map(|chunk| {
+                    chunk.map_err(|e| {
+                        E::from_server_fn_error(ServerFnErrorErr::Response(e))
+                            .ser()
+                    })
+                })))
+            }
+        }
+    }
+
+    fn status(&self) -> u16 {
+        match &self.0 {
+            ResponseInner::Direct(res) => res.status(),
+            ResponseInner::Batched(res) => res.status.as_u16(),
+        }
+    }
+
+    fn status_text(&self) -> String {
+        match &self.0 {
+            ResponseInner::Direct(res) => res.status_text(),
+            ResponseInner::Batched(res) => res
+                .status
+                .canonical_reason()
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+
+    fn location(&self) -> String {
+        match &self.0 {
+            ResponseInner::Direct(res) => res.location(),
+            ResponseInner::Batched(res) => res
+                .headers
+                .get(header::LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .unwrap_or(&res.path)
+                .to_string(),
+        }
+    }
+
+    fn has_redirect(&self) -> bool {
+        match &self.0 {
+            ResponseInner::Direct(res) => res.has_redirect(),
+            ResponseInner::Batched(res) => {
+                res.headers.contains_key(REDIRECT_HEADER)
+            }
+        }
+    }
+}
+
+impl<C, E, InputStreamError, OutputStreamError>
+    Client<E, InputStreamError, OutputStreamError> for BatchClient<C>
+where
+    C: Client<E, InputStreamError, OutputStreamError>
+        + Client<ServerFnError>
+        + 'static,
+    E: FromServerFnError + Send,
+{
+    type Request = BatchRequest<
+        <C as Client<E, InputStreamError, OutputStreamError>>::Request,
+    >;
+    type Response = BatchResponse<
+        <C as Client<E, InputStreamError, OutputStreamError>>::Response,
+    >;
+
+    fn send(
+        req: Self::Request,
+    ) -> impl Future<Output = Result<Self::Response, E>> + Send {
+        match req.0 {
+            RequestInner::Direct(req) => Either::Left(
+                <C as Client<E, InputStreamError, OutputStreamError>>::send(
+                    req,
+                )
+                .map_ok(|res| BatchResponse(ResponseInner::Direct(res))),
+            ),
+            RequestInner::Batched(call) => {
+                Either::Right(enqueue::<C>(call).map(|res| {
+                    res.map(|res| BatchResponse(ResponseInner::Batched(res)))
+                        .map_err(|e| {
+                            ServerFnErrorErr::Request(e).into_app_error()
+                        })
+                }))
+            }
+        }
+    }
+
+    fn open_websocket(
+        path: &str,
+    ) -> impl Future<
+        Output = Result<
+            (
+                impl Stream<Item = Result<Bytes, Bytes>> + Send + 'static,
+                impl futures::Sink<Bytes> + Send + 'static,
+            ),
+            E,
+        >,
+    > + Send {
+        <C as Client<E, InputStreamError, OutputStreamError>>::open_websocket(
+            path,
+        )
+    }
+
+    async fn open_event_stream(
+        req: Self::Request,
+    ) -> Result<
+        impl Stream<Item = Result<crate::sse::RawEvent, Bytes>> + Send + 'static,
+        E,
+    > {
+        // event streams are long-lived, so they are never batched
+        let req = req.into_direct()?;
+        <C as Client<E, InputStreamError, OutputStreamError>>::open_event_stream(
+            req,
+        )
+        .await
+    }
+
+    fn spawn(future: impl Future<Output = ()> + Send + 'static) {
+        <C as Client<E, InputStreamError, OutputStreamError>>::spawn(future)
+    }
+}
+
+struct Queued {
+    call: BatchedCall,
+    tx: oneshot::Sender<Result<BatchedResponse, String>>,
+}
+
+/// The calls that are waiting to be sent, for each inner client type.
+static QUEUES: LazyLock<Mutex<HashMap<TypeId, Vec<Queued>>>> =
+    LazyLock::new(Default::default);
+
+fn enqueue<C>(
+    call: BatchedCall,
+) -> impl Future<Output = Result<BatchedResponse, String>> + Send
+where
+    C: Client<ServerFnError> + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    let first = {
+        let mut queues = QUEUES.lock().or_poisoned();
+        let queue = queues.entry(TypeId::of::<C>()).or_default();
+        queue.push(Queued { call, tx });
+        queue.len() == 1
+    };
+    if first {
+        C::spawn(flush::<C>());
+    }
+    rx.map(|res| {
+        res.unwrap_or_else(|_| {
+            Err("the batch ended before this call received a response".into())
+        })
+    })
+}
+
+/// The state of one call in a batch that has been sent.
+enum Slot {
+    Waiting(String, oneshot::Sender<Result<BatchedResponse, String>>),
+    Receiving(mpsc::UnboundedSender<Result<Bytes, String>>),
+    Done,
+}
+
+impl Slot {
+    fn fail(&mut self, error: &str) {
+        match std::mem::replace(self, Slot::Done) {
+            Slot::Waiting(_, tx) => _ = tx.send(Err(error.to_string())),
+            Slot::Receiving(tx) => {
+                _ = tx.unbounded_send(Err(error.to_string()))
+            }
+            Slot::Done => {}
+        }
+    }
+
+    fn is_abandoned(&self) -> bool {
+        match self {
+            Slot::Waiting(_, tx) => tx.is_canceled(),
+            Slot::Receiving(tx) => tx.is_closed(),
+            Slot::Done => true,
+        }
+    }
+
+    fn receive(&mut self, frame: Frame) -> Result<(), String> {
+        match (frame.kind, &mut *self) {
+            (HEAD, Slot::Waiting(..)) => {
+                let Slot::Waiting(path, tx) =
+                    std::mem::replace(self, Slot::Done)
+                else {
+                    unreachable!()
+                };
+                let mut payload = frame.payload;
+                let status = StatusCode::from_bytes(&take_field(&mut payload)?)
+                    .map_err(|e| e.to_string())?;
+                let mut headers = HeaderMap::new();
+                while payload.has_remaining() {
+                    let name =
+                        HeaderName::from_bytes(&take_field(&mut payload)?)
+                            .map_err(|e| e.to_string())?;
+                    let value = HeaderValue::from_maybe_shared(take_field(
+                        &mut payload,
+                    )?)
+                    .map_err(|e| e.to_string())?;
+                    headers.append(name, value);
+                }
+                let (body_tx, body) = mpsc::unbounded();
+                _ = tx.send(Ok(BatchedResponse {
+                    path,
+                    status,
+                    headers,
+                    body,
+                }));
+                *self = Slot::Receiving(body_tx);
+            }
+            (DATA, Slot::Receiving(tx)) => {
+                _ = tx.unbounded_send(Ok(frame.payload));
+            }
+            (ERROR, Slot::Receiving(tx)) => {
+                _ = tx.unbounded_send(Err(String::from_utf8_lossy(
+                    &frame.payload,
+                )
+                .into_owned()));
+            }
+            (END, Slot::Receiving(_)) => *self = Slot::Done,
+            _ => return Err("invalid batch response".into()),
+        }
+        Ok(())
+    }
+}
+
+/// Sends every call that has been queued for `C` once the current task yields.
+async fn flush<C>()
+where
+    C: Client<ServerFnError> + 'static,
+{
+    // let every other call that is made at the same time join the batch
+    let mut yielded = false;
+    future::poll_fn(|cx| {
+        if yielded {
+            Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+    .await;
+
+    let queued = QUEUES
+        .lock()
+        .or_poisoned()
+        .remove(&TypeId::of::<C>())
+        .unwrap_or_default();
+
+    // split the calls into as many batches as it takes to stay within the limits
+    let limits = batch_limits();
+    let mut batches = Vec::new();
+    let mut body = BytesMut::new();
+    let mut slots = Vec::new();
+    for Queued { call, tx } in queued {
+        let frame = call.encode(slots.len() as u32);
+        let full = slots.len() == limits.max_calls
+            || body.len() + frame.len() > limits.max_body_size;
+        let frame = if full && !slots.is_empty() {
+            batches
+                .push((std::mem::take(&mut body), std::mem::take(&mut slots)));
+            call.encode(0)
+        } else {
+            frame
+        };
+        body.extend_from_slice(&frame);
+        slots.push(Slot::Waiting(call.path, tx));
+    }
+    batches.push((body, slots));
+
+    future::join_all(batches.into_iter().map(|(body, mut slots)| async move {
+        let res = send_batch::<C>(body.freeze(), &mut slots).await;
+        let error = res
+            .err()
+            .unwrap_or_else(|| "the batch response ended early".into());
+        for slot in &mut slots {
+            slot.fail(&error);
+        }
+    }))
+    .await;
+}
+
+async fn send_batch<C>(body: Bytes, slots: &mut [Slot]) -> Result<(), String>
+where
+    C: Client<ServerFnError>,
+{
+    let req = C::Request::try_new_post_bytes(
+        batch_path(),
+        CONTENT_TYPE,
+        CONTENT_TYPE,
+        body,
+    )
+    .map_err(|e| e.to_string())?;
+    let res = C::send(req).await.map_err(|e| e.to_string())?;
+    let status = res.status();
+    if !(200..300).contains(&status) {
+        let text = res.try_into_string().await.unwrap_or_default();
+        return Err(format!(
+            "batch request failed with status {status}: {text}"
+        ));
+    }
+
+    let mut stream = pin!(res.try_into_stream().map_err(|e| e.to_string())?);
+    let mut decoder = Decoder::default();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| <ServerFnError>::de(e).to_string())?;
+        decoder.feed(&chunk);
+        while let Some(frame) = decoder.next_frame() {
+            slots
+                .get_mut(frame.id as usize)
+                .ok_or("invalid batch response")?
+                .receive(frame)?;
+        }
+        // stop receiving if nobody is waiting for the rest, which drops and aborts the request
+        if slots.iter().all(Slot::is_abandoned) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(
+        method: Method,
+        query: Option<&str>,
+        body: &'static str,
+    ) -> BatchedCall {
+        BatchedCall {
+            method,
+            path: "/api/add".into(),
+            query: query.map(Into::into),
+            content_type: "application/json".into(),
+            accepts: "application/json".into(),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn calls_round_trip() {
+        let calls = vec![
+            call(Method::POST, None, "{\"a\":1}"),
+            call(Method::GET, Some("a=1&b=2"), ""),
+            call(Method::GET, Some(""), ""),
+        ];
+        let body = calls
+            .iter()
+            .enumerate()
+            .flat_map(|(id, call)| call.encode(id as u32))
+            .collect::<Bytes>();
+        assert_eq!(decode_calls(body.clone(), 3).unwrap(), calls);
+        assert!(decode_calls(body.slice(..body.len() - 1), 3).is_err());
+        assert!(decode_calls(body, 2).is_err());
+    }
+
+    #[cfg(feature = "axum-no-default")]
+    #[test]
+    fn batches_over_the_limits_are_rejected() {
+        use axum::body::Body;
+        use http::Response;
+
+        let send = |calls: Vec<BatchedCall>| {
+            let body = calls
+                .iter()
+                .enumerate()
+                .flat_map(|(id, call)| call.encode(id as u32))
+                .collect::<Bytes>();
+            let req = http::Request::post("/api/_batch")
+                .header(header::CONTENT_TYPE, CONTENT_TYPE)
+                .body(Body::from(body))
+                .unwrap();
+            futures::executor::block_on(crate::axum::handle_batch(
+                req,
+                |_| async { Response::new(Body::empty()) },
+            ))
+            .status()
+        };
+        let limits = batch_limits();
+
+        assert_eq!(
+            send(vec![call(Method::POST, None, "{}"); limits.max_calls]),
+            StatusCode::OK
+        );
+        assert_eq!(
+            send(vec![call(Method::POST, None, "{}"); limits.max_calls + 1]),
+            StatusCode::BAD_REQUEST
+        );
+        let mut large = call(Method::POST, None, "");
+        large.body = Bytes::from(vec![b'a'; limits.max_body_size]);
+        assert_eq!(send(vec![large]), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    fn encoded_len_matches_the_frame() {
+        for call in [
+            call(Method::POST, None, "{\"a\":1}"),
+            call(Method::GET, Some("a=1&b=2"), ""),
+            call(Method::GET, Some(""), ""),
+        ] {
+            assert_eq!(call.encoded_len(), call.encode(0).len());
+        }
+    }
+
+    /// A request that records which constructor created it.
+    #[derive(Debug, PartialEq)]
+    enum DirectRequest {
+        Query,
+        Bytes,
+    }
+
+    impl ClientReq<ServerFnError> for DirectRequest {
+        type FormData = ();
+
+        fn try_new_req_query(
+            _path: &str,
+            _content_type: &str,
+            _accepts: &str,
+            _query: &str,
+            _method: Method,
+        ) -> Result<Self, ServerFnError> {
+            Ok(Self::Query)
+        }
+
+        fn try_new_req_text(
+            _path: &str,
+            _content_type: &str,
+            _accepts: &str,
+            _body: String,
+            _method: Method,
+        ) -> Result<Self, ServerFnError> {
+            Ok(Self::Bytes)
+        }
+
+        fn try_new_req_bytes(
+            _path: &str,
+            _content_type: &str,
+            _accepts: &str,
+            _body: Bytes,
+            _method: Method,
+        ) -> Result<Self, ServerFnError> {
+            Ok(Self::Bytes)
+        }
+
+        fn try_new_req_form_data(
+            _path: &str,
+            _accepts: &str,
+            _content_type: &str,
+            _body: (),
+            _method: Method,
+        ) -> Result<Self, ServerFnError> {
+            unreachable!("form data is never batched")
+        }
+
+        fn try_new_req_multipart(
+            _path: &str,
+            _accepts: &str,
+            _body: (),
+            _method: Method,
+        ) -> Result<Self, ServerFnError> {
+            unreachable!("multipart bodies are never batched")
+        }
+
+        fn try_new_req_streaming(
+            _path: &str,
+            _accepts: &str,
+            _content_type: &str,
+            _body: impl Stream<Item = Bytes> + Send + 'static,
+            _method: Method,
+        ) -> Result<Self, ServerFnError> {
+            unreachable!("streaming bodies are never batched")
+        }
+    }
+
+    #[test]
+    fn calls_whose_frames_do_not_fit_are_sent_directly() {
+        let max = batch_limits().max_body_size;
+        let bytes = |len: usize| {
+            BatchRequest::<DirectRequest>::try_new_req_bytes(
+                "/api/add",
+                "application/json",
+                "application/json",
+                Bytes::from(vec![b'a'; len]),
+                Method::POST,
+            )
+            .unwrap()
+            .0
+        };
+        let query = |len: usize| {
+            BatchRequest::<DirectRequest>::try_new_req_query(
+                "/api/add",
+                "application/json",
+                "application/json",
+                &"a".repeat(len),
+                Method::GET,
+            )
+            .unwrap()
+            .0
+        };
+
+        assert!(matches!(bytes(16), RequestInner::Batched(_)));
+        assert!(matches!(query(16), RequestInner::Batched(_)));
+        // the body fits, but not along with the rest of the frame
+        assert!(matches!(
+            bytes(max - 16),
+            RequestInner::Direct(DirectRequest::Bytes)
+        ));
+        assert!(matches!(
+            query(max),
+            RequestInner::Direct(DirectRequest::Query)
+        ));
+    }
+
+    #[test]
+    fn call_requests_keep_the_batch_headers() {
+        let (batch, _) = http::Request::post("/api/_batch")
+            .header(header::COOKIE, "session=1")
+            .header(header::CONTENT_TYPE, CONTENT_TYPE)
+            .header(header::CONTENT_LENGTH, "100")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let req = call(Method::GET, Some("a=1"), "").into_request(&batch);
+        assert_eq!(req.method(), Method::GET);
+        assert_eq!(req.uri(), "/api/add?a=1");
+        assert_eq!(req.headers()[header::COOKIE], "session=1");
+        assert_eq!(req.headers()[header::CONTENT_TYPE], "application/json");
+        assert!(!req.headers().contains_key(header::CONTENT_LENGTH));
+    }
+
+    #[test]
+    fn responses_are_routed_to_their_calls() {
+        let calls = vec![
+            call(Method::POST, None, "first"),
+            call(Method::POST, None, "second"),
+        ];
+        let encoded = futures::executor::block_on(
+            encode_responses(calls, |call| async move {
+                let status = if call.body == "first" { 200 } else { 500 };
+                http::Response::builder()
+                    .status(status)
+                    .header(REDIRECT_HEADER, "")
+                    .body(stream::iter([
+                        Ok(call.body.clone()),
+                        Err("oops"),
+                        Ok(call.body),
+                    ]))
+                    .unwrap()
+            })
+            .collect::<Vec<_>>(),
+        );
+
+        let (tx1, rx1) = oneshot::channel();
+        let (tx2, rx2) = oneshot::channel();
+        let mut slots = [
+            Slot::Waiting("/api/first".into(), tx1),
+            Slot::Waiting("/api/second".into(), tx2),
+        ];
+        // feed the response in awkwardly-sized chunks
+        let encoded = encoded.concat();
+        let mut decoder = Decoder::default();
+        for chunk in encoded.chunks(7) {
+            decoder.feed(chunk);
+            while let Some(frame) = decoder.next_frame() {
+                slots[frame.id as usize].receive(frame).unwrap();
+            }
+        }
+        assert!(decoder.is_empty());
+        assert!(slots.iter().all(|slot| matches!(slot, Slot::Done)));
+
+        for (rx, status, body) in [(rx1, 200, "first"), (rx2, 500, "second")] {
+            let res = rx.now_or_never().unwrap().unwrap().unwrap();
+            assert_eq!(res.status, status);
+            assert!(res.headers.contains_key(REDIRECT_HEADER));
+            let chunks =
+                futures::executor::block_on(res.body.collect::<Vec<_>>());
+            assert_eq!(
+                chunks,
+                [Ok(body.into()), Err("oops".to_string()), Ok(body.into())]
+            );
+        }
+    }
+}
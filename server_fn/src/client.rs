@@ -1,6 +1,11 @@
-use crate::{request::ClientReq, response::ClientRes};
+use crate::{
+    error::FromServerFnError,
+    request::ClientReq,
+    response::ClientRes,
+    sse::{Parser, RawEvent},
+};
 use bytes::Bytes;
-use futures::{Sink, Stream};
+use futures::{stream, Sink, Stream, StreamExt};
 use std::{future::Future, sync::OnceLock};
 
 static ROOT_URL: OnceLock<&'static str> = OnceLock::new();
@@ -67,6 +72,42 @@ pub trait Client<Error, InputStreamError = Error, OutputStreamError = Error> {
         >,
     > + Send;
 
+    /// Sends a request for a stream of [server-sent events](crate::sse), and returns the events
+    /// as they arrive.
+    ///
+    /// The default implementation sends the request and parses the `text/event-stream` body of
+    /// the response. The stream ends if the connection is lost, rather than reconnecting.
+    fn open_event_stream(
+        req: Self::Request,
+    ) -> impl Future<
+        Output = Result<
+            impl Stream<Item = Result<RawEvent, Bytes>> + Send + 'static,
+            Error,
+        >,
+    > + Send
+    where
+        Error: FromServerFnError,
+    {
+        async move {
+            let res = Self::send(req).await?;
+            if (400..=599).contains(&res.status()) {
+                return Err(Error::de(res.try_into_bytes().await?));
+            }
+
+            let mut parser = Parser::default();
+            let events = res.try_into_stream()?.flat_map(move |chunk| {
+                let events = match chunk {
+                    Ok(chunk) => {
+                        parser.feed(&chunk).into_iter().map(Ok).collect()
+                    }
+                    Err(err) => vec![Err(err)],
+                };
+                stream::iter(events)
+            });
+            Ok(events)
+        }
+    }
+
     /// Spawn a future that runs in the background.
     fn spawn(future: impl Future<Output = ()> + Send + 'static);
 }
@@ -79,12 +120,23 @@ pub mod browser {
         error::{FromServerFnError, IntoAppError, ServerFnErrorErr},
         request::browser::{BrowserRequest, RequestInner},
         response::browser::BrowserResponse,
+        sse::{RawEvent, END_EVENT, ERROR_EVENT},
     };
     use bytes::Bytes;
-    use futures::{Sink, SinkExt, StreamExt};
+    use futures::{
+        channel::mpsc::{self, UnboundedReceiver},
+        Sink, SinkExt, Stream, StreamExt,
+    };
     use gloo_net::websocket::{Message, WebSocketError};
+    use http::Method;
     use send_wrapper::SendWrapper;
-    use std::future::Future;
+    use std::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{EventSource, MessageEvent};
 
     /// Implements [`Client`] for a `fetch` request in the browser.
     pub struct BrowserClient;
@@ -106,23 +158,27 @@ pub mod browser {
                 let req = req.0.take();
                 let RequestInner {
                     request,
-                    mut abort_ctrl,
+                    abort_ctrl,
                 } = req;
-                let res = request
+                if let Some(token) = crate::csrf::csrf_token() {
+                    request.headers().set(crate::csrf::CSRF_HEADER, &token);
+                }
+                // if this future is dropped before the response arrives, `abort_ctrl` is dropped
+                // and aborts the request; afterwards, it is owned by the response, so that reading
+                // the body can be aborted in the same way
+                request
                     .send()
                     .await
-                    .map(|res| BrowserResponse(SendWrapper::new(res)))
+                    .map(|res| {
+                        BrowserResponse(
+                            SendWrapper::new(res),
+                            SendWrapper::new(abort_ctrl),
+                        )
+                    })
                     .map_err(|e| {
                         ServerFnErrorErr::Request(e.to_string())
                             .into_app_error()
-                    });
-
-                // at this point, the future has successfully resolved without being dropped, so we
-                // can prevent the `AbortController` from firing
-                if let Some(ctrl) = abort_ctrl.as_mut() {
-                    ctrl.prevent_cancellation();
-                }
-                res
+                    })
             })
         }
 
@@ -236,10 +292,128 @@ pub mod browser {
             })
         }
 
+        fn open_event_stream(
+            req: Self::Request,
+        ) -> impl Future<
+            Output = Result<
+                impl Stream<Item = Result<RawEvent, Bytes>> + Send + 'static,
+                Error,
+            >,
+        > + Send {
+            SendWrapper::new(async move {
+                let RequestInner { request, .. } = req.0.take();
+                if request.method() != Method::GET {
+                    return Err(Error::from_server_fn_error(
+                        ServerFnErrorErr::Request(
+                            "server-sent events can only be requested with \
+                             GET in the browser"
+                                .into(),
+                        ),
+                    ));
+                }
+                EventSourceStream::open::<OutputStreamError>(&request.url())
+                    .map_err(|err| {
+                        Error::from_server_fn_error(ServerFnErrorErr::Request(
+                            format!("{err:?}"),
+                        ))
+                    })
+            })
+        }
+
         fn spawn(future: impl Future<Output = ()> + Send + 'static) {
             wasm_bindgen_futures::spawn_local(future);
         }
     }
+
+    /// The events received by an `EventSource`, which is closed when the stream is dropped.
+    struct EventSourceStream {
+        events: UnboundedReceiver<Result<RawEvent, Bytes>>,
+        _source: SendWrapper<EventSourceHandle>,
+    }
+
+    struct EventSourceHandle {
+        source: EventSource,
+        _on_message: Closure<dyn FnMut(MessageEvent)>,
+        _on_error: Closure<dyn FnMut(web_sys::Event)>,
+    }
+
+    impl Drop for EventSourceHandle {
+        fn drop(&mut self) {
+            self.source.close();
+        }
+    }
+
+    impl EventSourceStream {
+        fn open<E: FromServerFnError>(
+            url: &str,
+        ) -> Result<Self, wasm_bindgen::JsValue> {
+            let source = EventSource::new(url)?;
+            let (tx, events) = mpsc::unbounded();
+
+            let on_message = Closure::<dyn FnMut(MessageEvent)>::new({
+                let source = source.clone();
+                let tx = tx.clone();
+                move |event: MessageEvent| {
+                    let event = RawEvent {
+                        id: Some(event.last_event_id())
+                            .filter(|id| !id.is_empty()),
+                        event: event.type_(),
+                        data: event.data().as_string().unwrap_or_default(),
+                    };
+                    // otherwise, the browser would reconnect once the response ends
+                    if event.event == END_EVENT {
+                        source.close();
+                    }
+                    _ = tx.unbounded_send(Ok(event));
+                }
+            });
+            source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+            for event in [ERROR_EVENT, END_EVENT] {
+                source.add_event_listener_with_callback(
+                    event,
+                    on_message.as_ref().unchecked_ref(),
+                )?;
+            }
+
+            // the browser reconnects after most errors, and only gives up if the server responds
+            // with an error status or the wrong content type
+            let on_error = Closure::<dyn FnMut(web_sys::Event)>::new({
+                let source = source.clone();
+                move |_| {
+                    if source.ready_state() == EventSource::CLOSED {
+                        _ = tx.unbounded_send(Err(E::from_server_fn_error(
+                            ServerFnErrorErr::Request(
+                                "the event stream was closed".into(),
+                            ),
+                        )
+                        .ser()));
+                        tx.close_channel();
+                    }
+                }
+            });
+            source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+            Ok(Self {
+                events,
+                _source: SendWrapper::new(EventSourceHandle {
+                    source,
+                    _on_message: on_message,
+                    _on_error: on_error,
+                }),
+            })
+        }
+    }
+
+    impl Stream for EventSourceStream {
+        type Item = Result<RawEvent, Bytes>;
+
+        fn poll_next(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Self::Item>> {
+            self.events.poll_next_unpin(cx)
+        }
+    }
 }
 
 #[cfg(feature = "reqwest")]
@@ -268,9 +442,14 @@ pub mod reqwest {
         type Response = Response;
 
         fn send(
-            req: Self::Request,
+            mut req: Self::Request,
         ) -> impl Future<Output = Result<Self::Response, Error>> + Send
         {
+            if let Some(token) = crate::csrf::csrf_token() {
+                if let Ok(token) = token.parse() {
+                    req.headers_mut().insert(crate::csrf::CSRF_HEADER, token);
+                }
+            }
             CLIENT.execute(req).map_err(|e| {
                 ServerFnErrorErr::Request(e.to_string()).into_app_error()
             })
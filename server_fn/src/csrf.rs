@@ -0,0 +1,895 @@
+//! Server functions are called with ordinary `POST` requests, so a page on another site can
+//! submit a form to one and have the browser attach the user's cookies. To guard against this,
+//! call [`protect_server_fns`] when starting the server. Every server function call is then
+//! checked in two ways:
+//!
+//! 1. **Origin**: the `Sec-Fetch-Site` header (or the `Origin` header, for browsers that do not
+//!    send it) must show that the request came from the same origin, or from one of the
+//!    [allowed origins](Csrf::allow_origin).
+//! 2. **Token**: the request must include a token, in the [`CSRF_HEADER`] header or the
+//!    [`CSRF_FIELD`] form field, that matches the one issued to this browser.
+//!
+//! Tokens can be checked in one of two [modes](CsrfMode): the double-submit cookie pattern,
+//! which compares the token to a cookie and needs no server-side state, or the synchronizer
+//! token pattern, which compares it to the token stored for the user's session.
+//!
+//! The server integrations issue a token while rendering each page. The browser client sends it
+//! with every server function call, and `ActionForm` and `Form` include it as a hidden input,
+//! so forms keep working without JavaScript.
+//!
+//! ```rust,ignore
+//! use server_fn::csrf::{protect_server_fns, Csrf};
+//!
+//! protect_server_fns(
+//!     Csrf::double_submit_cookie().allow_origin("https://admin.example.com"),
+//! );
+//! ```
+
+use or_poisoned::OrPoisoned;
+use std::sync::{Arc, RwLock};
+
+/// The header the browser client sends the token in.
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+/// The name of the hidden form field that forms send the token in.
+pub const CSRF_FIELD: &str = "csrf_token";
+
+/// The name of the cookie the token is stored in, which is readable from JavaScript.
+pub const CSRF_COOKIE: &str = "csrf_token";
+
+/// The name of the cookie that identifies the session a token was issued to, when using
+/// [`CsrfMode::SynchronizerToken`].
+pub const CSRF_SESSION_COOKIE: &str = "csrf_session";
+
+static CLIENT_TOKEN: RwLock<Option<Arc<str>>> = RwLock::new(None);
+
+/// Sets the token that the client sends with each server function call, if the browser does not
+/// have a [`CSRF_COOKIE`] cookie.
+///
+/// In the browser, this is set with the token rendered on the server when a page is hydrated.
+/// It is also needed when calling server functions from outside the browser.
+pub fn set_csrf_token(token: impl Into<Arc<str>>) {
+    *CLIENT_TOKEN.write().or_poisoned() = Some(token.into());
+}
+
+/// Returns the token that the client sends with each server function call.
+///
+/// In the browser, this is the value of the [`CSRF_COOKIE`] cookie if there is one. The cookie
+/// is preferred over the token set with [`set_csrf_token`], because a page that was cached or
+/// generated ahead of time may have been rendered with a token issued to another browser.
+pub fn csrf_token() -> Option<Arc<str>> {
+    #[cfg(all(feature = "browser", target_family = "wasm"))]
+    if let Some(token) = cookie_token() {
+        return Some(token);
+    }
+    CLIENT_TOKEN.read().or_poisoned().clone()
+}
+
+/// Reads the token from the [`CSRF_COOKIE`] cookie in the browser.
+#[cfg(all(feature = "browser", target_family = "wasm"))]
+fn cookie_token() -> Option<Arc<str>> {
+    use wasm_bindgen::JsCast;
+
+    let cookies = web_sys::window()?
+        .document()?
+        .dyn_into::<web_sys::HtmlDocument>()
+        .ok()?
+        .cookie()
+        .ok()?;
+    cookie(&cookies, CSRF_COOKIE).map(Arc::from)
+}
+
+/// Finds the value of a cookie in a `Cookie` header.
+#[cfg(any(feature = "ssr", all(feature = "browser", target_family = "wasm")))]
+fn cookie<'a>(cookies: &'a str, name: &str) -> Option<&'a str> {
+    cookies.split(';').find_map(|cookie| {
+        let (key, value) = cookie.trim().split_once('=')?;
+        (key == name).then_some(value)
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub use server::*;
+
+#[cfg(feature = "ssr")]
+mod server {
+    use super::{cookie, CSRF_COOKIE, CSRF_HEADER, CSRF_SESSION_COOKIE};
+    use or_poisoned::OrPoisoned;
+    use std::{
+        collections::HashMap,
+        fmt::{self, Debug},
+        sync::{Arc, Mutex, OnceLock},
+        time::{Duration, Instant},
+    };
+    use thiserror::Error;
+
+    static PROTECTION: OnceLock<Csrf> = OnceLock::new();
+
+    /// Protects every server function against cross-site request forgery, using the given
+    /// configuration.
+    ///
+    /// This should be called once, before the server starts handling requests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if protection has already been set.
+    pub fn protect_server_fns(csrf: Csrf) {
+        PROTECTION.set(csrf).unwrap();
+    }
+
+    /// Returns the protection set with [`protect_server_fns`], if any.
+    pub fn csrf_protection() -> Option<&'static Csrf> {
+        PROTECTION.get()
+    }
+
+    /// How the token sent with a request is checked.
+    #[derive(Clone)]
+    pub enum CsrfMode {
+        /// The token is compared to the [`CSRF_COOKIE`] cookie. Another site cannot read this
+        /// cookie, so it cannot send a matching token.
+        DoubleSubmitCookie,
+        /// The token is compared to the one stored for the session in the
+        /// [`CSRF_SESSION_COOKIE`] cookie.
+        SynchronizerToken(Arc<dyn CsrfTokenStore>),
+    }
+
+    impl Debug for CsrfMode {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::DoubleSubmitCookie => f.write_str("DoubleSubmitCookie"),
+                Self::SynchronizerToken(_) => f.write_str("SynchronizerToken"),
+            }
+        }
+    }
+
+    /// Stores the token issued to each session, for [`CsrfMode::SynchronizerToken`].
+    pub trait CsrfTokenStore: Send + Sync + 'static {
+        /// Returns the token issued to the session, if any.
+        fn get(&self, session: &str) -> Option<String>;
+
+        /// Stores the token issued to the session.
+        fn insert(&self, session: String, token: String);
+    }
+
+    /// A [`CsrfTokenStore`] that keeps tokens in memory.
+    ///
+    /// Tokens are lost when the server restarts, and are not shared between servers. To keep
+    /// memory use bounded, tokens expire after a [time to live](Self::ttl), and once the store
+    /// holds [`max_sessions`](Self::max_sessions) tokens, the oldest is evicted to make room for
+    /// each new one.
+    #[derive(Debug)]
+    pub struct MemoryTokenStore {
+        tokens: Mutex<HashMap<String, (String, Instant)>>,
+        ttl: Duration,
+        max_sessions: usize,
+    }
+
+    impl Default for MemoryTokenStore {
+        fn default() -> Self {
+            Self {
+                tokens: Default::default(),
+                ttl: Duration::from_secs(24 * 60 * 60),
+                max_sessions: 100_000,
+            }
+        }
+    }
+
+    impl MemoryTokenStore {
+        /// Creates an empty store, whose tokens expire after 24 hours and which holds up to
+        /// 100,000 tokens.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// How long a token is valid for after it has been issued.
+        pub fn ttl(mut self, ttl: Duration) -> Self {
+            self.ttl = ttl;
+            self
+        }
+
+        /// The maximum number of tokens the store holds.
+        pub fn max_sessions(mut self, max_sessions: usize) -> Self {
+            self.max_sessions = max_sessions.max(1);
+            self
+        }
+    }
+
+    impl CsrfTokenStore for MemoryTokenStore {
+        fn get(&self, session: &str) -> Option<String> {
+            self.tokens
+                .lock()
+                .or_poisoned()
+                .get(session)
+                .filter(|(_, issued_at)| issued_at.elapsed() < self.ttl)
+                .map(|(token, _)| token.clone())
+        }
+
+        fn insert(&self, session: String, token: String) {
+            let mut tokens = self.tokens.lock().or_poisoned();
+            tokens.retain(|_, (_, issued_at)| issued_at.elapsed() < self.ttl);
+            if tokens.len() >= self.max_sessions {
+                let oldest = tokens
+                    .iter()
+                    .min_by_key(|(_, (_, issued_at))| *issued_at)
+                    .map(|(session, _)| session.clone());
+                if let Some(oldest) = oldest {
+                    tokens.remove(&oldest);
+                }
+            }
+            tokens.insert(session, (token, Instant::now()));
+        }
+    }
+
+    /// An error returned when a request fails a CSRF check.
+    #[derive(Error, Debug, Clone, PartialEq, Eq)]
+    pub enum CsrfError {
+        /// The request came from an origin that is not allowed.
+        #[error("cross-origin request from {0} was rejected")]
+        CrossOrigin(String),
+        /// The request did not include a token.
+        #[error("missing CSRF token")]
+        MissingToken,
+        /// The request included a token that does not match the one issued.
+        #[error("invalid CSRF token")]
+        InvalidToken,
+    }
+
+    /// A token issued by [`Csrf::issue`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct IssuedToken {
+        /// The token, which should be included in the page.
+        pub token: String,
+        /// The values of any `Set-Cookie` headers that should be added to the response.
+        pub set_cookies: Vec<String>,
+    }
+
+    /// Configuration for protecting server functions against cross-site request forgery.
+    #[derive(Debug, Clone)]
+    pub struct Csrf {
+        mode: CsrfMode,
+        allowed_origins: Vec<String>,
+        secure: bool,
+        max_form_size: usize,
+    }
+
+    impl Csrf {
+        /// Checks tokens using the [double-submit cookie](CsrfMode::DoubleSubmitCookie)
+        /// pattern.
+        pub fn double_submit_cookie() -> Self {
+            Self::new(CsrfMode::DoubleSubmitCookie)
+        }
+
+        /// Checks tokens using the [synchronizer token](CsrfMode::SynchronizerToken) pattern,
+        /// storing them in the given store.
+        pub fn synchronizer_token(store: impl CsrfTokenStore) -> Self {
+            Self::new(CsrfMode::SynchronizerToken(Arc::new(store)))
+        }
+
+        fn new(mode: CsrfMode) -> Self {
+            Self {
+                mode,
+                allowed_origins: Vec::new(),
+                secure: true,
+                max_form_size: 2 * 1024 * 1024,
+            }
+        }
+
+        /// Allows requests from another origin, like `https://admin.example.com`. These
+        /// requests must still include a valid token.
+        pub fn allow_origin(mut self, origin: impl Into<String>) -> Self {
+            self.allowed_origins.push(origin.into());
+            self
+        }
+
+        /// Whether cookies are only sent over HTTPS. Defaults to `true`.
+        pub fn secure(mut self, secure: bool) -> Self {
+            self.secure = secure;
+            self
+        }
+
+        /// The largest URL-encoded form that is read to find the token in, in bytes. Forms that
+        /// are larger than this are rejected with `413 Payload Too Large`. Defaults to 2 MiB.
+        pub fn max_form_size(mut self, max_form_size: usize) -> Self {
+            self.max_form_size = max_form_size;
+            self
+        }
+
+        /// The mode tokens are checked in.
+        pub fn mode(&self) -> &CsrfMode {
+            &self.mode
+        }
+
+        /// Returns the token for the browser that sent the given `Cookie` header, issuing a new
+        /// one if it does not have one yet.
+        pub fn issue(&self, cookies: Option<&str>) -> IssuedToken {
+            if let Some(token) = self.expected_token(cookies) {
+                return IssuedToken {
+                    token,
+                    set_cookies: Vec::new(),
+                };
+            }
+            let token = new_token();
+            let set_cookies = match &self.mode {
+                CsrfMode::DoubleSubmitCookie => {
+                    vec![self.set_cookie(CSRF_COOKIE, &token, false)]
+                }
+                CsrfMode::SynchronizerToken(store) => {
+                    let session = new_token();
+                    store.insert(session.clone(), token.clone());
+                    vec![
+                        self.set_cookie(CSRF_SESSION_COOKIE, &session, true),
+                        self.set_cookie(CSRF_COOKIE, &token, false),
+                    ]
+                }
+            };
+            IssuedToken { token, set_cookies }
+        }
+
+        /// Checks a request, given its method, a function that returns the value of one of
+        /// its headers, and the token submitted in the [`CSRF_FIELD`](super::CSRF_FIELD) form
+        /// field, if any.
+        ///
+        /// Requests with a safe method, like `GET`, are always allowed.
+        pub fn verify(
+            &self,
+            method: &str,
+            header: impl Fn(&str) -> Option<String>,
+            form_token: Option<&str>,
+        ) -> Result<(), CsrfError> {
+            if is_safe(method) {
+                return Ok(());
+            }
+            self.verify_origin(&header)?;
+            let token = header(CSRF_HEADER)
+                .or_else(|| form_token.map(ToOwned::to_owned))
+                .ok_or(CsrfError::MissingToken)?;
+            let expected = self
+                .expected_token(header("cookie").as_deref())
+                .ok_or(CsrfError::InvalidToken)?;
+            if constant_time_eq(token.as_bytes(), expected.as_bytes()) {
+                Ok(())
+            } else {
+                Err(CsrfError::InvalidToken)
+            }
+        }
+
+        fn verify_origin(
+            &self,
+            header: impl Fn(&str) -> Option<String>,
+        ) -> Result<(), CsrfError> {
+            let origin = header("origin");
+            let allowed = |origin: &str| {
+                self.allowed_origins.iter().any(|allowed| allowed == origin)
+            };
+            match header("sec-fetch-site").as_deref() {
+                Some("same-origin" | "none") => Ok(()),
+                // `same-site` requests come from another subdomain, which may not be trusted
+                Some(_) => match origin {
+                    Some(origin) if allowed(&origin) => Ok(()),
+                    origin => Err(CsrfError::CrossOrigin(
+                        origin.unwrap_or_else(|| "another site".to_string()),
+                    )),
+                },
+                // older browsers do not send `Sec-Fetch-Site`, so fall back to the `Origin`
+                None => match origin {
+                    None => Ok(()),
+                    Some(origin) if allowed(&origin) => Ok(()),
+                    Some(origin) => {
+                        let origin_host =
+                            origin.split_once("://").map(|(_, host)| host);
+                        if origin_host.is_some()
+                            && origin_host == header("host").as_deref()
+                        {
+                            Ok(())
+                        } else {
+                            Err(CsrfError::CrossOrigin(origin))
+                        }
+                    }
+                },
+            }
+        }
+
+        /// The token that a request with the given `Cookie` header should include.
+        fn expected_token(&self, cookies: Option<&str>) -> Option<String> {
+            let cookies = cookies?;
+            match &self.mode {
+                CsrfMode::DoubleSubmitCookie => {
+                    cookie(cookies, CSRF_COOKIE).map(ToOwned::to_owned)
+                }
+                CsrfMode::SynchronizerToken(store) => {
+                    store.get(cookie(cookies, CSRF_SESSION_COOKIE)?)
+                }
+            }
+        }
+
+        fn set_cookie(
+            &self,
+            name: &str,
+            value: &str,
+            http_only: bool,
+        ) -> String {
+            let mut cookie = format!("{name}={value}; Path=/; SameSite=Lax");
+            if http_only {
+                cookie.push_str("; HttpOnly");
+            }
+            if self.secure {
+                cookie.push_str("; Secure");
+            }
+            cookie
+        }
+    }
+
+    fn is_safe(method: &str) -> bool {
+        matches!(method, "GET" | "HEAD" | "OPTIONS" | "TRACE")
+    }
+
+    /// Generates a new token from 32 bytes (256 bits) of random data.
+    fn new_token() -> String {
+        use base64::{
+            alphabet,
+            engine::{self, general_purpose},
+            Engine as _,
+        };
+        use rand::{rng, RngCore as _};
+        const TOKEN_ENGINE: engine::GeneralPurpose =
+            engine::GeneralPurpose::new(
+                &alphabet::URL_SAFE,
+                general_purpose::NO_PAD,
+            );
+
+        let mut bytes = [0; 32];
+        rng().fill_bytes(&mut bytes);
+        TOKEN_ENGINE.encode(bytes)
+    }
+
+    fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+        a.len() == b.len()
+            && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+    }
+
+    /// Whether a request without a token in its header might have one in its body.
+    #[cfg(any(feature = "axum-no-default", feature = "actix-no-default"))]
+    fn may_have_form_token(header: impl Fn(&str) -> Option<String>) -> bool {
+        header(CSRF_HEADER).is_none()
+            && header("content-type").is_some_and(|content_type| {
+                content_type.starts_with("application/x-www-form-urlencoded")
+            })
+    }
+
+    /// Finds the token in a URL-encoded form body.
+    #[cfg(any(feature = "axum-no-default", feature = "actix-no-default"))]
+    fn form_token(body: &[u8]) -> Option<String> {
+        url::form_urlencoded::parse(body)
+            .find(|(key, _)| key == super::CSRF_FIELD)
+            .map(|(_, value)| value.into_owned())
+    }
+
+    #[cfg(feature = "axum-no-default")]
+    mod axum {
+        use super::{form_token, may_have_form_token, Csrf};
+        use crate::{
+            error::ServerFnErrorErr,
+            middleware::{BoxedService, Layer, Service},
+            response::Res,
+        };
+        use axum::body::Body;
+        use bytes::Bytes;
+        use http::{Request, Response, StatusCode};
+        use http_body_util::LengthLimitError;
+        use or_poisoned::OrPoisoned;
+        use std::{
+            future::Future,
+            pin::Pin,
+            sync::{Arc, Mutex},
+        };
+
+        impl Layer<Request<Body>, Response<Body>> for Csrf {
+            fn layer(
+                &self,
+                inner: BoxedService<Request<Body>, Response<Body>>,
+            ) -> BoxedService<Request<Body>, Response<Body>> {
+                BoxedService::new(
+                    inner.ser,
+                    CsrfService {
+                        csrf: self.clone(),
+                        inner: Arc::new(Mutex::new(inner)),
+                    },
+                )
+            }
+        }
+
+        struct CsrfService {
+            csrf: Csrf,
+            inner: Arc<Mutex<BoxedService<Request<Body>, Response<Body>>>>,
+        }
+
+        fn header(req: &Request<Body>, name: &str) -> Option<String> {
+            match req.headers().get(name) {
+                Some(value) => value.to_str().ok().map(ToOwned::to_owned),
+                // HTTP/2 requests carry the host in the URI instead
+                None if name == "host" => {
+                    req.uri().authority().map(ToString::to_string)
+                }
+                None => None,
+            }
+        }
+
+        impl Service<Request<Body>, Response<Body>> for CsrfService {
+            fn run(
+                &mut self,
+                req: Request<Body>,
+                ser: fn(ServerFnErrorErr) -> Bytes,
+            ) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>>
+            {
+                let csrf = self.csrf.clone();
+                let inner = Arc::clone(&self.inner);
+                Box::pin(async move {
+                    let path = req.uri().path().to_string();
+                    let method = req.method().to_string();
+                    let req = if may_have_form_token(|name| header(&req, name))
+                    {
+                        let (parts, body) = req.into_parts();
+                        match axum::body::to_bytes(body, csrf.max_form_size)
+                            .await
+                        {
+                            Ok(body) => {
+                                let req = Request::from_parts(
+                                    parts,
+                                    Body::from(body.clone()),
+                                );
+                                csrf.verify(
+                                    &method,
+                                    |name| header(&req, name),
+                                    form_token(&body).as_deref(),
+                                )
+                                .map(|()| req)
+                                .map_err(|e| {
+                                    (StatusCode::FORBIDDEN, e.to_string())
+                                })
+                            }
+                            Err(e) => {
+                                let e = e.into_inner();
+                                let status = if e.is::<LengthLimitError>() {
+                                    StatusCode::PAYLOAD_TOO_LARGE
+                                } else {
+                                    StatusCode::BAD_REQUEST
+                                };
+                                Err((
+                                    status,
+                                    format!(
+                                        "could not read the request body: {e}"
+                                    ),
+                                ))
+                            }
+                        }
+                    } else {
+                        csrf.verify(&method, |name| header(&req, name), None)
+                            .map(|()| req)
+                            .map_err(|e| (StatusCode::FORBIDDEN, e.to_string()))
+                    };
+                    match req {
+                        Ok(req) => {
+                            let res = inner.lock().or_poisoned().run(req);
+                            res.await
+                        }
+                        Err((status, message)) => {
+                            let err =
+                                ser(ServerFnErrorErr::MiddlewareError(message));
+                            let mut res =
+                                Response::<Body>::error_response(&path, err);
+                            *res.status_mut() = status;
+                            res
+                        }
+                    }
+                })
+            }
+        }
+    }
+
+    #[cfg(feature = "actix-no-default")]
+    mod actix {
+        use super::{form_token, may_have_form_token, Csrf};
+        use crate::{
+            error::ServerFnErrorErr,
+            middleware::{BoxedService, Layer, Service},
+            request::actix::ActixRequest,
+            response::{actix::ActixResponse, Res},
+        };
+        use actix_web::{http::StatusCode, HttpRequest};
+        use bytes::Bytes;
+        use or_poisoned::OrPoisoned;
+        use send_wrapper::SendWrapper;
+        use std::{
+            future::Future,
+            pin::Pin,
+            sync::{Arc, Mutex},
+        };
+
+        impl Layer<ActixRequest, ActixResponse> for Csrf {
+            fn layer(
+                &self,
+                inner: BoxedService<ActixRequest, ActixResponse>,
+            ) -> BoxedService<ActixRequest, ActixResponse> {
+                BoxedService::new(
+                    inner.ser,
+                    CsrfService {
+                        csrf: self.clone(),
+                        inner: Arc::new(Mutex::new(inner)),
+                    },
+                )
+            }
+        }
+
+        struct CsrfService {
+            csrf: Csrf,
+            inner: Arc<Mutex<BoxedService<ActixRequest, ActixResponse>>>,
+        }
+
+        fn header(req: &HttpRequest, name: &str) -> Option<String> {
+            match req.headers().get(name) {
+                Some(value) => value.to_str().ok().map(ToOwned::to_owned),
+                None if name == "host" => {
+                    Some(req.connection_info().host().to_string())
+                }
+                None => None,
+            }
+        }
+
+        impl Service<ActixRequest, ActixResponse> for CsrfService {
+            fn run(
+                &mut self,
+                req: ActixRequest,
+                ser: fn(ServerFnErrorErr) -> Bytes,
+            ) -> Pin<Box<dyn Future<Output = ActixResponse> + Send>>
+            {
+                let csrf = self.csrf.clone();
+                let inner = Arc::clone(&self.inner);
+                Box::pin(SendWrapper::new(async move {
+                    let http_req = req.0 .0.clone();
+                    let path = http_req.uri().path().to_string();
+                    let method = http_req.method().to_string();
+                    // the body of a call in a batch is not a form, so only the header is checked
+                    let req = if req.1.is_none()
+                        && may_have_form_token(|name| header(&http_req, name))
+                    {
+                        let (http_req, payload) = req.take();
+                        match payload.to_bytes_limited(csrf.max_form_size).await
+                        {
+                            Ok(Ok(body)) => csrf
+                                .verify(
+                                    &method,
+                                    |name| header(&http_req, name),
+                                    form_token(&body).as_deref(),
+                                )
+                                .map(|()| {
+                                    ActixRequest::with_body(http_req, body)
+                                })
+                                .map_err(|e| {
+                                    (StatusCode::FORBIDDEN, e.to_string())
+                                }),
+                            Ok(Err(e)) => Err((
+                                StatusCode::BAD_REQUEST,
+                                format!("could not read the request body: {e}"),
+                            )),
+                            Err(e) => Err((
+                                StatusCode::PAYLOAD_TOO_LARGE,
+                                format!("could not read the request body: {e}"),
+                            )),
+                        }
+                    } else {
+                        csrf.verify(
+                            &method,
+                            |name| header(&http_req, name),
+                            None,
+                        )
+                        .map(|()| req)
+                        .map_err(|e| (StatusCode::FORBIDDEN, e.to_string()))
+                    };
+                    match req {
+                        Ok(req) => {
+                            let res = inner.lock().or_poisoned().run(req);
+                            res.await
+                        }
+                        Err((status, message)) => {
+                            let err =
+                                ser(ServerFnErrorErr::MiddlewareError(message));
+                            let mut res =
+                                ActixResponse::error_response(&path, err)
+                                    .take();
+                            *res.status_mut() = status;
+                            ActixResponse::from(res)
+                        }
+                    }
+                }))
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{
+        Csrf, CsrfError, CsrfTokenStore, MemoryTokenStore, CSRF_HEADER,
+    };
+    use std::time::Duration;
+
+    fn headers<'a>(
+        headers: &'a [(&'a str, &'a str)],
+    ) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            headers
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn double_submit_cookie_compares_token_to_cookie() {
+        let csrf = Csrf::double_submit_cookie();
+        let issued = csrf.issue(None);
+        assert_eq!(issued.set_cookies.len(), 1);
+        let cookie = format!("csrf_token={}", issued.token);
+        assert_eq!(csrf.issue(Some(&cookie)).token, issued.token);
+
+        assert_eq!(
+            csrf.verify(
+                "POST",
+                headers(&[
+                    ("cookie", &cookie),
+                    ("sec-fetch-site", "same-origin"),
+                    (CSRF_HEADER, &issued.token),
+                ]),
+                None,
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            csrf.verify(
+                "POST",
+                headers(&[("cookie", &cookie)]),
+                Some(&issued.token)
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            csrf.verify("POST", headers(&[("cookie", &cookie)]), Some("nope")),
+            Err(CsrfError::InvalidToken)
+        );
+        assert_eq!(
+            csrf.verify("POST", headers(&[("cookie", &cookie)]), None),
+            Err(CsrfError::MissingToken)
+        );
+        assert_eq!(csrf.verify("GET", headers(&[]), None), Ok(()));
+    }
+
+    #[test]
+    fn synchronizer_token_compares_token_to_session() {
+        let csrf = Csrf::synchronizer_token(MemoryTokenStore::new());
+        let issued = csrf.issue(None);
+        let session =
+            issued.set_cookies[0].split(';').next().unwrap().to_string();
+        assert!(session.starts_with("csrf_session="));
+        assert!(issued.set_cookies[0].contains("HttpOnly"));
+
+        // the readable cookie is ignored, so a token set by another site does not match
+        let forged = format!("{session}; csrf_token=forged");
+        assert_eq!(
+            csrf.verify(
+                "POST",
+                headers(&[("cookie", &forged)]),
+                Some("forged")
+            ),
+            Err(CsrfError::InvalidToken)
+        );
+        assert_eq!(
+            csrf.verify(
+                "POST",
+                headers(&[("cookie", &session)]),
+                Some(&issued.token)
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn cross_origin_requests_are_rejected() {
+        let csrf = Csrf::double_submit_cookie()
+            .allow_origin("https://admin.example.com");
+        let issued = csrf.issue(None);
+        let cookie = format!("csrf_token={}", issued.token);
+        let verify = |extra: &[(&str, &str)]| {
+            let mut all = vec![("cookie", cookie.as_str())];
+            all.extend_from_slice(extra);
+            csrf.verify("POST", headers(&all), Some(&issued.token))
+        };
+
+        assert_eq!(
+            verify(&[
+                ("sec-fetch-site", "cross-site"),
+                ("origin", "https://evil.example"),
+            ]),
+            Err(CsrfError::CrossOrigin("https://evil.example".into()))
+        );
+        assert_eq!(
+            verify(&[
+                ("sec-fetch-site", "same-site"),
+                ("origin", "https://admin.example.com"),
+            ]),
+            Ok(())
+        );
+        assert_eq!(
+            verify(&[
+                ("origin", "https://example.com"),
+                ("host", "example.com"),
+            ]),
+            Ok(())
+        );
+        assert_eq!(
+            verify(&[("origin", "null"), ("host", "example.com")]),
+            Err(CsrfError::CrossOrigin("null".into()))
+        );
+    }
+
+    #[test]
+    fn memory_token_store_is_bounded() {
+        let store = MemoryTokenStore::new().max_sessions(2);
+        store.insert("a".into(), "1".into());
+        store.insert("b".into(), "2".into());
+        store.insert("c".into(), "3".into());
+        assert_eq!(store.get("a"), None);
+        assert_eq!(store.get("b").as_deref(), Some("2"));
+        assert_eq!(store.get("c").as_deref(), Some("3"));
+
+        let store = MemoryTokenStore::new().ttl(Duration::ZERO);
+        store.insert("a".into(), "1".into());
+        assert_eq!(store.get("a"), None);
+    }
+
+    #[cfg(feature = "axum-no-default")]
+    #[test]
+    fn forms_are_read_up_to_the_size_limit() {
+        use crate::middleware::{BoxedService, Layer, Service};
+        use axum::body::Body;
+        use bytes::Bytes;
+        use http::{Request, Response, StatusCode};
+        use std::{future::Future, pin::Pin};
+
+        struct Respond;
+
+        impl Service<Request<Body>, Response<Body>> for Respond {
+            fn run(
+                &mut self,
+                _req: Request<Body>,
+                _ser: fn(crate::error::ServerFnErrorErr) -> Bytes,
+            ) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>>
+            {
+                Box::pin(async { Response::new(Body::empty()) })
+            }
+        }
+
+        let csrf = Csrf::double_submit_cookie().max_form_size(64);
+        let issued = csrf.issue(None);
+        let mut service = csrf
+            .layer(BoxedService::new(|e| Bytes::from(e.to_string()), Respond));
+        let mut call = |body: String| {
+            let req = Request::post("/api/add")
+                .header("content-type", "application/x-www-form-urlencoded")
+                .header("sec-fetch-site", "same-origin")
+                .header("cookie", format!("csrf_token={}", issued.token))
+                .body(Body::from(body))
+                .unwrap();
+            futures::executor::block_on(service.run(req)).status()
+        };
+
+        assert_eq!(
+            call(format!("csrf_token={}", issued.token)),
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(format!("csrf_token={}&a={}", issued.token, "a".repeat(64))),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+        assert_eq!(call("csrf_token=nope".into()), StatusCode::FORBIDDEN);
+    }
+}
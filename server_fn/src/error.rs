@@ -1,6 +1,8 @@
 #![allow(deprecated)]
 
-use crate::{ContentType, Decodes, Encodes, Format, FormatType};
+use crate::{
+    validate::FieldErrors, ContentType, Decodes, Encodes, Format, FormatType,
+};
 use base64::{engine::general_purpose::URL_SAFE, Engine as _};
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
@@ -194,6 +196,8 @@ pub enum ServerFnError<E = NoCustomError> {
     Args(String),
     /// Occurs on the server if there's a missing argument.
     MissingArg(String),
+    /// Occurs if one or more of the arguments are invalid, with the errors for each field.
+    Validation(FieldErrors),
 }
 
 impl ServerFnError<NoCustomError> {
@@ -242,6 +246,8 @@ where
                     "error deserializing server function arguments: {s}"
                 ),
                 ServerFnError::MissingArg(s) => format!("missing argument {s}"),
+                ServerFnError::Validation(e) =>
+                    format!("invalid server function arguments: {e}"),
                 ServerFnError::Response(s) =>
                     format!("error generating HTTP response: {s}"),
                 ServerFnError::WrappedServerError(e) => format!("{e}"),
@@ -294,6 +300,9 @@ where
             ServerFnError::MissingArg(e) => {
                 write!(&mut buf, "MissingArg|{e}")
             }
+            ServerFnError::Validation(e) => serde_json::to_string(e)
+                .map_err(|_| fmt::Error)
+                .and_then(|e| write!(&mut buf, "Validation|{e}")),
         };
 
         match result {
@@ -342,6 +351,11 @@ where
                 }
                 "Args" => Ok(ServerFnError::Args(data.to_string())),
                 "MissingArg" => Ok(ServerFnError::MissingArg(data.to_string())),
+                "Validation" => serde_json::from_str(data)
+                    .map(ServerFnError::Validation)
+                    .map_err(|err| {
+                        format!("Failed to parse field errors: {err}")
+                    }),
                 _ => Err(format!("Unknown error type: {ty}")),
             })
     }
@@ -379,6 +393,16 @@ where
             ServerFnErrorErr::UnsupportedRequestMethod(value) => {
                 ServerFnError::Request(value)
             }
+            ServerFnErrorErr::Validation(value) => {
+                ServerFnError::Validation(value)
+            }
+        }
+    }
+
+    fn field_errors(&self) -> Option<&FieldErrors> {
+        match self {
+            ServerFnError::Validation(errors) => Some(errors),
+            _ => None,
         }
     }
 }
@@ -435,6 +459,9 @@ pub enum ServerFnErrorErr {
     /// Occurs on the server if there is an error creating an HTTP response.
     #[error("error creating response {0}")]
     Response(String),
+    /// Occurs if one or more of the arguments are invalid, with the errors for each field.
+    #[error("invalid server function arguments: {0}")]
+    Validation(FieldErrors),
 }
 
 /// Associates a particular server function error with the server function
@@ -568,6 +595,14 @@ pub trait FromServerFnError: std::fmt::Debug + Sized + 'static {
     /// Converts a [`ServerFnErrorErr`] into the application-specific custom error type.
     fn from_server_fn_error(value: ServerFnErrorErr) -> Self;
 
+    /// Returns the errors for each invalid field, if this error was caused by invalid arguments.
+    ///
+    /// This is used to show field-level errors in forms. Custom error types that store the
+    /// field errors from [`ServerFnErrorErr::Validation`] should return them here.
+    fn field_errors(&self) -> Option<&FieldErrors> {
+        None
+    }
+
     /// Serializes the custom error type to bytes, according to the encoding given by `Self::Encoding`.
     fn ser(&self) -> Bytes {
         Self::Encoder::encode(self).unwrap_or_else(|e| {
@@ -635,3 +670,15 @@ fn assert_from_server_fn_error_impl() {
 
     assert_impl::<ServerFnError>();
 }
+
+#[test]
+fn validation_error_round_trips() {
+    let mut errors = FieldErrors::new();
+    errors.add("user[name]", "This field is required.");
+    errors.add("user[name]", "Must be at least 3 characters.");
+    let error = ServerFnError::<NoCustomError>::Validation(errors.clone());
+
+    let decoded = ServerFnError::<NoCustomError>::de(error.ser());
+    assert_eq!(decoded, error);
+    assert_eq!(decoded.field_errors(), Some(&errors));
+}
@@ -105,12 +105,20 @@ pub mod client;
 /// Implementations of the server side of the server function call.
 pub mod server;
 
+/// Streams of server-sent events.
+pub mod sse;
+
+/// Batching server function calls that are made at the same time into a single request.
+pub mod batch;
+
 /// Encodings for arguments and results.
 pub mod codec;
 
 #[macro_use]
 /// Error types and utilities.
 pub mod error;
+/// Protection against cross-site request forgery (CSRF) for server functions.
+pub mod csrf;
 /// Types to add server middleware to a server function.
 pub mod middleware;
 /// Utilities to allow client-side redirects.
@@ -119,6 +127,8 @@ pub mod redirect;
 pub mod request;
 /// Types and traits for HTTP responses.
 pub mod response;
+/// Field-level validation of server function arguments.
+pub mod validate;
 
 #[cfg(feature = "actix-no-default")]
 #[doc(hidden)]
@@ -148,7 +158,7 @@ pub use const_str;
 pub use error::ServerFnError;
 #[cfg(feature = "form-redirects")]
 use error::ServerFnUrlError;
-use error::{FromServerFnError, ServerFnErrorErr};
+use error::{FromServerFnError, IntoAppError, ServerFnErrorErr};
 use futures::{pin_mut, SinkExt, Stream, StreamExt};
 use http::Method;
 use middleware::{BoxedService, Layer, Service};
@@ -172,6 +182,7 @@ use std::{
     pin::Pin,
     sync::{Arc, LazyLock, RwLock},
 };
+use validate::FieldErrors;
 #[doc(hidden)]
 pub use xxhash_rust;
 
@@ -286,6 +297,18 @@ pub trait ServerFn: Send + Sized {
         self,
     ) -> impl Future<Output = Result<Self::Output, Self::Error>> + Send;
 
+    /// Checks the arguments before the body of the server function runs.
+    ///
+    /// This is called on the server before [`ServerFn::run_body`], and can also be called on the
+    /// client before the request is sent. If it returns any errors, the body does not run, and
+    /// the errors are returned to the client as [`ServerFnErrorErr::Validation`].
+    ///
+    /// The `#[server]` macro implements this for any server function that has `#[validate(...)]`
+    /// attributes on its arguments.
+    fn validate(&self) -> Result<(), FieldErrors> {
+        Ok(())
+    }
+
     #[doc(hidden)]
     fn run_on_server(
         req: ServerFnServerRequest<Self>,
@@ -305,7 +328,7 @@ pub trait ServerFn: Send + Sized {
             #[allow(unused_variables, unused_mut)]
             // used in form redirects feature
             let (mut res, err) =
-                Self::Protocol::run_server(req, Self::run_body)
+                Self::Protocol::run_server(req, Self::validate_and_run_body)
                     .await
                     .map(|res| (res, None))
                     .unwrap_or_else(|e| {
@@ -354,6 +377,20 @@ pub trait ServerFn: Send + Sized {
     ) -> impl Future<Output = Result<Self::Output, Self::Error>> + Send {
         async move { Self::Protocol::run_client(Self::PATH, self).await }
     }
+
+    #[doc(hidden)]
+    fn validate_and_run_body(
+        self,
+    ) -> impl Future<Output = Result<Self::Output, Self::Error>> + Send {
+        async move {
+            if let Err(errors) = self.validate() {
+                return Err(
+                    ServerFnErrorErr::Validation(errors).into_app_error()
+                );
+            }
+            self.run_body().await
+        }
+    }
 }
 
 /// The protocol that a server function uses to communicate with the client. This trait handles
@@ -1003,13 +1040,15 @@ impl<Req: 'static, Res: 'static> inventory::Collect
 #[cfg(feature = "axum-no-default")]
 pub mod axum {
     use crate::{
-        error::FromServerFnError, middleware::BoxedService, LazyServerFnMap,
-        Protocol, Server, ServerFn, ServerFnTraitObj,
+        error::FromServerFnError,
+        middleware::BoxedService,
+        LazyServerFnMap, Protocol, Server, ServerFn, ServerFnTraitObj,
     };
     use axum::body::Body;
-    use http::{Method, Request, Response, StatusCode};
+    use futures::{FutureExt, StreamExt};
+    use http::{header::CONTENT_TYPE, Method, Request, Response, StatusCode};
     use or_poisoned::OrPoisoned;
-    use std::future::Future;
+    use std::{convert::Infallible, future::Future};
 
     static REGISTERED_SERVER_FUNCTIONS: LazyServerFnMap<
         Request<Body>,
@@ -1052,6 +1091,13 @@ pub mod axum {
                 ))
             }
         }
+
+        #[cfg(feature = "axum")]
+        fn sleep(
+            duration: std::time::Duration,
+        ) -> impl Future<Output = ()> + Send {
+            tokio::time::sleep(duration)
+        }
     }
 
     /// Explicitly register a server function. This is only necessary if you are
@@ -1088,7 +1134,17 @@ pub mod axum {
     }
 
     /// An Axum handler that responds to a server function request.
+    ///
+    /// Requests to the [batch path](crate::batch::batch_path) are handled with [`handle_batch`].
     pub async fn handle_server_fn(req: Request<Body>) -> Response<Body> {
+        if req.uri().path() == crate::batch::batch_path() {
+            handle_batch(req, run_server_fn).await
+        } else {
+            run_server_fn(req).await
+        }
+    }
+
+    async fn run_server_fn(req: Request<Body>) -> Response<Body> {
         let path = req.uri().path();
 
         if let Some(mut service) =
@@ -1112,6 +1168,63 @@ pub mod axum {
         }
     }
 
+    /// Responds to a batch of server function calls sent by a
+    /// [`BatchClient`](crate::batch::BatchClient).
+    ///
+    /// Each call is turned into a request with the same headers and extensions as the batch
+    /// request, and passed to `dispatch`, which should respond to it as it would to any other
+    /// server function request. The responses are streamed back as the body of a single response.
+    ///
+    /// Batches that exceed the [limits](crate::batch::BatchLimits) are rejected.
+    pub async fn handle_batch<F, Fut>(
+        req: Request<Body>,
+        mut dispatch: F,
+    ) -> Response<Body>
+    where
+        F: FnMut(Request<Body>) -> Fut + 'static,
+        Fut: Future<Output = Response<Body>> + Send + 'static,
+    {
+        let limits = crate::batch::batch_limits();
+        let (parts, body) = req.into_parts();
+        let calls = match axum::body::to_bytes(body, limits.max_body_size).await
+        {
+            Ok(body) => crate::batch::decode_calls(body, limits.max_calls)
+                .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string())),
+            Err(e) => {
+                let e = e.into_inner();
+                Err(if e.is::<http_body_util::LengthLimitError>() {
+                    (
+                        StatusCode::PAYLOAD_TOO_LARGE,
+                        format!(
+                            "a batch may be at most {} bytes",
+                            limits.max_body_size
+                        ),
+                    )
+                } else {
+                    (StatusCode::BAD_REQUEST, e.to_string())
+                })
+            }
+        };
+        let calls = match calls {
+            Ok(calls) => calls,
+            Err((status, message)) => {
+                return Response::builder()
+                    .status(status)
+                    .body(Body::from(message))
+                    .unwrap()
+            }
+        };
+
+        let responses = crate::batch::encode_responses(calls, move |call| {
+            dispatch(call.into_request(&parts).map(Body::from))
+                .map(|res| res.map(Body::into_data_stream))
+        });
+        Response::builder()
+            .header(CONTENT_TYPE, crate::batch::CONTENT_TYPE)
+            .body(Body::from_stream(responses.map(Ok::<_, Infallible>)))
+            .unwrap()
+    }
+
     /// Returns the server function at the given path as a service that can be modified.
     pub fn get_server_fn_service(
         path: &str,
@@ -1128,6 +1241,10 @@ pub mod axum {
                 for middleware in middleware {
                     service = middleware.layer(service);
                 }
+                // CSRF checks run before any other middleware
+                if let Some(csrf) = crate::csrf::csrf_protection() {
+                    service = crate::middleware::Layer::layer(csrf, service);
+                }
                 service
             })
     }
@@ -1137,16 +1254,28 @@ pub mod axum {
 #[cfg(feature = "actix-no-default")]
 pub mod actix {
     use crate::{
-        error::FromServerFnError, middleware::BoxedService,
-        request::actix::ActixRequest, response::actix::ActixResponse,
-        server::Server, LazyServerFnMap, Protocol, ServerFn, ServerFnTraitObj,
+        error::FromServerFnError,
+        middleware::BoxedService,
+        request::actix::ActixRequest,
+        response::actix::ActixResponse,
+        server::Server,
+        LazyServerFnMap, Protocol, ServerFn, ServerFnTraitObj,
+    };
+    use actix_web::{
+        body::MessageBody, web::Payload, HttpRequest, HttpResponse,
+    };
+    use bytes::Bytes;
+    use futures::{
+        future::{self, Either},
+        stream, FutureExt, Stream, StreamExt,
     };
-    use actix_web::{web::Payload, HttpRequest, HttpResponse};
     use http::Method;
     use or_poisoned::OrPoisoned;
     #[doc(hidden)]
     pub use send_wrapper::SendWrapper;
-    use std::future::Future;
+    use std::{
+        convert::Infallible, error::Error as StdError, future::Future, pin::Pin,
+    };
 
     static REGISTERED_SERVER_FUNCTIONS: LazyServerFnMap<
         ActixRequest,
@@ -1173,6 +1302,12 @@ pub mod actix {
             actix_web::rt::spawn(future);
             Ok(())
         }
+
+        fn sleep(
+            duration: std::time::Duration,
+        ) -> impl Future<Output = ()> + Send {
+            actix_web::rt::time::sleep(duration)
+        }
     }
 
     /// Explicitly register a server function. This is only necessary if you are
@@ -1209,11 +1344,20 @@ pub mod actix {
     }
 
     /// An Actix handler that responds to a server function request.
+    ///
+    /// Requests to the [batch path](crate::batch::batch_path) are handled with [`handle_batch`].
     pub async fn handle_server_fn(
         req: HttpRequest,
         payload: Payload,
     ) -> HttpResponse {
         let path = req.uri().path();
+        if path == crate::batch::batch_path() {
+            return handle_batch(req, payload, |_, req, mut service| async move {
+                service.run(req).await.0.take()
+            })
+            .await;
+        }
+
         let method = req.method();
         if let Some(mut service) = get_server_fn_service(path, method) {
             service
@@ -1222,17 +1366,98 @@ pub mod actix {
                 .0
                 .take()
         } else {
-            HttpResponse::BadRequest().body(format!(
-                "Could not find a server function at the route {path}. \
-                 \n\nIt's likely that either\n 1. The API prefix you specify \
-                 in the `#[server]` macro doesn't match the prefix at which \
-                 your server function handler is mounted, or \n2. You are on \
-                 a platform that doesn't support automatic server function \
-                 registration and you need to call \
-                 ServerFn::register_explicit() on the server function type, \
-                 somewhere in your `main` function.",
-            ))
+            not_found(path)
+        }
+    }
+
+    fn not_found(path: &str) -> HttpResponse {
+        HttpResponse::BadRequest().body(format!(
+            "Could not find a server function at the route {path}. \
+             \n\nIt's likely that either\n 1. The API prefix you specify in \
+             the `#[server]` macro doesn't match the prefix at which your \
+             server function handler is mounted, or \n2. You are on a \
+             platform that doesn't support automatic server function \
+             registration and you need to call \
+             ServerFn::register_explicit() on the server function type, \
+             somewhere in your `main` function.",
+        ))
+    }
+
+    /// Responds to a batch of server function calls sent by a
+    /// [`BatchClient`](crate::batch::BatchClient).
+    ///
+    /// For each call, `dispatch` is given the request for the whole batch, the request for the
+    /// call (see [`ActixRequest::from_batched_call`]), and the server function it should be passed
+    /// to. It should respond to the call as it would to any other server function request. The
+    /// responses are streamed back as the body of a single response.
+    ///
+    /// Batches that exceed the [limits](crate::batch::BatchLimits) are rejected.
+    pub async fn handle_batch<F, Fut>(
+        req: HttpRequest,
+        payload: Payload,
+        mut dispatch: F,
+    ) -> HttpResponse
+    where
+        F: FnMut(
+                HttpRequest,
+                ActixRequest,
+                BoxedService<ActixRequest, ActixResponse>,
+            ) -> Fut
+            + 'static,
+        Fut: Future<Output = HttpResponse> + 'static,
+    {
+        let limits = crate::batch::batch_limits();
+        let calls = match payload.to_bytes_limited(limits.max_body_size).await {
+            Ok(Ok(body)) => {
+                match crate::batch::decode_calls(body, limits.max_calls) {
+                    Ok(calls) => calls,
+                    Err(e) => {
+                        return HttpResponse::BadRequest().body(e.to_string())
+                    }
+                }
+            }
+            Ok(Err(e)) => {
+                return HttpResponse::BadRequest().body(e.to_string())
+            }
+            Err(_) => {
+                return HttpResponse::PayloadTooLarge().body(format!(
+                    "a batch may be at most {} bytes",
+                    limits.max_body_size
+                ))
+            }
+        };
+
+        let responses = crate::batch::encode_responses(calls, move |call| {
+            let service = server_fn_service(&call.path, call.method.clone());
+            let path = call.path.clone();
+            let res = match service {
+                Some(service) => Either::Left(dispatch(
+                    req.clone(),
+                    ActixRequest::from_batched_call(req.clone(), call),
+                    service,
+                )),
+                None => Either::Right(future::ready(not_found(&path))),
+            };
+            res.map(into_http_response)
+        });
+        HttpResponse::Ok()
+            .content_type(crate::batch::CONTENT_TYPE)
+            .streaming(responses.map(Ok::<_, Infallible>))
+    }
+
+    fn into_http_response(
+        res: HttpResponse,
+    ) -> http::Response<impl Stream<Item = Result<Bytes, Box<dyn StdError>>>>
+    {
+        let mut builder =
+            http::Response::builder().status(res.status().as_u16());
+        for (name, value) in res.headers() {
+            builder = builder.header(name.as_str(), value.as_bytes());
         }
+        let mut body = res.into_body();
+        builder
+            .body(stream::poll_fn(move |cx| Pin::new(&mut body).poll_next(cx)))
+            .expect("the response should already be valid")
     }
 
     /// Returns the server function at the given path as a service that can be modified.
@@ -1254,6 +1479,13 @@ pub mod actix {
             ActixMethod::CONNECT => Method::CONNECT,
             _ => unreachable!(),
         };
+        server_fn_service(path, method)
+    }
+
+    fn server_fn_service(
+        path: &str,
+        method: Method,
+    ) -> Option<BoxedService<ActixRequest, ActixResponse>> {
         REGISTERED_SERVER_FUNCTIONS
             .read()
             .or_poisoned()
@@ -1264,6 +1496,10 @@ pub mod actix {
                 for middleware in middleware {
                     service = middleware.layer(service);
                 }
+                // CSRF checks run before any other middleware
+                if let Some(csrf) = crate::csrf::csrf_protection() {
+                    service = crate::middleware::Layer::layer(csrf, service);
+                }
                 service
             })
     }
@@ -1,9 +1,10 @@
 use crate::{
+    batch::BatchedCall,
     error::{FromServerFnError, IntoAppError, ServerFnErrorErr},
     request::Req,
     response::actix::ActixResponse,
 };
-use actix_web::{web::Payload, FromRequest, HttpRequest};
+use actix_web::{dev, web::Payload, FromRequest, HttpRequest};
 use actix_ws::Message;
 use bytes::Bytes;
 use futures::{FutureExt, Stream, StreamExt};
@@ -15,14 +16,58 @@ use std::{borrow::Cow, future::Future};
 /// This uses a [`SendWrapper`] that allows the Actix `HttpRequest` type to be `Send`, but panics
 /// if it it is ever sent to another thread. Actix pins request handling to a single thread, so this
 /// is necessary to be compatible with traits that require `Send` but should never panic in actual use.
-pub struct ActixRequest(pub(crate) SendWrapper<(HttpRequest, Payload)>);
+pub struct ActixRequest(
+    pub(crate) SendWrapper<(HttpRequest, Payload)>,
+    pub(crate) Option<BatchedParts>,
+);
+
+/// The parts of a call in a batch that are not shared with the request for the whole batch.
+pub(crate) struct BatchedParts {
+    query: Option<String>,
+    content_type: String,
+    accepts: String,
+}
 
 impl ActixRequest {
+    /// Creates a request for a call in a batch, which shares everything but its arguments with
+    /// the request for the whole batch.
+    ///
+    /// [`take`](ActixRequest::take) returns the request for the whole batch, with the body of
+    /// the call.
+    pub fn from_batched_call(req: HttpRequest, call: BatchedCall) -> Self {
+        ActixRequest(
+            SendWrapper::new((req.clone(), payload(&req, call.body))),
+            Some(BatchedParts {
+                query: call.query,
+                content_type: call.content_type,
+                accepts: call.accepts,
+            }),
+        )
+    }
+
+    /// Creates a request with a body that has already been read.
+    pub(crate) fn with_body(req: HttpRequest, body: Bytes) -> Self {
+        let payload = payload(&req, body);
+        ActixRequest(SendWrapper::new((req, payload)), None)
+    }
+
     /// Returns the raw Actix request, and its body.
     pub fn take(self) -> (HttpRequest, Payload) {
         self.0.take()
     }
 
+    async fn read_body(self) -> Result<Bytes, actix_web::Error> {
+        let batched = self.1.is_some();
+        let (req, payload) = self.0.take();
+        if batched {
+            // the body of a call in a batch has already been received, and the headers of the
+            // batch request (like its `Content-Length`) do not describe it
+            payload.to_bytes().await
+        } else {
+            Bytes::from_request(&req, &mut payload.into_inner()).await
+        }
+    }
+
     fn header(&self, name: &str) -> Option<Cow<'_, str>> {
         self.0
              .0
@@ -32,9 +77,16 @@ impl ActixRequest {
     }
 }
 
+fn payload(req: &HttpRequest, body: Bytes) -> Payload {
+    Payload::from_request(req, &mut dev::Payload::from(body))
+        .now_or_never()
+        .expect("extracting the payload is synchronous")
+        .expect("extracting the payload is infallible")
+}
+
 impl From<(HttpRequest, Payload)> for ActixRequest {
     fn from(value: (HttpRequest, Payload)) -> Self {
-        ActixRequest(SendWrapper::new(value))
+        ActixRequest(SendWrapper::new(value), None)
     }
 }
 
@@ -48,30 +100,41 @@ where
     type WebsocketResponse = ActixResponse;
 
     fn as_query(&self) -> Option<&str> {
-        self.0 .0.uri().query()
+        match &self.1 {
+            Some(batched) => batched.query.as_deref(),
+            None => self.0 .0.uri().query(),
+        }
     }
 
     fn to_content_type(&self) -> Option<Cow<'_, str>> {
-        self.header("Content-Type")
+        match &self.1 {
+            Some(batched) => Some(Cow::Borrowed(&batched.content_type)),
+            None => self.header("Content-Type"),
+        }
     }
 
     fn accepts(&self) -> Option<Cow<'_, str>> {
-        self.header("Accept")
+        match &self.1 {
+            Some(batched) => Some(Cow::Borrowed(&batched.accepts)),
+            None => self.header("Accept"),
+        }
     }
 
     fn referer(&self) -> Option<Cow<'_, str>> {
         self.header("Referer")
     }
 
+    fn last_event_id(&self) -> Option<Cow<'_, str>> {
+        self.header("Last-Event-ID")
+    }
+
     fn try_into_bytes(
         self,
     ) -> impl Future<Output = Result<Bytes, Error>> + Send {
         // Actix is going to keep this on a single thread anyway so it's fine to wrap it
         // with SendWrapper, which makes it `Send` but will panic if it moves to another thread
         SendWrapper::new(async move {
-            let (req, payload) = self.0.take();
-            let mut payload = payload.into_inner();
-            Bytes::from_request(&req, &mut payload).await.map_err(|e| {
+            self.read_body().await.map_err(|e| {
                 ServerFnErrorErr::Deserialization(e.to_string())
                     .into_app_error()
             })
@@ -84,14 +147,11 @@ where
         // Actix is going to keep this on a single thread anyway so it's fine to wrap it
         // with SendWrapper, which makes it `Send` but will panic if it moves to another thread
         SendWrapper::new(async move {
-            let (req, payload) = self.0.take();
-            let mut payload = payload.into_inner();
-            let bytes =
-                Bytes::from_request(&req, &mut payload).await.map_err(|e| {
-                    Error::from_server_fn_error(
-                        ServerFnErrorErr::Deserialization(e.to_string()),
-                    )
-                })?;
+            let bytes = self.read_body().await.map_err(|e| {
+                Error::from_server_fn_error(ServerFnErrorErr::Deserialization(
+                    e.to_string(),
+                ))
+            })?;
             String::from_utf8(bytes.into()).map_err(|e| {
                 Error::from_server_fn_error(ServerFnErrorErr::Deserialization(
                     e.to_string(),
@@ -10,7 +10,7 @@ use axum::{
 use futures::{Sink, Stream, StreamExt};
 use http::{
     header::{ACCEPT, CONTENT_TYPE, REFERER},
-    Request,
+    HeaderName, Request,
 };
 use http_body_util::BodyExt;
 use std::borrow::Cow;
@@ -46,6 +46,12 @@ where
             .map(|h| String::from_utf8_lossy(h.as_bytes()))
     }
 
+    fn last_event_id(&self) -> Option<Cow<'_, str>> {
+        self.headers()
+            .get(HeaderName::from_static("last-event-id"))
+            .map(|h| String::from_utf8_lossy(h.as_bytes()))
+    }
+
     async fn try_into_bytes(self) -> Result<Bytes, Error> {
         let body = self.into_limited_body();
         body.collect().await.map(|c| c.to_bytes()).map_err(|e| {
@@ -30,13 +30,6 @@ pub(crate) struct RequestInner {
 #[derive(Debug)]
 pub(crate) struct AbortOnDrop(Option<AbortController>);
 
-impl AbortOnDrop {
-    /// Prevents the request from being aborted on drop.
-    pub fn prevent_cancellation(&mut self) {
-        self.0.take();
-    }
-}
-
 impl Drop for AbortOnDrop {
     fn drop(&mut self) {
         if let Some(inner) = self.0.take() {
@@ -319,7 +312,6 @@ where
                 ))
             }
         }
-        // TODO abort signal
         let (request, abort_ctrl) =
             streaming_request(path, accepts, content_type, body, method)
                 .map_err(|e| {
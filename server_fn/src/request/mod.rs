@@ -337,6 +337,16 @@ where
     /// Returns the `Referer` header, if any.
     fn referer(&self) -> Option<Cow<'_, str>>;
 
+    /// Returns the `Last-Event-ID` header, if any.
+    ///
+    /// This is sent by the browser when it reconnects to a stream of
+    /// [server-sent events](crate::sse).
+    fn last_event_id(&self) -> Option<Cow<'_, str>> {
+        // request types that don't override this treat every connection as a new one, so a
+        // resumable stream starts from the beginning, as it did before the header was read
+        None
+    }
+
     /// Attempts to extract the body of the request into [`Bytes`].
     fn try_into_bytes(
         self,
@@ -69,6 +69,22 @@ where
                 })),
         )))
     }
+
+    fn try_from_event_stream(
+        data: impl Stream<Item = Result<Bytes, Bytes>> + 'static,
+    ) -> Result<Self, E> {
+        let mut builder = HttpResponse::build(StatusCode::OK);
+        Ok(ActixResponse(SendWrapper::new(
+            builder
+                .insert_header((header::CONTENT_TYPE, crate::sse::CONTENT_TYPE))
+                .insert_header((header::CACHE_CONTROL, "no-cache"))
+                // prevents reverse proxies like nginx from buffering the events
+                .insert_header(("X-Accel-Buffering", "no"))
+                .streaming(data.map(|data| {
+                    data.map_err(|e| ServerFnErrorWrapper(E::de(e)))
+                })),
+        )))
+    }
 }
 
 impl Res for ActixResponse {
@@ -2,6 +2,7 @@ use super::ClientRes;
 use crate::{
     error::{FromServerFnError, IntoAppError, ServerFnErrorErr},
     redirect::REDIRECT_HEADER,
+    request::browser::AbortOnDrop,
 };
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
@@ -14,7 +15,12 @@ use wasm_bindgen::JsCast;
 use wasm_streams::ReadableStream;
 
 /// The response to a `fetch` request made in the browser.
-pub struct BrowserResponse(pub(crate) SendWrapper<Response>);
+///
+/// The request is aborted if the response is dropped before its body has been read.
+pub struct BrowserResponse(
+    pub(crate) SendWrapper<Response>,
+    pub(crate) SendWrapper<Option<AbortOnDrop>>,
+);
 
 impl BrowserResponse {
     /// Generate the headers from the internal [`Response`] object.
@@ -61,8 +67,14 @@ impl<E: FromServerFnError> ClientRes<E> for BrowserResponse {
         self,
     ) -> Result<impl Stream<Item = Result<Bytes, Bytes>> + Send + 'static, E>
     {
+        let abort_ctrl = self.1;
         let stream = ReadableStream::from_raw(self.0.body().unwrap())
             .into_stream()
+            .map(move |data| {
+                // keep the request alive for as long as the stream is
+                let _ = &abort_ctrl;
+                data
+            })
             .map(|data| match data {
                 Err(e) => {
                     web_sys::console::error_1(&e);
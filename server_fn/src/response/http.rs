@@ -49,6 +49,24 @@ where
                 ServerFnErrorErr::Response(e.to_string()).into_app_error()
             })
     }
+
+    fn try_from_event_stream(
+        data: impl Stream<Item = Result<Bytes, Bytes>> + Send + 'static,
+    ) -> Result<Self, E> {
+        let body =
+            Body::from_stream(data.map_err(|e| ServerFnErrorWrapper(E::de(e))));
+        let builder = http::Response::builder();
+        builder
+            .status(200)
+            .header(http::header::CONTENT_TYPE, crate::sse::CONTENT_TYPE)
+            .header(http::header::CACHE_CONTROL, "no-cache")
+            // prevents reverse proxies like nginx from buffering the events
+            .header("X-Accel-Buffering", "no")
+            .body(body)
+            .map_err(|e| {
+                ServerFnErrorErr::Response(e.to_string()).into_app_error()
+            })
+    }
 }
 
 impl Res for Response<Body> {
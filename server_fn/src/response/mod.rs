@@ -33,6 +33,16 @@ where
         content_type: &str,
         data: impl Stream<Item = Result<Bytes, Bytes>> + Send + 'static,
     ) -> Result<Self, E>;
+
+    /// Attempts to convert a stream of [server-sent events](crate::sse), which have already been
+    /// encoded in the `text/event-stream` format, into an HTTP response.
+    ///
+    /// Implementations should prevent the response from being cached or buffered.
+    fn try_from_event_stream(
+        data: impl Stream<Item = Result<Bytes, Bytes>> + Send + 'static,
+    ) -> Result<Self, E> {
+        Self::try_from_stream(crate::sse::CONTENT_TYPE, data)
+    }
 }
 
 /// Represents the response as created by the server;
@@ -2,7 +2,18 @@ use crate::{
     request::Req,
     response::{Res, TryRes},
 };
-use std::future::Future;
+use futures::{
+    channel::oneshot,
+    future::{FutureExt, Shared},
+};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 /// A server defines a pair of request/response types and the logic to spawn
 /// an async task.
@@ -27,4 +38,127 @@ pub trait Server<Error, InputStreamError = Error, OutputStreamError = Error> {
     fn spawn(
         future: impl Future<Output = ()> + Send + 'static,
     ) -> Result<(), Error>;
+
+    /// Waits until the given duration has passed.
+    ///
+    /// This is used to send keep-alive messages on long-lived responses, like
+    /// [server-sent events](crate::sse). The default implementation never completes, so that
+    /// no keep-alive messages are sent.
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        // a server without timers can still send events: only the keep-alive comments, which are
+        // raced against the events, are never sent
+        _ = duration;
+        futures::future::pending()
+    }
+}
+
+/// Signals that the client is no longer waiting for a server function to respond.
+///
+/// Server integrations create a token with [`CancellationToken::new`] for each server function
+/// call, and make it available to the server function (for example, via context in
+/// `leptos_axum` and `leptos_actix`). The token is canceled if the request is dropped before a
+/// response has been produced, which typically happens when the client disconnects or aborts
+/// the request.
+///
+/// The server function's own `Future` stops being polled at that point, but work it has spawned
+/// does not. Long-running work can check [`is_cancelled`](CancellationToken::is_cancelled), or
+/// race against [`cancelled`](CancellationToken::cancelled), to stop early.
+///
+/// ```
+/// # use server_fn::server::CancellationToken;
+/// # futures::executor::block_on(async {
+/// let (token, guard) = CancellationToken::new();
+/// assert!(!token.is_cancelled());
+///
+/// drop(guard);
+/// assert!(token.is_cancelled());
+/// token.cancelled().await;
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    rx: Shared<oneshot::Receiver<()>>,
+}
+
+impl CancellationToken {
+    /// Creates a new token, along with the guard that cancels it when dropped.
+    pub fn new() -> (Self, CancelOnDrop) {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = oneshot::channel();
+        (
+            Self {
+                cancelled: Arc::clone(&cancelled),
+                rx: rx.shared(),
+            },
+            CancelOnDrop(Some((cancelled, tx))),
+        )
+    }
+
+    /// Whether the request has been canceled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Completes when the request has been canceled.
+    ///
+    /// If the request completes normally, this never resolves.
+    pub fn cancelled(&self) -> impl Future<Output = ()> + Send + 'static {
+        let rx = self.rx.clone();
+        async move {
+            // the sender is dropped without sending if the guard has been disarmed
+            if rx.await.is_err() {
+                futures::future::pending::<()>().await;
+            }
+        }
+    }
+}
+
+/// Cancels a [`CancellationToken`] when dropped, unless it has been
+/// [disarmed](CancelOnDrop::disarm).
+#[derive(Debug)]
+#[must_use = "the token is canceled as soon as the guard is dropped"]
+pub struct CancelOnDrop(Option<(Arc<AtomicBool>, oneshot::Sender<()>)>);
+
+impl CancelOnDrop {
+    /// Prevents the token from being canceled. This should be called once the response has been
+    /// produced.
+    pub fn disarm(mut self) {
+        self.0.take();
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some((cancelled, tx)) = self.0.take() {
+            cancelled.store(true, Ordering::Release);
+            _ = tx.send(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CancellationToken;
+    use futures::FutureExt;
+
+    #[test]
+    fn disarmed_token_is_never_cancelled() {
+        let (token, guard) = CancellationToken::new();
+        let cancelled = token.cancelled();
+        guard.disarm();
+        assert!(!token.is_cancelled());
+        assert!(cancelled.now_or_never().is_none());
+        assert!(token.cancelled().now_or_never().is_none());
+    }
+
+    #[test]
+    fn clones_are_cancelled_together() {
+        let (token, guard) = CancellationToken::new();
+        let clone = token.clone();
+        drop(guard);
+        assert!(clone.is_cancelled());
+        assert!(clone.cancelled().now_or_never().is_some());
+        assert!(token.cancelled().now_or_never().is_some());
+    }
 }
@@ -0,0 +1,813 @@
+use crate::{
+    codec::{FromReq, IntoReq},
+    error::{FromServerFnError, ServerFnErrorErr},
+    request::Req,
+    response::TryRes,
+    Decodes, Encodes, FormatType, Protocol, ServerFnError,
+};
+use bytes::Bytes;
+use futures::{
+    future::{self, Either},
+    stream, Stream, StreamExt,
+};
+use http::Method;
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Write},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    time::Duration,
+};
+
+/// The MIME type of a stream of server-sent events.
+pub(crate) const CONTENT_TYPE: &str = "text/event-stream";
+
+/// The name of the events used to send errors from the stream to the client.
+pub(crate) const ERROR_EVENT: &str = "server_fn_error";
+
+/// The name of the event that marks the end of the stream, so that the client does not reconnect.
+pub(crate) const END_EVENT: &str = "server_fn_end";
+
+/// How often a comment is sent when the server has no events to send, by default.
+const DEFAULT_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// A protocol that sends the output of a server function to the client as a stream of
+/// [server-sent events](https://html.spec.whatwg.org/multipage/server-sent-events.html).
+///
+/// Unlike a [`Websocket`](crate::Websocket), server-sent events are sent over a plain HTTP
+/// response, so they work through proxies that do not allow websockets. In the browser, they are
+/// received with an `EventSource`, which reconnects automatically if the connection is lost.
+///
+/// The input is sent with `InputEncoding`, like the input of the [`Http`](crate::Http) protocol.
+/// `EventSource` can only make `GET` requests, so this should be [`GetUrl`](crate::codec::GetUrl)
+/// for server functions that are called from the browser. The data of each event is encoded with
+/// `OutputEncoding`.
+///
+/// The server function returns an [`EventStream`]. Each event can have an ID. When the browser
+/// reconnects, it sends the ID of the last event it received, which is passed to
+/// [`EventStream::resumable`] so that the stream can continue where it left off.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use server_fn_macro_default::server;
+/// # #[cfg(feature = "browser")] {
+/// use server_fn::{
+///     codec::{GetUrl, JsonEncoding},
+///     sse::{Event, EventStream, ServerSentEvents},
+///     ServerFnError,
+/// };
+///
+/// #[server(protocol = ServerSentEvents<GetUrl, JsonEncoding>)]
+/// async fn countdown(from: u32) -> Result<EventStream<u32>, ServerFnError> {
+///     Ok(EventStream::resumable(move |last_event_id| {
+///         // start from just after the last event the client received, if it is reconnecting
+///         let from = last_event_id
+///             .and_then(|id| id.parse::<u32>().ok())
+///             .map_or(from, |id| id.saturating_sub(1));
+///         futures::stream::iter((0..=from).rev().map(|n| {
+///             Ok(Event::new(n).with_id(n.to_string()))
+///         }))
+///     }))
+/// }
+/// # }
+/// ```
+pub struct ServerSentEvents<InputEncoding, OutputEncoding>(
+    PhantomData<(InputEncoding, OutputEncoding)>,
+);
+
+/// A single server-sent event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event<T> {
+    /// The ID of the event, which the client sends back to the server when it reconnects.
+    pub id: Option<String>,
+    /// The data of the event.
+    pub data: T,
+}
+
+impl<T> Event<T> {
+    /// Creates an event without an ID.
+    pub fn new(data: T) -> Self {
+        Self { id: None, data }
+    }
+
+    /// Sets the ID of the event.
+    ///
+    /// Line breaks and null characters are not allowed in IDs, and are removed.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        let mut id = id.into();
+        id.retain(|c| !matches!(c, '\n' | '\r' | '\0'));
+        self.id = Some(id);
+        self
+    }
+}
+
+type BoxedEvents<T, E> =
+    Pin<Box<dyn Stream<Item = Result<Event<T>, E>> + Send>>;
+
+/// A stream of [`Event`]s, which a server function using the [`ServerSentEvents`] protocol
+/// returns.
+///
+/// On the server, the stream is only started once the ID of the last event the client received (if
+/// any) is known. On the client, it yields the events as they arrive.
+pub struct EventStream<T, E = ServerFnError> {
+    start: Box<dyn FnOnce(Option<String>) -> BoxedEvents<T, E> + Send>,
+    keep_alive: Option<Duration>,
+    retry: Option<Duration>,
+}
+
+impl<T, E> EventStream<T, E> {
+    /// Creates an event stream that always starts from the beginning, even if the client is
+    /// reconnecting.
+    pub fn new(
+        stream: impl Stream<Item = Result<Event<T>, E>> + Send + 'static,
+    ) -> Self {
+        Self::resumable(move |_| stream)
+    }
+
+    /// Creates an event stream that can continue where it left off when the client reconnects.
+    ///
+    /// `start` is called with the ID of the last event the client received, or `None` if this is
+    /// a new connection.
+    pub fn resumable<S>(
+        start: impl FnOnce(Option<String>) -> S + Send + 'static,
+    ) -> Self
+    where
+        S: Stream<Item = Result<Event<T>, E>> + Send + 'static,
+    {
+        Self {
+            start: Box::new(move |last_event_id| {
+                Box::pin(start(last_event_id)) as BoxedEvents<T, E>
+            }),
+            keep_alive: Some(DEFAULT_KEEP_ALIVE),
+            retry: None,
+        }
+    }
+
+    /// Sets how often a comment is sent while there are no events, so that proxies do not close
+    /// the connection. This is every 15 seconds by default; `None` disables it.
+    ///
+    /// Keep-alive comments are only sent if the [`Server`](crate::server::Server) supports timers.
+    pub fn with_keep_alive(mut self, interval: Option<Duration>) -> Self {
+        self.keep_alive = interval;
+        self
+    }
+
+    /// Tells the browser how long to wait before reconnecting, if the connection is lost.
+    pub fn with_retry(mut self, delay: Duration) -> Self {
+        self.retry = Some(delay);
+        self
+    }
+
+    /// Starts the stream, continuing after the event with the given ID.
+    pub fn resume(
+        self,
+        last_event_id: Option<String>,
+    ) -> impl Stream<Item = Result<Event<T>, E>> + Send {
+        (self.start)(last_event_id)
+    }
+
+    /// Consumes the wrapper, returning the stream of events.
+    pub fn into_inner(self) -> impl Stream<Item = Result<Event<T>, E>> + Send {
+        self.resume(None)
+    }
+}
+
+impl<T, E> Debug for EventStream<T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventStream")
+            .field("keep_alive", &self.keep_alive)
+            .field("retry", &self.retry)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, E, S> From<S> for EventStream<T, E>
+where
+    S: Stream<Item = Result<Event<T>, E>> + Send + 'static,
+{
+    fn from(stream: S) -> Self {
+        Self::new(stream)
+    }
+}
+
+/// A server-sent event as it was received, before its data has been decoded.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RawEvent {
+    /// The ID of the last event that had one, if any.
+    pub id: Option<String>,
+    /// The type of the event, which is `message` unless the server set another one.
+    pub event: String,
+    /// The data of the event.
+    pub data: String,
+}
+
+impl<
+        Input,
+        OutputItem,
+        InputEncoding,
+        OutputEncoding,
+        Client,
+        Server,
+        Error,
+        InputStreamError,
+        OutputStreamError,
+    >
+    Protocol<
+        Input,
+        EventStream<OutputItem, OutputStreamError>,
+        Client,
+        Server,
+        Error,
+        InputStreamError,
+        OutputStreamError,
+    > for ServerSentEvents<InputEncoding, OutputEncoding>
+where
+    Input: IntoReq<InputEncoding, Client::Request, Error>
+        + FromReq<InputEncoding, Server::Request, Error>
+        + Send,
+    InputEncoding: crate::codec::Encoding,
+    OutputEncoding: Encodes<OutputItem> + Decodes<OutputItem>,
+    OutputStreamError: FromServerFnError + Send,
+    Error: FromServerFnError + Send,
+    Server: crate::Server<Error, InputStreamError, OutputStreamError> + 'static,
+    Client: crate::Client<Error, InputStreamError, OutputStreamError>,
+    OutputItem: Send + 'static,
+{
+    const METHOD: Method = InputEncoding::METHOD;
+
+    async fn run_server<F, Fut>(
+        request: Server::Request,
+        server_fn: F,
+    ) -> Result<Server::Response, Error>
+    where
+        F: Fn(Input) -> Fut + Send,
+        Fut: Future<
+                Output = Result<
+                    EventStream<OutputItem, OutputStreamError>,
+                    Error,
+                >,
+            > + Send,
+    {
+        let last_event_id = request.last_event_id().map(Cow::into_owned);
+        let input = Input::from_req(request).await?;
+
+        let output = server_fn(input).await?;
+        let keep_alive = output.keep_alive;
+        let retry = output.retry;
+
+        let events = output.resume(last_event_id).map(|event| {
+            let frame = match event {
+                Ok(event) => match OutputEncoding::encode(&event.data) {
+                    Ok(data) => encode_event(
+                        event.id.as_deref(),
+                        None,
+                        &OutputEncoding::into_encoded_string(data),
+                    ),
+                    Err(e) => {
+                        encode_error(&OutputStreamError::from_server_fn_error(
+                            ServerFnErrorErr::Serialization(e.to_string()),
+                        ))
+                    }
+                },
+                Err(err) => encode_error(&err),
+            };
+            Some(Ok(frame))
+        });
+        let retry = stream::iter(retry.map(|retry| {
+            Some(Ok(Bytes::from(format!("retry: {}\n\n", retry.as_millis()))))
+        }));
+        // the client is told that the stream has ended, so that it does not reconnect, and
+        // `None` marks the end of the response, as the keep-alive comments never end
+        let end = [Some(Ok(encode_event(None, Some(END_EVENT), ""))), None];
+        let events = retry.chain(events).chain(stream::iter(end));
+        let frames = match keep_alive {
+            Some(interval) => {
+                let keep_alive = stream::unfold((), move |_| async move {
+                    Server::sleep(interval).await;
+                    Some((
+                        Some(Ok(Bytes::from_static(b": keep-alive\n\n"))),
+                        (),
+                    ))
+                });
+                Either::Left(stream::select(events, keep_alive))
+            }
+            None => Either::Right(events),
+        }
+        .take_while(|frame| future::ready(frame.is_some()))
+        .filter_map(future::ready);
+
+        Server::Response::try_from_event_stream(frames)
+    }
+
+    async fn run_client(
+        path: &str,
+        input: Input,
+    ) -> Result<EventStream<OutputItem, OutputStreamError>, Error> {
+        let req = input.into_req(path, CONTENT_TYPE)?;
+        let events = Client::open_event_stream(req).await?;
+
+        let events = events
+            .take_while(|event| {
+                future::ready(!matches!(
+                    event,
+                    Ok(RawEvent { event, .. }) if event == END_EVENT
+                ))
+            })
+            .map(|event| match event {
+            Ok(RawEvent { event, data, .. }) if event == ERROR_EVENT => {
+                let err = <OutputStreamError::Encoder as FormatType>::from_encoded_string(&data)
+                    .map(OutputStreamError::de)
+                    .unwrap_or_else(|e| {
+                        OutputStreamError::from_server_fn_error(
+                            ServerFnErrorErr::Deserialization(e.to_string()),
+                        )
+                    });
+                Err(err)
+            }
+            Ok(RawEvent { id, data, .. }) => {
+                OutputEncoding::from_encoded_string(&data)
+                    .map_err(|e| e.to_string())
+                    .and_then(|data| {
+                        OutputEncoding::decode(data).map_err(|e| e.to_string())
+                    })
+                    .map(|data| Event { id, data })
+                    .map_err(|e| {
+                        OutputStreamError::from_server_fn_error(
+                            ServerFnErrorErr::Deserialization(e),
+                        )
+                    })
+            }
+            Err(err) => Err(OutputStreamError::de(err)),
+        });
+        Ok(EventStream::new(events))
+    }
+}
+
+/// Encodes a single event in the `text/event-stream` format.
+fn encode_event(id: Option<&str>, event: Option<&str>, data: &str) -> Bytes {
+    let mut frame = String::with_capacity(data.len() + 16);
+    if let Some(event) = event {
+        _ = writeln!(frame, "event: {event}");
+    }
+    if let Some(id) = id {
+        _ = writeln!(frame, "id: {id}");
+    }
+    for line in data.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        _ = writeln!(frame, "data: {line}");
+    }
+    frame.push('\n');
+    frame.into()
+}
+
+fn encode_error<E: FromServerFnError>(err: &E) -> Bytes {
+    encode_event(
+        None,
+        Some(ERROR_EVENT),
+        &E::Encoder::into_encoded_string(err.ser()),
+    )
+}
+
+/// An incremental parser for the `text/event-stream` format, as described in the
+/// [HTML specification](https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation).
+#[derive(Debug, Default)]
+pub(crate) struct Parser {
+    /// Bytes that have been received, but do not make up a full line yet.
+    buffer: Vec<u8>,
+    last_event_id: String,
+    event: String,
+    data: String,
+}
+
+impl Parser {
+    /// Parses the next chunk of the stream, returning any events that it completes.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<RawEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        let mut start = 0;
+        while let Some(len) = self.buffer[start..]
+            .iter()
+            .position(|byte| matches!(byte, b'\n' | b'\r'))
+        {
+            let end = start + len;
+            // lines can end with CRLF, so a CR at the end of the chunk might be followed by a LF
+            let next = match (self.buffer[end], self.buffer.get(end + 1)) {
+                (b'\r', Some(b'\n')) => end + 2,
+                (b'\r', None) => break,
+                _ => end + 1,
+            };
+            let line =
+                String::from_utf8_lossy(&self.buffer[start..end]).into_owned();
+            if let Some(event) = self.line(&line) {
+                events.push(event);
+            }
+            start = next;
+        }
+        self.buffer.drain(..start);
+        events
+    }
+
+    fn line(&mut self, line: &str) -> Option<RawEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+
+        let (field, value) = match line.split_once(':') {
+            // lines starting with a colon are comments
+            Some(("", _)) => return None,
+            Some((field, value)) => {
+                (field, value.strip_prefix(' ').unwrap_or(value))
+            }
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => {
+                self.last_event_id = value.to_string()
+            }
+            // `retry` is only used by the browser, and other fields are ignored
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<RawEvent> {
+        let event = std::mem::take(&mut self.event);
+        if self.data.is_empty() {
+            return None;
+        }
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+        Some(RawEvent {
+            id: (!self.last_event_id.is_empty())
+                .then(|| self.last_event_id.clone()),
+            event: if event.is_empty() {
+                "message".to_string()
+            } else {
+                event
+            },
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: Option<&str>, data: &str) -> RawEvent {
+        RawEvent {
+            id: id.map(String::from),
+            event: "message".into(),
+            data: data.into(),
+        }
+    }
+
+    #[test]
+    fn parses_events_split_across_chunks() {
+        let mut parser = Parser::default();
+        assert_eq!(parser.feed(b"id: 1\nda"), vec![]);
+        assert_eq!(parser.feed(b"ta: {\"a\":1}\n"), vec![]);
+        assert_eq!(
+            parser.feed(b"\n: keep-alive\n\ndata: 2\r"),
+            vec![message(Some("1"), "{\"a\":1}")]
+        );
+        // the ID is kept for later events
+        assert_eq!(parser.feed(b"\n\r\n"), vec![message(Some("1"), "2")]);
+    }
+
+    #[test]
+    fn parses_multiline_data_and_event_types() {
+        let mut parser = Parser::default();
+        let events = parser.feed(
+            b"event: server_fn_error\ndata:first\ndata: second\n\nretry: 10\n\n",
+        );
+        assert_eq!(
+            events,
+            vec![RawEvent {
+                id: None,
+                event: ERROR_EVENT.into(),
+                data: "first\nsecond".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn encoded_events_can_be_parsed() {
+        let mut parser = Parser::default();
+        let mut bytes = encode_event(Some("7"), None, "a\nb").to_vec();
+        bytes.extend_from_slice(b": keep-alive\n\n");
+        bytes.extend_from_slice(&encode_error(&ServerFnError::new("oops")));
+
+        let events = parser.feed(&bytes);
+        assert_eq!(events[0], message(Some("7"), "a\nb"));
+        assert_eq!(events[1].event, ERROR_EVENT);
+        assert_eq!(
+            ServerFnError::de(
+                <ServerFnError as FromServerFnError>::Encoder::from_encoded_string(
+                    &events[1].data
+                )
+                .unwrap()
+            ),
+            ServerFnError::new("oops")
+        );
+    }
+
+    #[test]
+    fn ids_cannot_contain_line_breaks() {
+        assert_eq!(Event::new(()).with_id("a\nb\r").id.as_deref(), Some("ab"));
+    }
+
+    /// Runs server functions through [`ServerSentEvents::run_client`] and
+    /// [`ServerSentEvents::run_server`], with a client that calls the server in memory.
+    #[cfg(feature = "axum")]
+    mod end_to_end {
+        use super::*;
+        use crate::{
+            axum::AxumServerFnBackend, codec::GetUrl, request::ClientReq,
+            response::ClientRes, Client, Protocol,
+        };
+        use axum::body::Body;
+        use futures::{channel::mpsc, sink, Sink};
+        use http::{
+            header::{ACCEPT, CONTENT_TYPE as CONTENT_TYPE_HEADER},
+            Request, Response,
+        };
+        use http_body_util::BodyExt;
+        use serde::{Deserialize, Serialize};
+        use std::{cell::RefCell, future::Future};
+
+        type Sse = ServerSentEvents<GetUrl, crate::codec::JsonEncoding>;
+
+        #[derive(Serialize, Deserialize)]
+        struct Countdown {
+            from: u32,
+            /// How long to wait before each event after the first, in milliseconds.
+            pause: u64,
+        }
+
+        /// Counts down to zero, giving each event its number as an ID.
+        async fn countdown(
+            args: Countdown,
+        ) -> Result<EventStream<u32>, ServerFnError> {
+            let pause = Duration::from_millis(args.pause);
+            let stream = EventStream::resumable(move |last_event_id| {
+                let from = last_event_id
+                    .and_then(|id| id.parse::<u32>().ok())
+                    .map_or(args.from, |id| id.saturating_sub(1));
+                stream::iter((0..=from).rev()).then(move |n| async move {
+                    if n != from {
+                        tokio::time::sleep(pause).await;
+                    }
+                    Ok(Event::new(n).with_id(n.to_string()))
+                })
+            });
+            Ok(stream.with_keep_alive(Some(Duration::from_millis(10))))
+        }
+
+        thread_local! {
+            /// The ID the client sends as `Last-Event-ID`, as a reconnecting `EventSource` would.
+            static LAST_EVENT_ID: RefCell<Option<String>> = const { RefCell::new(None) };
+        }
+
+        struct TestClient;
+
+        struct TestRequest(Request<Body>);
+
+        struct TestResponse(Response<Body>);
+
+        impl ClientReq<ServerFnError> for TestRequest {
+            type FormData = ();
+
+            fn try_new_req_query(
+                path: &str,
+                content_type: &str,
+                accepts: &str,
+                query: &str,
+                method: Method,
+            ) -> Result<Self, ServerFnError> {
+                let mut req = Request::builder()
+                    .method(method)
+                    .uri(format!("{path}?{query}"))
+                    .header(CONTENT_TYPE_HEADER, content_type)
+                    .header(ACCEPT, accepts);
+                if let Some(id) = LAST_EVENT_ID.with_borrow(Clone::clone) {
+                    req = req.header("last-event-id", id);
+                }
+                Ok(Self(req.body(Body::empty()).unwrap()))
+            }
+
+            fn try_new_req_text(
+                _path: &str,
+                _content_type: &str,
+                _accepts: &str,
+                _body: String,
+                _method: Method,
+            ) -> Result<Self, ServerFnError> {
+                unreachable!("only GET requests are sent")
+            }
+
+            fn try_new_req_bytes(
+                _path: &str,
+                _content_type: &str,
+                _accepts: &str,
+                _body: Bytes,
+                _method: Method,
+            ) -> Result<Self, ServerFnError> {
+                unreachable!("only GET requests are sent")
+            }
+
+            fn try_new_req_form_data(
+                _path: &str,
+                _accepts: &str,
+                _content_type: &str,
+                _body: (),
+                _method: Method,
+            ) -> Result<Self, ServerFnError> {
+                unreachable!("only GET requests are sent")
+            }
+
+            fn try_new_req_multipart(
+                _path: &str,
+                _accepts: &str,
+                _body: (),
+                _method: Method,
+            ) -> Result<Self, ServerFnError> {
+                unreachable!("only GET requests are sent")
+            }
+
+            fn try_new_req_streaming(
+                _path: &str,
+                _accepts: &str,
+                _content_type: &str,
+                _body: impl Stream<Item = Bytes> + Send + 'static,
+                _method: Method,
+            ) -> Result<Self, ServerFnError> {
+                unreachable!("only GET requests are sent")
+            }
+        }
+
+        impl ClientRes<ServerFnError> for TestResponse {
+            async fn try_into_string(self) -> Result<String, ServerFnError> {
+                let bytes = self.try_into_bytes().await?;
+                Ok(String::from_utf8_lossy(&bytes).into_owned())
+            }
+
+            async fn try_into_bytes(self) -> Result<Bytes, ServerFnError> {
+                let body = self.0.into_body().collect().await;
+                body.map(|body| body.to_bytes())
+                    .map_err(|e| ServerFnError::new(e.to_string()))
+            }
+
+            fn try_into_stream(
+                self,
+            ) -> Result<
+                impl Stream<Item = Result<Bytes, Bytes>> + Send + Sync + 'static,
+                ServerFnError,
+            > {
+                // the body isn't `Sync`, so it is read by a task and sent over a channel
+                let (tx, rx) = mpsc::unbounded();
+                let mut body = self.0.into_body().into_data_stream();
+                tokio::spawn(async move {
+                    while let Some(chunk) = body.next().await {
+                        let chunk =
+                            chunk.map_err(|e| Bytes::from(e.to_string()));
+                        if tx.unbounded_send(chunk).is_err() {
+                            break;
+                        }
+                    }
+                });
+                Ok(rx)
+            }
+
+            fn status(&self) -> u16 {
+                self.0.status().as_u16()
+            }
+
+            fn status_text(&self) -> String {
+                self.0.status().to_string()
+            }
+
+            fn location(&self) -> String {
+                String::new()
+            }
+
+            fn has_redirect(&self) -> bool {
+                false
+            }
+        }
+
+        impl Client<ServerFnError> for TestClient {
+            type Request = TestRequest;
+            type Response = TestResponse;
+
+            async fn send(
+                req: TestRequest,
+            ) -> Result<TestResponse, ServerFnError> {
+                <Sse as Protocol<
+                    Countdown,
+                    EventStream<u32>,
+                    TestClient,
+                    AxumServerFnBackend,
+                    ServerFnError,
+                >>::run_server(req.0, countdown)
+                .await
+                .map(TestResponse)
+            }
+
+            async fn open_websocket(
+                _path: &str,
+            ) -> Result<
+                (
+                    impl Stream<Item = Result<Bytes, Bytes>> + Send + 'static,
+                    impl Sink<Bytes> + Send + 'static,
+                ),
+                ServerFnError,
+            > {
+                Err::<(stream::Empty<_>, sink::Drain<_>), _>(
+                    ServerFnError::new("websockets are not supported"),
+                )
+            }
+
+            fn spawn(future: impl Future<Output = ()> + Send + 'static) {
+                tokio::spawn(future);
+            }
+        }
+
+        fn run<T>(fut: impl Future<Output = T>) -> T {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_time()
+                .build()
+                .unwrap()
+                .block_on(fut)
+        }
+
+        async fn call(from: u32, pause: u64) -> Vec<(Option<String>, u32)> {
+            let events = <Sse as Protocol<
+                Countdown,
+                EventStream<u32>,
+                TestClient,
+                AxumServerFnBackend,
+                ServerFnError,
+            >>::run_client(
+                "/api/countdown", Countdown { from, pause }
+            )
+            .await
+            .unwrap();
+            events
+                .into_inner()
+                .map(|event| event.map(|event| (event.id, event.data)))
+                .collect::<Vec<_>>()
+                .await
+                .into_iter()
+                .collect::<Result<_, _>>()
+                .unwrap()
+        }
+
+        fn events(ids: &[u32]) -> Vec<(Option<String>, u32)> {
+            ids.iter().map(|id| (Some(id.to_string()), *id)).collect()
+        }
+
+        #[test]
+        fn events_are_received_until_the_stream_ends() {
+            let received = run(call(3, 0));
+            assert_eq!(received, events(&[3, 2, 1, 0]));
+        }
+
+        #[test]
+        fn reconnecting_clients_resume_after_the_last_event_id() {
+            LAST_EVENT_ID.set(Some("2".into()));
+            let received = run(call(3, 0));
+            LAST_EVENT_ID.set(None);
+            assert_eq!(received, events(&[1, 0]));
+        }
+
+        #[test]
+        fn keep_alive_comments_are_sent_while_waiting_for_events() {
+            let body = run(async {
+                let req: TestRequest =
+                    IntoReq::<GetUrl, _, ServerFnError>::into_req(
+                        Countdown { from: 1, pause: 50 },
+                        "/api/countdown",
+                        CONTENT_TYPE,
+                    )
+                    .unwrap();
+                let res = TestClient::send(req).await.unwrap();
+                res.try_into_string().await.unwrap()
+            });
+            let keep_alive = body.find(": keep-alive\n\n").unwrap();
+            assert!(body.find("id: 1\n").unwrap() < keep_alive);
+            assert!(keep_alive < body.find("id: 0\n").unwrap());
+
+            // the client skips the comments
+            assert_eq!(run(call(1, 50)), events(&[1, 0]));
+        }
+    }
+}
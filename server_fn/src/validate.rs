@@ -0,0 +1,338 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+#[cfg(feature = "regex")]
+pub use regex::Regex;
+
+/// Validation errors for the fields of a server function's arguments, keyed by the name of the
+/// field.
+///
+/// Field names use the same format as the `name` of the `<input>` they are submitted from, so
+/// a field nested inside an argument called `user` is keyed as `user[email]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[serde(transparent)]
+pub struct FieldErrors(BTreeMap<String, Vec<String>>);
+
+impl FieldErrors {
+    /// Creates an empty set of field errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error message for the given field.
+    pub fn add(
+        &mut self,
+        field: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.0.entry(field.into()).or_default().push(message.into());
+    }
+
+    /// Returns the error messages for the given field, if any.
+    pub fn get(&self, field: &str) -> &[String] {
+        self.0.get(field).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns the first error message for the given field, if any.
+    pub fn first(&self, field: &str) -> Option<&str> {
+        self.get(field).first().map(String::as_str)
+    }
+
+    /// Whether there are no errors for any field.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The number of fields with errors.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Iterates over each field that has errors, along with its error messages.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.0
+            .iter()
+            .map(|(field, messages)| (field.as_str(), messages.as_slice()))
+    }
+
+    /// Adds the errors of a nested value, prefixing each of its fields with the name of the
+    /// field that contains it.
+    pub fn extend_nested(&mut self, prefix: &str, nested: FieldErrors) {
+        for (field, messages) in nested.0 {
+            let field = match field.split_once('[') {
+                Some((head, rest)) => format!("{prefix}[{head}][{rest}"),
+                None => format!("{prefix}[{field}]"),
+            };
+            self.0.entry(field).or_default().extend(messages);
+        }
+    }
+
+    /// Returns `Ok(())` if there are no errors, or `Err(self)` otherwise.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Display for FieldErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, (field, messages)) in self.iter().enumerate() {
+            if idx > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{field}: {}", messages.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl IntoIterator for FieldErrors {
+    type Item = (String, Vec<String>);
+    type IntoIter = std::collections::btree_map::IntoIter<String, Vec<String>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// A type whose fields can be checked against a set of validation rules.
+///
+/// This is usually derived with `#[derive(Validate)]`, using `#[validate(...)]` attributes on
+/// each field:
+///
+/// ```rust,ignore
+/// #[derive(Deserialize, Validate)]
+/// struct NewUser {
+///     #[validate(required, length(min = 3, max = 32))]
+///     name: String,
+///     #[validate(range(min = 13, message = "You must be at least 13."))]
+///     age: u8,
+///     #[validate(regex = "^[^@]+@[^@]+$")]
+///     email: String,
+///     #[validate(custom = not_taken)]
+///     username: String,
+///     #[validate(nested)]
+///     address: Option<Address>,
+/// }
+/// ```
+///
+/// The `length`, `range`, `regex`, `custom` and `nested` rules are skipped for fields of type
+/// `Option<_>` that are `None`. The `regex` rule requires the `regex` feature.
+///
+/// The same attributes can be used on the arguments of a `#[server]` function, in which case
+/// the arguments are validated on the server before the function body runs.
+pub trait Validate {
+    /// Checks each field, returning the errors for any that are invalid.
+    fn validate(&self) -> Result<(), FieldErrors>;
+}
+
+/// The rules that can be used in `#[validate(...)]` attributes.
+///
+/// Each rule returns the default error message if the value is invalid.
+pub mod rules {
+    use std::fmt::Display;
+
+    /// A value that can be checked by the `required` rule.
+    pub trait Required {
+        /// Whether a value is present.
+        fn is_present(&self) -> bool;
+    }
+
+    impl Required for str {
+        fn is_present(&self) -> bool {
+            !self.trim().is_empty()
+        }
+    }
+
+    impl Required for String {
+        fn is_present(&self) -> bool {
+            self.as_str().is_present()
+        }
+    }
+
+    impl<T> Required for [T] {
+        fn is_present(&self) -> bool {
+            !self.is_empty()
+        }
+    }
+
+    impl<T> Required for Vec<T> {
+        fn is_present(&self) -> bool {
+            !self.is_empty()
+        }
+    }
+
+    impl<T: Required> Required for Option<T> {
+        fn is_present(&self) -> bool {
+            self.as_ref().is_some_and(T::is_present)
+        }
+    }
+
+    impl<T: Required + ?Sized> Required for &T {
+        fn is_present(&self) -> bool {
+            T::is_present(self)
+        }
+    }
+
+    macro_rules! always_present {
+        ($($ty:ty),*) => {
+            $(
+                impl Required for $ty {
+                    fn is_present(&self) -> bool {
+                        true
+                    }
+                }
+            )*
+        };
+    }
+
+    always_present!(
+        bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128,
+        isize, f32, f64
+    );
+
+    /// A value that can be checked by the `length` rule.
+    pub trait HasLength {
+        /// The length of the value, in characters for strings or items for collections.
+        fn length(&self) -> usize;
+    }
+
+    impl HasLength for str {
+        fn length(&self) -> usize {
+            self.chars().count()
+        }
+    }
+
+    impl HasLength for String {
+        fn length(&self) -> usize {
+            self.as_str().length()
+        }
+    }
+
+    impl<T> HasLength for [T] {
+        fn length(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl<T> HasLength for Vec<T> {
+        fn length(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl<T: HasLength + ?Sized> HasLength for &T {
+        fn length(&self) -> usize {
+            T::length(self)
+        }
+    }
+
+    /// Checks that a value is present, i.e., not empty.
+    pub fn required<T: Required + ?Sized>(value: &T) -> Result<(), String> {
+        if value.is_present() {
+            Ok(())
+        } else {
+            Err("This field is required.".to_string())
+        }
+    }
+
+    /// Checks that the length of a value is within the given bounds, inclusive.
+    pub fn length<T: HasLength + ?Sized>(
+        value: &T,
+        min: Option<usize>,
+        max: Option<usize>,
+    ) -> Result<(), String> {
+        let len = value.length();
+        match (min, max) {
+            (Some(min), Some(max)) if len < min || len > max => {
+                Err(format!("Must be between {min} and {max} characters."))
+            }
+            (Some(min), _) if len < min => {
+                Err(format!("Must be at least {min} characters."))
+            }
+            (_, Some(max)) if len > max => {
+                Err(format!("Must be at most {max} characters."))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that a value is within the given bounds, inclusive.
+    pub fn range<T: PartialOrd + Display>(
+        value: &T,
+        min: Option<T>,
+        max: Option<T>,
+    ) -> Result<(), String> {
+        match (min, max) {
+            (Some(min), Some(max)) if *value < min || *value > max => {
+                Err(format!("Must be between {min} and {max}."))
+            }
+            (Some(min), _) if *value < min => {
+                Err(format!("Must be at least {min}."))
+            }
+            (_, Some(max)) if *value > max => {
+                Err(format!("Must be at most {max}."))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that a value matches a regular expression.
+    #[cfg(feature = "regex")]
+    pub fn regex<T: AsRef<str> + ?Sized>(
+        value: &T,
+        regex: &super::Regex,
+    ) -> Result<(), String> {
+        if regex.is_match(value.as_ref()) {
+            Ok(())
+        } else {
+            Err("Invalid format.".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rules, FieldErrors};
+
+    #[test]
+    fn nested_errors_use_input_names() {
+        let mut inner = FieldErrors::new();
+        inner.add("city", "This field is required.");
+        inner.add("lines[0]", "Must be at most 3 characters.");
+        let mut errors = FieldErrors::new();
+        errors.extend_nested("address", inner);
+
+        assert_eq!(
+            errors.first("address[city]"),
+            Some("This field is required.")
+        );
+        assert_eq!(
+            errors.get("address[lines][0]"),
+            ["Must be at most 3 characters."]
+        );
+        assert!(errors.get("city").is_empty());
+    }
+
+    #[test]
+    fn rules_check_bounds_inclusively() {
+        assert!(rules::required("  ").is_err());
+        assert!(rules::required(&Some(String::from("a"))).is_ok());
+        assert!(rules::required(&None::<String>).is_err());
+        assert!(rules::length("abc", Some(3), Some(3)).is_ok());
+        assert!(rules::length("ab", Some(3), None).is_err());
+        assert!(rules::length(&vec![1, 2], None, Some(1)).is_err());
+        assert!(rules::range(&5, Some(1), Some(5)).is_ok());
+        assert!(rules::range(&0.5, Some(1.0), None).is_err());
+    }
+}
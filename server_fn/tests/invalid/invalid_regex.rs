@@ -0,0 +1,9 @@
+use server_fn_macro_default::Validate;
+
+#[derive(Validate)]
+pub struct SignUp {
+    #[validate(regex = "[a-z")]
+    name: String,
+}
+
+fn main() {}
@@ -14,6 +14,10 @@ use syn::{
     spanned::Spanned,
     *,
 };
+pub use validate::validate_derive_impl;
+use validate::ValidatedField;
+
+mod validate;
 
 /// A parsed server function call.
 pub struct ServerFnCall {
@@ -664,6 +668,27 @@ impl ServerFnCall {
         };
         let wrapped_struct_name = self.wrapped_struct_name();
 
+        let validate = if self.validated_fields().is_empty() {
+            quote! {}
+        } else {
+            let inner = if let Some(wrapper) = self.args.custom_wrapper.as_ref()
+            {
+                quote! {
+                    let #wrapper(inner) = self;
+                }
+            } else {
+                quote! {
+                    let inner = self;
+                }
+            };
+            quote! {
+                fn validate(&self) -> ::std::result::Result<(), #server_fn_path::validate::FieldErrors> {
+                    #inner
+                    #server_fn_path::validate::Validate::validate(inner)
+                }
+            }
+        };
+
         quote! {
             impl #server_fn_path::ServerFn for #wrapped_struct_name {
                 const PATH: &'static str = #path;
@@ -681,6 +706,35 @@ impl ServerFnCall {
                 }
 
                 #run_body
+
+                #validate
+            }
+        }
+    }
+
+    /// The arguments that have `#[validate(...)]` rules.
+    fn validated_fields(&self) -> Vec<ValidatedField> {
+        self.body
+            .inputs
+            .iter()
+            .filter_map(|arg| arg.validation.clone())
+            .collect()
+    }
+
+    /// Implement `Validate` for the struct, if any of its arguments have validation rules.
+    fn validate_impl(&self) -> TokenStream2 {
+        let fields = self.validated_fields();
+        if fields.is_empty() {
+            return quote! {};
+        }
+        let server_fn_path = self.server_fn_path();
+        let struct_name = self.struct_name();
+        let body = validate::validate_body(&fields, &server_fn_path);
+        quote! {
+            impl #server_fn_path::validate::Validate for #struct_name {
+                fn validate(&self) -> ::std::result::Result<(), #server_fn_path::validate::FieldErrors> {
+                    #body
+                }
             }
         }
     }
@@ -833,9 +887,13 @@ impl ToTokens for ServerFnCall {
 
         let struct_tokens = self.struct_tokens();
 
+        let validate_impl = self.validate_impl();
+
         tokens.extend(quote! {
             #struct_tokens
 
+            #validate_impl
+
             #impl_from
 
             #deref_impl
@@ -1298,6 +1356,8 @@ impl Parse for ServerFnArgs {
 pub struct ServerFnArg {
     /// The attributes on the server function argument.
     server_fn_attributes: Vec<Attribute>,
+    /// The validation rules for the server function argument, if it has any.
+    validation: Option<ValidatedField>,
     /// The type of the server function argument.
     arg: syn::PatType,
 }
@@ -1338,10 +1398,13 @@ impl Parse for ServerFnArg {
             }
         }
 
-        let server_fn_attributes = arg
+        let (validate_attributes, other_attributes): (Vec<_>, Vec<_>) = arg
             .attrs
             .iter()
             .cloned()
+            .partition(|attr| attr.path().is_ident("validate"));
+        let server_fn_attributes = other_attributes
+            .into_iter()
             .map(|attr| {
                 if attr.path().is_ident("server") {
                     // Allow the following attributes:
@@ -1424,15 +1487,36 @@ impl Parse for ServerFnArg {
                 } else {
                     Err(Error::new(
                         attr.span(),
-                        "Unrecognized attribute, expected #[server(...)]",
+                        "Unrecognized attribute, expected #[server(...)] or \
+                         #[validate(...)]",
                     ))
                 }
             })
             .collect::<Result<Vec<_>>>()?;
+        // #[validate(...)]
+        let validation = if validate_attributes.is_empty() {
+            None
+        } else {
+            let Pat::Ident(ident) = &*arg.pat else {
+                return Err(Error::new(
+                    arg.pat.span(),
+                    "#[validate] can only be used on arguments that are \
+                     plain identifiers",
+                ));
+            };
+            Some(ValidatedField::new(
+                ident.ident.clone(),
+                &arg.ty,
+                &validate_attributes,
+                &server_fn_attributes,
+            )?)
+            .filter(ValidatedField::has_rules)
+        };
         arg.attrs = vec![];
         Ok(ServerFnArg {
             arg,
             server_fn_attributes,
+            validation,
         })
     }
 }
@@ -0,0 +1,382 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{
+    meta::ParseNestedMeta, parse::Parse, token, Attribute, Data, DeriveInput,
+    Expr, Fields, GenericArgument, Ident, LitStr, Path, PathArguments, Result,
+    Token, Type,
+};
+
+/// The implementation of `#[derive(Validate)]`.
+///
+/// ```ignore
+/// #[proc_macro_derive(Validate, attributes(validate))]
+/// pub fn validate_derive(input: TokenStream) -> TokenStream {
+///     match validate_derive_impl(
+///         input.into(),
+///         Some(syn::parse_quote!(my_crate::exports::server_fn)),
+///     ) {
+///         Err(e) => e.to_compile_error().into(),
+///         Ok(s) => s.into(),
+///     }
+/// }
+/// ```
+pub fn validate_derive_impl(
+    input: TokenStream2,
+    server_fn_path: Option<Path>,
+) -> Result<TokenStream2> {
+    let input: DeriveInput = syn::parse2(input)?;
+    let server_fn_path =
+        server_fn_path.unwrap_or_else(|| syn::parse_quote!(server_fn));
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .map(|field| {
+                    ValidatedField::new(
+                        field.ident.clone().expect("named field"),
+                        &field.ty,
+                        &field.attrs,
+                        &field.attrs,
+                    )
+                })
+                .collect::<Result<Vec<_>>>()?,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "#[derive(Validate)] can only be used on structs with \
+                     named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "#[derive(Validate)] can only be used on structs",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+    let body = validate_body(&fields, &server_fn_path);
+    Ok(quote! {
+        impl #impl_generics #server_fn_path::validate::Validate for #ident #ty_generics #where_clause {
+            fn validate(&self) -> ::std::result::Result<(), #server_fn_path::validate::FieldErrors> {
+                #body
+            }
+        }
+    })
+}
+
+/// A struct field, or server function argument, with the rules it should be validated with.
+#[derive(Debug, Clone)]
+pub(crate) struct ValidatedField {
+    ident: Ident,
+    name: String,
+    is_option: bool,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    kind: RuleKind,
+    message: Option<LitStr>,
+}
+
+#[derive(Debug, Clone)]
+enum RuleKind {
+    Required,
+    Length {
+        min: Option<Expr>,
+        max: Option<Expr>,
+    },
+    Range {
+        min: Option<Expr>,
+        max: Option<Expr>,
+    },
+    Regex(LitStr),
+    Custom(Path),
+    Nested,
+}
+
+impl ValidatedField {
+    /// Parses the `#[validate(...)]` attributes of a field.
+    ///
+    /// The name of the field is taken from any `#[serde(rename = "...")]` in `serde_attrs`, so
+    /// that errors are keyed by the same name the field is submitted with.
+    pub(crate) fn new(
+        ident: Ident,
+        ty: &Type,
+        attrs: &[Attribute],
+        serde_attrs: &[Attribute],
+    ) -> Result<Self> {
+        let name = renamed(serde_attrs).unwrap_or_else(|| {
+            ident.to_string().trim_start_matches("r#").to_string()
+        });
+        let mut rules = Vec::new();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("validate"))
+        {
+            attr.parse_nested_meta(|meta| {
+                let path = &meta.path;
+                let rule = if path.is_ident("required") {
+                    Rule {
+                        kind: RuleKind::Required,
+                        message: parse_message(&meta)?,
+                    }
+                } else if path.is_ident("nested") {
+                    Rule {
+                        kind: RuleKind::Nested,
+                        message: parse_message(&meta)?,
+                    }
+                } else if path.is_ident("length") || path.is_ident("range") {
+                    let (mut min, mut max) = (None, None);
+                    let mut message = None;
+                    meta.parse_nested_meta(|bound| {
+                        if bound.path.is_ident("min") {
+                            min = Some(bound.value()?.parse()?);
+                        } else if bound.path.is_ident("max") {
+                            max = Some(bound.value()?.parse()?);
+                        } else if bound.path.is_ident("message") {
+                            message = Some(bound.value()?.parse()?);
+                        } else {
+                            return Err(bound
+                                .error("expected `min`, `max` or `message`"));
+                        }
+                        Ok(())
+                    })?;
+                    let kind = if path.is_ident("length") {
+                        RuleKind::Length { min, max }
+                    } else {
+                        RuleKind::Range { min, max }
+                    };
+                    Rule { kind, message }
+                } else if path.is_ident("regex") {
+                    let (pattern, message) =
+                        parse_value_and_message::<LitStr>(&meta, "pattern")?;
+                    // compile the pattern now, so that an invalid one is a compile error
+                    // rather than a panic on the first request
+                    if let Err(e) = regex::Regex::new(&pattern.value()) {
+                        return Err(syn::Error::new(
+                            pattern.span(),
+                            format!("invalid regex: {e}"),
+                        ));
+                    }
+                    Rule {
+                        kind: RuleKind::Regex(pattern),
+                        message,
+                    }
+                } else if path.is_ident("custom") {
+                    let (function, message) =
+                        parse_value_and_message(&meta, "function")?;
+                    Rule {
+                        kind: RuleKind::Custom(function),
+                        message,
+                    }
+                } else {
+                    return Err(meta.error(
+                        "unrecognized validation rule, expected `required`, \
+                         `length`, `range`, `regex`, `custom` or `nested`",
+                    ));
+                };
+                rules.push(rule);
+                Ok(())
+            })?;
+        }
+
+        Ok(Self {
+            ident,
+            name,
+            is_option: is_option(ty),
+            rules,
+        })
+    }
+
+    /// Whether the field has any validation rules.
+    pub(crate) fn has_rules(&self) -> bool {
+        !self.rules.is_empty()
+    }
+}
+
+/// Parses the message of a rule like `required(message = "...")`, if any.
+fn parse_message(meta: &ParseNestedMeta) -> Result<Option<LitStr>> {
+    let mut message = None;
+    if meta.input.peek(token::Paren) {
+        meta.parse_nested_meta(|inner| {
+            if inner.path.is_ident("message") {
+                message = Some(inner.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(inner.error("expected `message`"))
+            }
+        })?;
+    }
+    Ok(message)
+}
+
+/// Parses a rule like `regex = "..."` or `regex(pattern = "...", message = "...")`.
+fn parse_value_and_message<T: Parse>(
+    meta: &ParseNestedMeta,
+    key: &str,
+) -> Result<(T, Option<LitStr>)> {
+    if meta.input.peek(Token![=]) {
+        return Ok((meta.value()?.parse()?, None));
+    }
+    let mut value = None;
+    let mut message = None;
+    meta.parse_nested_meta(|inner| {
+        if inner.path.is_ident(key) {
+            value = Some(inner.value()?.parse()?);
+        } else if inner.path.is_ident("message") {
+            message = Some(inner.value()?.parse()?);
+        } else {
+            return Err(inner.error(format!("expected `{key}` or `message`")));
+        }
+        Ok(())
+    })?;
+    let value =
+        value.ok_or_else(|| meta.error(format!("missing `{key}` argument")))?;
+    Ok((value, message))
+}
+
+/// Finds the name given by `#[serde(rename = "...")]`, if any.
+fn renamed(attrs: &[Attribute]) -> Option<String> {
+    let mut name = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        // other serde attributes are checked by serde itself, so errors can be ignored here
+        _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") && meta.input.peek(Token![=]) {
+                let value: LitStr = meta.value()?.parse()?;
+                name = Some(value.value());
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            } else if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                meta.input.parse::<TokenStream2>()?;
+            }
+            Ok(())
+        });
+    }
+    name
+}
+
+fn is_option(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    path.path.segments.last().is_some_and(|segment| {
+        segment.ident == "Option"
+            && matches!(
+                &segment.arguments,
+                PathArguments::AngleBracketed(args)
+                    if matches!(args.args.first(), Some(GenericArgument::Type(_)))
+            )
+    })
+}
+
+/// Generates the body of [`Validate::validate`], which checks each field of `self`.
+pub(crate) fn validate_body(
+    fields: &[ValidatedField],
+    server_fn_path: &Path,
+) -> TokenStream2 {
+    let checks = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let name = &field.name;
+        let rules = field.rules.iter().map(|rule| {
+            let add_message = |check: TokenStream2| {
+                let message = match &rule.message {
+                    Some(message) => quote! { #message.to_string() },
+                    None => quote! { message },
+                };
+                quote! {
+                    if let ::std::result::Result::Err(message) = #check {
+                        errors.add(#name, #message);
+                    }
+                }
+            };
+            let check = match &rule.kind {
+                RuleKind::Required => {
+                    return add_message(quote! {
+                        #server_fn_path::validate::rules::required(&self.#ident)
+                    });
+                }
+                RuleKind::Length { min, max } => {
+                    let min = optional(min);
+                    let max = optional(max);
+                    add_message(quote! {
+                        #server_fn_path::validate::rules::length(value, #min, #max)
+                    })
+                }
+                RuleKind::Range { min, max } => {
+                    let min = optional(min);
+                    let max = optional(max);
+                    add_message(quote! {
+                        #server_fn_path::validate::rules::range(value, #min, #max)
+                    })
+                }
+                RuleKind::Regex(pattern) => {
+                    let check = add_message(quote! {
+                        #server_fn_path::validate::rules::regex(value, &REGEX)
+                    });
+                    quote_spanned! {pattern.span()=>
+                        {
+                            static REGEX: ::std::sync::LazyLock<#server_fn_path::validate::Regex> =
+                                ::std::sync::LazyLock::new(|| {
+                                    #server_fn_path::validate::Regex::new(#pattern)
+                                        .expect("the regex was checked when the macro was expanded")
+                                });
+                            #check
+                        }
+                    }
+                }
+                RuleKind::Custom(function) => add_message(quote! {
+                    #function(value)
+                }),
+                RuleKind::Nested => match &rule.message {
+                    Some(message) => quote! {
+                        if #server_fn_path::validate::Validate::validate(value).is_err() {
+                            errors.add(#name, #message.to_string());
+                        }
+                    },
+                    None => quote! {
+                        if let ::std::result::Result::Err(nested) =
+                            #server_fn_path::validate::Validate::validate(value)
+                        {
+                            errors.extend_nested(#name, nested);
+                        }
+                    },
+                },
+            };
+            // every rule but `required` is skipped for a field that is `None`
+            if field.is_option {
+                quote! {
+                    if let ::std::option::Option::Some(value) = &self.#ident {
+                        #check
+                    }
+                }
+            } else {
+                quote! {
+                    {
+                        let value = &self.#ident;
+                        #check
+                    }
+                }
+            }
+        });
+        quote! { #(#rules)* }
+    });
+
+    quote! {
+        #[allow(unused_mut)]
+        let mut errors = #server_fn_path::validate::FieldErrors::new();
+        #(#checks)*
+        errors.into_result()
+    }
+}
+
+fn optional(expr: &Option<Expr>) -> TokenStream2 {
+    match expr {
+        Some(expr) => quote! { ::std::option::Option::Some(#expr) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
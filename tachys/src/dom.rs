@@ -1,5 +1,6 @@
+use crate::renderer::{types::HtmlElement, Rndr};
 use wasm_bindgen::JsCast;
-use web_sys::{Document, HtmlElement, Window};
+use web_sys::{Document, Window};
 
 thread_local! {
     pub(crate) static WINDOW: web_sys::Window = web_sys::window().unwrap();
@@ -32,7 +33,7 @@ pub fn document() -> Document {
 /// Panics if there is no `<body>` in the current document, or if it is called outside a browser
 /// environment.
 pub fn body() -> HtmlElement {
-    document().body().unwrap()
+    Rndr::body()
 }
 
 /// Helper function to extract [`Event.target`](https://developer.mozilla.org/en-US/docs/Web/API/Event/target)
@@ -51,20 +52,12 @@ pub fn event_target_value<T>(event: &T) -> String
 where
     T: JsCast,
 {
-    event
-        .unchecked_ref::<web_sys::Event>()
-        .target()
-        .unwrap()
-        .unchecked_into::<web_sys::HtmlInputElement>()
-        .value()
+    Rndr::event_target_value(event.unchecked_ref())
 }
 
 /// Helper function to extract `event.target.checked` from an event.
 ///
 /// This is useful in the `on:change` listeners for an `<input type="checkbox">` element.
 pub fn event_target_checked(ev: &web_sys::Event) -> bool {
-    ev.target()
-        .unwrap()
-        .unchecked_into::<web_sys::HtmlInputElement>()
-        .checked()
+    Rndr::event_target_checked(ev)
 }
@@ -2,7 +2,7 @@ use super::{Attribute, NextAttribute};
 use crate::{
     erased::{Erased, ErasedLocal},
     html::attribute::NamedAttributeKey,
-    renderer::{dom::Element, Rndr},
+    renderer::{types::Element, Rndr},
 };
 use std::{any::TypeId, fmt::Debug, mem};
 #[cfg(feature = "ssr")]
@@ -384,7 +384,7 @@ impl Attribute for Vec<AnyAttribute> {
                         Rndr::set_property(
                             &old.el,
                             &prop_name,
-                            &wasm_bindgen::JsValue::UNDEFINED,
+                            &crate::renderer::types::PropertyValue::UNDEFINED,
                         );
                     }
                     NamedAttributeKey::Attribute(key) => {
@@ -9,6 +9,8 @@ pub mod global;
 mod key;
 pub(crate) mod maybe_next_attr_erasure_macros;
 mod value;
+/// Helpers for the View Transition API.
+pub mod view_transition;
 
 use crate::view::{Position, ToTemplate};
 pub use key::*;
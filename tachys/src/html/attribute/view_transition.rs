@@ -0,0 +1,48 @@
+use crate::html::style::{style, IntoStyleValue, Style};
+
+/// The CSS property that names an element for view transitions.
+const VIEW_TRANSITION_NAME: &str = "view-transition-name";
+
+/// Returns an [`Attribute`](super::Attribute) that sets the
+/// [`view-transition-name`](https://developer.mozilla.org/en-US/docs/Web/CSS/view-transition-name)
+/// of an element.
+///
+/// During a view transition, an element that has the same name on the old page and the new page
+/// is animated from its old position and size to its new ones, rather than cross-fading along
+/// with the rest of the page. This allows shared-element transitions, like a thumbnail in a list
+/// that grows into the header image of a detail page. A name should only be used by one element
+/// on the page at a time.
+///
+/// ```
+/// # use tachys::prelude::*;
+/// # use tachys::html::{attribute::view_transition::view_transition_name, element::img};
+/// fn thumbnail(id: usize) -> impl RenderHtml {
+///     img()
+///         .src(format!("/images/{id}.jpg"))
+///         .add_any_attr(view_transition_name(format!("image-{id}")))
+/// }
+/// ```
+#[inline(always)]
+pub fn view_transition_name<V>(name: V) -> Style<(&'static str, V)>
+where
+    V: IntoStyleValue,
+{
+    style((VIEW_TRANSITION_NAME, name))
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::view_transition_name;
+    use crate::{
+        html::element::img,
+        view::{add_attr::AddAnyAttr, RenderHtml},
+    };
+
+    #[test]
+    fn name_is_rendered_as_a_style() {
+        let html = img()
+            .add_any_attr(view_transition_name("image-1"))
+            .to_html();
+        assert_eq!(html, r#"<img style="view-transition-name:image-1;">"#);
+    }
+}
@@ -855,8 +855,8 @@ where
         }
     }
 }
-/*
-#[cfg(all(test, feature = "testing"))]
+
+#[cfg(all(test, feature = "mock_dom"))]
 mod tests {
     #[cfg(all(feature = "nightly", rustc_nightly))]
     use super::RenderHtml;
@@ -866,31 +866,30 @@ mod tests {
             attribute::global::GlobalAttributes,
             element::{em, ElementChild, Main},
         },
-        renderer::mock_dom::MockDom,
         view::Render,
     };
 
     #[test]
     fn mock_dom_creates_element() {
-        let el: HtmlElement<Main, _, _, MockDom> =
+        let el: HtmlElement<Main, _, _> =
             main().child(p().id("test").lang("en").child("Hello, world!"));
         let el = el.build();
         assert_eq!(
-            el.el.to_debug_html(),
+            el.el.outer_html(),
             "<main><p id=\"test\" lang=\"en\">Hello, world!</p></main>"
         );
     }
 
     #[test]
     fn mock_dom_creates_element_with_several_children() {
-        let el: HtmlElement<Main, _, _, MockDom> = main().child(p().child((
+        let el: HtmlElement<Main, _, _> = main().child(p().child((
             "Hello, ",
             em().child("beautiful"),
             " world!",
         )));
         let el = el.build();
         assert_eq!(
-            el.el.to_debug_html(),
+            el.el.outer_html(),
             "<main><p>Hello, <em>beautiful</em> world!</p></main>"
         );
     }
@@ -900,7 +899,7 @@ mod tests {
     fn html_render_allocates_appropriate_buffer() {
         use crate::view::static_types::Static;
 
-        let el: HtmlElement<Main, _, _, MockDom> = main().child(p().child((
+        let el: HtmlElement<Main, _, _> = main().child(p().child((
             Static::<"Hello, ">,
             em().child(Static::<"beautiful">),
             Static::<" world!">,
@@ -914,4 +913,3 @@ mod tests {
         assert_eq!(html.len(), allocated_len);
     }
 }
- */
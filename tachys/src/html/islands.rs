@@ -11,8 +11,45 @@ pub struct Island<View> {
     has_element_representation: bool,
     component: &'static str,
     props_json: String,
+    hydration: IslandHydration,
     view: View,
 }
+
+/// When an [`Island`] should be hydrated in the browser.
+///
+/// Every strategy other than [`IslandHydration::Eager`] is rendered as a
+/// `data-hydrate` attribute on the island, which the islands hydration script
+/// uses to defer hydrating the island (and any islands nested inside it).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IslandHydration {
+    /// Hydrate as soon as the WASM module has loaded.
+    #[default]
+    Eager,
+    /// Hydrate when the browser is idle, using `requestIdleCallback`.
+    Idle,
+    /// Hydrate when the island scrolls into the viewport, using an
+    /// `IntersectionObserver`.
+    Visible,
+    /// Hydrate on the first interaction with the island. Events that would
+    /// have been handled by the island (like `click`, `input`, or `submit`)
+    /// are held and replayed once it has hydrated.
+    Interaction,
+    /// Hydrate once the given CSS media query matches.
+    Media(&'static str),
+}
+
+impl IslandHydration {
+    /// The value of the `data-hydrate` attribute for this strategy, if any.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            IslandHydration::Eager => None,
+            IslandHydration::Idle => Some("idle"),
+            IslandHydration::Visible => Some("visible"),
+            IslandHydration::Interaction => Some("interaction"),
+            IslandHydration::Media(_) => Some("media"),
+        }
+    }
+}
 const ISLAND_TAG: &str = "leptos-island";
 const ISLAND_CHILDREN_TAG: &str = "leptos-children";
 
@@ -24,6 +61,7 @@ impl<View> Island<View> {
                 Self::should_have_element_representation(),
             component,
             props_json: String::new(),
+            hydration: IslandHydration::Eager,
             view,
         }
     }
@@ -34,7 +72,18 @@ impl<View> Island<View> {
         self
     }
 
-    fn open_tag(component: &'static str, props: &str, buf: &mut String) {
+    /// Sets the strategy used to decide when this island is hydrated.
+    pub fn with_hydration(mut self, hydration: IslandHydration) -> Self {
+        self.hydration = hydration;
+        self
+    }
+
+    fn open_tag(
+        component: &'static str,
+        props: &str,
+        hydration: IslandHydration,
+        buf: &mut String,
+    ) {
         buf.push('<');
         buf.push_str(ISLAND_TAG);
         buf.push(' ');
@@ -46,6 +95,16 @@ impl<View> Island<View> {
             buf.push_str(&html_escape::encode_double_quoted_attribute(&props));
             buf.push('"');
         }
+        if let Some(strategy) = hydration.as_str() {
+            buf.push_str(" data-hydrate=\"");
+            buf.push_str(strategy);
+            buf.push('"');
+        }
+        if let IslandHydration::Media(query) = hydration {
+            buf.push_str(" data-media=\"");
+            buf.push_str(&html_escape::encode_double_quoted_attribute(query));
+            buf.push('"');
+        }
         buf.push('>');
     }
 
@@ -104,12 +163,14 @@ where
             has_element_representation,
             component,
             props_json,
+            hydration,
             view,
         } = self;
         Island {
             has_element_representation,
             component,
             props_json,
+            hydration,
             view: view.add_any_attr(attr),
         }
     }
@@ -137,12 +198,14 @@ where
             has_element_representation,
             component,
             props_json,
+            hydration,
             view,
         } = self;
         Island {
             has_element_representation,
             component,
             props_json,
+            hydration,
             view: view.resolve().await,
         }
     }
@@ -157,7 +220,12 @@ where
     ) {
         let has_element = self.has_element_representation;
         if has_element {
-            Self::open_tag(self.component, &self.props_json, buf);
+            Self::open_tag(
+                self.component,
+                &self.props_json,
+                self.hydration,
+                buf,
+            );
         }
         self.view.to_html_with_buf(
             buf,
@@ -185,7 +253,12 @@ where
         // insert the opening tag synchronously
         let mut tag = String::new();
         if has_element {
-            Self::open_tag(self.component, &self.props_json, &mut tag);
+            Self::open_tag(
+                self.component,
+                &self.props_json,
+                self.hydration,
+                &mut tag,
+            );
         }
         buf.push_sync(&tag);
 
@@ -228,6 +301,7 @@ where
             has_element_representation: self.has_element_representation,
             component: self.component,
             props_json: self.props_json,
+            hydration: self.hydration,
             view: self.view.into_owned(),
         }
     }
@@ -397,7 +471,8 @@ where
 
         if let Some(on_hydrate) = self.on_hydrate {
             use crate::{
-                hydration::failed_to_cast_element, renderer::CastFrom,
+                hydration::failed_to_cast_element,
+                renderer::{CastFrom, Rndr},
             };
 
             let el =
@@ -408,14 +483,7 @@ where
                             cursor.current(),
                         )
                     });
-            let cb = wasm_bindgen::closure::Closure::wrap(
-                on_hydrate as Box<dyn Fn()>,
-            );
-            _ = js_sys::Reflect::set(
-                &el,
-                &wasm_bindgen::JsValue::from_str("$$on_hydrate"),
-                &cb.into_js_value(),
-            );
+            Rndr::set_on_hydrate(&el, on_hydrate);
         }
     }
 
@@ -426,3 +494,39 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Island, IslandHydration};
+    use crate::view::RenderHtml;
+
+    #[test]
+    fn eager_islands_have_no_hydration_attribute() {
+        let html = Island::new("Counter", "0").to_html();
+        assert_eq!(
+            html,
+            "<leptos-island data-component=\"Counter\">0</leptos-island>"
+        );
+    }
+
+    #[test]
+    fn hydration_strategy_is_rendered_as_attributes() {
+        let html = Island::new("Counter", "0")
+            .with_hydration(IslandHydration::Visible)
+            .to_html();
+        assert_eq!(
+            html,
+            "<leptos-island data-component=\"Counter\" \
+             data-hydrate=\"visible\">0</leptos-island>"
+        );
+
+        let html = Island::new("Menu", "menu")
+            .with_hydration(IslandHydration::Media("(max-width: 600px)"))
+            .to_html();
+        assert_eq!(
+            html,
+            "<leptos-island data-component=\"Menu\" data-hydrate=\"media\" \
+             data-media=\"(max-width: 600px)\">menu</leptos-island>"
+        );
+    }
+}
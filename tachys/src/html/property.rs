@@ -6,12 +6,11 @@ use crate::{
     html::attribute::{
         maybe_next_attr_erasure_macros::next_attr_combine, NamedAttributeKey,
     },
-    renderer::Rndr,
+    renderer::{types::PropertyValue, Rndr},
     view::{Position, ToTemplate},
 };
 use send_wrapper::SendWrapper;
 use std::{borrow::Cow, sync::Arc};
-use wasm_bindgen::JsValue;
 
 /// Creates an [`Attribute`] that will set a DOM property on an element.
 #[inline(always)]
@@ -194,7 +193,7 @@ pub trait IntoProperty {
 macro_rules! prop_type {
     ($prop_type:ty) => {
         impl IntoProperty for $prop_type {
-            type State = (crate::renderer::types::Element, JsValue);
+            type State = (crate::renderer::types::Element, PropertyValue);
             type Cloneable = Self;
             type CloneableOwned = Self;
 
@@ -235,7 +234,7 @@ macro_rules! prop_type {
         }
 
         impl IntoProperty for Option<$prop_type> {
-            type State = (crate::renderer::types::Element, JsValue);
+            type State = (crate::renderer::types::Element, PropertyValue);
             type Cloneable = Self;
             type CloneableOwned = Self;
 
@@ -286,7 +285,7 @@ macro_rules! prop_type {
 macro_rules! prop_type_str {
     ($prop_type:ty) => {
         impl IntoProperty for $prop_type {
-            type State = (crate::renderer::types::Element, JsValue);
+            type State = (crate::renderer::types::Element, PropertyValue);
             type Cloneable = Arc<str>;
             type CloneableOwned = Arc<str>;
 
@@ -295,7 +294,7 @@ macro_rules! prop_type_str {
                 el: &crate::renderer::types::Element,
                 key: &str,
             ) -> Self::State {
-                let value = JsValue::from(&*self);
+                let value = PropertyValue::from(&*self);
                 Rndr::set_property_or_value(el, key, &value);
                 (el.clone(), value)
             }
@@ -305,14 +304,14 @@ macro_rules! prop_type_str {
                 el: &crate::renderer::types::Element,
                 key: &str,
             ) -> Self::State {
-                let value = JsValue::from(&*self);
+                let value = PropertyValue::from(&*self);
                 Rndr::set_property_or_value(el, key, &value);
                 (el.clone(), value)
             }
 
             fn rebuild(self, state: &mut Self::State, key: &str) {
                 let (el, prev) = state;
-                let value = JsValue::from(&*self);
+                let value = PropertyValue::from(&*self);
                 Rndr::set_property_or_value(el, key, &value);
                 *prev = value;
             }
@@ -329,7 +328,7 @@ macro_rules! prop_type_str {
         }
 
         impl IntoProperty for Option<$prop_type> {
-            type State = (crate::renderer::types::Element, JsValue);
+            type State = (crate::renderer::types::Element, PropertyValue);
             type Cloneable = Option<Arc<str>>;
             type CloneableOwned = Option<Arc<str>>;
 
@@ -339,7 +338,8 @@ macro_rules! prop_type_str {
                 key: &str,
             ) -> Self::State {
                 let was_some = self.is_some();
-                let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+                let value =
+                    PropertyValue::from(self.map(|n| PropertyValue::from(&*n)));
                 if was_some {
                     Rndr::set_property_or_value(el, key, &value);
                 }
@@ -352,7 +352,8 @@ macro_rules! prop_type_str {
                 key: &str,
             ) -> Self::State {
                 let was_some = self.is_some();
-                let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+                let value =
+                    PropertyValue::from(self.map(|n| PropertyValue::from(&*n)));
                 if was_some {
                     Rndr::set_property_or_value(el, key, &value);
                 }
@@ -361,7 +362,8 @@ macro_rules! prop_type_str {
 
             fn rebuild(self, state: &mut Self::State, key: &str) {
                 let (el, prev) = state;
-                let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+                let value =
+                    PropertyValue::from(self.map(|n| PropertyValue::from(&*n)));
                 Rndr::set_property_or_value(el, key, &value);
                 *prev = value;
             }
@@ -384,7 +386,7 @@ macro_rules! prop_type_str {
 }
 
 impl IntoProperty for Arc<str> {
-    type State = (crate::renderer::types::Element, JsValue);
+    type State = (crate::renderer::types::Element, PropertyValue);
     type Cloneable = Self;
     type CloneableOwned = Self;
 
@@ -393,7 +395,7 @@ impl IntoProperty for Arc<str> {
         el: &crate::renderer::types::Element,
         key: &str,
     ) -> Self::State {
-        let value = JsValue::from_str(self.as_ref());
+        let value = PropertyValue::from(self.as_ref());
         Rndr::set_property_or_value(el, key, &value);
         (el.clone(), value)
     }
@@ -403,14 +405,14 @@ impl IntoProperty for Arc<str> {
         el: &crate::renderer::types::Element,
         key: &str,
     ) -> Self::State {
-        let value = JsValue::from_str(self.as_ref());
+        let value = PropertyValue::from(self.as_ref());
         Rndr::set_property_or_value(el, key, &value);
         (el.clone(), value)
     }
 
     fn rebuild(self, state: &mut Self::State, key: &str) {
         let (el, prev) = state;
-        let value = JsValue::from_str(self.as_ref());
+        let value = PropertyValue::from(self.as_ref());
         Rndr::set_property_or_value(el, key, &value);
         *prev = value;
     }
@@ -425,7 +427,7 @@ impl IntoProperty for Arc<str> {
 }
 
 impl IntoProperty for Option<Arc<str>> {
-    type State = (crate::renderer::types::Element, JsValue);
+    type State = (crate::renderer::types::Element, PropertyValue);
     type Cloneable = Self;
     type CloneableOwned = Self;
 
@@ -435,7 +437,7 @@ impl IntoProperty for Option<Arc<str>> {
         key: &str,
     ) -> Self::State {
         let was_some = self.is_some();
-        let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+        let value = PropertyValue::from(self.map(|n| PropertyValue::from(&*n)));
         if was_some {
             Rndr::set_property_or_value(el, key, &value);
         }
@@ -448,7 +450,7 @@ impl IntoProperty for Option<Arc<str>> {
         key: &str,
     ) -> Self::State {
         let was_some = self.is_some();
-        let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+        let value = PropertyValue::from(self.map(|n| PropertyValue::from(&*n)));
         if was_some {
             Rndr::set_property_or_value(el, key, &value);
         }
@@ -457,7 +459,7 @@ impl IntoProperty for Option<Arc<str>> {
 
     fn rebuild(self, state: &mut Self::State, key: &str) {
         let (el, prev) = state;
-        let value = JsValue::from(self.map(|n| JsValue::from_str(&n)));
+        let value = PropertyValue::from(self.map(|n| PropertyValue::from(&*n)));
         Rndr::set_property_or_value(el, key, &value);
         *prev = value;
     }
@@ -471,7 +473,7 @@ impl IntoProperty for Option<Arc<str>> {
     }
 }
 
-prop_type!(JsValue);
+prop_type!(PropertyValue);
 prop_type!(usize);
 prop_type!(u8);
 prop_type!(u16);
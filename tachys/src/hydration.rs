@@ -1,11 +1,13 @@
 use crate::{
-    renderer::{CastFrom, Rndr},
+    renderer::{
+        types::{Element, Node, Placeholder as Comment, Text},
+        CastFrom, Rndr,
+    },
     view::{Position, PositionState},
 };
 #[cfg(any(debug_assertions, leptos_debuginfo))]
 use std::cell::Cell;
 use std::{cell::RefCell, panic::Location, rc::Rc};
-use web_sys::{Comment, Element, Node, Text};
 
 #[cfg(feature = "mark_branches")]
 const COMMENT_NODE: u16 = 8;
@@ -164,23 +166,7 @@ pub(crate) fn failed_to_cast_element(tag_name: &str, node: Node) -> Element {
     }
     #[cfg(any(debug_assertions, leptos_debuginfo))]
     {
-        let hydrating = CURRENTLY_HYDRATING
-            .take()
-            .map(|n| n.to_string())
-            .unwrap_or_else(|| "{unknown}".to_string());
-        web_sys::console::error_3(
-            &wasm_bindgen::JsValue::from_str(&format!(
-                "A hydration error occurred while trying to hydrate an \
-                 element defined at {hydrating}.\n\nThe framework expected an \
-                 HTML <{tag_name}> element, but found this instead: ",
-            )),
-            &node,
-            &wasm_bindgen::JsValue::from_str(
-                "\n\nThe hydration mismatch may have occurred slightly \
-                 earlier, but this is the first time the framework found a \
-                 node of an unexpected type.",
-            ),
-        );
+        log_hydration_error(&format!("an HTML <{tag_name}> element"), &node);
         panic!(
             "Unrecoverable hydration error. Please read the error message \
              directly above this for more details."
@@ -196,23 +182,7 @@ pub(crate) fn failed_to_cast_marker_node(node: Node) -> Comment {
     }
     #[cfg(any(debug_assertions, leptos_debuginfo))]
     {
-        let hydrating = CURRENTLY_HYDRATING
-            .take()
-            .map(|n| n.to_string())
-            .unwrap_or_else(|| "{unknown}".to_string());
-        web_sys::console::error_3(
-            &wasm_bindgen::JsValue::from_str(&format!(
-                "A hydration error occurred while trying to hydrate an \
-                 element defined at {hydrating}.\n\nThe framework expected a \
-                 marker node, but found this instead: ",
-            )),
-            &node,
-            &wasm_bindgen::JsValue::from_str(
-                "\n\nThe hydration mismatch may have occurred slightly \
-                 earlier, but this is the first time the framework found a \
-                 node of an unexpected type.",
-            ),
-        );
+        log_hydration_error("a marker node", &node);
         panic!(
             "Unrecoverable hydration error. Please read the error message \
              directly above this for more details."
@@ -228,26 +198,27 @@ pub(crate) fn failed_to_cast_text_node(node: Node) -> Text {
     }
     #[cfg(any(debug_assertions, leptos_debuginfo))]
     {
-        let hydrating = CURRENTLY_HYDRATING
-            .take()
-            .map(|n| n.to_string())
-            .unwrap_or_else(|| "{unknown}".to_string());
-        web_sys::console::error_3(
-            &wasm_bindgen::JsValue::from_str(&format!(
-                "A hydration error occurred while trying to hydrate an \
-                 element defined at {hydrating}.\n\nThe framework expected a \
-                 text node, but found this instead: ",
-            )),
-            &node,
-            &wasm_bindgen::JsValue::from_str(
-                "\n\nThe hydration mismatch may have occurred slightly \
-                 earlier, but this is the first time the framework found a \
-                 node of an unexpected type.",
-            ),
-        );
+        log_hydration_error("a text node", &node);
         panic!(
             "Unrecoverable hydration error. Please read the error message \
              directly above this for more details."
         );
     }
 }
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+fn log_hydration_error(expected: &str, node: &Node) {
+    let hydrating = CURRENTLY_HYDRATING
+        .take()
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| "{unknown}".to_string());
+    let message = format!(
+        "A hydration error occurred while trying to hydrate an element \
+         defined at {hydrating}.\n\nThe framework expected {expected}, but \
+         found this instead: ",
+    );
+    let explanation = "\n\nThe hydration mismatch may have occurred slightly \
+                       earlier, but this is the first time the framework \
+                       found a node of an unexpected type.";
+    Rndr::log_error_with_node(&message, node, explanation);
+}
@@ -9,11 +9,10 @@ use crate::{
     hydration::Cursor,
     no_attrs,
     prelude::{Mountable, Render, RenderHtml},
-    renderer::Rndr,
+    renderer::{types::PropertyValue, Rndr},
     view::{strings::StrState, Position, PositionState, ToTemplate},
 };
 use oco_ref::Oco;
-use wasm_bindgen::JsValue;
 
 /// Retained view state for [`Oco`].
 pub struct OcoStrState {
@@ -252,7 +251,7 @@ impl IntoClass for Oco<'static, str> {
 }
 
 impl IntoProperty for Oco<'static, str> {
-    type State = (crate::renderer::types::Element, JsValue);
+    type State = (crate::renderer::types::Element, PropertyValue);
     type Cloneable = Self;
     type CloneableOwned = Self;
 
@@ -261,7 +260,7 @@ impl IntoProperty for Oco<'static, str> {
         el: &crate::renderer::types::Element,
         key: &str,
     ) -> Self::State {
-        let value = JsValue::from_str(self.as_ref());
+        let value = PropertyValue::from(&*self);
         Rndr::set_property_or_value(el, key, &value);
         (el.clone(), value)
     }
@@ -271,14 +270,14 @@ impl IntoProperty for Oco<'static, str> {
         el: &crate::renderer::types::Element,
         key: &str,
     ) -> Self::State {
-        let value = JsValue::from_str(self.as_ref());
+        let value = PropertyValue::from(&*self);
         Rndr::set_property_or_value(el, key, &value);
         (el.clone(), value)
     }
 
     fn rebuild(self, state: &mut Self::State, key: &str) {
         let (el, prev) = state;
-        let value = JsValue::from_str(self.as_ref());
+        let value = PropertyValue::from(&*self);
         Rndr::set_property_or_value(el, key, &value);
         *prev = value;
     }
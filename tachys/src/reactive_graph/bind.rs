@@ -12,7 +12,10 @@ use crate::{
         property::{prop, IntoProperty},
     },
     prelude::AddAnyAttr,
-    renderer::{types::Element, RemoveEventHandler},
+    renderer::{
+        types::{Element, PropertyValue},
+        RemoveEventHandler,
+    },
     view::{Position, ToTemplate},
 };
 use reactive_graph::{
@@ -21,7 +24,6 @@ use reactive_graph::{
     wrappers::read::Signal,
 };
 use send_wrapper::SendWrapper;
-use wasm_bindgen::JsValue;
 #[cfg(feature = "reactive_stores")]
 use {
     reactive_graph::owner::Storage,
@@ -492,7 +494,7 @@ pub trait ChangeEvent {
         Self: Sized;
 }
 
-impl ChangeEvent for web_sys::Element {
+impl ChangeEvent for Element {
     fn attach_change_event<T, W>(
         &self,
         key: &str,
@@ -532,13 +534,13 @@ pub trait GetValue<T> {
     fn get_value(&self) -> T;
 }
 
-impl GetValue<String> for web_sys::Element {
+impl GetValue<String> for Element {
     fn get_value(&self) -> String {
         self.get_attribute("value").unwrap_or_default()
     }
 }
 
-impl GetValue<bool> for web_sys::Element {
+impl GetValue<bool> for Element {
     fn get_value(&self) -> bool {
         self.get_attribute("checked").unwrap_or_default() == "true"
     }
@@ -556,12 +558,12 @@ pub enum BoolOrT<T> {
 
 impl<T> IntoProperty for BoolOrT<T>
 where
-    T: IntoProperty<State = (Element, JsValue)>
-        + Into<JsValue>
+    T: IntoProperty<State = (Element, PropertyValue)>
+        + Into<PropertyValue>
         + Clone
         + 'static,
 {
-    type State = (Element, JsValue);
+    type State = (Element, PropertyValue);
     type Cloneable = Self;
     type CloneableOwned = Self;
 
@@ -617,9 +619,9 @@ where
     }
 }
 
-impl<T> From<BoolOrT<T>> for JsValue
+impl<T> From<BoolOrT<T>> for PropertyValue
 where
-    T: Into<JsValue>,
+    T: Into<PropertyValue>,
 {
     fn from(value: BoolOrT<T>) -> Self {
         match value {
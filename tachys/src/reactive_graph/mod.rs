@@ -991,62 +991,93 @@ mod reactive_stores {
     reactive_impl!(ArcField, <V>, V, false, ArcField<V>: Get<Value = V>);
 }
 
-/*
-#[cfg(test)]
+#[cfg(all(test, feature = "mock_dom"))]
 mod tests {
     use crate::{
-        html::element::{button, main, HtmlElement},
-        renderer::mock_dom::MockDom,
+        html::{
+            attribute::global::OnAttribute,
+            element::{button, main, p, ElementChild},
+            event::click,
+        },
         view::Render,
     };
-    use leptos_reactive::{create_runtime, RwSignal, SignalGet, SignalSet};
-
-    #[test]
-    fn create_dynamic_element() {
-        let rt = create_runtime();
-        let count = RwSignal::new(0);
-        let app: HtmlElement<_, _, _, MockDom> =
-            button((), move || count.get().to_string());
-        let el = app.build();
-        assert_eq!(el.el.to_debug_html(), "<button>0</button>");
-        rt.dispose();
+    use any_spawner::Executor;
+    use reactive_graph::{
+        owner::Owner,
+        signal::RwSignal,
+        traits::{Get, Set, Update},
+    };
+    use tokio::task::LocalSet;
+
+    #[tokio::test]
+    async fn update_dynamic_element() {
+        _ = Executor::init_tokio();
+        let owner = Owner::new();
+        owner.set();
+        LocalSet::new()
+            .run_until(async {
+                let count = RwSignal::new(0);
+                let app = button().child(move || count.get().to_string());
+                let el = app.build();
+                assert_eq!(el.el.outer_html(), "<button>0</button>");
+                count.set(1);
+                Executor::tick().await;
+                assert_eq!(el.el.outer_html(), "<button>1</button>");
+            })
+            .await;
     }
 
-    #[test]
-    fn update_dynamic_element() {
-        let rt = create_runtime();
-        let count = RwSignal::new(0);
-        let app: HtmlElement<_, _, _, MockDom> =
-            button((), move || count.get().to_string());
-        let el = app.build();
-        assert_eq!(el.el.to_debug_html(), "<button>0</button>");
-        count.set(1);
-        assert_eq!(el.el.to_debug_html(), "<button>1</button>");
-        rt.dispose();
+    #[tokio::test]
+    async fn update_dynamic_element_among_siblings() {
+        _ = Executor::init_tokio();
+        let owner = Owner::new();
+        owner.set();
+        LocalSet::new()
+            .run_until(async {
+                let count = RwSignal::new(0);
+                let app = main().child(button().child((
+                    "Hello, my ",
+                    move || count.get().to_string(),
+                    " friends.",
+                )));
+                let el = app.build();
+                assert_eq!(
+                    el.el.outer_html(),
+                    "<main><button>Hello, my 0 friends.</button></main>"
+                );
+                count.set(42);
+                Executor::tick().await;
+                assert_eq!(
+                    el.el.outer_html(),
+                    "<main><button>Hello, my 42 friends.</button></main>"
+                );
+            })
+            .await;
     }
 
-    #[test]
-    fn update_dynamic_element_among_siblings() {
-        let rt = create_runtime();
-        let count = RwSignal::new(0);
-        let app: HtmlElement<_, _, _, MockDom> = main(
-            (),
-            button(
-                (),
-                ("Hello, my ", move || count.get().to_string(), " friends."),
-            ),
-        );
-        let el = app.build();
-        assert_eq!(
-            el.el.to_debug_html(),
-            "<main><button>Hello, my 0 friends.</button></main>"
-        );
-        count.set(42);
-        assert_eq!(
-            el.el.to_debug_html(),
-            "<main><button>Hello, my 42 friends.</button></main>"
-        );
-        rt.dispose();
+    #[tokio::test]
+    async fn clicking_a_button_updates_the_view() {
+        _ = Executor::init_tokio();
+        let owner = Owner::new();
+        owner.set();
+        LocalSet::new()
+            .run_until(async {
+                let count = RwSignal::new(0);
+                let app = main().child((
+                    button().on(click, move |_| count.update(|n| *n += 1)),
+                    p().child(move || count.get()),
+                ));
+                let el = app.build();
+                let button = el.el.query_selector("button").unwrap();
+                button.click();
+                button.click();
+                Executor::tick().await;
+                assert_eq!(count.get(), 2);
+                assert_eq!(
+                    el.el.query_selector("p").unwrap().text_content(),
+                    "2"
+                );
+            })
+            .await;
     }
 }
- */
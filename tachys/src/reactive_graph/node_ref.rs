@@ -1,4 +1,7 @@
-use crate::html::{element::ElementType, node_ref::NodeRefContainer};
+use crate::{
+    html::{element::ElementType, node_ref::NodeRefContainer},
+    renderer::Rndr,
+};
 use reactive_graph::{
     effect::Effect,
     graph::untrack,
@@ -89,10 +92,13 @@ where
     E::Output: JsCast + 'static,
 {
     fn load(self, el: &crate::renderer::types::Element) {
-        // safe to construct SendWrapper here, because it will only run in the browser
-        // so it will always be accessed or dropped from the main thread
-        self.0
-            .set(Some(SendWrapper::new(el.clone().unchecked_into())));
+        // mock DOM elements can't be converted into `web_sys` types, so with the mock DOM the
+        // node ref is never filled
+        if let Some(el) = Rndr::cast_element(el) {
+            // safe to construct SendWrapper here, because it will only run in the browser
+            // so it will always be accessed or dropped from the main thread
+            self.0.set(Some(SendWrapper::new(el)));
+        }
     }
 }
 
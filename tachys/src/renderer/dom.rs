@@ -6,7 +6,7 @@ use super::{CastFrom, RemoveEventHandler};
 use crate::{
     dom::{document, window},
     ok_or_debug, or_debug,
-    view::{Mountable, ToTemplate},
+    view::ToTemplate,
 };
 use rustc_hash::FxHashSet;
 use std::{
@@ -212,42 +212,49 @@ impl Dom {
         web_sys::console::log_1(node);
     }
 
-    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
-    pub fn clear_children(parent: &Element) {
-        parent.set_text_content(Some(""));
+    /// Logs an error to the console, with the node between the two parts of the message.
+    pub fn log_error_with_node(before: &str, node: &Node, after: &str) {
+        web_sys::console::error_3(
+            &JsValue::from_str(before),
+            node,
+            &JsValue::from_str(after),
+        );
     }
 
-    /// Mounts the new child before the marker as its sibling.
-    ///
-    /// ## Panics
-    /// The default implementation panics if `before` does not have a parent [`crate::renderer::types::Element`].
-    pub fn mount_before<M>(new_child: &mut M, before: &Node)
-    where
-        M: Mountable,
-    {
-        let parent = Element::cast_from(
-            Self::get_parent(before).expect("could not find parent element"),
-        )
-        .expect("placeholder parent should be Element");
-        new_child.mount(&parent, Some(before));
-    }
-
-    /// Tries to mount the new child before the marker as its sibling.
-    ///
-    /// Returns `false` if the child did not have a valid parent.
-    #[track_caller]
-    pub fn try_mount_before<M>(new_child: &mut M, before: &Node) -> bool
+    /// Returns the `<html>` element.
+    pub fn document_element() -> Element {
+        document().document_element().unwrap()
+    }
+
+    /// Returns the `<head>` element, creating it if the document has none.
+    pub fn head() -> Element {
+        let document = document();
+        match document.head() {
+            Some(head) => head.unchecked_into(),
+            None => {
+                let el = document.create_element("head").unwrap();
+                _ = Self::document_element().append_child(&el);
+                el
+            }
+        }
+    }
+
+    /// Returns the `<body>` element.
+    pub fn body() -> web_sys::HtmlElement {
+        document().body().unwrap()
+    }
+
+    /// Converts the element into the given `web_sys` type, without checking it.
+    pub fn cast_element<T>(el: &Element) -> Option<T>
     where
-        M: Mountable,
+        T: JsCast,
     {
-        if let Some(parent) =
-            Self::get_parent(before).and_then(Element::cast_from)
-        {
-            new_child.mount(&parent, Some(before));
-            true
-        } else {
-            false
-        }
+        Some(el.clone().unchecked_into())
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
+    pub fn clear_children(parent: &Element) {
+        parent.set_text_content(Some(""));
     }
 
     pub fn set_property_or_value(el: &Element, key: &str, value: &JsValue) {
@@ -352,6 +359,22 @@ impl Dom {
         })
     }
 
+    /// Returns the `value` of the event's target.
+    pub fn event_target_value(ev: &web_sys::Event) -> String {
+        ev.target()
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlInputElement>()
+            .value()
+    }
+
+    /// Returns whether the event's target is `checked`.
+    pub fn event_target_checked(ev: &web_sys::Event) -> bool {
+        ev.target()
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlInputElement>()
+            .checked()
+    }
+
     pub fn event_target<T>(ev: &Event) -> T
     where
         T: CastFrom<Element>,
@@ -503,6 +526,17 @@ impl Dom {
         );
     }
 
+    /// Stores a callback on the element, to be called by the islands runtime once the element's
+    /// children have been hydrated.
+    pub fn set_on_hydrate(el: &Element, on_hydrate: Box<dyn Fn()>) {
+        let cb = Closure::wrap(on_hydrate);
+        _ = js_sys::Reflect::set(
+            el,
+            &JsValue::from_str("$$on_hydrate"),
+            &cb.into_js_value(),
+        );
+    }
+
     pub fn set_inner_html(el: &Element, html: &str) {
         el.set_inner_html(html);
     }
@@ -609,113 +643,6 @@ impl Dom {
     }
 }
 
-impl Mountable for Node {
-    fn unmount(&mut self) {
-        todo!()
-    }
-
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        Dom::insert_node(parent, self, marker);
-    }
-
-    fn try_mount(&mut self, parent: &Element, marker: Option<&Node>) -> bool {
-        Dom::try_insert_node(parent, self, marker)
-    }
-
-    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
-        let parent = Dom::get_parent(self).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self));
-            return true;
-        }
-        false
-    }
-
-    fn elements(&self) -> Vec<crate::renderer::types::Element> {
-        vec![]
-    }
-}
-
-impl Mountable for Text {
-    fn unmount(&mut self) {
-        self.remove();
-    }
-
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        Dom::insert_node(parent, self, marker);
-    }
-
-    fn try_mount(&mut self, parent: &Element, marker: Option<&Node>) -> bool {
-        Dom::try_insert_node(parent, self, marker)
-    }
-
-    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
-        let parent =
-            Dom::get_parent(self.as_ref()).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self));
-            return true;
-        }
-        false
-    }
-
-    fn elements(&self) -> Vec<crate::renderer::types::Element> {
-        vec![]
-    }
-}
-
-impl Mountable for Comment {
-    fn unmount(&mut self) {
-        self.remove();
-    }
-
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        Dom::insert_node(parent, self, marker);
-    }
-
-    fn try_mount(&mut self, parent: &Element, marker: Option<&Node>) -> bool {
-        Dom::try_insert_node(parent, self, marker)
-    }
-
-    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
-        let parent =
-            Dom::get_parent(self.as_ref()).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self));
-            return true;
-        }
-        false
-    }
-
-    fn elements(&self) -> Vec<crate::renderer::types::Element> {
-        vec![]
-    }
-}
-
-impl Mountable for Element {
-    fn unmount(&mut self) {
-        self.remove();
-    }
-
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        Dom::insert_node(parent, self, marker);
-    }
-
-    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
-        let parent =
-            Dom::get_parent(self.as_ref()).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self));
-            return true;
-        }
-        false
-    }
-
-    fn elements(&self) -> Vec<crate::renderer::types::Element> {
-        vec![self.clone()]
-    }
-}
-
 impl CastFrom<Node> for Text {
     fn cast_from(node: Node) -> Option<Text> {
         node.clone().dyn_into().ok()
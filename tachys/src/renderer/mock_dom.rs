@@ -1,24 +1,55 @@
-#![allow(unused)]
-
-//! A stupidly-simple mock DOM implementation that can be used for testing.
+//! An in-memory mock of the DOM, which can be used to test views natively, without a browser.
+//!
+//! When the `mock_dom` feature is enabled, [`Rndr`](crate::renderer::Rndr) is [`MockDom`] on
+//! native targets, so building, rebuilding, and mounting views creates and updates a tree of
+//! nodes held in memory. (WASM targets still render to the real DOM.) These nodes can then be
+//! inspected with methods like [`Element::get_attribute`], [`Element::property`],
+//! [`Element::text_content`], and [`Element::query_selector`], and events can be fired on them
+//! with [`Element::dispatch_event`].
+//!
+//! ```rust
+//! use tachys::{
+//!     html::element::{li, ul, ElementChild},
+//!     renderer::mock_dom::MockDom,
+//!     view::{Mountable, Render},
+//! };
+//!
+//! let view = ul().child((li().child("A"), li().child("B")));
+//! let mut state = view.build();
+//! state.mount(&MockDom::body(), None);
 //!
-//! Do not use this for anything real.
+//! let items = MockDom::body().query_selector_all("li");
+//! assert_eq!(items.len(), 2);
+//! assert_eq!(items[1].text_content(), "B");
+//! ```
+//!
+//! Because there is no JavaScript environment, event handlers are called with an empty
+//! placeholder for the event object: calling any of its methods will panic. The target of the
+//! event currently being dispatched is available from [`current_event_target`], which is also
+//! used by helpers like [`event_target_value`](crate::dom::event_target_value).
+//!
+//! This is intended for testing component logic, not for rendering anything real.
 
-use super::{CastFrom, DomRenderer, RemoveEventHandler, Renderer};
-use crate::{
-    html::element::{CreateElement, ElementType},
-    view::Mountable,
-};
+use super::{CastFrom, RemoveEventHandler};
+use crate::view::ToTemplate;
 use indexmap::IndexMap;
+use rustc_hash::FxHashMap;
 use slotmap::{new_key_type, SlotMap};
-use std::{borrow::Cow, cell::RefCell, rc::Rc};
-use wasm_bindgen::JsValue;
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    fmt::Write as _,
+    ops::Deref,
+    rc::Rc,
+};
+use wasm_bindgen::JsCast;
 
-/// A [`Renderer`] that uses a mock DOM structure running in Rust code.
+/// A renderer that creates and updates a mock DOM structure held in memory.
 ///
 /// This is intended as a rendering background that can be used to test component logic, without
 /// running a browser.
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct MockDom;
 
 new_key_type! {
@@ -27,723 +58,1514 @@ new_key_type! {
 }
 
 /// A mock DOM node.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Node(NodeId);
 
 /// A mock element.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Element(Node);
 
 /// A mock text node.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Text(Node);
 
 /// A mock comment node.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Placeholder(Node);
 
-impl AsRef<Node> for Node {
-    fn as_ref(&self) -> &Node {
-        self
+/// The event passed to event listeners.
+///
+/// There is no JavaScript event object in the mock DOM, so this is always `undefined`. Use
+/// [`current_event_target`] to access the target of the event.
+pub type Event = wasm_bindgen::JsValue;
+
+/// The class list of an element, which is simply the element itself.
+pub type ClassList = Element;
+
+/// The style declaration of an element, which is simply the element itself.
+pub type CssStyleDeclaration = Element;
+
+/// A `<template>` element.
+pub type TemplateElement = Element;
+
+/// The value of a DOM property.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Value {
+    /// `undefined`.
+    #[default]
+    Undefined,
+    /// `null`.
+    Null,
+    /// A boolean.
+    Bool(bool),
+    /// A number.
+    Number(f64),
+    /// A string.
+    String(String),
+}
+
+impl Value {
+    /// The `undefined` value.
+    pub const UNDEFINED: Value = Value::Undefined;
+    /// The `null` value.
+    pub const NULL: Value = Value::Null;
+
+    /// Returns the value as a string, if it is one.
+    pub fn as_string(&self) -> Option<String> {
+        match self {
+            Value::String(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a boolean, if it is one.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a number, if it is one.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Whether the value is `undefined`.
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, Value::Undefined)
+    }
+
+    /// Whether the value is `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Whether the value would be considered truthy in JavaScript.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Undefined | Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(n) => *n != 0.0 && !n.is_nan(),
+            Value::String(s) => !s.is_empty(),
+        }
     }
 }
 
-impl AsRef<Node> for Element {
-    fn as_ref(&self) -> &Node {
-        &self.0
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
     }
 }
 
-impl AsRef<Node> for Text {
-    fn as_ref(&self) -> &Node {
-        &self.0
+macro_rules! value_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(value: $ty) -> Self {
+                    Value::Number(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+value_from_number!(
+    usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128, f32, f64
+);
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
     }
 }
 
-impl AsRef<Node> for Placeholder {
-    fn as_ref(&self) -> &Node {
-        &self.0
+impl From<&String> for Value {
+    fn from(value: &String) -> Self {
+        Value::String(value.clone())
     }
 }
 
-/// Tests whether two nodes are references to the same underlying node.
-pub fn node_eq(a: impl AsRef<Node>, b: impl AsRef<Node>) -> bool {
-    a.as_ref() == b.as_ref()
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
 }
 
-impl From<Text> for Node {
-    fn from(value: Text) -> Self {
-        Node(value.0 .0)
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Value::Undefined)
     }
 }
 
-impl From<Element> for Node {
-    fn from(value: Element) -> Self {
-        Node(value.0 .0)
+struct NodeData {
+    parent: Option<NodeId>,
+    kind: NodeKind,
+}
+
+enum NodeKind {
+    Text(String),
+    Comment(String),
+    Element(Box<ElementData>),
+}
+
+#[derive(Default)]
+struct ElementData {
+    tag: String,
+    namespace: Option<String>,
+    attrs: IndexMap<String, String>,
+    props: FxHashMap<String, Value>,
+    children: Vec<NodeId>,
+    listeners: Vec<Listener>,
+}
+
+type SharedListener = Rc<RefCell<Box<dyn FnMut(Event)>>>;
+
+struct Listener {
+    id: usize,
+    name: String,
+    capture: bool,
+    delegated: bool,
+    cb: SharedListener,
+}
+
+thread_local! {
+    static NODES: RefCell<SlotMap<NodeId, NodeData>> = Default::default();
+    static NEXT_LISTENER_ID: Cell<usize> = const { Cell::new(0) };
+    static EVENT_TARGETS: RefCell<Vec<Element>> = const { RefCell::new(Vec::new()) };
+    static TEMPLATE_CACHE: RefCell<Vec<(Cow<'static, str>, Element)>> = Default::default();
+    static DOCUMENT: Document = Document::new();
+}
+
+/// The root elements of the mock document.
+struct Document {
+    html: Element,
+    head: Element,
+    body: Element,
+}
+
+impl Document {
+    fn new() -> Self {
+        let html = MockDom::create_element("html", None);
+        let head = MockDom::create_element("head", None);
+        let body = MockDom::create_element("body", None);
+        MockDom::insert_node(&html, &head, None);
+        MockDom::insert_node(&html, &body, None);
+        Self { html, head, body }
     }
 }
 
-impl From<Placeholder> for Node {
-    fn from(value: Placeholder) -> Self {
-        Node(value.0 .0)
+fn with_nodes<U>(f: impl FnOnce(&mut SlotMap<NodeId, NodeData>) -> U) -> U {
+    NODES.with_borrow_mut(f)
+}
+
+fn insert(nodes: &mut SlotMap<NodeId, NodeData>, kind: NodeKind) -> NodeId {
+    nodes.insert(NodeData { parent: None, kind })
+}
+
+fn element_data(
+    nodes: &SlotMap<NodeId, NodeData>,
+    id: NodeId,
+) -> Option<&ElementData> {
+    match &nodes.get(id)?.kind {
+        NodeKind::Element(data) => Some(data),
+        _ => None,
     }
 }
 
-impl Element {
-    /// Outputs an HTML form of the element, for testing and debugging purposes.
-    pub fn to_debug_html(&self) -> String {
-        let mut buf = String::new();
-        self.debug_html(&mut buf);
-        buf
+fn element_data_mut(
+    nodes: &mut SlotMap<NodeId, NodeData>,
+    id: NodeId,
+) -> Option<&mut ElementData> {
+    match &mut nodes.get_mut(id)?.kind {
+        NodeKind::Element(data) => Some(data),
+        _ => None,
     }
 }
 
-/// The DOM data associated with a particular node.
-#[derive(Debug, PartialEq, Eq)]
-pub struct NodeData {
-    /// The node's parent.
-    pub parent: Option<NodeId>,
-    /// The node itself.
-    pub ty: NodeType,
+fn children(nodes: &SlotMap<NodeId, NodeData>, id: NodeId) -> &[NodeId] {
+    element_data(nodes, id)
+        .map(|data| data.children.as_slice())
+        .unwrap_or_default()
 }
 
-trait DebugHtml {
-    fn debug_html(&self, buf: &mut String);
+fn detach(nodes: &mut SlotMap<NodeId, NodeData>, id: NodeId) {
+    let parent = nodes.get_mut(id).and_then(|node| node.parent.take());
+    if let Some(data) =
+        parent.and_then(|parent| element_data_mut(nodes, parent))
+    {
+        data.children.retain(|child| *child != id);
+    }
 }
 
-impl DebugHtml for Element {
-    fn debug_html(&self, buf: &mut String) {
-        Document::with_node(self.0 .0, |node| {
-            node.debug_html(buf);
-        });
+fn insert_before(
+    nodes: &mut SlotMap<NodeId, NodeData>,
+    parent: NodeId,
+    child: NodeId,
+    anchor: Option<NodeId>,
+) -> bool {
+    // a node cannot be inserted into itself or one of its descendants
+    let mut ancestor = Some(parent);
+    while let Some(id) = ancestor {
+        if id == child {
+            return false;
+        }
+        ancestor = nodes.get(id).and_then(|node| node.parent);
+    }
+    if element_data(nodes, parent).is_none() || !nodes.contains_key(child) {
+        return false;
+    }
+    if let Some(anchor) = anchor {
+        if !children(nodes, parent).contains(&anchor) {
+            return false;
+        }
     }
+
+    detach(nodes, child);
+    nodes[child].parent = Some(parent);
+    let data = element_data_mut(nodes, parent).unwrap();
+    let index = anchor
+        .and_then(|anchor| data.children.iter().position(|c| *c == anchor))
+        .unwrap_or(data.children.len());
+    data.children.insert(index, child);
+    true
 }
 
-impl DebugHtml for Text {
-    fn debug_html(&self, buf: &mut String) {
-        Document::with_node(self.0 .0, |node| {
-            node.debug_html(buf);
-        });
+fn deep_clone(nodes: &mut SlotMap<NodeId, NodeData>, id: NodeId) -> NodeId {
+    let kind = match &nodes[id].kind {
+        NodeKind::Text(text) => NodeKind::Text(text.clone()),
+        NodeKind::Comment(text) => NodeKind::Comment(text.clone()),
+        NodeKind::Element(data) => NodeKind::Element(Box::new(ElementData {
+            tag: data.tag.clone(),
+            namespace: data.namespace.clone(),
+            attrs: data.attrs.clone(),
+            ..Default::default()
+        })),
+    };
+    let clone = insert(nodes, kind);
+    for child in children(nodes, id).to_vec() {
+        let child = deep_clone(nodes, child);
+        insert_before(nodes, clone, child, None);
     }
+    clone
 }
 
-impl DebugHtml for Node {
-    fn debug_html(&self, buf: &mut String) {
-        Document::with_node(self.0, |node| {
-            node.debug_html(buf);
-        });
+fn text_content(
+    nodes: &SlotMap<NodeId, NodeData>,
+    id: NodeId,
+    buf: &mut String,
+) {
+    match nodes.get(id).map(|node| &node.kind) {
+        Some(NodeKind::Text(text)) => buf.push_str(text),
+        Some(NodeKind::Element(data)) => {
+            for child in &data.children {
+                text_content(nodes, *child, buf);
+            }
+        }
+        _ => {}
+    }
+}
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+    "source", "track", "wbr",
+];
+
+fn outer_html(nodes: &SlotMap<NodeId, NodeData>, id: NodeId, buf: &mut String) {
+    match nodes.get(id).map(|node| &node.kind) {
+        Some(NodeKind::Text(text)) => {
+            buf.push_str(&html_escape::encode_text(text))
+        }
+        Some(NodeKind::Comment(text)) => {
+            _ = write!(buf, "<!--{text}-->");
+        }
+        Some(NodeKind::Element(data)) => {
+            buf.push('<');
+            buf.push_str(&data.tag);
+            for (name, value) in &data.attrs {
+                _ = write!(
+                    buf,
+                    " {name}=\"{}\"",
+                    html_escape::encode_double_quoted_attribute(value)
+                );
+            }
+            buf.push('>');
+            if !VOID_ELEMENTS.contains(&data.tag.as_str()) {
+                inner_html(nodes, id, buf);
+                _ = write!(buf, "</{}>", data.tag);
+            }
+        }
+        None => {}
+    }
+}
+
+fn inner_html(nodes: &SlotMap<NodeId, NodeData>, id: NodeId, buf: &mut String) {
+    for child in children(nodes, id) {
+        outer_html(nodes, *child, buf);
     }
 }
 
-impl DebugHtml for NodeData {
-    fn debug_html(&self, buf: &mut String) {
-        match &self.ty {
-            NodeType::Text(text) => buf.push_str(text),
-            NodeType::Element {
-                tag,
-                attrs,
-                children,
-            } => {
-                buf.push('<');
-                buf.push_str(tag);
-                for (k, v) in attrs {
-                    buf.push(' ');
-                    buf.push_str(k);
-                    buf.push_str("=\"");
-                    buf.push_str(v);
-                    buf.push('"');
+/// Parses an HTML fragment and appends the nodes it contains to `parent`.
+///
+/// This is a very forgiving parser, which only understands as much HTML as views and templates
+/// generate: elements, attributes, text, and comments.
+fn parse_html(
+    nodes: &mut SlotMap<NodeId, NodeData>,
+    parent: NodeId,
+    html: &str,
+    namespace: Option<&str>,
+) {
+    let mut open = vec![parent];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let current = *open.last().unwrap();
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").unwrap_or(comment.len());
+            let node = insert(nodes, NodeKind::Comment(comment[..end].into()));
+            insert_before(nodes, current, node, None);
+            rest = comment.get(end + 3..).unwrap_or_default();
+        } else if let Some(comment) = rest.strip_prefix("<!") {
+            // includes the empty `<!>` comments used as markers in templates
+            let end = comment.find('>').unwrap_or(comment.len());
+            let node = insert(nodes, NodeKind::Comment(comment[..end].into()));
+            insert_before(nodes, current, node, None);
+            rest = comment.get(end + 1..).unwrap_or_default();
+        } else if let Some(closing) = rest.strip_prefix("</") {
+            let end = closing.find('>').unwrap_or(closing.len());
+            let tag = closing[..end].trim();
+            let matching = open.iter().skip(1).rposition(|id| {
+                element_data(nodes, *id).is_some_and(|data| data.tag == tag)
+            });
+            if let Some(index) = matching {
+                open.truncate(index + 1);
+            }
+            rest = closing.get(end + 1..).unwrap_or_default();
+        } else if rest.starts_with('<')
+            && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            let (el, self_closing, remaining) =
+                parse_start_tag(nodes, &rest[1..], namespace);
+            insert_before(nodes, current, el, None);
+            rest = remaining;
+
+            let tag = element_data(nodes, el).unwrap().tag.clone();
+            if tag == "script" || tag == "style" {
+                let close = format!("</{tag}");
+                let end = rest.find(&close).unwrap_or(rest.len());
+                if end > 0 {
+                    let text =
+                        insert(nodes, NodeKind::Text(rest[..end].into()));
+                    insert_before(nodes, el, text, None);
                 }
-                buf.push('>');
+                rest = &rest[end..];
+                open.push(el);
+            } else if !self_closing && !VOID_ELEMENTS.contains(&tag.as_str()) {
+                open.push(el);
+            }
+        } else {
+            let end = rest[1..].find('<').map(|i| i + 1).unwrap_or(rest.len());
+            let text = html_escape::decode_html_entities(&rest[..end]);
+            let node = insert(nodes, NodeKind::Text(text.into_owned()));
+            insert_before(nodes, current, node, None);
+            rest = &rest[end..];
+        }
+    }
+}
 
-                for child in children {
-                    child.debug_html(buf);
+/// Parses the tag name and attributes of a start tag (after the `<`), returning the new
+/// element, whether it was self-closing, and the remaining input.
+fn parse_start_tag<'a>(
+    nodes: &mut SlotMap<NodeId, NodeData>,
+    input: &'a str,
+    namespace: Option<&str>,
+) -> (NodeId, bool, &'a str) {
+    let is_name_end =
+        |c: char| c.is_whitespace() || c == '>' || c == '/' || c == '=';
+    let end = input.find(is_name_end).unwrap_or(input.len());
+    let mut data = ElementData {
+        tag: input[..end].to_string(),
+        namespace: namespace.map(ToString::to_string),
+        ..Default::default()
+    };
+    let mut rest = &input[end..];
+    let mut self_closing = false;
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        } else if let Some(after) = rest.strip_prefix("/>") {
+            self_closing = true;
+            rest = after;
+            break;
+        } else if let Some(after) = rest.strip_prefix('>') {
+            rest = after;
+            break;
+        } else if let Some(after) = rest.strip_prefix('/') {
+            rest = after;
+            continue;
+        }
+
+        let end = rest.find(is_name_end).unwrap_or(rest.len()).max(1);
+        let name = rest[..end].to_string();
+        rest = rest[end..].trim_start();
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (value, remaining) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let after = &after[1..];
+                    let end = after.find(quote).unwrap_or(after.len());
+                    (&after[..end], after.get(end + 1..).unwrap_or_default())
                 }
+                _ => {
+                    let end = after
+                        .find(|c: char| c.is_whitespace() || c == '>')
+                        .unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+            rest = remaining;
+            html_escape::decode_html_entities(value).into_owned()
+        } else {
+            String::new()
+        };
+        data.attrs.entry(name).or_insert(value);
+    }
 
-                buf.push_str("</");
-                buf.push_str(tag);
-                buf.push('>');
-            }
-            NodeType::Placeholder => buf.push_str("<!>"),
+    let el = insert(nodes, NodeKind::Element(Box::new(data)));
+    (el, self_closing, rest)
+}
+
+/// Returns the element that is the target of the event currently being dispatched, if any.
+pub fn current_event_target() -> Option<Element> {
+    EVENT_TARGETS.with_borrow(|targets| targets.last().cloned())
+}
+
+/// Events that do not bubble up to the target's ancestors.
+const NON_BUBBLING_EVENTS: &[&str] = &[
+    "blur",
+    "focus",
+    "load",
+    "mouseenter",
+    "mouseleave",
+    "pointerenter",
+    "pointerleave",
+    "scroll",
+];
+
+fn listeners(
+    id: NodeId,
+    filter: impl Fn(&Listener) -> bool,
+) -> Vec<SharedListener> {
+    with_nodes(|nodes| {
+        element_data(nodes, id)
+            .map(|data| {
+                data.listeners
+                    .iter()
+                    .filter(|listener| filter(listener))
+                    .map(|listener| Rc::clone(&listener.cb))
+                    .collect()
+            })
+            .unwrap_or_default()
+    })
+}
+
+fn dispatch(target: &Element, name: &str) {
+    // the target and its ancestors, from the target up to the root
+    let path = with_nodes(|nodes| {
+        let mut path = vec![target.id()];
+        while let Some(parent) = nodes
+            .get(*path.last().unwrap())
+            .and_then(|node| node.parent)
+        {
+            path.push(parent);
+        }
+        path
+    });
+    let bubbles = !NON_BUBBLING_EVENTS.contains(&name);
+
+    EVENT_TARGETS.with_borrow_mut(|targets| targets.push(target.clone()));
+
+    // capture phase
+    for id in path.iter().skip(1).rev() {
+        for cb in listeners(*id, |l| l.name == name && l.capture) {
+            (cb.borrow_mut())(Event::UNDEFINED);
         }
     }
+    // target and bubble phases
+    for (index, id) in path.iter().enumerate() {
+        if index > 0 && !bubbles {
+            break;
+        }
+        let disabled = Element(Node(*id)).is_disabled();
+        for cb in listeners(*id, |l| {
+            l.name == name
+                && (index == 0 || !l.capture)
+                && !(l.delegated && disabled)
+        }) {
+            (cb.borrow_mut())(Event::UNDEFINED);
+        }
+    }
+
+    EVENT_TARGETS.with_borrow_mut(|targets| targets.pop());
 }
 
-/// The mock DOM document.
-#[derive(Clone)]
-pub struct Document(Rc<RefCell<SlotMap<NodeId, NodeData>>>);
+fn add_listener(
+    el: &Element,
+    name: &str,
+    capture: bool,
+    delegated: bool,
+    cb: Box<dyn FnMut(Event)>,
+) -> RemoveEventHandler<Element> {
+    let id = NEXT_LISTENER_ID.with(|next| next.replace(next.get() + 1));
+    let node = el.id();
+    with_nodes(|nodes| {
+        if let Some(data) = element_data_mut(nodes, node) {
+            data.listeners.push(Listener {
+                id,
+                name: name.to_string(),
+                capture,
+                delegated,
+                cb: Rc::new(RefCell::new(cb)),
+            });
+        }
+    });
 
-impl Document {
-    /// Creates a new document.
-    pub fn new() -> Self {
-        Document(Default::default())
+    RemoveEventHandler::new(move || {
+        _ = NODES.try_with(|nodes| {
+            if let Some(data) = element_data_mut(&mut nodes.borrow_mut(), node)
+            {
+                data.listeners.retain(|listener| listener.id != id);
+            }
+        });
+    })
+}
+
+/// Splits a `style` attribute into its declarations.
+fn parse_style(style: &str) -> IndexMap<String, String> {
+    style
+        .split(';')
+        .filter_map(|decl| {
+            let (name, value) = decl.split_once(':')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+fn serialize_style(decls: &IndexMap<String, String>) -> String {
+    decls
+        .iter()
+        .map(|(name, value)| format!("{name}: {value};"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// These mirror the methods of [`Dom`](super::dom::Dom), but operate on the mock DOM.
+#[allow(missing_docs)]
+impl MockDom {
+    pub fn intern(text: &str) -> &str {
+        text
     }
 
-    fn with_node<U>(id: NodeId, f: impl FnOnce(&NodeData) -> U) -> Option<U> {
-        DOCUMENT.with(|d| {
-            let data = d.0.borrow();
-            let data = data.get(id);
-            data.map(f)
+    pub fn create_element(tag: &str, namespace: Option<&str>) -> Element {
+        with_nodes(|nodes| {
+            Element(Node(insert(
+                nodes,
+                NodeKind::Element(Box::new(ElementData {
+                    tag: tag.to_string(),
+                    namespace: namespace.map(ToString::to_string),
+                    ..Default::default()
+                })),
+            )))
         })
     }
 
-    fn with_node_mut<U>(
-        id: NodeId,
-        f: impl FnOnce(&mut NodeData) -> U,
-    ) -> Option<U> {
-        DOCUMENT.with(|d| {
-            let mut data = d.0.borrow_mut();
-            let data = data.get_mut(id);
-            data.map(f)
+    pub fn create_text_node(text: &str) -> Text {
+        with_nodes(|nodes| {
+            Text(Node(insert(nodes, NodeKind::Text(text.into()))))
         })
     }
 
-    /// Resets the document's contents.
-    pub fn reset(&self) {
-        self.0.borrow_mut().clear();
+    pub fn create_placeholder() -> Placeholder {
+        with_nodes(|nodes| {
+            Placeholder(Node(insert(nodes, NodeKind::Comment(String::new()))))
+        })
     }
 
-    fn create_element(&self, tag: &str) -> Element {
-        Element(Node(self.0.borrow_mut().insert(NodeData {
-            parent: None,
-            ty: NodeType::Element {
-                tag: tag.to_string().into(),
-                attrs: IndexMap::new(),
-                children: Vec::new(),
-            },
-        })))
+    pub fn set_text(node: &Text, text: &str) {
+        with_nodes(|nodes| {
+            if let Some(NodeKind::Text(prev)) =
+                nodes.get_mut(node.id()).map(|node| &mut node.kind)
+            {
+                *prev = text.to_string();
+            }
+        })
+    }
+
+    pub fn set_attribute(node: &Element, name: &str, value: &str) {
+        with_nodes(|nodes| {
+            if let Some(data) = element_data_mut(nodes, node.id()) {
+                data.attrs.insert(name.to_string(), value.to_string());
+            }
+        })
     }
 
-    fn create_text_node(&self, data: &str) -> Text {
-        Text(Node(self.0.borrow_mut().insert(NodeData {
-            parent: None,
-            ty: NodeType::Text(data.to_string()),
-        })))
+    pub fn remove_attribute(node: &Element, name: &str) {
+        with_nodes(|nodes| {
+            if let Some(data) = element_data_mut(nodes, node.id()) {
+                data.attrs.shift_remove(name);
+            }
+        })
     }
 
-    fn create_placeholder(&self) -> Placeholder {
-        Placeholder(Node(self.0.borrow_mut().insert(NodeData {
-            parent: None,
-            ty: NodeType::Placeholder,
-        })))
+    pub fn insert_node(
+        parent: &Element,
+        new_child: &Node,
+        anchor: Option<&Node>,
+    ) {
+        Self::try_insert_node(parent, new_child, anchor);
     }
-}
 
-// TODO!
-impl DomRenderer for MockDom {
-    type Event = ();
-    type ClassList = ();
-    type CssStyleDeclaration = ();
-    type TemplateElement = ();
+    pub fn try_insert_node(
+        parent: &Element,
+        new_child: &Node,
+        anchor: Option<&Node>,
+    ) -> bool {
+        with_nodes(|nodes| {
+            insert_before(nodes, parent.id(), new_child.0, anchor.map(|a| a.0))
+        })
+    }
+
+    pub fn remove_node(parent: &Element, child: &Node) -> Option<Node> {
+        with_nodes(|nodes| {
+            let is_child = nodes
+                .get(child.0)
+                .is_some_and(|node| node.parent == Some(parent.id()));
+            is_child.then(|| {
+                detach(nodes, child.0);
+                child.clone()
+            })
+        })
+    }
 
-    fn set_property(el: &Self::Element, key: &str, value: &JsValue) {
-        todo!()
+    pub fn remove(node: &Node) {
+        with_nodes(|nodes| detach(nodes, node.0))
     }
 
-    fn add_event_listener(
-        el: &Self::Element,
+    pub fn get_parent(node: &Node) -> Option<Node> {
+        with_nodes(|nodes| nodes.get(node.0)?.parent.map(Node))
+    }
+
+    pub fn first_child(node: &Node) -> Option<Node> {
+        with_nodes(|nodes| children(nodes, node.0).first().copied().map(Node))
+    }
+
+    pub fn next_sibling(node: &Node) -> Option<Node> {
+        with_nodes(|nodes| {
+            let parent = nodes.get(node.0)?.parent?;
+            let siblings = children(nodes, parent);
+            let index = siblings.iter().position(|id| *id == node.0)?;
+            siblings.get(index + 1).copied().map(Node)
+        })
+    }
+
+    /// Prints an error to standard error, with the node's HTML between the two parts of the
+    /// message.
+    pub fn log_error_with_node(before: &str, node: &Node, after: &str) {
+        eprintln!("{before}{}{after}", node.outer_html());
+    }
+
+    /// Returns the `<html>` element of the mock document, which is created the first time it is
+    /// used on each thread.
+    pub fn document_element() -> Element {
+        DOCUMENT.with(|document| document.html.clone())
+    }
+
+    /// Returns the `<head>` element of the mock document.
+    pub fn head() -> Element {
+        DOCUMENT.with(|document| document.head.clone())
+    }
+
+    /// Returns the `<body>` element of the mock document.
+    pub fn body() -> Element {
+        DOCUMENT.with(|document| document.body.clone())
+    }
+
+    /// Mock elements can't be converted into `web_sys` types, so this always returns `None`.
+    pub fn cast_element<T>(_el: &Element) -> Option<T>
+    where
+        T: JsCast,
+    {
+        None
+    }
+
+    pub fn clear_children(parent: &Element) {
+        with_nodes(|nodes| {
+            for child in children(nodes, parent.id()).to_vec() {
+                detach(nodes, child);
+            }
+        })
+    }
+
+    pub fn set_property_or_value(el: &Element, key: &str, value: &Value) {
+        Self::set_property(el, key, value);
+    }
+
+    pub fn set_property(el: &Element, key: &str, value: &Value) {
+        with_nodes(|nodes| {
+            if let Some(data) = element_data_mut(nodes, el.id()) {
+                if value.is_undefined() {
+                    data.props.remove(key);
+                } else {
+                    data.props.insert(key.to_string(), value.clone());
+                }
+            }
+        })
+    }
+
+    pub fn add_event_listener(
+        el: &Element,
         name: &str,
-        cb: Box<dyn FnMut(Self::Event)>,
-    ) -> RemoveEventHandler<Self::Element> {
-        todo!()
+        cb: Box<dyn FnMut(Event)>,
+    ) -> RemoveEventHandler<Element> {
+        add_listener(el, name, false, false, cb)
+    }
+
+    pub fn add_event_listener_use_capture(
+        el: &Element,
+        name: &str,
+        cb: Box<dyn FnMut(Event)>,
+    ) -> RemoveEventHandler<Element> {
+        add_listener(el, name, true, false, cb)
+    }
+
+    /// Returns the `value` property (or attribute) of the target of the event currently being
+    /// dispatched.
+    pub fn event_target_value(_ev: &web_sys::Event) -> String {
+        let target = current_event_target().expect("event.target not found");
+        match target.property("value") {
+            Some(value) => value.as_string().unwrap_or_default(),
+            None => target.get_attribute("value").unwrap_or_default(),
+        }
+    }
+
+    /// Returns the `checked` property (or attribute) of the target of the event currently being
+    /// dispatched.
+    pub fn event_target_checked(_ev: &web_sys::Event) -> bool {
+        let target = current_event_target().expect("event.target not found");
+        match target.property("checked") {
+            Some(checked) => checked.is_truthy(),
+            None => target.has_attribute("checked"),
+        }
     }
 
-    fn add_event_listener_delegated(
-        el: &Self::Element,
+    pub fn event_target<T>(_ev: &Event) -> T
+    where
+        T: CastFrom<Element>,
+    {
+        let el = current_event_target().expect("event.target not found");
+        T::cast_from(el).expect("incorrect element type")
+    }
+
+    pub fn add_event_listener_delegated(
+        el: &Element,
         name: Cow<'static, str>,
-        delegation_key: Cow<'static, str>,
-        cb: Box<dyn FnMut(Self::Event)>,
-    ) -> RemoveEventHandler<Self::Element> {
-        todo!()
+        _delegation_key: Cow<'static, str>,
+        cb: Box<dyn FnMut(Event)>,
+    ) -> RemoveEventHandler<Element> {
+        add_listener(el, &name, false, true, cb)
     }
 
-    fn class_list(el: &Self::Element) -> Self::ClassList {
-        todo!()
+    pub fn class_list(el: &Element) -> ClassList {
+        el.clone()
     }
 
-    fn add_class(class_list: &Self::ClassList, name: &str) {
-        todo!()
+    pub fn add_class(list: &ClassList, name: &str) {
+        let mut classes = list.classes();
+        if !classes.iter().any(|class| class == name) {
+            classes.push(name.to_string());
+        }
+        Self::set_attribute(list, "class", &classes.join(" "));
     }
 
-    fn remove_class(class_list: &Self::ClassList, name: &str) {
-        todo!()
+    pub fn remove_class(list: &ClassList, name: &str) {
+        if list.has_attribute("class") {
+            let mut classes = list.classes();
+            classes.retain(|class| class != name);
+            Self::set_attribute(list, "class", &classes.join(" "));
+        }
     }
 
-    fn style(el: &Self::Element) -> Self::CssStyleDeclaration {
-        todo!()
+    pub fn style(el: &Element) -> CssStyleDeclaration {
+        el.clone()
     }
 
-    fn set_css_property(
-        style: &Self::CssStyleDeclaration,
+    pub fn set_css_property(
+        style: &CssStyleDeclaration,
         name: &str,
         value: &str,
     ) {
-        todo!()
+        let mut decls =
+            parse_style(&style.get_attribute("style").unwrap_or_default());
+        decls.insert(name.to_string(), value.to_string());
+        Self::set_attribute(style, "style", &serialize_style(&decls));
     }
 
-    fn set_inner_html(el: &Self::Element, html: &str) {
-        todo!()
+    pub fn remove_css_property(style: &CssStyleDeclaration, name: &str) {
+        if let Some(prev) = style.get_attribute("style") {
+            let mut decls = parse_style(&prev);
+            decls.shift_remove(name);
+            Self::set_attribute(style, "style", &serialize_style(&decls));
+        }
     }
 
-    fn event_target<T>(ev: &Self::Event) -> T
-    where
-        T: CastFrom<Self::Element>,
-    {
-        todo!()
+    /// The mock DOM is never hydrated from server-rendered HTML, so this does nothing.
+    pub fn set_on_hydrate(_el: &Element, _on_hydrate: Box<dyn Fn()>) {}
+
+    pub fn set_inner_html(el: &Element, html: &str) {
+        Self::clear_children(el);
+        with_nodes(|nodes| {
+            let namespace = element_data(nodes, el.id())
+                .and_then(|data| data.namespace.clone());
+            parse_html(nodes, el.id(), html, namespace.as_deref());
+        })
     }
 
-    fn get_template<V>() -> Self::TemplateElement
+    pub fn get_template<V>() -> TemplateElement
     where
-        V: crate::view::ToTemplate + 'static,
+        V: ToTemplate + 'static,
     {
-        todo!()
+        thread_local! {
+            static TEMPLATES: RefCell<Vec<(TypeId, TemplateElement)>> = Default::default();
+        }
+
+        TEMPLATES.with_borrow_mut(|t| {
+            let id = TypeId::of::<V>();
+            t.iter()
+                .find_map(|entry| (entry.0 == id).then(|| entry.1.clone()))
+                .unwrap_or_else(|| {
+                    let tpl = Self::create_element("template", None);
+                    let mut buf = String::new();
+                    V::to_template(
+                        &mut buf,
+                        &mut String::new(),
+                        &mut String::new(),
+                        &mut String::new(),
+                        &mut Default::default(),
+                    );
+                    Self::set_inner_html(&tpl, &buf);
+                    t.push((id, tpl.clone()));
+                    tpl
+                })
+        })
     }
 
-    fn clone_template(tpl: &Self::TemplateElement) -> Self::Element {
-        todo!()
+    /// Clones the contents of the template into a new document fragment, which is represented
+    /// as an element with the tag `#document-fragment`.
+    pub fn clone_template(tpl: &TemplateElement) -> Element {
+        let fragment = Self::create_element("#document-fragment", None);
+        with_nodes(|nodes| {
+            for child in children(nodes, tpl.id()).to_vec() {
+                let child = deep_clone(nodes, child);
+                insert_before(nodes, fragment.id(), child, None);
+            }
+        });
+        fragment
     }
 
-    fn create_element_from_html(html: &str) -> Self::Element {
-        todo!()
+    fn create_from_html(
+        html: Cow<'static, str>,
+        namespace: Option<&str>,
+    ) -> Element {
+        let tpl = TEMPLATE_CACHE.with_borrow_mut(|cache| {
+            if let Some((_, tpl)) = cache.iter().find(|(key, _)| html == *key) {
+                tpl.clone()
+            } else {
+                let tpl = Self::create_element("template", namespace);
+                Self::set_inner_html(&tpl, &html);
+                cache.push((html, tpl.clone()));
+                tpl
+            }
+        });
+        let fragment = Self::clone_template(&tpl);
+        fragment.children().into_iter().next().unwrap_or(fragment)
     }
-}
 
-impl Default for Document {
-    fn default() -> Self {
-        Self::new()
+    pub fn create_element_from_html(html: Cow<'static, str>) -> Element {
+        Self::create_from_html(html, None)
     }
-}
 
-thread_local! {
-    static DOCUMENT: Document = Document::new();
+    pub fn create_svg_element_from_html(html: Cow<'static, str>) -> Element {
+        Self::create_from_html(html, Some("http://www.w3.org/2000/svg"))
+    }
 }
 
-/// Returns the global document.
-pub fn document() -> Document {
-    DOCUMENT.with(Clone::clone)
-}
+impl Node {
+    fn id(&self) -> NodeId {
+        self.0
+    }
 
-/// The type of mock DOM node.
-#[derive(Debug, PartialEq, Eq)]
-pub enum NodeType {
-    /// A text node.
-    Text(String),
-    /// An element.
-    Element {
-        /// The HTML tag name.
-        tag: Cow<'static, str>,
-        /// The attributes.
-        attrs: IndexMap<String, String>,
-        /// The element's children.
-        children: Vec<Node>,
-    },
-    /// A placeholder.
-    Placeholder,
-}
+    /// The [`nodeType`](https://developer.mozilla.org/en-US/docs/Web/API/Node/nodeType) of this
+    /// node: `1` for elements, `3` for text nodes, and `8` for comments.
+    pub fn node_type(&self) -> u16 {
+        with_nodes(|nodes| match nodes.get(self.0).map(|node| &node.kind) {
+            Some(NodeKind::Element(_)) => 1,
+            Some(NodeKind::Text(_)) => 3,
+            Some(NodeKind::Comment(_)) => 8,
+            None => 0,
+        })
+    }
 
-impl Mountable<MockDom> for Node {
-    fn unmount(&mut self) {
-        todo!()
+    /// The text content of this node and all its descendants. For a comment, this is the text
+    /// of the comment.
+    pub fn text_content(&self) -> Option<String> {
+        with_nodes(|nodes| match nodes.get(self.0).map(|node| &node.kind) {
+            Some(NodeKind::Comment(text)) => Some(text.clone()),
+            Some(_) => {
+                let mut buf = String::new();
+                text_content(nodes, self.0, &mut buf);
+                Some(buf)
+            }
+            None => None,
+        })
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        MockDom::insert_node(parent, self, marker);
+    /// Whether this node has been attached to a parent node.
+    pub fn is_attached(&self) -> bool {
+        MockDom::get_parent(self).is_some()
     }
 
-    fn insert_before_this(&self, child: &mut dyn Mountable<MockDom>) -> bool {
-        let parent = MockDom::get_parent(self).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self));
-            return true;
-        }
-        false
+    /// Outputs an HTML form of this node, for testing and debugging purposes.
+    pub fn outer_html(&self) -> String {
+        let mut buf = String::new();
+        with_nodes(|nodes| outer_html(nodes, self.0, &mut buf));
+        buf
+    }
+
+    /// The child nodes of this node.
+    pub fn child_nodes(&self) -> Vec<Node> {
+        with_nodes(|nodes| {
+            children(nodes, self.0).iter().copied().map(Node).collect()
+        })
     }
 }
 
-impl Mountable<MockDom> for Text {
-    fn unmount(&mut self) {
-        todo!()
+impl Element {
+    /// The element's tag name. As in the DOM, this is uppercase for HTML elements.
+    pub fn tag_name(&self) -> String {
+        with_nodes(|nodes| {
+            element_data(nodes, self.id())
+                .map(|data| {
+                    if data.namespace.is_none() {
+                        data.tag.to_ascii_uppercase()
+                    } else {
+                        data.tag.clone()
+                    }
+                })
+                .unwrap_or_default()
+        })
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        MockDom::insert_node(parent, self.as_ref(), marker);
+    /// The element's namespace, if it was created with one.
+    pub fn namespace(&self) -> Option<String> {
+        with_nodes(|nodes| element_data(nodes, self.id())?.namespace.clone())
     }
 
-    fn insert_before_this(&self, child: &mut dyn Mountable<MockDom>) -> bool {
-        let parent =
-            MockDom::get_parent(self.as_ref()).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self.as_ref()));
-            return true;
-        }
-        false
+    /// Returns the value of an attribute, if it is set.
+    pub fn get_attribute(&self, name: &str) -> Option<String> {
+        with_nodes(|nodes| {
+            element_data(nodes, self.id())?.attrs.get(name).cloned()
+        })
     }
-}
 
-impl Mountable<MockDom> for Element {
-    fn unmount(&mut self) {
-        todo!()
+    /// Whether an attribute is set.
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.get_attribute(name).is_some()
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        MockDom::insert_node(parent, self.as_ref(), marker);
+    /// All the element's attributes, in the order they were first set.
+    pub fn attributes(&self) -> Vec<(String, String)> {
+        with_nodes(|nodes| {
+            element_data(nodes, self.id())
+                .map(|data| {
+                    data.attrs
+                        .iter()
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        })
     }
 
-    fn insert_before_this(&self, child: &mut dyn Mountable<MockDom>) -> bool {
-        let parent =
-            MockDom::get_parent(self.as_ref()).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self.as_ref()));
-            return true;
-        }
-        false
+    /// Returns the value of a property, if it has been set.
+    pub fn property(&self, name: &str) -> Option<Value> {
+        with_nodes(|nodes| {
+            element_data(nodes, self.id())?.props.get(name).cloned()
+        })
     }
-}
 
-impl Mountable<MockDom> for Placeholder {
-    fn unmount(&mut self) {
-        todo!()
+    /// The classes in the element's `class` attribute.
+    pub fn classes(&self) -> Vec<String> {
+        self.get_attribute("class")
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect()
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        MockDom::insert_node(parent, self.as_ref(), marker);
+    /// Whether the element's `class` attribute contains the given class.
+    pub fn has_class(&self, name: &str) -> bool {
+        self.classes().iter().any(|class| class == name)
     }
 
-    fn insert_before_this(&self, child: &mut dyn Mountable<MockDom>) -> bool {
-        let parent =
-            MockDom::get_parent(self.as_ref()).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self.as_ref()));
-            return true;
-        }
-        false
+    /// Returns the value of a CSS property in the element's `style` attribute, if it is set.
+    pub fn style_property(&self, name: &str) -> Option<String> {
+        parse_style(&self.get_attribute("style")?).shift_remove(name)
     }
-}
 
-impl<E: ElementType> CreateElement<MockDom> for E {
-    fn create_element(&self) -> crate::renderer::types::Element {
-        document().create_element(E::TAG)
+    /// The text content of the element and all its descendants.
+    pub fn text_content(&self) -> String {
+        self.0.text_content().unwrap_or_default()
     }
-}
 
-impl Renderer for MockDom {
-    type Node = Node;
-    type Text = Text;
-    type Element = Element;
-    type Placeholder = Placeholder;
+    /// Outputs the HTML of the element's children, for testing and debugging purposes.
+    pub fn inner_html(&self) -> String {
+        let mut buf = String::new();
+        with_nodes(|nodes| inner_html(nodes, self.id(), &mut buf));
+        buf
+    }
 
-    fn intern(text: &str) -> &str {
-        text
+    /// The element's parent element, if any.
+    pub fn parent_element(&self) -> Option<Element> {
+        MockDom::get_parent(self).and_then(Element::cast_from)
     }
 
-    fn create_text_node(data: &str) -> Self::Text {
-        document().create_text_node(data)
+    /// The element's child elements, excluding text and comment nodes.
+    pub fn children(&self) -> Vec<Element> {
+        self.child_nodes()
+            .into_iter()
+            .filter_map(Element::cast_from)
+            .collect()
     }
 
-    fn create_placeholder() -> Self::Placeholder {
-        document().create_placeholder()
+    /// Returns the first descendant of this element that matches the selector, if any.
+    ///
+    /// Selectors can be made up of tag names, `#id`, `.class`, `[attr]`, and `[attr="value"]`,
+    /// and combined with the descendant combinator, like `ul.todos li[data-done="true"]`.
+    pub fn query_selector(&self, selector: &str) -> Option<Element> {
+        self.query_selector_all(selector).into_iter().next()
     }
 
-    fn set_text(node: &Self::Text, text: &str) {
-        Document::with_node_mut(node.0 .0, |node| {
-            if let NodeType::Text(ref mut node) = node.ty {
-                *node = text.to_string();
+    /// Returns all the descendants of this element that match the selector, in document order.
+    ///
+    /// See [`Element::query_selector`] for the supported selectors.
+    pub fn query_selector_all(&self, selector: &str) -> Vec<Element> {
+        let selector = Selector::parse(selector);
+        let mut matches = Vec::new();
+        with_nodes(|nodes| {
+            let mut stack = children(nodes, self.id()).to_vec();
+            stack.reverse();
+            while let Some(id) = stack.pop() {
+                if selector.matches(nodes, id, self.id()) {
+                    matches.push(Element(Node(id)));
+                }
+                stack.extend(children(nodes, id).iter().rev());
             }
         });
+        matches
     }
 
-    fn set_attribute(node: &Self::Element, name: &str, value: &str) {
-        Document::with_node_mut(node.0 .0, |node| {
-            if let NodeType::Element { ref mut attrs, .. } = node.ty {
-                attrs.insert(name.to_string(), value.to_string());
-            }
-        });
+    /// Dispatches an event with the given name to this element.
+    ///
+    /// Listeners added with the capture flag on its ancestors are called first, then listeners on
+    /// the element itself, and then (for events that bubble) listeners on its ancestors.
+    /// Delegated listeners are not called on disabled elements.
+    pub fn dispatch_event(&self, name: &str) {
+        dispatch(self, name);
     }
 
-    fn remove_attribute(node: &Self::Element, name: &str) {
-        Document::with_node_mut(node.0 .0, |node| {
-            if let NodeType::Element { ref mut attrs, .. } = node.ty {
-                attrs.shift_remove(name);
-            }
-        });
+    /// Dispatches a `click` event to this element.
+    pub fn click(&self) {
+        self.dispatch_event("click");
     }
 
-    fn insert_node(
-        parent: &Self::Element,
-        new_child: &Self::Node,
-        anchor: Option<&Self::Node>,
-    ) {
-        debug_assert!(&parent.0 != new_child);
-        // remove if already mounted
-        if let Some(parent) = MockDom::get_parent(new_child) {
-            let parent = Element(parent);
-            MockDom::remove_node(&parent, new_child);
+    /// Sets the `value` property of the element and dispatches an `input` event, as if a user
+    /// had typed into it.
+    pub fn input(&self, value: &str) {
+        MockDom::set_property(self, "value", &value.into());
+        self.dispatch_event("input");
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.property("disabled")
+            .map(|disabled| disabled.is_truthy())
+            .unwrap_or_else(|| self.has_attribute("disabled"))
+    }
+}
+
+impl Text {
+    /// The text of this node.
+    pub fn data(&self) -> String {
+        self.0.text_content().unwrap_or_default()
+    }
+}
+
+struct Selector(Vec<Compound>);
+
+#[derive(Default)]
+struct Compound {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attrs: Vec<(String, Option<String>)>,
+}
+
+impl Selector {
+    fn parse(selector: &str) -> Self {
+        Self(selector.split_whitespace().map(Compound::parse).collect())
+    }
+
+    /// Whether the node matches the selector, looking for ancestors up to (but not including)
+    /// `scope`.
+    fn matches(
+        &self,
+        nodes: &SlotMap<NodeId, NodeData>,
+        id: NodeId,
+        scope: NodeId,
+    ) -> bool {
+        let Some((last, ancestors)) = self.0.split_last() else {
+            return false;
+        };
+        if !last.matches(nodes, id) {
+            return false;
         }
-        // mount on new parent
-        Document::with_node_mut(parent.0 .0, |parent| {
-            if let NodeType::Element {
-                ref mut children, ..
-            } = parent.ty
-            {
-                match anchor {
-                    None => children.push(new_child.clone()),
-                    Some(anchor) => {
-                        let anchor_pos = children
-                            .iter()
-                            .position(|item| item.0 == anchor.0)
-                            .expect("anchor is not a child of the parent");
-                        children.insert(anchor_pos, new_child.clone());
-                    }
-                }
-            } else {
-                panic!("parent is not an element");
+        let mut current = nodes[id].parent;
+        let mut remaining = ancestors.iter().rev().peekable();
+        while let (Some(compound), Some(ancestor)) = (remaining.peek(), current)
+        {
+            if ancestor == scope {
+                break;
             }
-        });
-        // set parent on child node
-        Document::with_node_mut(new_child.0, |node| {
-            node.parent = Some(parent.0 .0)
-        });
+            if compound.matches(nodes, ancestor) {
+                remaining.next();
+            }
+            current = nodes[ancestor].parent;
+        }
+        remaining.peek().is_none()
     }
+}
 
-    fn remove_node(
-        parent: &Self::Element,
-        child: &Self::Node,
-    ) -> Option<Self::Node> {
-        let child = Document::with_node_mut(parent.0 .0, |parent| {
-            if let NodeType::Element {
-                ref mut children, ..
-            } = parent.ty
-            {
-                let current_pos = children
-                    .iter()
-                    .position(|item| item.0 == child.0)
-                    .expect("anchor is not a child of the parent");
-                Some(children.remove(current_pos))
+impl Compound {
+    fn parse(input: &str) -> Self {
+        let mut compound = Compound::default();
+        let mut rest = input;
+        fn take_name(rest: &str) -> (String, &str) {
+            let end = rest.find(['#', '.', '[']).unwrap_or(rest.len());
+            (rest[..end].to_string(), &rest[end..])
+        }
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('#') {
+                let (id, after) = take_name(after);
+                compound.id = Some(id);
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('.') {
+                let (class, after) = take_name(after);
+                compound.classes.push(class);
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('[') {
+                let end = after.find(']').unwrap_or(after.len());
+                let attr = match after[..end].split_once('=') {
+                    Some((name, value)) => (
+                        name.to_string(),
+                        Some(value.trim_matches(['"', '\'']).to_string()),
+                    ),
+                    None => (after[..end].to_string(), None),
+                };
+                compound.attrs.push(attr);
+                rest = after.get(end + 1..).unwrap_or_default();
             } else {
-                None
+                let (tag, after) = take_name(rest);
+                if tag != "*" {
+                    compound.tag = Some(tag);
+                }
+                rest = after;
             }
-        })
-        .flatten()?;
-        Document::with_node_mut(child.0, |node| {
-            node.parent = None;
-        });
-        Some(child)
+        }
+        compound
+    }
+
+    fn matches(&self, nodes: &SlotMap<NodeId, NodeData>, id: NodeId) -> bool {
+        let Some(data) = element_data(nodes, id) else {
+            return false;
+        };
+        let attr = |name: &str| data.attrs.get(name).map(String::as_str);
+        self.tag
+            .as_ref()
+            .is_none_or(|tag| data.tag.eq_ignore_ascii_case(tag))
+            && self.id.as_ref().is_none_or(|id| attr("id") == Some(id))
+            && self.classes.iter().all(|class| {
+                attr("class")
+                    .is_some_and(|c| c.split_whitespace().any(|c| c == class))
+            })
+            && self.attrs.iter().all(|(name, value)| match value {
+                Some(value) => attr(name) == Some(value),
+                None => attr(name).is_some(),
+            })
     }
+}
 
-    fn remove(node: &Self::Node) {
-        let parent = Element(Node(
-            Self::get_parent(node)
-                .expect("tried to remove a parentless node")
-                .0,
-        ));
-        Self::remove_node(&parent, node);
+impl Deref for Element {
+    type Target = Node;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
+}
+
+impl Deref for Text {
+    type Target = Node;
 
-    fn get_parent(node: &Self::Node) -> Option<Self::Node> {
-        Document::with_node(node.0, |node| node.parent)
-            .flatten()
-            .map(Node)
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
+}
 
-    fn first_child(node: &Self::Node) -> Option<Self::Node> {
-        Document::with_node(node.0, |node| match &node.ty {
-            NodeType::Text(_) => None,
-            NodeType::Element { children, .. } => children.first().cloned(),
-            NodeType::Placeholder => None,
-        })
-        .flatten()
-    }
-
-    fn next_sibling(node: &Self::Node) -> Option<Self::Node> {
-        let node_id = node.0;
-        Document::with_node(node_id, |node| {
-            node.parent.and_then(|parent| {
-                Document::with_node(parent, |parent| match &parent.ty {
-                    NodeType::Element { children, .. } => {
-                        let this = children
-                            .iter()
-                            .position(|check| check == &Node(node_id))?;
-                        children.get(this + 1).cloned()
-                    }
-                    _ => panic!(
-                        "Called next_sibling with parent as a node that's not \
-                         an Element."
-                    ),
-                })
-            })
-        })
-        .flatten()
-        .flatten()
+impl Deref for Placeholder {
+    type Target = Node;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
+}
 
-    fn log_node(node: &Self::Node) {
-        eprintln!("{node:?}");
+impl AsRef<Node> for Node {
+    fn as_ref(&self) -> &Node {
+        self
     }
+}
 
-    fn clear_children(parent: &Self::Element) {
-        let prev_children =
-            Document::with_node_mut(parent.0 .0, |node| match node.ty {
-                NodeType::Element {
-                    ref mut children, ..
-                } => std::mem::take(children),
-                _ => panic!("Called clear_children on a non-Element node."),
-            })
-            .unwrap_or_default();
-        for child in prev_children {
-            Document::with_node_mut(child.0, |node| {
-                node.parent = None;
-            });
-        }
+impl AsRef<Node> for Element {
+    fn as_ref(&self) -> &Node {
+        &self.0
     }
 }
 
-impl CastFrom<Node> for Text {
-    fn cast_from(source: Node) -> Option<Self> {
-        Document::with_node(source.0, |node| {
-            matches!(node.ty, NodeType::Text(_))
-        })
-        .and_then(|matches| matches.then_some(Text(Node(source.0))))
+impl AsRef<Node> for Text {
+    fn as_ref(&self) -> &Node {
+        &self.0
     }
 }
 
-impl CastFrom<Node> for Element {
-    fn cast_from(source: Node) -> Option<Self> {
-        Document::with_node(source.0, |node| {
-            matches!(node.ty, NodeType::Element { .. })
-        })
-        .and_then(|matches| matches.then_some(Element(Node(source.0))))
+impl AsRef<Node> for Placeholder {
+    fn as_ref(&self) -> &Node {
+        &self.0
     }
 }
 
-impl CastFrom<Node> for Placeholder {
-    fn cast_from(source: Node) -> Option<Self> {
-        Document::with_node(source.0, |node| {
-            matches!(node.ty, NodeType::Placeholder)
-        })
-        .and_then(|matches| matches.then_some(Placeholder(Node(source.0))))
+impl From<Element> for Node {
+    fn from(value: Element) -> Self {
+        value.0
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::MockDom;
-    use crate::{
-        html::element,
-        renderer::{mock_dom::node_eq, Renderer},
-    };
+impl From<Text> for Node {
+    fn from(value: Text) -> Self {
+        value.0
+    }
+}
 
-    #[test]
-    fn html_debugging_works() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        MockDom::set_attribute(&p, "id", "foo");
-        let text = MockDom::create_text_node("Hello, world!");
-        MockDom::insert_node(&main, p.as_ref(), None);
-        MockDom::insert_node(&p, text.as_ref(), None);
-        assert_eq!(
-            main.to_debug_html(),
-            "<main><p id=\"foo\">Hello, world!</p></main>"
-        );
+impl From<Placeholder> for Node {
+    fn from(value: Placeholder) -> Self {
+        value.0
     }
+}
 
-    #[test]
-    fn remove_attribute_works() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        MockDom::set_attribute(&p, "id", "foo");
-        let text = MockDom::create_text_node("Hello, world!");
-        MockDom::insert_node(&main, p.as_ref(), None);
-        MockDom::insert_node(&p, text.as_ref(), None);
-        MockDom::remove_attribute(&p, "id");
-        assert_eq!(main.to_debug_html(), "<main><p>Hello, world!</p></main>");
+impl CastFrom<Node> for Node {
+    fn cast_from(node: Node) -> Option<Node> {
+        Some(node)
     }
+}
 
-    #[test]
-    fn remove_node_works() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        MockDom::set_attribute(&p, "id", "foo");
-        let text = MockDom::create_text_node("Hello, world!");
-        MockDom::insert_node(&main, p.as_ref(), None);
-        MockDom::insert_node(&p, text.as_ref(), None);
-        MockDom::remove_node(&main, p.as_ref());
-        assert_eq!(main.to_debug_html(), "<main></main>");
+impl CastFrom<Node> for Element {
+    fn cast_from(node: Node) -> Option<Element> {
+        (node.node_type() == 1).then_some(Element(node))
     }
+}
 
-    #[test]
-    fn insert_before_works() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        let span = MockDom::create_element(element::Span);
-        let text = MockDom::create_text_node("Hello, world!");
-        MockDom::insert_node(&main, p.as_ref(), None);
-        MockDom::insert_node(&span, text.as_ref(), None);
-        MockDom::insert_node(&main, span.as_ref(), Some(p.as_ref()));
-        assert_eq!(
-            main.to_debug_html(),
-            "<main><span>Hello, world!</span><p></p></main>"
-        );
+impl CastFrom<Node> for Text {
+    fn cast_from(node: Node) -> Option<Text> {
+        (node.node_type() == 3).then_some(Text(node))
     }
+}
 
-    #[test]
-    fn insert_before_sets_parent() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        MockDom::insert_node(&main, p.as_ref(), None);
-        let parent =
-            MockDom::get_parent(p.as_ref()).expect("p should have parent set");
-        assert!(node_eq(parent, main));
+impl CastFrom<Node> for Placeholder {
+    fn cast_from(node: Node) -> Option<Placeholder> {
+        (node.node_type() == 8).then_some(Placeholder(node))
     }
+}
 
-    #[test]
-    fn insert_before_moves_node() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        let span = MockDom::create_element(element::Span);
-        let text = MockDom::create_text_node("Hello, world!");
-        MockDom::insert_node(&main, p.as_ref(), None);
-        MockDom::insert_node(&span, text.as_ref(), None);
-        MockDom::insert_node(&main, span.as_ref(), Some(p.as_ref()));
-        MockDom::insert_node(&main, p.as_ref(), Some(span.as_ref()));
-        assert_eq!(
-            main.to_debug_html(),
-            "<main><p></p><span>Hello, world!</span></main>"
-        );
+impl CastFrom<Element> for Element {
+    fn cast_from(el: Element) -> Option<Element> {
+        Some(el)
     }
+}
+
+impl AsRef<Element> for Element {
+    fn as_ref(&self) -> &Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MockDom;
+    use crate::{
+        html::{
+            attribute::{
+                custom::custom_attribute,
+                global::{ClassAttribute, GlobalAttributes},
+            },
+            element::{div, li, p, ul, ElementChild},
+        },
+        view::{add_attr::AddAnyAttr, Render},
+    };
+    // properties and event listeners are not created with the `ssr` feature, so the tests that
+    // use them only run without it
+    #[cfg(not(feature = "ssr"))]
+    use {
+        super::{current_event_target, Value},
+        crate::{
+            html::{
+                attribute::global::{
+                    OnAttribute, PropAttribute, StyleAttribute,
+                },
+                element::{button, input},
+                event,
+            },
+            view::Mountable,
+        },
+        std::{cell::RefCell, rc::Rc},
+    };
 
     #[test]
-    fn first_child_gets_first_child() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        let span = MockDom::create_element(element::Span);
-        MockDom::insert_node(&main, p.as_ref(), None);
-        MockDom::insert_node(&p, span.as_ref(), None);
-        assert_eq!(
-            MockDom::first_child(main.as_ref()).as_ref(),
-            Some(p.as_ref())
+    fn inner_html_is_parsed_and_serialized() {
+        let el = MockDom::create_element("div", None);
+        MockDom::set_inner_html(
+            &el,
+            "<p class=\"a b\">Hello, <em>world</em>!<br><!--x--></p><input \
+             disabled value='&lt;3'>",
         );
         assert_eq!(
-            MockDom::first_child(&MockDom::first_child(main.as_ref()).unwrap())
-                .as_ref(),
-            Some(span.as_ref())
+            el.inner_html(),
+            "<p class=\"a b\">Hello, <em>world</em>!<br><!--x--></p><input \
+             disabled=\"\" value=\"&lt;3\">"
         );
+        assert_eq!(el.text_content(), "Hello, world!");
+        let input = el.query_selector("input").unwrap();
+        assert_eq!(input.tag_name(), "INPUT");
+        assert_eq!(input.get_attribute("value").as_deref(), Some("<3"));
     }
 
     #[test]
-    fn next_sibling_gets_next_sibling() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        let span = MockDom::create_element(element::Span);
-        let text = MockDom::create_text_node("foo");
-        MockDom::insert_node(&main, p.as_ref(), None);
-        MockDom::insert_node(&main, span.as_ref(), None);
-        MockDom::insert_node(&main, text.as_ref(), None);
-        assert_eq!(
-            MockDom::next_sibling(p.as_ref()).as_ref(),
-            Some(span.as_ref())
-        );
+    fn query_selector_matches_compound_and_descendant_selectors() {
+        let view = div().child((
+            ul().class("todos").child((
+                li().id("first")
+                    .add_any_attr(custom_attribute("data-done", "true"))
+                    .child("A"),
+                li().class("active").child("B"),
+            )),
+            p().class("active").child("C"),
+        ));
+        let state = view.build();
+        let root = &state.el;
+
+        assert_eq!(root.query_selector("#first").unwrap().text_content(), "A");
         assert_eq!(
-            MockDom::next_sibling(span.as_ref()).as_ref(),
-            Some(text.as_ref())
+            root.query_selector("li[data-done=\"true\"]")
+                .unwrap()
+                .text_content(),
+            "A"
         );
+        assert_eq!(root.query_selector_all(".active").len(), 2);
+        let active_items = root.query_selector_all("ul.todos .active");
+        assert_eq!(active_items.len(), 1);
+        assert_eq!(active_items[0].text_content(), "B");
+        assert!(root.query_selector("p li").is_none());
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    #[test]
+    fn classes_styles_and_properties_can_be_inspected() {
+        let view = input()
+            .class("a")
+            .class(("b", true))
+            .style(("color", "red"))
+            .prop("value", "hello")
+            .prop("checked", true);
+        let state = view.build();
+        let el = &state.el;
+
+        assert!(el.has_class("a"));
+        assert!(el.has_class("b"));
+        assert_eq!(el.style_property("color").as_deref(), Some("red"));
+        assert_eq!(el.property("value"), Some(Value::from("hello")));
+        assert_eq!(el.property("checked"), Some(Value::Bool(true)));
+        // properties are not reflected as attributes
+        assert!(!el.has_attribute("value"));
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    #[test]
+    fn events_bubble_to_ancestors() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let view = div()
+            .on(event::capture(event::click), {
+                let log = Rc::clone(&log);
+                move |_| log.borrow_mut().push("capture")
+            })
+            .on(event::click, {
+                let log = Rc::clone(&log);
+                move |_| log.borrow_mut().push("parent")
+            })
+            .child(button().on(event::click, {
+                let log = Rc::clone(&log);
+                move |_| {
+                    let target = current_event_target().unwrap();
+                    assert_eq!(target.tag_name(), "BUTTON");
+                    log.borrow_mut().push("button")
+                }
+            }));
+        let mut state = view.build();
+        let parent = MockDom::create_element("main", None);
+        state.mount(&parent, None);
+
+        parent.query_selector("button").unwrap().click();
+        assert_eq!(*log.borrow(), ["capture", "button", "parent"]);
+
+        // focus events don't bubble
+        log.borrow_mut().clear();
+        parent
+            .query_selector("button")
+            .unwrap()
+            .dispatch_event("focus");
+        assert!(log.borrow().is_empty());
+
+        // listeners are removed when the view is dropped
+        state.unmount();
+        let button = state.el.query_selector("button").unwrap();
+        drop(state);
+        button.click();
+        assert!(log.borrow().is_empty());
+        assert!(parent.children().is_empty());
     }
 }
@@ -17,25 +17,130 @@ pub mod dom;
 /// future, so to the extent possible the rest of the crate tries to stick to using
 /// [`Renderer`].
 /// methods rather than directly manipulating the DOM inline.
+///
+/// With the `mock_dom` feature enabled, native (non-WASM) targets use the in-memory
+/// [`MockDom`](mock_dom::MockDom) instead, so that views can be tested without a browser. WASM
+/// targets always use the [`Dom`](dom::Dom).
+#[cfg(not(all(feature = "mock_dom", not(target_family = "wasm"))))]
 pub type Rndr = dom::Dom;
+/// The renderer being used for the application.
+///
+/// With the `mock_dom` feature enabled, native (non-WASM) targets use the in-memory
+/// [`MockDom`](mock_dom::MockDom) instead of the [`Dom`](dom::Dom), so that views can be tested
+/// without a browser.
+#[cfg(all(feature = "mock_dom", not(target_family = "wasm")))]
+pub type Rndr = mock_dom::MockDom;
 
 /// Types used by the renderer.
 ///
 /// See [`Rndr`] for additional information on this rendering approach.
 pub mod types {
+    #[cfg(not(all(feature = "mock_dom", not(target_family = "wasm"))))]
     pub use super::dom::{
         ClassList, CssStyleDeclaration, Element, Event, Node, Placeholder,
         TemplateElement, Text,
     };
+    #[cfg(all(feature = "mock_dom", not(target_family = "wasm")))]
+    pub use super::mock_dom::{
+        ClassList, CssStyleDeclaration, Element, Element as HtmlElement, Event,
+        Node, Placeholder, TemplateElement, Text, Value as PropertyValue,
+    };
+    /// The value of a DOM property.
+    #[cfg(not(all(feature = "mock_dom", not(target_family = "wasm"))))]
+    pub use wasm_bindgen::JsValue as PropertyValue;
+    /// The type of element an application is mounted to.
+    #[cfg(not(all(feature = "mock_dom", not(target_family = "wasm"))))]
+    pub use web_sys::HtmlElement;
 }
 
-/* #[cfg(feature = "testing")]
-/// A renderer based on a mock DOM.
+/// A renderer based on an in-memory mock of the DOM, which can be used to test views natively.
+#[cfg(all(feature = "mock_dom", not(target_family = "wasm")))]
 pub mod mock_dom;
-/// A DOM renderer optimized for element creation.
+/* /// A DOM renderer optimized for element creation.
 #[cfg(feature = "sledgehammer")]
 pub mod sledgehammer; */
 
+impl Rndr {
+    /// Mounts the new child before the marker as its sibling.
+    ///
+    /// ## Panics
+    /// Panics if `before` does not have a parent [`types::Element`].
+    pub fn mount_before<M>(new_child: &mut M, before: &types::Node)
+    where
+        M: Mountable,
+    {
+        let parent = types::Element::cast_from(
+            Self::get_parent(before).expect("could not find parent element"),
+        )
+        .expect("placeholder parent should be Element");
+        new_child.mount(&parent, Some(before));
+    }
+
+    /// Tries to mount the new child before the marker as its sibling.
+    ///
+    /// Returns `false` if the child did not have a valid parent.
+    #[track_caller]
+    pub fn try_mount_before<M>(new_child: &mut M, before: &types::Node) -> bool
+    where
+        M: Mountable,
+    {
+        if let Some(parent) =
+            Self::get_parent(before).and_then(types::Element::cast_from)
+        {
+            new_child.mount(&parent, Some(before));
+            true
+        } else {
+            false
+        }
+    }
+}
+
+macro_rules! mountable {
+    ($ty:ty, |$this:ident| $elements:expr) => {
+        impl Mountable for $ty {
+            fn unmount(&mut self) {
+                Rndr::remove(self);
+            }
+
+            fn mount(
+                &mut self,
+                parent: &types::Element,
+                marker: Option<&types::Node>,
+            ) {
+                Rndr::insert_node(parent, self, marker);
+            }
+
+            fn try_mount(
+                &mut self,
+                parent: &types::Element,
+                marker: Option<&types::Node>,
+            ) -> bool {
+                Rndr::try_insert_node(parent, self, marker)
+            }
+
+            fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+                let parent =
+                    Rndr::get_parent(self).and_then(types::Element::cast_from);
+                if let Some(parent) = parent {
+                    child.mount(&parent, Some(self));
+                    return true;
+                }
+                false
+            }
+
+            fn elements(&self) -> Vec<types::Element> {
+                let $this = self;
+                $elements
+            }
+        }
+    };
+}
+
+mountable!(types::Node, |_this| vec![]);
+mountable!(types::Text, |_this| vec![]);
+mountable!(types::Placeholder, |_this| vec![]);
+mountable!(types::Element, |this| vec![this.clone()]);
+
 /// Implements the instructions necessary to render an interface on some platform.
 ///
 /// By default, this is implemented for the Document Object Model (DOM) in a Web